@@ -1,10 +1,10 @@
 //! Supported AI coding agents and their skill directories.
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 
 /// Supported AI coding agents.
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum Agent {
     /// OpenCode.
@@ -41,7 +41,7 @@ pub enum Agent {
 }
 
 /// Agent feature support flags.
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
 pub struct AgentFeatures {
     /// Supports `context: fork` in SKILL.md.
     pub context_fork: bool,
@@ -53,6 +53,82 @@ pub struct AgentFeatures {
     pub scripts: bool,
 }
 
+/// A config file that lists the directories an agent loads skills from.
+///
+/// Most agents find skills by scanning their skills directory; these also
+/// need the directory listed in their config.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SkillsRegistration {
+    /// Config file relative to the project root, if the agent reads one.
+    pub project_file: Option<&'static str>,
+    /// Global config file.
+    pub global_file: &'static str,
+    /// Keys leading to the list of skill directories.
+    pub key: &'static [&'static str],
+}
+
+/// How confident detection is that an agent is in use, from weakest to strongest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Confidence {
+    /// Only the agent's executable was found on `PATH`.
+    Low,
+    /// An agent-specific config file exists.
+    Medium,
+    /// The agent's config directory exists.
+    High,
+}
+
+impl std::fmt::Display for Confidence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Confidence::Low => write!(f, "low"),
+            Confidence::Medium => write!(f, "medium"),
+            Confidence::High => write!(f, "high"),
+        }
+    }
+}
+
+/// Evidence that an agent is in use.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DetectionSignal {
+    /// The agent's config directory exists.
+    Directory(PathBuf),
+    /// An agent-specific config file exists.
+    ConfigFile(PathBuf),
+    /// The agent's executable was found on `PATH`.
+    Binary(PathBuf),
+}
+
+impl DetectionSignal {
+    /// Returns the confidence this signal provides.
+    pub fn confidence(&self) -> Confidence {
+        match self {
+            DetectionSignal::Directory(_) => Confidence::High,
+            DetectionSignal::ConfigFile(_) => Confidence::Medium,
+            DetectionSignal::Binary(_) => Confidence::Low,
+        }
+    }
+
+    /// Returns the kind of signal ("directory", "config-file", or "binary").
+    pub fn kind(&self) -> &'static str {
+        match self {
+            DetectionSignal::Directory(_) => "directory",
+            DetectionSignal::ConfigFile(_) => "config-file",
+            DetectionSignal::Binary(_) => "binary",
+        }
+    }
+
+    /// Returns the path that triggered this signal.
+    pub fn path(&self) -> &Path {
+        match self {
+            DetectionSignal::Directory(p)
+            | DetectionSignal::ConfigFile(p)
+            | DetectionSignal::Binary(p) => p,
+        }
+    }
+}
+
 /// Information about a detected agent.
 #[derive(Debug, Clone)]
 pub struct DetectedAgent {
@@ -64,6 +140,19 @@ pub struct DetectedAgent {
     pub skill_count: usize,
     /// Whether this is a global installation.
     pub is_global: bool,
+    /// The evidence that led to detection.
+    pub signals: Vec<DetectionSignal>,
+}
+
+impl DetectedAgent {
+    /// Returns the strongest confidence among the detection signals.
+    pub fn confidence(&self) -> Confidence {
+        self.signals
+            .iter()
+            .map(DetectionSignal::confidence)
+            .max()
+            .unwrap_or(Confidence::Low)
+    }
 }
 
 impl Agent {
@@ -198,6 +287,95 @@ impl Agent {
         }
     }
 
+    /// Returns names a skill should not use with this agent.
+    ///
+    /// These are the agent's built-in slash commands; a skill with the same
+    /// name is shadowed by, or shadows, the built-in.
+    pub fn reserved_names(&self) -> &'static [&'static str] {
+        match self {
+            Agent::Claude => &[
+                "add-dir",
+                "agents",
+                "bug",
+                "clear",
+                "compact",
+                "config",
+                "context",
+                "cost",
+                "doctor",
+                "exit",
+                "export",
+                "help",
+                "hooks",
+                "ide",
+                "init",
+                "login",
+                "logout",
+                "mcp",
+                "memory",
+                "model",
+                "permissions",
+                "pr-comments",
+                "release-notes",
+                "resume",
+                "review",
+                "rewind",
+                "status",
+                "statusline",
+                "upgrade",
+                "usage",
+                "vim",
+            ],
+            Agent::Codex => &[
+                "approvals",
+                "compact",
+                "diff",
+                "exit",
+                "init",
+                "logout",
+                "mcp",
+                "mention",
+                "model",
+                "new",
+                "quit",
+                "review",
+                "status",
+            ],
+            Agent::Gemini => &[
+                "about",
+                "auth",
+                "bug",
+                "chat",
+                "clear",
+                "compress",
+                "copy",
+                "directory",
+                "docs",
+                "editor",
+                "extensions",
+                "help",
+                "ide",
+                "init",
+                "mcp",
+                "memory",
+                "privacy",
+                "quit",
+                "restore",
+                "settings",
+                "stats",
+                "theme",
+                "tools",
+                "vim",
+            ],
+            _ => &[],
+        }
+    }
+
+    /// Returns true if `name` collides with a reserved name of this agent.
+    pub fn is_reserved_name(&self, name: &str) -> bool {
+        self.reserved_names().contains(&name)
+    }
+
     /// Returns the detection path for this agent (config directory).
     pub fn detection_dir(&self) -> &'static str {
         match self {
@@ -240,6 +418,109 @@ impl Agent {
         }
     }
 
+    /// Returns agent-specific config files that indicate project-level use.
+    ///
+    /// Paths are relative to the project root.
+    pub fn config_files(&self) -> &'static [&'static str] {
+        match self {
+            Agent::OpenCode => &["opencode.json", "opencode.jsonc"],
+            Agent::Claude => &["CLAUDE.md", "CLAUDE.local.md"],
+            Agent::Cursor => &[".cursorrules", ".cursorignore"],
+            Agent::KiloCode => &[".kilocodemodes", ".kilocoderules"],
+            Agent::RooCode => &[".roomodes", ".roorules"],
+            Agent::Goose => &[".goosehints"],
+            Agent::Gemini => &["GEMINI.md"],
+            Agent::Copilot => &[".github/copilot-instructions.md"],
+            Agent::Windsurf => &[".windsurfrules"],
+            Agent::Codex
+            | Agent::Amp
+            | Agent::Antigravity
+            | Agent::Clawdbot
+            | Agent::Droid
+            | Agent::PiMono => &[],
+        }
+    }
+
+    /// Returns the config file skill directories must be listed in, for
+    /// agents that do not scan their skills directory on their own.
+    pub fn skills_registration(&self) -> Option<SkillsRegistration> {
+        match self {
+            Agent::OpenCode => Some(SkillsRegistration {
+                project_file: Some("opencode.json"),
+                global_file: "~/.config/opencode/opencode.json",
+                key: &["skills", "paths"],
+            }),
+            Agent::Goose => Some(SkillsRegistration {
+                project_file: None,
+                global_file: "~/.config/goose/config.yaml",
+                key: &["skills", "paths"],
+            }),
+            _ => None,
+        }
+    }
+
+    /// Returns executable names that indicate the agent is installed.
+    ///
+    /// Names also used by unrelated tools (`pi`, `amp`, `goose`) are left
+    /// out; those agents are only detected by their directories.
+    pub fn binary_names(&self) -> &'static [&'static str] {
+        match self {
+            Agent::OpenCode => &["opencode"],
+            Agent::Claude => &["claude"],
+            Agent::Codex => &["codex"],
+            Agent::Cursor => &["cursor-agent"],
+            Agent::Gemini => &["gemini"],
+            Agent::Antigravity => &["antigravity"],
+            Agent::Clawdbot => &["clawdbot"],
+            Agent::Droid => &["droid"],
+            Agent::Windsurf => &["windsurf"],
+            Agent::Amp
+            | Agent::Goose
+            | Agent::PiMono
+            | Agent::KiloCode
+            | Agent::RooCode
+            | Agent::Copilot => &[],
+        }
+    }
+
+    /// Collect project-level detection signals for this agent.
+    pub fn project_signals(&self, project_root: &Path) -> Vec<DetectionSignal> {
+        let mut signals = Vec::new();
+
+        let detection_path = project_root.join(self.detection_dir());
+        if detection_path.exists() {
+            signals.push(DetectionSignal::Directory(detection_path));
+        }
+
+        for file in self.config_files() {
+            let path = project_root.join(file);
+            if path.is_file() {
+                signals.push(DetectionSignal::ConfigFile(path));
+            }
+        }
+
+        signals
+    }
+
+    /// Collect global detection signals for this agent.
+    pub fn global_signals(&self) -> Vec<DetectionSignal> {
+        let mut signals = Vec::new();
+
+        if let Some(path) = expand_tilde(self.global_detection_dir()) {
+            if path.exists() {
+                signals.push(DetectionSignal::Directory(path));
+            }
+        }
+
+        for name in self.binary_names() {
+            if let Some(path) = find_executable(name) {
+                signals.push(DetectionSignal::Binary(path));
+            }
+        }
+
+        signals
+    }
+
     /// Resolve the project-level skills directory to an absolute path.
     pub fn resolve_project_skills_dir(&self, project_root: &Path) -> PathBuf {
         project_root.join(self.skills_dir())
@@ -253,14 +534,12 @@ impl Agent {
 
     /// Check if this agent is detected at the project level.
     pub fn is_detected_project(&self, project_root: &Path) -> bool {
-        let detection_path = project_root.join(self.detection_dir());
-        detection_path.exists()
+        !self.project_signals(project_root).is_empty()
     }
 
     /// Check if this agent is detected at the global level.
     pub fn is_detected_global(&self) -> bool {
-        let dir = self.global_detection_dir();
-        expand_tilde(dir).map(|p| p.exists()).unwrap_or(false)
+        !self.global_signals().is_empty()
     }
 
     /// Detect all agents installed at the project level.
@@ -288,25 +567,29 @@ impl Agent {
         for agent in Agent::all() {
             // Check project level
             let project_path = agent.resolve_project_skills_dir(project_root);
-            if agent.is_detected_project(project_root) {
+            let signals = agent.project_signals(project_root);
+            if !signals.is_empty() {
                 let skill_count = count_skills(&project_path);
                 detected.push(DetectedAgent {
                     agent: *agent,
                     skills_path: project_path,
                     skill_count,
                     is_global: false,
+                    signals,
                 });
             }
 
             // Check global level
             if let Some(global_path) = agent.resolve_global_skills_dir() {
-                if agent.is_detected_global() {
+                let signals = agent.global_signals();
+                if !signals.is_empty() {
                     let skill_count = count_skills(&global_path);
                     detected.push(DetectedAgent {
                         agent: *agent,
                         skills_path: global_path,
                         skill_count,
                         is_global: true,
+                        signals,
                     });
                 }
             }
@@ -333,6 +616,42 @@ pub fn expand_tilde(path: &str) -> Option<PathBuf> {
     }
 }
 
+/// Find an executable by name on `PATH`.
+pub fn find_executable(name: &str) -> Option<PathBuf> {
+    let path_var = std::env::var_os("PATH")?;
+
+    for dir in std::env::split_paths(&path_var) {
+        let candidate = dir.join(name);
+        if is_executable(&candidate) {
+            return Some(candidate);
+        }
+
+        #[cfg(windows)]
+        {
+            let candidate = dir.join(format!("{}.exe", name));
+            if candidate.is_file() {
+                return Some(candidate);
+            }
+        }
+    }
+
+    None
+}
+
+/// Whether `path` is a file with an execute bit set.
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+}
+
+/// Whether `path` is a file; other platforms have no execute bit.
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
 /// Count the number of skills in a directory.
 fn count_skills(path: &Path) -> usize {
     if !path.exists() {
@@ -348,3 +667,59 @@ fn count_skills(path: &Path) -> usize {
         })
         .unwrap_or(0)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_project_signals_directory() {
+        let temp = TempDir::new().unwrap();
+        std::fs::create_dir(temp.path().join(".claude")).unwrap();
+
+        let signals = Agent::Claude.project_signals(temp.path());
+        assert_eq!(signals.len(), 1);
+        assert_eq!(signals[0].confidence(), Confidence::High);
+    }
+
+    #[test]
+    fn test_project_signals_config_file() {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(temp.path().join(".cursorrules"), "").unwrap();
+
+        assert!(Agent::Cursor.is_detected_project(temp.path()));
+        let signals = Agent::Cursor.project_signals(temp.path());
+        assert_eq!(signals[0].kind(), "config-file");
+        assert_eq!(signals[0].confidence(), Confidence::Medium);
+    }
+
+    #[test]
+    fn test_detected_agent_confidence_is_strongest_signal() {
+        let detected = DetectedAgent {
+            agent: Agent::Claude,
+            skills_path: PathBuf::from(".claude/skills"),
+            skill_count: 0,
+            is_global: false,
+            signals: vec![
+                DetectionSignal::ConfigFile(PathBuf::from("CLAUDE.md")),
+                DetectionSignal::Directory(PathBuf::from(".claude")),
+            ],
+        };
+        assert_eq!(detected.confidence(), Confidence::High);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_is_executable() {
+        use std::os::unix::fs::PermissionsExt;
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("claude");
+        std::fs::write(&path, "#!/bin/sh\n").unwrap();
+
+        assert!(!is_executable(&path));
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        assert!(is_executable(&path));
+        assert!(!is_executable(temp.path()));
+    }
+}
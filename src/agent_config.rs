@@ -0,0 +1,394 @@
+//! Edits to agent config files.
+//!
+//! Some agents only load skills from directories listed in their config
+//! (see [`Agent::skills_registration`]). Registering adds the skills
+//! directory to that list:
+//!
+//! ```json
+//! { "skills": { "paths": [".opencode/skill"] } }
+//! ```
+//!
+//! Claude Code asks before using a tool unless its settings allow it.
+//! [`sync_permissions`] keeps the allowed tools of installed skills in
+//! `permissions.allow`, recording the rules it manages so they can be
+//! removed again:
+//!
+//! ```json
+//! {
+//!   "permissions": { "allow": ["Read", "Bash(git log:*)"] },
+//!   "skilo": { "managedPermissions": ["Bash(git log:*)"] }
+//! }
+//! ```
+//!
+//! Edits are idempotent, keep the rest of the file and its key order, and
+//! back up the previous file as `<file>.bak` before changing it.
+
+use crate::agent::{expand_tilde, Agent};
+use crate::error::SkiloError;
+use crate::journal::write_atomic;
+use crate::scope::Scope;
+use serde_yaml::{Mapping, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The outcome of registering a directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Registration {
+    /// The directory was added; the previous file was copied to `backup`,
+    /// if there was one.
+    Added {
+        /// Path of the backup.
+        backup: Option<PathBuf>,
+    },
+    /// The directory was already listed.
+    AlreadyRegistered,
+}
+
+/// The outcome of syncing permissions.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PermissionSync {
+    /// Rules added to `permissions.allow`.
+    pub added: Vec<String>,
+    /// Managed rules removed because no skill needs them anymore.
+    pub removed: Vec<String>,
+    /// Path of the backup, if an existing file was changed.
+    pub backup: Option<PathBuf>,
+}
+
+impl PermissionSync {
+    /// Returns true if `permissions.allow` changed.
+    pub fn is_changed(&self) -> bool {
+        !self.added.is_empty() || !self.removed.is_empty()
+    }
+}
+
+/// Key of the allowed tools list in Claude Code settings.
+const ALLOW_KEY: &[&str] = &["permissions", "allow"];
+
+/// Key of the list of rules skilo added to [`ALLOW_KEY`].
+const MANAGED_KEY: &[&str] = &["skilo", "managedPermissions"];
+
+/// The config file to register `agent`'s skills in for `scope`, and the
+/// entry to add for `skills_dir`.
+///
+/// Returns `None` if the agent needs no registration or has no config
+/// file for the scope. Project entries are relative to `project_root`.
+pub fn registration_target(
+    agent: Agent,
+    scope: Scope,
+    project_root: &Path,
+    skills_dir: &Path,
+) -> Option<(PathBuf, String)> {
+    let registration = agent.skills_registration()?;
+    match scope {
+        Scope::Project => {
+            let file = project_root.join(registration.project_file?);
+            let entry = skills_dir.strip_prefix(project_root).unwrap_or(skills_dir);
+            Some((file, to_entry(entry)))
+        }
+        Scope::Global => {
+            let file = expand_tilde(registration.global_file)?;
+            Some((file, skills_dir.display().to_string()))
+        }
+    }
+}
+
+/// Add `entry` to the list at `key` in the config file at `path`.
+///
+/// The file and any missing keys are created. JSON and YAML files are
+/// supported, judging by the extension.
+pub fn register(path: &Path, key: &[&str], entry: &str) -> Result<Registration, SkiloError> {
+    let (existing, mut doc) = load(path)?;
+
+    if !insert(&mut doc, key, entry)
+        .map_err(|e| SkiloError::Config(format!("{}: {}", path.display(), e)))?
+    {
+        return Ok(Registration::AlreadyRegistered);
+    }
+
+    let backup = save(path, &doc, existing.is_some())?;
+    Ok(Registration::Added { backup })
+}
+
+/// Make the rules skilo manages in the `permissions.allow` list of the
+/// Claude Code settings file at `path` exactly `rules`.
+///
+/// Rules skilo added earlier that are no longer in `rules` are removed.
+/// Rules that were already allowed by hand are left alone and never become
+/// managed, so removing a skill does not revoke them. With `dry_run`, the
+/// changes are computed but the file is not written.
+pub fn sync_permissions(
+    path: &Path,
+    rules: &[String],
+    dry_run: bool,
+) -> Result<PermissionSync, SkiloError> {
+    let (existing, mut doc) = load(path)?;
+    let invalid = |e: String| SkiloError::Config(format!("{}: {}", path.display(), e));
+
+    let previous: Vec<String> = MANAGED_KEY
+        .iter()
+        .try_fold(&doc, |value, key| value.get(*key))
+        .and_then(Value::as_sequence)
+        .map(|list| {
+            list.iter()
+                .filter_map(|item| item.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut sync = PermissionSync::default();
+    let mut managed = Vec::new();
+    let allow = list_at(&mut doc, ALLOW_KEY).map_err(invalid)?;
+    allow.retain(|item| match item.as_str() {
+        Some(rule) if previous.iter().any(|p| p == rule) && !rules.iter().any(|r| r == rule) => {
+            sync.removed.push(rule.to_string());
+            false
+        }
+        _ => true,
+    });
+    for rule in rules {
+        if !allow.iter().any(|item| item.as_str() == Some(rule)) {
+            allow.push(Value::from(rule.as_str()));
+            sync.added.push(rule.clone());
+            managed.push(rule.clone());
+        } else if previous.contains(rule) && !managed.contains(rule) {
+            managed.push(rule.clone());
+        }
+    }
+    if allow.is_empty() {
+        remove_at(&mut doc, ALLOW_KEY);
+    }
+
+    if managed.is_empty() {
+        remove_at(&mut doc, MANAGED_KEY);
+    } else {
+        *list_at(&mut doc, MANAGED_KEY).map_err(invalid)? = managed
+            .iter()
+            .map(|rule| Value::from(rule.as_str()))
+            .collect();
+    }
+
+    if (sync.is_changed() || managed != previous) && !dry_run {
+        sync.backup = save(path, &doc, existing.is_some())?;
+    }
+    Ok(sync)
+}
+
+/// Read the config file at `path`, returning its content, if it exists,
+/// and the parsed document.
+fn load(path: &Path) -> Result<(Option<String>, Value), SkiloError> {
+    let existing = match fs::read_to_string(path) {
+        Ok(content) => Some(content),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => return Err(e.into()),
+    };
+
+    // JSON is parsed as YAML too: YAML mappings keep their key order,
+    // which serde_json's maps do not.
+    let doc = match existing.as_deref() {
+        Some(content) if !content.trim().is_empty() => serde_yaml::from_str(content)
+            .map_err(|e| SkiloError::Config(format!("{}: {}", path.display(), e)))?,
+        _ => Value::Mapping(Mapping::new()),
+    };
+    Ok((existing, doc))
+}
+
+/// Write `doc` to `path`, backing up the previous file if it `existed`.
+///
+/// Returns the path of the backup.
+fn save(path: &Path, doc: &Value, existed: bool) -> Result<Option<PathBuf>, SkiloError> {
+    let content = if is_json(path) {
+        serde_json::to_string_pretty(&doc)
+            .map(|json| json + "\n")
+            .map_err(|e| {
+                SkiloError::Config(format!("Failed to serialize {}: {}", path.display(), e))
+            })?
+    } else {
+        serde_yaml::to_string(&doc).map_err(|e| {
+            SkiloError::Config(format!("Failed to serialize {}: {}", path.display(), e))
+        })?
+    };
+
+    let backup = if existed {
+        let mut name = path.file_name().unwrap_or_default().to_os_string();
+        name.push(".bak");
+        let backup = path.with_file_name(name);
+        fs::copy(path, &backup)?;
+        Some(backup)
+    } else {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        None
+    };
+    write_atomic(path, content)?;
+
+    Ok(backup)
+}
+
+/// Add `entry` to the list at `key`, creating missing keys.
+///
+/// Returns false if the entry is already listed.
+fn insert(doc: &mut Value, key: &[&str], entry: &str) -> Result<bool, String> {
+    let list = list_at(doc, key)?;
+    if list.iter().any(|item| item.as_str() == Some(entry)) {
+        return Ok(false);
+    }
+    list.push(Value::from(entry));
+    Ok(true)
+}
+
+/// The list at `key`, creating it and any missing keys.
+fn list_at<'a>(doc: &'a mut Value, key: &[&str]) -> Result<&'a mut Vec<Value>, String> {
+    let mut current = doc;
+    for (i, part) in key.iter().enumerate() {
+        let path = key[..=i].join(".");
+        if current.is_null() {
+            *current = Value::Mapping(Mapping::new());
+        }
+        let Value::Mapping(map) = current else {
+            return Err(format!("expected a mapping containing '{}'", path));
+        };
+        current = map
+            .entry(Value::from(*part))
+            .or_insert(if i + 1 == key.len() {
+                Value::Sequence(Vec::new())
+            } else {
+                Value::Mapping(Mapping::new())
+            });
+    }
+
+    if current.is_null() {
+        *current = Value::Sequence(Vec::new());
+    }
+    let Value::Sequence(list) = current else {
+        return Err(format!("expected a list at '{}'", key.join(".")));
+    };
+    Ok(list)
+}
+
+/// Remove `key`, and any mappings left empty by removing it.
+fn remove_at(doc: &mut Value, key: &[&str]) {
+    let (Some((first, rest)), Value::Mapping(map)) = (key.split_first(), doc) else {
+        return;
+    };
+    if !rest.is_empty() {
+        let Some(child) = map.get_mut(*first) else {
+            return;
+        };
+        remove_at(child, rest);
+        if !child.as_mapping().is_some_and(Mapping::is_empty) {
+            return;
+        }
+    }
+    map.remove(*first);
+}
+
+/// Returns true if `path` is a JSON file.
+fn is_json(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+}
+
+/// A relative path as a config entry, with `/` separators.
+fn to_entry(path: &Path) -> String {
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_register_json() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("opencode.json");
+        fs::write(
+            &path,
+            "{\n  \"theme\": \"dark\",\n  \"$schema\": \"https://opencode.ai/config.json\"\n}\n",
+        )
+        .unwrap();
+
+        let key = &["skills", "paths"];
+        let outcome = register(&path, key, ".opencode/skill").unwrap();
+        assert_eq!(
+            outcome,
+            Registration::Added {
+                backup: Some(temp.path().join("opencode.json.bak"))
+            }
+        );
+        assert_eq!(
+            register(&path, key, ".opencode/skill").unwrap(),
+            Registration::AlreadyRegistered
+        );
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.find("theme").unwrap() < content.find("$schema").unwrap());
+        let json: serde_json::Value = serde_json::from_str(&content).unwrap();
+        assert_eq!(json["skills"]["paths"][0], ".opencode/skill");
+        assert!(fs::read_to_string(temp.path().join("opencode.json.bak"))
+            .unwrap()
+            .contains("dark"));
+    }
+
+    #[test]
+    fn test_register_yaml() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("goose/config.yaml");
+
+        let key = &["skills", "paths"];
+        assert_eq!(
+            register(&path, key, "/home/me/.config/goose/skills").unwrap(),
+            Registration::Added { backup: None }
+        );
+        register(&path, key, "/work/skills").unwrap();
+
+        let doc: Value = serde_yaml::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(doc["skills"]["paths"].as_sequence().unwrap().len(), 2);
+
+        fs::write(&path, "skills: enabled\n").unwrap();
+        assert!(register(&path, key, "/work/skills").is_err());
+    }
+
+    #[test]
+    fn test_sync_permissions() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join(".claude/settings.json");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(
+            &path,
+            "{\n  \"permissions\": { \"allow\": [\"Read\"] }\n}\n",
+        )
+        .unwrap();
+
+        let rules = vec!["Read".to_string(), "Bash(git log:*)".to_string()];
+        let sync = sync_permissions(&path, &rules, true).unwrap();
+        assert_eq!(sync.added, vec!["Bash(git log:*)"]);
+        assert!(!fs::read_to_string(&path).unwrap().contains("git log"));
+
+        sync_permissions(&path, &rules, false).unwrap();
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(
+            json["permissions"]["allow"],
+            serde_json::json!(["Read", "Bash(git log:*)"])
+        );
+        assert_eq!(
+            json["skilo"]["managedPermissions"],
+            serde_json::json!(["Bash(git log:*)"])
+        );
+        assert!(!sync_permissions(&path, &rules, false).unwrap().is_changed());
+
+        // Hand-added rules stay when no skill needs them anymore
+        let sync = sync_permissions(&path, &[], false).unwrap();
+        assert_eq!(sync.removed, vec!["Bash(git log:*)"]);
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(json["permissions"]["allow"], serde_json::json!(["Read"]));
+        assert!(json.get("skilo").is_none());
+    }
+}
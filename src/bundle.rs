@@ -0,0 +1,175 @@
+//! Bundles of skills for installing without network access.
+//!
+//! A bundle is a gzip-compressed tarball:
+//!
+//! ```text
+//! index.json           # Sources, their skills, and checksums
+//! sources/<n>/<name>/  # Files of each skill, grouped by source
+//! ```
+//!
+//! `bundle export` writes one on a connected machine; `bundle import`
+//! checks every skill against its checksum before installing anything.
+
+use crate::journal::write_atomic;
+use crate::lockfile::checksum_dir;
+use crate::SkiloError;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+/// Current bundle format version.
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// Name of the index at the root of a bundle.
+pub const INDEX_FILE: &str = "index.json";
+
+/// The index of a bundle.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleIndex {
+    /// Format version.
+    pub version: u32,
+    /// When the bundle was written (seconds since the Unix epoch).
+    pub created_at: u64,
+    /// Bundled sources, in the order they were listed.
+    pub sources: Vec<BundledSource>,
+}
+
+/// A source in a bundle and the skills taken from it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundledSource {
+    /// The source the skills were fetched from.
+    pub source: String,
+    /// Subdirectory within the source.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subdir: Option<String>,
+    /// The requested branch, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    /// The requested tag, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    /// The requested `@ref`, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rev: Option<String>,
+    /// The commit that was fetched, for git sources.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    /// Directory in the bundle holding the source's skills.
+    pub path: String,
+    /// The bundled skills, each in a directory named after it.
+    pub skills: Vec<BundledSkill>,
+}
+
+/// A skill in a bundle.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundledSkill {
+    /// The skill name.
+    pub name: String,
+    /// Checksum of the skill's files, as computed by [`checksum_dir`].
+    pub checksum: String,
+}
+
+/// Write the directory `dir`, holding the files `index` lists, to the
+/// bundle `output` with `index` at its root.
+pub fn pack(dir: &Path, index: &BundleIndex, output: &Path) -> Result<(), SkiloError> {
+    let json = serde_json::to_string_pretty(index)
+        .map_err(|e| SkiloError::Config(format!("JSON serialization failed: {}", e)))?;
+    fs::write(dir.join(INDEX_FILE), format!("{}\n", json))?;
+
+    let mut builder = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+    builder.follow_symlinks(false);
+    builder.append_dir_all(".", dir)?;
+    let data = builder.into_inner()?.finish()?;
+
+    write_atomic(output, data)?;
+    Ok(())
+}
+
+/// Unpack the bundle `bundle` into a temporary directory, checking its
+/// version and the checksum of every skill.
+pub fn unpack(bundle: &Path) -> Result<(TempDir, BundleIndex), SkiloError> {
+    let invalid = |reason: String| {
+        SkiloError::Config(format!("Invalid bundle {}: {}", bundle.display(), reason))
+    };
+
+    let file = fs::File::open(bundle)?;
+    let temp_dir = TempDir::new()?;
+    tar::Archive::new(GzDecoder::new(file))
+        .unpack(temp_dir.path())
+        .map_err(|e| invalid(e.to_string()))?;
+
+    let content = fs::read_to_string(temp_dir.path().join(INDEX_FILE))
+        .map_err(|_| invalid(format!("missing {}", INDEX_FILE)))?;
+    let index: BundleIndex = serde_json::from_str(&content).map_err(|e| invalid(e.to_string()))?;
+    if index.version > BUNDLE_VERSION {
+        return Err(invalid(format!(
+            "version {} is newer than supported version {}",
+            index.version, BUNDLE_VERSION
+        )));
+    }
+
+    for source in &index.sources {
+        for skill in &source.skills {
+            let dir = temp_dir.path().join(&source.path).join(&skill.name);
+            let checksum = checksum_dir(&dir).ok();
+            if checksum.as_deref() != Some(skill.checksum.as_str()) {
+                return Err(invalid(format!(
+                    "checksum mismatch for skill '{}' from {}",
+                    skill.name, source.source
+                )));
+            }
+        }
+    }
+
+    Ok((temp_dir, index))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pack_unpack() {
+        let temp = TempDir::new().unwrap();
+        let staging = temp.path().join("staging");
+        let skill = staging.join("sources/0/my-skill");
+        fs::create_dir_all(&skill).unwrap();
+        fs::write(skill.join("SKILL.md"), "---\nname: my-skill\n---\n").unwrap();
+
+        let mut index = BundleIndex {
+            version: BUNDLE_VERSION,
+            created_at: 0,
+            sources: vec![BundledSource {
+                source: "https://github.com/owner/repo.git".to_string(),
+                subdir: None,
+                branch: None,
+                tag: None,
+                rev: None,
+                commit: Some("abc123".to_string()),
+                path: "sources/0".to_string(),
+                skills: vec![BundledSkill {
+                    name: "my-skill".to_string(),
+                    checksum: checksum_dir(&skill).unwrap(),
+                }],
+            }],
+        };
+        let bundle = temp.path().join("out.bundle");
+        pack(&staging, &index, &bundle).unwrap();
+
+        let (unpacked, read) = unpack(&bundle).unwrap();
+        assert_eq!(read.sources[0].commit.as_deref(), Some("abc123"));
+        assert!(unpacked
+            .path()
+            .join("sources/0/my-skill/SKILL.md")
+            .is_file());
+
+        index.sources[0].skills[0].checksum = "sha256:0".to_string();
+        pack(&staging, &index, &bundle).unwrap();
+        let err = unpack(&bundle).unwrap_err().to_string();
+        assert!(err.contains("checksum mismatch"), "{}", err);
+    }
+}
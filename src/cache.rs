@@ -9,22 +9,41 @@
 //!     └── db/           # Bare git repositories (fetch targets)
 //! ```
 
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashSet};
 use std::env;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::SystemTime;
+use walkdir::WalkDir;
+
+/// Smallest file considered by [`find_duplicates`] (64 KiB).
+pub const DUPLICATE_MIN_SIZE: u64 = 64 * 1024;
 
 /// Get the skilo home directory.
 ///
 /// Resolution order:
 /// 1. `SKILO_HOME` environment variable
 /// 2. `~/.skilo/`
+#[cfg(not(test))]
 pub fn skilo_home() -> Option<PathBuf> {
     env::var_os("SKILO_HOME")
         .map(PathBuf::from)
         .or_else(|| dirs::home_dir().map(|h| h.join(".skilo")))
 }
 
+/// Get the skilo home directory: in tests, a temporary directory per
+/// thread, so the journal, store, and history of the user are never
+/// touched.
+#[cfg(test)]
+pub fn skilo_home() -> Option<PathBuf> {
+    thread_local! {
+        static HOME: tempfile::TempDir = tempfile::TempDir::new().unwrap();
+    }
+    HOME.with(|home| Some(home.path().to_path_buf()))
+}
+
 /// Get the git cache directory.
 ///
 /// Resolution order:
@@ -36,6 +55,11 @@ pub fn git_dir() -> Option<PathBuf> {
         .or_else(|| skilo_home().map(|h| h.join("git")))
 }
 
+/// Get the directory replaced skills are backed up to (`~/.skilo/backups/`).
+pub fn backups_dir() -> Option<PathBuf> {
+    skilo_home().map(|h| h.join("backups"))
+}
+
 /// Get the bare repositories directory (`~/.skilo/git/db/`).
 pub fn db_dir() -> Option<PathBuf> {
     git_dir().map(|g| g.join("db"))
@@ -203,7 +227,7 @@ impl CacheStats {
 }
 
 /// Calculate directory size recursively.
-fn dir_size(path: &PathBuf) -> u64 {
+pub fn dir_size(path: &Path) -> u64 {
     let mut size = 0;
 
     if let Ok(entries) = fs::read_dir(path) {
@@ -220,6 +244,106 @@ fn dir_size(path: &PathBuf) -> u64 {
     size
 }
 
+/// A file whose content is stored at several paths.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DuplicateFile {
+    /// Size of each copy in bytes.
+    pub size: u64,
+    /// Checksum of the content (`sha256:<hex>`).
+    pub hash: String,
+    /// Paths of the copies, sorted.
+    pub paths: Vec<PathBuf>,
+}
+
+impl DuplicateFile {
+    /// Bytes that keeping a single copy would free.
+    pub fn wasted(&self) -> u64 {
+        self.size * (self.paths.len() as u64).saturating_sub(1)
+    }
+}
+
+/// Find files of at least `min_size` bytes with identical content under
+/// `roots`, largest waste first.
+///
+/// Only files of equal size are hashed. `.git` directories are skipped,
+/// and hard links to the same file count as one copy since they take no
+/// extra space.
+pub fn find_duplicates(roots: &[PathBuf], min_size: u64) -> Vec<DuplicateFile> {
+    let mut by_size: BTreeMap<u64, Vec<PathBuf>> = BTreeMap::new();
+    let mut seen = HashSet::new();
+
+    for root in roots {
+        let files = WalkDir::new(root)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|e| e.file_name() != ".git")
+            .filter_map(Result::ok)
+            .filter(|e| e.file_type().is_file());
+        for file in files {
+            let Ok(metadata) = file.metadata() else {
+                continue;
+            };
+            if metadata.len() < min_size {
+                continue;
+            }
+            if file_id(&metadata).is_none_or(|id| seen.insert(id)) {
+                by_size
+                    .entry(metadata.len())
+                    .or_default()
+                    .push(file.into_path());
+            }
+        }
+    }
+
+    let mut duplicates = Vec::new();
+    for (size, paths) in by_size.into_iter().filter(|(_, paths)| paths.len() > 1) {
+        let mut by_hash: BTreeMap<String, Vec<PathBuf>> = BTreeMap::new();
+        for path in paths {
+            if let Ok(hash) = file_hash(&path) {
+                by_hash.entry(hash).or_default().push(path);
+            }
+        }
+        for (hash, mut paths) in by_hash {
+            if paths.len() > 1 {
+                paths.sort();
+                duplicates.push(DuplicateFile { size, hash, paths });
+            }
+        }
+    }
+
+    duplicates.sort_by(|a, b| {
+        b.wasted()
+            .cmp(&a.wasted())
+            .then_with(|| a.paths.cmp(&b.paths))
+    });
+    duplicates
+}
+
+/// The device and inode of a file, identifying hard links to it.
+#[cfg(unix)]
+fn file_id(metadata: &fs::Metadata) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    Some((metadata.dev(), metadata.ino()))
+}
+
+/// Hard links are not detected on this platform.
+#[cfg(not(unix))]
+fn file_id(_metadata: &fs::Metadata) -> Option<(u64, u64)> {
+    None
+}
+
+/// Checksum of a file's content (`sha256:<hex>`).
+fn file_hash(path: &Path) -> std::io::Result<String> {
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut fs::File::open(path)?, &mut hasher)?;
+    let hex: String = hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    Ok(format!("sha256:{}", hex))
+}
+
 /// Format bytes as human-readable string.
 pub fn format_size(bytes: u64) -> String {
     const KB: u64 = 1024;
@@ -342,6 +466,43 @@ mod tests {
         assert_eq!(repo, "skills");
     }
 
+    #[test]
+    fn test_find_duplicates() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let data = vec![7u8; 2048];
+        for skill in ["a", "b", "c"] {
+            fs::create_dir_all(temp.path().join(skill).join("assets")).unwrap();
+        }
+        fs::write(temp.path().join("a/assets/logo.png"), &data).unwrap();
+        fs::write(temp.path().join("b/assets/logo.png"), &data).unwrap();
+        fs::write(temp.path().join("c/assets/other.png"), vec![8u8; 2048]).unwrap();
+        fs::write(temp.path().join("c/assets/small.txt"), "tiny").unwrap();
+        fs::write(temp.path().join("a/small.txt"), "tiny").unwrap();
+
+        let duplicates = find_duplicates(&[temp.path().to_path_buf()], 1024);
+        assert_eq!(duplicates.len(), 1);
+        assert_eq!(duplicates[0].size, 2048);
+        assert_eq!(duplicates[0].wasted(), 2048);
+        assert_eq!(
+            duplicates[0].paths,
+            vec![
+                temp.path().join("a/assets/logo.png"),
+                temp.path().join("b/assets/logo.png")
+            ]
+        );
+
+        #[cfg(unix)]
+        {
+            fs::remove_file(temp.path().join("b/assets/logo.png")).unwrap();
+            fs::hard_link(
+                temp.path().join("a/assets/logo.png"),
+                temp.path().join("b/assets/logo.png"),
+            )
+            .unwrap();
+            assert!(find_duplicates(&[temp.path().to_path_buf()], 1024).is_empty());
+        }
+    }
+
     #[test]
     fn test_db_name() {
         assert_eq!(db_name("anthropics", "skills"), "anthropics-skills");
@@ -1,5 +1,7 @@
 //! Command-line interface definitions.
 
+use crate::scope::Scope;
+use crate::skill::rules::NameFix;
 use clap::{Parser, Subcommand, ValueEnum};
 use std::path::PathBuf;
 
@@ -13,7 +15,8 @@ pub struct Cli {
     #[command(subcommand)]
     pub command: Command,
 
-    /// Configuration file path
+    /// Configuration file path, read instead of both the global and the
+    /// project config
     #[arg(long, global = true, env = "SKILO_CONFIG")]
     pub config: Option<PathBuf>,
 
@@ -24,6 +27,25 @@ pub struct Cli {
     /// Suppress non-error output
     #[arg(long, short, global = true)]
     pub quiet: bool,
+
+    /// When to use colored output
+    ///
+    /// `auto` respects NO_COLOR and CLICOLOR_FORCE and only colors terminal output.
+    #[arg(long, global = true, default_value = "auto", value_enum)]
+    pub color: ColorChoice,
+
+    /// How file paths appear in diagnostics
+    ///
+    /// `relative` is relative to the current directory, `root` to the git
+    /// repository root. Paths outside the base are shown absolute.
+    #[arg(long, global = true, default_value = "relative", value_enum)]
+    pub path_style: PathStyle,
+
+    /// Stable tab-separated output for scripts (list, agents, add)
+    ///
+    /// One record per line, no color or headers. See README for field layouts.
+    #[arg(long, global = true)]
+    pub porcelain: bool,
 }
 
 /// Available CLI commands.
@@ -42,6 +64,23 @@ pub enum Command {
     /// Create a new skill from a template
     New(NewArgs),
 
+    /// Scaffold a repository for a collection of skills
+    ///
+    /// `init repo` creates skills/, a .skilorc.toml, a CI workflow running
+    /// `skilo ci-check` and checking the skill index in AGENTS.md, and adds
+    /// recommended .gitattributes and .gitignore entries.
+    #[command(verbatim_doc_comment)]
+    Init(InitArgs),
+
+    /// Combine several skills into one
+    ///
+    /// Writes a new skill with one section per source skill. Scripts,
+    /// references, and other files are copied with the source skill's name
+    /// as a prefix, and links to them are updated. allowed-tools, tags,
+    /// metadata, hooks, and requires are combined. The result is then validated.
+    #[command(verbatim_doc_comment)]
+    Merge(MergeArgs),
+
     /// Validate skills against the specification
     ///
     /// Skills must be directories containing a SKILL.md file with valid frontmatter.
@@ -66,6 +105,8 @@ pub enum Command {
     ///
     /// Outputs skill metadata including name, description, license,
     /// compatibility, metadata, and allowed_tools for one or more skills.
+    /// With --aggregate, reports which metadata keys the skills set, how
+    /// often each value occurs, and which skills lack --require'd keys.
     #[command(verbatim_doc_comment)]
     ReadProperties(ReadPropertiesArgs),
 
@@ -77,12 +118,62 @@ pub enum Command {
     #[command(verbatim_doc_comment)]
     ToPrompt(ToPromptArgs),
 
+    /// Show a tree view of skill files
+    ///
+    /// Prints each skill's files with sizes, SKILL.md line and token
+    /// counts, detected script interpreters, and a validation badge.
+    #[command(verbatim_doc_comment)]
+    Tree(TreeArgs),
+
+    /// Preview a skill as rendered markdown
+    ///
+    /// Shows a summary of the frontmatter followed by the styled SKILL.md
+    /// body, and optionally a reference document, so you can review a
+    /// skill without opening an editor.
+    #[command(verbatim_doc_comment)]
+    Preview(PreviewArgs),
+
+    /// Check that this machine meets skills' runtime requirements
+    ///
+    /// Verifies the programs (and minimum versions) and environment
+    /// variables listed under each skill's `requires` frontmatter.
+    /// Exits with 1 if anything is missing.
+    #[command(verbatim_doc_comment)]
+    EnvCheck(EnvCheckArgs),
+
     /// List installed skills
     ///
     /// Shows skills installed at project or global level.
     #[command(verbatim_doc_comment)]
     List(ListArgs),
 
+    /// Search installed skills
+    ///
+    /// Searches names, descriptions, bodies, and scripts of the skills
+    /// installed for every agent and prints matches as file:line:text.
+    /// Exits with 1 when nothing matches.
+    #[command(verbatim_doc_comment)]
+    Grep(GrepArgs),
+
+    /// Sync Claude Code tool permissions with installed skills
+    ///
+    /// Collects the allowed-tools of installed Claude Code skills and shows
+    /// the permission rules missing from .claude/settings.json, or merges
+    /// them in with --write. Rules skilo adds are recorded under
+    /// "skilo.managedPermissions" and removed again when no installed
+    /// skill needs them; rules you added yourself are never touched.
+    #[command(verbatim_doc_comment)]
+    Permissions(PermissionsArgs),
+
+    /// Find which installed skills provide a script or request a tool
+    ///
+    /// Looks up a script file name (or path) and tool names from
+    /// allowed-tools across agents and scopes, printing the owning skill,
+    /// its path, and the source it was installed from. Exits with 1 when
+    /// no skill matches.
+    #[command(verbatim_doc_comment)]
+    Which(WhichArgs),
+
     /// Remove installed skills
     ///
     /// Removes skills from project or global level by name.
@@ -96,6 +187,91 @@ pub enum Command {
     #[command(verbatim_doc_comment)]
     Agents(AgentsArgs),
 
+    /// Verify installed skills match skills.lock
+    ///
+    /// Fails if any locked skill is missing or locally modified, or if
+    /// skills are installed that the lockfile does not record.
+    /// Use --format json for a machine-readable drift report.
+    #[command(verbatim_doc_comment)]
+    Ci(CiArgs),
+
+    /// Run every skill check CI needs in one invocation
+    ///
+    /// Discovers skills, lints them strictly, checks formatting (W019),
+    /// and looks for duplicate skill names (E017) and committed secrets
+    /// (E016). Prints one merged report in --format and writes a Markdown
+    /// summary to --summary or $GITHUB_STEP_SUMMARY.
+    /// Exits with 1 on any error or warning.
+    #[command(verbatim_doc_comment)]
+    CiCheck(CiCheckArgs),
+
+    /// Generate Claude Code plugin metadata for a set of skills
+    ///
+    /// Validates the skills in DIR/skills/ and writes
+    /// .claude-plugin/plugin.json. With --marketplace, also writes
+    /// .claude-plugin/marketplace.json listing the plugin. Existing files
+    /// are updated in place, keeping fields skilo does not manage.
+    #[command(verbatim_doc_comment)]
+    Plugin(PluginArgs),
+
+    /// Export installed skills to a manifest
+    ///
+    /// Writes every skill recorded in skills.lock (project and global),
+    /// with its agent, scope, source, and version, as JSON.
+    #[command(verbatim_doc_comment)]
+    Export(ExportArgs),
+
+    /// Reinstall skills from an exported manifest
+    ///
+    /// Installs each skill in the manifest from its recorded source,
+    /// for the same agent and scope.
+    #[command(verbatim_doc_comment)]
+    Import(ImportArgs),
+
+    /// Carry skills to machines without network access
+    ///
+    /// `bundle export` fetches every listed source into one archive with
+    /// an index and checksums; `bundle import` installs from the archive,
+    /// recording the original sources in skills.lock.
+    #[command(verbatim_doc_comment)]
+    Bundle(BundleArgs),
+
+    /// Remove artifacts left behind by skilo
+    ///
+    /// Deletes stray .skilo-update-tmp and .skilo-backup files from
+    /// interrupted self-updates. With --empty-dirs, also removes empty
+    /// skills directories in the current project.
+    #[command(verbatim_doc_comment)]
+    Clean(CleanArgs),
+
+    /// Vendor skills into the repository
+    ///
+    /// Installs skills into vendor/skills/ for committing alongside the
+    /// project, independent of any agent, and records their sources in
+    /// skills.lock. Use --update to refetch all vendored skills.
+    #[command(verbatim_doc_comment)]
+    Vendor(VendorArgs),
+
+    /// Find and repair interrupted operations
+    ///
+    /// Lists installs and fetches that were interrupted before finishing.
+    /// With --repair, completes each one if its new content is already in
+    /// place, or otherwise restores what was there before.
+    ///
+    /// With --auth SOURCE, tests each way of authenticating to the source's
+    /// host instead and reports which fail and why.
+    #[command(verbatim_doc_comment)]
+    Doctor(DoctorArgs),
+
+    /// Find skills installed more than once under different names
+    ///
+    /// Compares installed skills across agents and scopes and reports
+    /// identical copies (only the name differs) and similar copies (only
+    /// the frontmatter differs). With --consolidate, keeps the first copy
+    /// of each and removes the others, or links them with --link.
+    #[command(verbatim_doc_comment)]
+    Dedupe(DedupeArgs),
+
     /// Manage the git cache
     ///
     /// Skilo caches git repositories in ~/.skilo/git/ to speed up
@@ -103,6 +279,55 @@ pub enum Command {
     #[command(verbatim_doc_comment)]
     Cache(CacheArgs),
 
+    /// Manage the shared store of installed skill files
+    ///
+    /// Installed skills are kept once in ~/.skilo/store/ and hard linked
+    /// into each skills directory. Use `store gc` to remove content no
+    /// installed skill links to anymore.
+    #[command(verbatim_doc_comment)]
+    Store(StoreArgs),
+
+    /// Show recent installs, updates, and removals
+    ///
+    /// Reads the local history in ~/.skilo/history.jsonl, newest first.
+    /// Nothing is sent anywhere; disable recording with
+    /// `[history] enabled = false`.
+    #[command(verbatim_doc_comment)]
+    Activity(ActivityArgs),
+
+    /// Drive skilo over a JSON-RPC 2.0 protocol
+    ///
+    /// Reads one request per line from stdin and writes responses and
+    /// progress notifications to stdout. Methods: discover, lint, list,
+    /// add, remove.
+    #[command(verbatim_doc_comment)]
+    Api(ApiArgs),
+
+    /// Serve a local web UI and REST API for managing skills
+    ///
+    /// Browse installed skills per agent, view rendered SKILL.md files,
+    /// run lint, and install or remove skills from a browser.
+    /// Listens on 127.0.0.1 unless --bind is given; other addresses
+    /// also need --allow-remote.
+    #[cfg(feature = "serve")]
+    #[command(verbatim_doc_comment)]
+    Serve(ServeArgs),
+
+    /// Time discovery, parsing, validation, and formatting
+    ///
+    /// Runs each phase over the skills under --path and reports the time
+    /// taken and throughput, with a breakdown per lint rule.
+    #[command(hide = true, verbatim_doc_comment)]
+    Bench(BenchArgs),
+
+    /// Run golden tests for lint rules
+    ///
+    /// Lints each case directory under DIR and compares the diagnostics
+    /// with the case's expected.txt. Use --bless to record the current
+    /// diagnostics as expected.
+    #[command(hide = true, verbatim_doc_comment)]
+    Selftest(SelftestArgs),
+
     /// Manage the skilo installation
     #[command(name = "self")]
     SelfCmd(SelfArgs),
@@ -112,7 +337,16 @@ pub enum Command {
 #[derive(clap::Args, Clone)]
 pub struct AddArgs {
     /// Source to install skills from (e.g., owner/repo, URL, or path)
-    pub source: String,
+    #[arg(required_unless_present = "from_file")]
+    pub source: Option<String>,
+
+    /// Install from every source listed in FILE ('-' reads stdin)
+    ///
+    /// One source per line, optionally followed by `--skill NAME` filters
+    /// that replace any `--skill` given on the command line. Blank lines and
+    /// lines starting with '#' are ignored.
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["source", "list"])]
+    pub from_file: Option<std::path::PathBuf>,
 
     /// Install specific skill(s) by name
     #[arg(long, short)]
@@ -134,9 +368,20 @@ pub struct AddArgs {
     #[arg(long, short = 't')]
     pub tag: Option<String>,
 
+    /// Install from a specific commit (full or abbreviated SHA)
+    #[arg(
+        long,
+        value_name = "SHA",
+        conflicts_with_all = ["branch", "tag", "from_file"]
+    )]
+    pub rev: Option<String>,
+
     /// Only import skills from a specific subdirectory within the source
     ///
-    /// Example: --path moonbeam-engineering
+    /// Globs select several subdirectories at once; `*` stays within one
+    /// path segment and `**` matches at any depth.
+    ///
+    /// Example: --path moonbeam-engineering, --path 'skills/web-*'
     #[arg(long, short)]
     pub path: Option<String>,
 
@@ -147,20 +392,72 @@ pub struct AddArgs {
     #[arg(long, short, value_enum)]
     pub agent: Option<Vec<Agent>>,
 
-    /// Install to global skills directory (~/.claude/skills/)
+    /// Install to global skills directory (shorthand for --scope global)
     #[arg(long, short = 'g')]
     pub global: bool,
 
+    /// Installation scope
+    #[arg(long, value_enum, conflicts_with = "global")]
+    pub scope: Option<ScopeArg>,
+
     /// Custom output directory
-    #[arg(long, short, conflicts_with_all = ["agent", "global"])]
+    #[arg(long, short, conflicts_with_all = ["agent", "global", "scope"])]
     pub output: Option<std::path::PathBuf>,
+
+    /// List the skills directory in the agent's config file, for agents
+    /// that only load skills from configured paths (OpenCode, Goose)
+    #[arg(long, conflicts_with = "output")]
+    pub register: bool,
+
+    /// SSH private key to authenticate git fetches with
+    ///
+    /// Tried after the SSH agent and before the keys ~/.ssh/config lists
+    /// for the host. Overrides `add.ssh_key` in the config.
+    #[arg(long, value_name = "PATH")]
+    pub ssh_key: Option<std::path::PathBuf>,
+}
+
+/// Installation scope selection for `add`, `remove`, and `list`.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScopeArg {
+    /// Project-level skills directory
+    Project,
+    /// Global skills directory in the home directory
+    Global,
+    /// Both project and global skills directories
+    Both,
 }
 
-/// Represents a CLI agent selection: either all agents or a specific one.
+impl ScopeArg {
+    /// Returns the scopes covered by this selection.
+    pub fn scopes(self) -> Vec<Scope> {
+        match self {
+            ScopeArg::Project => vec![Scope::Project],
+            ScopeArg::Global => vec![Scope::Global],
+            ScopeArg::Both => vec![Scope::Project, Scope::Global],
+        }
+    }
+
+    /// Resolve the scopes selected by `--scope` or `--global`.
+    ///
+    /// Falls back to `default` when neither flag is given.
+    pub fn resolve(scope: Option<ScopeArg>, global: bool, default: Scope) -> Vec<Scope> {
+        match scope {
+            Some(scope) => scope.scopes(),
+            None if global => vec![Scope::Global],
+            None => vec![default],
+        }
+    }
+}
+
+/// Represents a CLI agent selection: all agents, a specific one, or the
+/// plain `./skills/` directory.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AgentSelection {
     /// All detected agents.
     All,
+    /// The project's `./skills/` directory, not tied to any agent.
+    Generic,
     /// A single specific agent.
     Single(crate::agent::Agent),
 }
@@ -170,6 +467,8 @@ pub enum AgentSelection {
 pub enum Agent {
     /// All detected agents
     All,
+    /// The project's ./skills/ directory, not tied to any agent
+    Generic,
     /// OpenCode
     OpenCode,
     /// Claude Code
@@ -207,6 +506,7 @@ impl Agent {
     pub fn to_selection(&self) -> AgentSelection {
         match self {
             Agent::All => AgentSelection::All,
+            Agent::Generic => AgentSelection::Generic,
             Agent::OpenCode => AgentSelection::Single(crate::agent::Agent::OpenCode),
             Agent::Claude => AgentSelection::Single(crate::agent::Agent::Claude),
             Agent::Codex => AgentSelection::Single(crate::agent::Agent::Codex),
@@ -226,11 +526,89 @@ impl Agent {
     }
 }
 
+impl From<crate::agent::Agent> for Agent {
+    fn from(agent: crate::agent::Agent) -> Self {
+        match agent {
+            crate::agent::Agent::OpenCode => Agent::OpenCode,
+            crate::agent::Agent::Claude => Agent::Claude,
+            crate::agent::Agent::Codex => Agent::Codex,
+            crate::agent::Agent::Cursor => Agent::Cursor,
+            crate::agent::Agent::Amp => Agent::Amp,
+            crate::agent::Agent::KiloCode => Agent::KiloCode,
+            crate::agent::Agent::RooCode => Agent::RooCode,
+            crate::agent::Agent::Goose => Agent::Goose,
+            crate::agent::Agent::Gemini => Agent::Gemini,
+            crate::agent::Agent::Antigravity => Agent::Antigravity,
+            crate::agent::Agent::Copilot => Agent::Copilot,
+            crate::agent::Agent::Clawdbot => Agent::Clawdbot,
+            crate::agent::Agent::Droid => Agent::Droid,
+            crate::agent::Agent::Windsurf => Agent::Windsurf,
+            crate::agent::Agent::PiMono => Agent::PiMono,
+        }
+    }
+}
+
+impl From<Scope> for ScopeArg {
+    fn from(scope: Scope) -> Self {
+        match scope {
+            Scope::Project => ScopeArg::Project,
+            Scope::Global => ScopeArg::Global,
+        }
+    }
+}
+
+/// Arguments for the `init` command.
+#[derive(clap::Args, Clone)]
+pub struct InitArgs {
+    /// Init subcommand
+    #[command(subcommand)]
+    pub command: InitCommand,
+}
+
+/// Init subcommands.
+#[derive(Subcommand, Clone)]
+pub enum InitCommand {
+    /// Scaffold a skill repository
+    Repo {
+        /// Repository root
+        #[arg(default_value = ".")]
+        path: PathBuf,
+
+        /// Overwrite existing .skilorc.toml, AGENTS.md, and workflow files
+        #[arg(long, short)]
+        force: bool,
+
+        /// Do not create the GitHub Actions workflow
+        #[arg(long)]
+        no_ci: bool,
+    },
+}
+
 /// Arguments for the `new` command.
 #[derive(clap::Args, Clone)]
 pub struct NewArgs {
     /// Name of the skill to create
-    pub name: String,
+    #[arg(required_unless_present = "from_title")]
+    pub name: Option<String>,
+
+    /// Derive the name from a human-readable title, also used as the heading
+    #[arg(long, value_name = "TITLE", conflicts_with = "name")]
+    pub from_title: Option<String>,
+
+    /// Use the name derived from --from-title without asking
+    #[arg(long, short)]
+    pub yes: bool,
+
+    /// Embed a prompt or document in the skill body ('-' for stdin)
+    ///
+    /// Content beyond the body-length limit is moved to references/, and the
+    /// description is drafted from the first paragraph.
+    #[arg(
+        long,
+        value_name = "FILE",
+        conflicts_with_all = ["template", "lang", "no_scripts"]
+    )]
+    pub from_prompt: Option<PathBuf>,
 
     /// Template to use
     #[arg(long, short, default_value = "hello-world", value_enum)]
@@ -267,30 +645,107 @@ pub struct NewArgs {
     /// Output directory (defaults to agent skills directory)
     #[arg(long, short, conflicts_with_all = ["agent", "global"])]
     pub output: Option<PathBuf>,
+
+    /// Replace an existing skill directory, after confirmation, backing it
+    /// up to ~/.skilo/backups/
+    #[arg(long, conflicts_with = "merge")]
+    pub force: bool,
+
+    /// Add the template's files missing from an existing skill directory,
+    /// never overwriting existing files
+    #[arg(long)]
+    pub merge: bool,
+
+    /// Set a template variable, used as {{key}} (repeatable; overrides
+    /// [new.vars]). `header` and `footer` are added around the body
+    #[arg(long = "var", value_name = "KEY=VALUE")]
+    pub vars: Vec<String>,
+}
+
+/// Arguments for the `merge` command.
+#[derive(clap::Args, Clone)]
+pub struct MergeArgs {
+    /// Skill directories or SKILL.md files to combine, in section order
+    #[arg(required = true, num_args = 2..)]
+    pub skills: Vec<PathBuf>,
+
+    /// Name of the merged skill
+    #[arg(long, short)]
+    pub name: String,
+
+    /// Description of the merged skill (defaults to the joined descriptions)
+    #[arg(long, short)]
+    pub description: Option<String>,
+
+    /// Directory to create the skill in (defaults to that of the first skill)
+    #[arg(long, short)]
+    pub output: Option<PathBuf>,
 }
 
 /// Arguments for the `lint` command.
 #[derive(clap::Args, Clone)]
 pub struct LintArgs {
-    /// Path to skill or directory containing skills
+    /// Paths to skills, SKILL.md files, directories containing skills, or globs
     #[arg(default_value = ".")]
-    pub path: PathBuf,
+    pub paths: Vec<PathBuf>,
 
     /// Treat warnings as errors
     #[arg(long)]
     pub strict: bool,
 
-    /// Auto-fix simple issues
+    /// Auto-fix simple issues: resolve name/directory mismatches (E003),
+    /// move sections of an over-long body (W001) to references/, make
+    /// scripts executable (W002), and add missing shebang lines (W003)
     #[arg(long)]
     pub fix: bool,
+
+    /// How --fix resolves name/directory mismatches (E003): rewrite the
+    /// frontmatter name, or rename the directory [default: lint.name_fix]
+    #[arg(long, value_name = "HOW")]
+    pub name_fix: Option<NameFix>,
+
+    /// Show the changes --fix would make without applying them
+    #[arg(long, conflicts_with = "fix")]
+    pub diff: bool,
+
+    /// Group diagnostics by rule with counts and a few examples each
+    #[arg(long)]
+    pub summary_only: bool,
+
+    /// Show at most N diagnostics (the summary still counts all of them)
+    #[arg(long, value_name = "N")]
+    pub max_diagnostics: Option<usize>,
+
+    /// Quote the offending line under each diagnostic, with carets marking
+    /// the problem (text output)
+    #[arg(long)]
+    pub explain_failures: bool,
+
+    /// Report only these rules or codes (comma-separated rule names such
+    /// as name-format, or codes such as E009)
+    #[arg(long, alias = "rules", value_name = "RULES", value_delimiter = ',')]
+    pub only: Vec<String>,
+
+    /// Leave out these rules or codes (comma-separated)
+    #[arg(long, value_name = "RULES", value_delimiter = ',')]
+    pub skip: Vec<String>,
+
+    /// Post diagnostics on changed lines as a review of the current GitHub
+    /// pull request (needs GITHUB_TOKEN and the GitHub Actions environment)
+    #[arg(long, conflicts_with_all = ["fix", "diff"])]
+    pub github_pr_review: bool,
+
+    /// Exit code thresholds
+    #[command(flatten)]
+    pub exit: ExitArgs,
 }
 
 /// Arguments for the `fmt` command.
 #[derive(clap::Args, Clone)]
 pub struct FmtArgs {
-    /// Path to skill or directory containing skills
+    /// Paths to skills, SKILL.md files, directories containing skills, or globs
     #[arg(default_value = ".")]
-    pub path: PathBuf,
+    pub paths: Vec<PathBuf>,
 
     /// Check formatting without modifying
     #[arg(long)]
@@ -299,14 +754,83 @@ pub struct FmtArgs {
     /// Show diff of changes
     #[arg(long)]
     pub diff: bool,
+
+    /// Verify that formatting the output again leaves it unchanged
+    #[arg(long)]
+    pub verify: bool,
+
+    /// Exit code thresholds
+    #[command(flatten)]
+    pub exit: ExitArgs,
 }
 
 /// Arguments for the `check` command.
 #[derive(clap::Args, Clone)]
 pub struct CheckArgs {
-    /// Path to skill or directory containing skills
+    /// Paths to skills, SKILL.md files, directories containing skills, or globs
     #[arg(default_value = ".")]
-    pub path: PathBuf,
+    pub paths: Vec<PathBuf>,
+
+    /// Group lint diagnostics by rule with counts and a few examples each
+    #[arg(long)]
+    pub summary_only: bool,
+
+    /// Show at most N lint diagnostics (the summary still counts all of them)
+    #[arg(long, value_name = "N")]
+    pub max_diagnostics: Option<usize>,
+
+    /// Exit code thresholds
+    #[command(flatten)]
+    pub exit: ExitArgs,
+}
+
+/// Exit code thresholds shared by `lint`, `fmt`, and `check`.
+#[derive(clap::Args, Clone, Debug, Default)]
+pub struct ExitArgs {
+    /// Lowest severity that fails the command; `never` only reports
+    #[arg(long, value_name = "LEVEL")]
+    pub fail_on: Option<FailOn>,
+
+    /// Fail when more than N warnings are reported
+    #[arg(long, value_name = "N")]
+    pub max_warnings: Option<usize>,
+}
+
+impl ExitArgs {
+    /// Exit code for a run that reported `errors` and `warnings`.
+    ///
+    /// `strict` fails on warnings unless `--fail-on` or `--max-warnings`
+    /// says otherwise.
+    pub fn exit_code(&self, errors: usize, warnings: usize, strict: bool) -> i32 {
+        let fail_on = match self.fail_on {
+            Some(fail_on) => fail_on,
+            None if strict && self.max_warnings.is_none() => FailOn::Warning,
+            None => FailOn::Error,
+        };
+        let failed = match fail_on {
+            FailOn::Never => false,
+            FailOn::Error => errors > 0,
+            FailOn::Warning => errors > 0 || warnings > 0,
+        } || (fail_on != FailOn::Never
+            && self.max_warnings.is_some_and(|max| warnings > max));
+
+        if failed {
+            1
+        } else {
+            0
+        }
+    }
+}
+
+/// Lowest diagnostic severity that fails a command.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FailOn {
+    /// Fail on warnings and errors.
+    Warning,
+    /// Fail on errors only.
+    Error,
+    /// Always exit zero (report only).
+    Never,
 }
 
 /// Arguments for the `read-properties` command.
@@ -315,6 +839,18 @@ pub struct ReadPropertiesArgs {
     /// Paths to skills or directories containing skills
     #[arg(default_value = ".")]
     pub paths: Vec<PathBuf>,
+
+    /// Report metadata usage across all skills instead of each skill's properties
+    #[arg(long)]
+    pub aggregate: bool,
+
+    /// Metadata keys every skill must set; exits 1 if any skill lacks one
+    #[arg(long, value_name = "KEY", value_delimiter = ',')]
+    pub require: Vec<String>,
+
+    /// Write each skill's metadata values as CSV to FILE ('-' for stdout)
+    #[arg(long, value_name = "FILE")]
+    pub csv: Option<PathBuf>,
 }
 
 /// Arguments for the `to-prompt` command.
@@ -323,6 +859,135 @@ pub struct ToPromptArgs {
     /// Paths to skills or directories containing skills
     #[arg(default_value = ".")]
     pub paths: Vec<PathBuf>,
+
+    /// Include each skill's headings, scripts, and references
+    #[arg(long)]
+    pub with_outline: bool,
+
+    /// Include a summary of each body: its first paragraph and headings
+    #[arg(long)]
+    pub summary: bool,
+
+    /// Summarize each body as its headings and the first N lines of each section
+    #[arg(long, value_name = "N")]
+    pub summary_lines: Option<usize>,
+
+    /// Group skills by their first tag or by the directory containing them
+    #[arg(long, value_name = "BY")]
+    pub group_by: Option<PromptGroup>,
+
+    /// Order of skills within the output (groups are ordered by name)
+    #[arg(long, default_value = "name", value_enum)]
+    pub sort: PromptSort,
+
+    /// Write the output to FILE, between managed markers when it is XML
+    #[arg(long, short, value_name = "FILE")]
+    pub output: Option<PathBuf>,
+
+    /// Keep running and rewrite the output file whenever the skills change
+    #[arg(long, requires = "output")]
+    pub watch: bool,
+}
+
+/// How `to-prompt` groups skills.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PromptGroup {
+    /// By each skill's first tag; untagged skills come last.
+    Tag,
+    /// By the directory containing each skill.
+    Directory,
+}
+
+/// Order of skills in `to-prompt` output.
+#[derive(ValueEnum, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub enum PromptSort {
+    /// By name, then location.
+    #[default]
+    Name,
+    /// By `metadata.priority`, highest first, then by name.
+    Priority,
+}
+
+/// Arguments for the `tree` command.
+#[derive(clap::Args, Clone)]
+pub struct TreeArgs {
+    /// Paths to skills or directories containing skills
+    #[arg(default_value = ".")]
+    pub paths: Vec<PathBuf>,
+}
+
+/// Arguments for the `preview` command.
+#[derive(clap::Args, Clone)]
+pub struct PreviewArgs {
+    /// Path to the skill or its SKILL.md
+    #[arg(default_value = ".")]
+    pub path: PathBuf,
+
+    /// Also render a reference document (relative to the skill or references/)
+    #[arg(long, short)]
+    pub reference: Option<PathBuf>,
+
+    /// Wrap text at this width instead of the terminal width
+    #[arg(long)]
+    pub width: Option<usize>,
+}
+
+/// Arguments for the `env-check` command.
+#[derive(clap::Args, Clone)]
+pub struct EnvCheckArgs {
+    /// Paths to skills or directories containing skills
+    #[arg(default_value = ".")]
+    pub paths: Vec<PathBuf>,
+}
+
+/// Arguments for the `activity` command.
+#[derive(clap::Args, Clone)]
+pub struct ActivityArgs {
+    /// Only show activity since an age (30m, 12h, 7d, 2w) or date (YYYY-MM-DD)
+    #[arg(long)]
+    pub since: Option<String>,
+
+    /// Only show this kind of operation
+    #[arg(long, value_enum)]
+    pub action: Option<crate::history::Action>,
+
+    /// Only show activity for this skill
+    #[arg(long)]
+    pub skill: Option<String>,
+
+    /// Show at most this many entries
+    #[arg(long, short = 'n')]
+    pub limit: Option<usize>,
+
+    /// Delete history older than an age or date instead of showing it
+    #[arg(long, value_name = "WHEN", conflicts_with_all = ["since", "action", "skill", "limit"])]
+    pub prune: Option<String>,
+}
+
+/// Arguments for the `api` command.
+#[derive(clap::Args, Clone)]
+pub struct ApiArgs {
+    /// Communicate over stdin and stdout (currently the only transport)
+    #[arg(long, required = true)]
+    pub stdio: bool,
+}
+
+/// Arguments for the `serve` command.
+#[cfg(feature = "serve")]
+#[derive(clap::Args, Clone)]
+pub struct ServeArgs {
+    /// Address to listen on
+    #[arg(long, default_value = "127.0.0.1")]
+    pub bind: String,
+
+    /// Port to listen on
+    #[arg(long, short, default_value_t = 4747)]
+    pub port: u16,
+
+    /// Allow --bind to name an address other than loopback, exposing
+    /// installs and removals to the network
+    #[arg(long)]
+    pub allow_remote: bool,
 }
 
 /// Output format for command results.
@@ -335,6 +1000,39 @@ pub enum OutputFormat {
     Json,
     /// SARIF output for code scanning integrations.
     Sarif,
+    /// Aligned table output (used by `list`; other commands print text).
+    Table,
+    /// CSV rows (used by `lint`, `check`, and `list`; other commands print text).
+    Csv,
+    /// Code Climate issues for GitLab code quality and reviewdog.
+    CodeClimate,
+}
+
+/// When to colorize output.
+#[derive(ValueEnum, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub enum ColorChoice {
+    /// Color when writing to a terminal, honoring NO_COLOR and CLICOLOR_FORCE.
+    #[default]
+    Auto,
+    /// Always emit colors.
+    Always,
+    /// Never emit colors.
+    Never,
+}
+
+/// How file paths appear in diagnostics.
+#[derive(ValueEnum, Clone, Copy, Default, Debug, PartialEq, Eq)]
+#[value(rename_all = "kebab-case")]
+pub enum PathStyle {
+    /// Relative to the current directory.
+    #[default]
+    Relative,
+    /// Relative to the root of the enclosing git repository.
+    Root,
+    /// Absolute, with symlinks resolved.
+    Absolute,
+    /// Exactly as found during discovery.
+    AsGiven,
 }
 
 /// Available skill templates.
@@ -373,17 +1071,132 @@ pub struct ListArgs {
     #[arg(default_value = ".")]
     pub path: PathBuf,
 
-    /// List global skills only
+    /// List global skills only (shorthand for --scope global)
     #[arg(long, short = 'g')]
     pub global: bool,
 
-    /// List all skills (project + global)
+    /// List all skills (shorthand for --scope both)
     #[arg(long, conflicts_with = "global")]
     pub all: bool,
 
+    /// Scope to list skills from
+    #[arg(long, value_enum, conflicts_with_all = ["global", "all"])]
+    pub scope: Option<ScopeArg>,
+
+    /// Only list skills with this tag (can be repeated; matches any)
+    #[arg(long)]
+    pub tag: Vec<String>,
+
     /// Target agent
     #[arg(long, short, value_enum)]
     pub agent: Option<Agent>,
+
+    /// Sort order for --format table and json
+    #[arg(long, value_enum, default_value = "name")]
+    pub sort: ListSort,
+
+    /// Show disk usage per agent and scope, and large files duplicated
+    /// across skills
+    #[arg(long)]
+    pub du: bool,
+}
+
+/// Arguments for the `permissions` command.
+#[derive(clap::Args, Clone)]
+pub struct PermissionsArgs {
+    /// Project directory whose skills and settings to sync
+    #[arg(default_value = ".")]
+    pub path: PathBuf,
+
+    /// Sync global skills into ~/.claude/settings.json
+    #[arg(long, short = 'g')]
+    pub global: bool,
+
+    /// Settings file to update instead of the scope's settings.json
+    #[arg(long, value_name = "FILE")]
+    pub settings: Option<PathBuf>,
+
+    /// Write the changes (otherwise only show them)
+    #[arg(long)]
+    pub write: bool,
+}
+
+/// Arguments for the `grep` command.
+#[derive(clap::Args, Clone)]
+pub struct GrepArgs {
+    /// Regular expression to search for
+    pub pattern: String,
+
+    /// Project directory to search skills in
+    #[arg(default_value = ".")]
+    pub path: PathBuf,
+
+    /// Search global skills only (shorthand for --scope global)
+    #[arg(long, short = 'g')]
+    pub global: bool,
+
+    /// Search project and global skills (shorthand for --scope both)
+    #[arg(long, conflicts_with = "global")]
+    pub all: bool,
+
+    /// Scope to search skills in
+    #[arg(long, value_enum, conflicts_with_all = ["global", "all"])]
+    pub scope: Option<ScopeArg>,
+
+    /// Only search skills installed for this agent
+    #[arg(long, short, value_enum)]
+    pub agent: Option<Agent>,
+
+    /// Match case-insensitively
+    #[arg(long, short)]
+    pub ignore_case: bool,
+
+    /// Treat the pattern as a literal string
+    #[arg(long, short = 'F')]
+    pub fixed_strings: bool,
+
+    /// Print only the paths of files with matches
+    #[arg(long, short = 'l')]
+    pub files_with_matches: bool,
+}
+
+/// Arguments for the `which` command.
+#[derive(clap::Args, Clone)]
+pub struct WhichArgs {
+    /// Script file name or path, or tool name (such as `Bash` or `Bash(git:*)`)
+    pub name: String,
+
+    /// Project directory to search skills in
+    #[arg(default_value = ".")]
+    pub path: PathBuf,
+
+    /// Search global skills only (shorthand for --scope global)
+    #[arg(long, short = 'g')]
+    pub global: bool,
+
+    /// Search project and global skills (shorthand for --scope both)
+    #[arg(long, conflicts_with = "global")]
+    pub all: bool,
+
+    /// Scope to search skills in
+    #[arg(long, value_enum, conflicts_with_all = ["global", "all"])]
+    pub scope: Option<ScopeArg>,
+
+    /// Only search skills installed for this agent
+    #[arg(long, short, value_enum)]
+    pub agent: Option<Agent>,
+}
+
+/// Sort order for `list --format table`.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ListSort {
+    /// Alphabetically by skill name.
+    #[default]
+    Name,
+    /// Largest skills first.
+    Size,
+    /// Most recently installed first.
+    InstalledAt,
 }
 
 /// Arguments for the `remove` command.
@@ -393,13 +1206,20 @@ pub struct RemoveArgs {
     #[arg(required = true)]
     pub skills: Vec<String>,
 
-    /// Remove from global scope
+    /// Remove from global scope (shorthand for --scope global)
     #[arg(long, short = 'g')]
     pub global: bool,
 
-    /// Target agent
+    /// Scope to remove skills from
+    #[arg(long, value_enum, conflicts_with = "global")]
+    pub scope: Option<ScopeArg>,
+
+    /// Target agent(s)
+    ///
+    /// Can be specified multiple times: --agent claude --agent cursor
+    /// Use 'all' to remove from every agent and ./skills/.
     #[arg(long, short, value_enum)]
-    pub agent: Option<Agent>,
+    pub agent: Option<Vec<Agent>>,
 
     /// Skip confirmation prompts
     #[arg(long, short)]
@@ -409,9 +1229,229 @@ pub struct RemoveArgs {
 /// Arguments for the `agents` command.
 #[derive(clap::Args, Clone)]
 pub struct AgentsArgs {
-    /// Show verbose output (feature support matrix)
+    /// Show verbose output (detection signals and feature support matrix)
     #[arg(long, short)]
     pub verbose: bool,
+
+    /// Create the skills directory of each detected agent that lacks one
+    #[arg(long)]
+    pub fix_missing: bool,
+
+    /// Scopes to create missing skills directories in
+    #[arg(long, value_enum, default_value = "both", requires = "fix_missing")]
+    pub scope: ScopeArg,
+
+    /// Add a README.md explaining that skilo manages the directory
+    #[arg(long, requires = "fix_missing")]
+    pub readme: bool,
+
+    /// Skip confirmation prompts
+    #[arg(long, short, requires = "fix_missing")]
+    pub yes: bool,
+}
+
+/// Arguments for the `ci-check` command.
+#[derive(clap::Args, Clone)]
+pub struct CiCheckArgs {
+    /// Paths to skills, SKILL.md files, directories containing skills, or globs
+    #[arg(default_value = ".")]
+    pub paths: Vec<PathBuf>,
+
+    /// Append a Markdown summary to FILE (defaults to $GITHUB_STEP_SUMMARY)
+    #[arg(long, value_name = "FILE")]
+    pub summary: Option<PathBuf>,
+}
+
+/// Arguments for the `ci` command.
+#[derive(clap::Args, Clone)]
+pub struct CiArgs {
+    /// Scope to verify
+    #[arg(long, value_enum, default_value = "project")]
+    pub scope: ScopeArg,
+}
+
+/// Arguments for the `plugin` command.
+#[derive(clap::Args, Clone)]
+pub struct PluginArgs {
+    /// Plugin root directory, containing skills/
+    #[arg(default_value = ".")]
+    pub dir: PathBuf,
+
+    /// Plugin name (defaults to the existing name or the directory name)
+    #[arg(long, short)]
+    pub name: Option<String>,
+
+    /// Plugin description (defaults to a list of the skills)
+    #[arg(long, short)]
+    pub description: Option<String>,
+
+    /// Plugin version
+    #[arg(long, value_name = "VERSION")]
+    pub plugin_version: Option<String>,
+
+    /// Author name (defaults to git's user.name)
+    #[arg(long)]
+    pub author: Option<String>,
+
+    /// Also write a marketplace.json listing the plugin
+    #[arg(long)]
+    pub marketplace: bool,
+
+    /// Validate without writing any files
+    #[arg(long)]
+    pub check: bool,
+}
+
+/// Arguments for the `export` command.
+#[derive(clap::Args, Clone)]
+pub struct ExportArgs {
+    /// Write the manifest to a file instead of stdout
+    #[arg(long, short)]
+    pub output: Option<PathBuf>,
+
+    /// Scope to export skills from
+    #[arg(long, value_enum, default_value = "both")]
+    pub scope: ScopeArg,
+}
+
+/// Arguments for the `import` command.
+#[derive(clap::Args, Clone)]
+pub struct ImportArgs {
+    /// Manifest file created by `skilo export`
+    pub file: PathBuf,
+
+    /// Skip confirmation prompts
+    #[arg(long, short)]
+    pub yes: bool,
+}
+
+/// Arguments for the `bundle` command.
+#[derive(clap::Args, Clone)]
+pub struct BundleArgs {
+    /// Bundle subcommand
+    #[command(subcommand)]
+    pub command: BundleCommand,
+}
+
+/// Bundle subcommands.
+#[derive(Subcommand, Clone)]
+pub enum BundleCommand {
+    /// Fetch every listed source into a bundle
+    Export {
+        /// File listing sources, in the format of `add --from-file` ('-'
+        /// reads stdin)
+        #[arg(long, value_name = "FILE")]
+        sources: PathBuf,
+
+        /// Bundle file to write
+        output: PathBuf,
+    },
+
+    /// Install skills from a bundle without network access
+    Import {
+        /// Bundle file created by `skilo bundle export`
+        file: PathBuf,
+
+        /// Install specific skill(s) by name
+        #[arg(long, short)]
+        skill: Option<Vec<String>>,
+
+        /// Target agent(s) (determines install directory)
+        #[arg(long, short, value_enum)]
+        agent: Option<Vec<Agent>>,
+
+        /// Installation scope
+        #[arg(long, value_enum)]
+        scope: Option<ScopeArg>,
+
+        /// Skip confirmation prompts
+        #[arg(long, short)]
+        yes: bool,
+    },
+}
+
+/// Arguments for the `vendor` command.
+#[derive(clap::Args, Clone)]
+pub struct VendorArgs {
+    /// Source to vendor skills from (same formats as `add`)
+    #[arg(required_unless_present = "update", conflicts_with = "update")]
+    pub source: Option<String>,
+
+    /// Vendor or update specific skill(s) by name
+    #[arg(long, short)]
+    pub skill: Option<Vec<String>>,
+
+    /// Refetch vendored skills from their recorded sources
+    #[arg(long, short)]
+    pub update: bool,
+
+    /// Skip confirmation prompts
+    #[arg(long, short)]
+    pub yes: bool,
+
+    /// Specify git branch
+    #[arg(long, short, conflicts_with = "update")]
+    pub branch: Option<String>,
+
+    /// Specify git tag
+    #[arg(long, short = 't', conflicts_with = "update")]
+    pub tag: Option<String>,
+
+    /// Vendor from a specific commit (full or abbreviated SHA)
+    #[arg(long, value_name = "SHA", conflicts_with_all = ["update", "branch", "tag"])]
+    pub rev: Option<String>,
+
+    /// Only vendor skills from a subdirectory (or glob) within the source
+    #[arg(long, short, conflicts_with = "update")]
+    pub path: Option<String>,
+}
+
+/// Arguments for the `clean` command.
+#[derive(clap::Args, Clone)]
+pub struct CleanArgs {
+    /// Show what would be removed without removing anything
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Also remove empty skills directories in the project
+    #[arg(long)]
+    pub empty_dirs: bool,
+}
+
+/// Arguments for the `doctor` command.
+#[derive(clap::Args, Clone)]
+pub struct DoctorArgs {
+    /// Finish or roll back interrupted operations
+    #[arg(long)]
+    pub repair: bool,
+
+    /// Test which credentials can fetch SOURCE instead
+    ///
+    /// Tries anonymous HTTPS, HTTPS with the git credential helper, the SSH
+    /// agent, and each SSH key file for the source's host, and explains how
+    /// to fix the ones that fail.
+    #[arg(long, value_name = "SOURCE", conflicts_with = "repair")]
+    pub auth: Option<String>,
+
+    /// SSH private key to test along with the ones skilo finds
+    #[arg(long, value_name = "PATH", requires = "auth")]
+    pub ssh_key: Option<std::path::PathBuf>,
+}
+
+/// Arguments for the `dedupe` command.
+#[derive(clap::Args, Clone)]
+pub struct DedupeArgs {
+    /// Keep the first copy of each duplicate and remove the others
+    #[arg(long)]
+    pub consolidate: bool,
+
+    /// Replace duplicates with symlinks to the kept copy instead of removing them
+    #[arg(long, requires = "consolidate")]
+    pub link: bool,
+
+    /// Skip confirmation prompts
+    #[arg(long, short)]
+    pub yes: bool,
 }
 
 /// Arguments for the `cache` command.
@@ -440,6 +1480,52 @@ pub enum CacheCommand {
     },
 }
 
+/// Arguments for the `store` command.
+#[derive(clap::Args, Clone)]
+pub struct StoreArgs {
+    /// Store subcommand
+    #[command(subcommand)]
+    pub command: Option<StoreCommand>,
+}
+
+/// Store subcommands.
+#[derive(Subcommand, Clone)]
+pub enum StoreCommand {
+    /// Show store location
+    Path,
+
+    /// Remove content no installed skill links to
+    Gc {
+        /// Show what would be removed without removing it
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+/// Arguments for the `bench` command.
+#[derive(clap::Args, Clone)]
+pub struct BenchArgs {
+    /// Directory to search for skills
+    #[arg(long, default_value = ".")]
+    pub path: PathBuf,
+
+    /// Number of times to run each phase; times are averaged
+    #[arg(long, short = 'n', default_value_t = 5, value_parser = clap::value_parser!(u32).range(1..))]
+    pub iterations: u32,
+}
+
+/// Arguments for the `selftest` command.
+#[derive(clap::Args, Clone)]
+pub struct SelftestArgs {
+    /// Directory of cases, or a single case directory
+    #[arg(default_value = "tests/fixtures/rules")]
+    pub dir: PathBuf,
+
+    /// Overwrite expected.txt with the current diagnostics
+    #[arg(long)]
+    pub bless: bool,
+}
+
 /// Arguments for the `self` command.
 #[derive(clap::Args, Clone)]
 pub struct SelfArgs {
@@ -492,3 +1578,37 @@ pub struct SelfUpdateArgs {
     #[arg(long, short)]
     pub yes: bool,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cli_definition() {
+        use clap::CommandFactory;
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_exit_code_thresholds() {
+        let exit = |fail_on, max_warnings| ExitArgs {
+            fail_on,
+            max_warnings,
+        };
+
+        assert_eq!(exit(None, None).exit_code(0, 3, false), 0);
+        assert_eq!(exit(None, None).exit_code(0, 3, true), 1);
+        assert_eq!(exit(None, None).exit_code(1, 0, false), 1);
+        assert_eq!(exit(Some(FailOn::Error), None).exit_code(0, 3, true), 0);
+        assert_eq!(exit(Some(FailOn::Warning), None).exit_code(0, 1, false), 1);
+        assert_eq!(exit(Some(FailOn::Never), Some(0)).exit_code(5, 5, true), 0);
+
+        // --max-warnings replaces strict's zero-warning limit
+        assert_eq!(exit(None, Some(3)).exit_code(0, 3, true), 0);
+        assert_eq!(exit(None, Some(3)).exit_code(0, 4, true), 1);
+        assert_eq!(
+            exit(Some(FailOn::Warning), Some(3)).exit_code(0, 3, false),
+            1
+        );
+    }
+}
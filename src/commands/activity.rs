@@ -0,0 +1,98 @@
+//! Shows the local history of install, update, and remove operations.
+
+use crate::cli::{ActivityArgs, Cli, OutputFormat};
+use crate::config::Config;
+use crate::error::SkiloError;
+use crate::history::{self, format_timestamp, HistoryEntry};
+use crate::lockfile::now_secs;
+use crate::output::{get_formatter, print_porcelain, Table, EMPTY_FIELD};
+
+/// Run the activity command.
+pub fn run(args: ActivityArgs, config: &Config, cli: &Cli) -> Result<i32, SkiloError> {
+    let formatter = get_formatter(cli.format, cli.quiet);
+    let path = history::history_path()
+        .ok_or_else(|| SkiloError::Config("Could not determine skilo home".to_string()))?;
+
+    let parse_when = |value: &str| {
+        history::parse_since(value, now_secs()).ok_or_else(|| {
+            SkiloError::Config(format!(
+                "Invalid time '{}': use an age like 7d or a date like 2025-01-31",
+                value
+            ))
+        })
+    };
+
+    if let Some(prune) = &args.prune {
+        let cutoff = parse_when(prune)?;
+        let dropped = history::prune(&path, cutoff)?;
+        formatter.format_success(&format!(
+            "Pruned {} entr{} before {}",
+            dropped,
+            if dropped == 1 { "y" } else { "ies" },
+            format_timestamp(cutoff)
+        ));
+        return Ok(0);
+    }
+
+    let since = args.since.as_deref().map(parse_when).transpose()?;
+    let mut entries: Vec<HistoryEntry> = history::load(&path)?
+        .into_iter()
+        .filter(|e| since.is_none_or(|since| e.timestamp >= since))
+        .filter(|e| args.action.is_none_or(|action| e.action == action))
+        .filter(|e| args.skill.as_ref().is_none_or(|skill| &e.skill == skill))
+        .collect();
+
+    // Newest first; the file is in append order
+    entries.reverse();
+    if let Some(limit) = args.limit {
+        entries.truncate(limit);
+    }
+
+    if matches!(cli.format, OutputFormat::Json) {
+        let json = serde_json::to_string_pretty(&entries)
+            .map_err(|e| SkiloError::Config(format!("JSON serialization failed: {}", e)))?;
+        println!("{}", json);
+        return Ok(0);
+    }
+
+    if cli.porcelain {
+        for entry in &entries {
+            print_porcelain(&[
+                &entry.timestamp.to_string(),
+                entry.action.as_str(),
+                &entry.skill,
+                entry.agent.map(|a| a.cli_name()).unwrap_or(EMPTY_FIELD),
+                entry.scope.display_name(),
+                entry.source.as_deref().unwrap_or(EMPTY_FIELD),
+            ]);
+        }
+        return Ok(0);
+    }
+
+    if entries.is_empty() {
+        let message = if config.history.enabled {
+            "No recorded activity."
+        } else {
+            "No recorded activity. History is disabled by `[history] enabled = false`."
+        };
+        formatter.format_message(message);
+        return Ok(0);
+    }
+
+    let mut table = Table::new(["Date (UTC)", "Action", "Skill", "Agent", "Scope", "Source"]);
+    for entry in &entries {
+        table.row([
+            format_timestamp(entry.timestamp),
+            entry.action.as_str().to_string(),
+            entry.skill.clone(),
+            entry
+                .agent
+                .map(|a| a.display_name().to_string())
+                .unwrap_or_else(|| "skills/".to_string()),
+            entry.scope.display_name().to_string(),
+            entry.source.clone().unwrap_or_default(),
+        ]);
+    }
+    table.print();
+    Ok(0)
+}
@@ -1,21 +1,30 @@
 //! The `add` command implementation for installing skills from git repositories.
 
 use crate::agent::{expand_tilde, Agent};
-use crate::cli::{AddArgs, Cli};
-use crate::config::Config;
-use crate::git::{fetch, Source};
-use crate::output::get_formatter;
+use crate::agent_config::{register, registration_target, Registration};
+use crate::cli::{AddArgs, Cli, ScopeArg};
+use crate::config::{Config, SkillOverride};
+use crate::git::{fetch, fetch_release_asset, CheckoutOptions, Credentials, Source};
+use crate::history::{self, Action};
+use crate::hooks::{self, HookEvent, HookSkill};
+use crate::journal::{write_atomic, OperationKind, StagedDir};
+use crate::lockfile::{checksum_dir, lock_path, now_secs, LockedSkill, Lockfile};
+use crate::output::{get_formatter, print_porcelain, Progress, ProgressEvent};
 use crate::scope::Scope;
-use crate::skill::discovery::Discovery;
+use crate::skill::discovery::{glob_paths, is_glob, Discovery};
+use crate::skill::formatter::minimal_diff;
 use crate::skill::manifest::Manifest;
 use crate::skill::validator::Validator;
+use crate::skill::{overrides, provenance};
+use crate::store;
 use crate::SkiloError;
 use colored::Colorize;
-use dialoguer::Confirm;
-use std::collections::HashSet;
+use dialoguer::{Confirm, Select};
+use std::collections::{BTreeSet, HashSet};
 use std::fs;
 use std::io::{self, Write};
 use std::path::{Path, PathBuf};
+use tempfile::TempDir;
 
 /// Information about a discovered skill.
 #[derive(Clone)]
@@ -30,6 +39,54 @@ struct SkillInfo {
     valid: bool,
     /// Validation errors, if any.
     errors: Vec<String>,
+    /// Whether the skill is marked deprecated.
+    deprecated: bool,
+    /// The skill that replaces this one, if any.
+    superseded_by: Option<String>,
+}
+
+/// Outcome of installing a single skill to a target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum InstallStatus {
+    /// The skill was copied to the target.
+    Installed,
+    /// The skill already existed and the user declined to overwrite it.
+    Skipped,
+    /// The skill failed validation and was not installed.
+    Invalid,
+    /// The skill matches `add.exclude` and was not installed.
+    Excluded,
+}
+
+impl InstallStatus {
+    /// Returns the porcelain name of this status.
+    fn as_str(&self) -> &'static str {
+        match self {
+            InstallStatus::Installed => "installed",
+            InstallStatus::Skipped => "skipped",
+            InstallStatus::Invalid => "invalid",
+            InstallStatus::Excluded => "excluded",
+        }
+    }
+}
+
+/// Record of a skill installation attempt.
+struct InstallRecord {
+    name: String,
+    status: InstallStatus,
+    path: PathBuf,
+    /// Whether an installed copy of the skill was replaced.
+    replaced: bool,
+}
+
+/// Where installed skills came from, as recorded in the lockfile.
+pub(crate) struct Provenance {
+    pub(crate) source: String,
+    pub(crate) subdir: Option<String>,
+    pub(crate) branch: Option<String>,
+    pub(crate) tag: Option<String>,
+    pub(crate) rev: Option<String>,
+    pub(crate) commit: Option<String>,
 }
 
 /// Target information for skill installation.
@@ -42,11 +99,6 @@ struct InstallTarget {
 /// Resolve install targets from CLI arguments.
 fn resolve_targets(args: &AddArgs, config: &Config) -> Result<Vec<InstallTarget>, SkiloError> {
     let project_root = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
-    let scope = if args.global {
-        Scope::Global
-    } else {
-        Scope::Project
-    };
 
     // If --output is specified, use it directly
     if let Some(ref output) = args.output {
@@ -57,27 +109,106 @@ fn resolve_targets(args: &AddArgs, config: &Config) -> Result<Vec<InstallTarget>
         }]);
     }
 
+    let scopes = ScopeArg::resolve(args.scope, args.global, config.add.default_scope);
+
+    let mut targets = Vec::new();
+    for scope in scopes {
+        targets.extend(resolve_scope_targets(args, config, scope, &project_root)?);
+    }
+
+    if targets.is_empty() {
+        return Err(SkiloError::Config(
+            "No valid install targets found".to_string(),
+        ));
+    }
+
+    Ok(targets)
+}
+
+/// Assign skills to install targets, honoring `add.overrides`.
+///
+/// Skills without an override go to `targets`. Targets that end up with no
+/// skills are dropped.
+fn plan_installs(
+    args: &AddArgs,
+    config: &Config,
+    targets: Vec<InstallTarget>,
+    skills: &[SkillInfo],
+) -> Result<Vec<(InstallTarget, Vec<SkillInfo>)>, SkiloError> {
+    let defaults = targets.len();
+    let mut plan: Vec<(InstallTarget, Vec<SkillInfo>)> =
+        targets.into_iter().map(|t| (t, Vec::new())).collect();
+
+    for skill in skills {
+        let override_args = config
+            .add
+            .overrides
+            .get(&skill.name)
+            .and_then(|o| override_args(args, o));
+
+        let Some(override_args) = override_args else {
+            for (_, target_skills) in &mut plan[..defaults] {
+                target_skills.push(skill.clone());
+            }
+            continue;
+        };
+
+        for target in resolve_targets(&override_args, config)? {
+            match plan.iter_mut().find(|(t, _)| t.path == target.path) {
+                Some((_, target_skills)) => target_skills.push(skill.clone()),
+                None => plan.push((target, vec![skill.clone()])),
+            }
+        }
+    }
+
+    plan.retain(|(_, target_skills)| !target_skills.is_empty());
+    Ok(plan)
+}
+
+/// Apply a per-skill override to the add arguments.
+///
+/// Returns `None` when the targets were chosen explicitly on the command line.
+fn override_args(args: &AddArgs, skill_override: &SkillOverride) -> Option<AddArgs> {
+    if args.agent.is_some() || args.global || args.scope.is_some() || args.output.is_some() {
+        return None;
+    }
+
+    let mut args = args.clone();
+    if !skill_override.agents.is_empty() {
+        args.agent = Some(skill_override.agents.iter().map(|&a| a.into()).collect());
+    }
+    args.scope = skill_override.scope.map(ScopeArg::from);
+    Some(args)
+}
+
+/// Resolve install targets for a single scope.
+fn resolve_scope_targets(
+    args: &AddArgs,
+    config: &Config,
+    scope: Scope,
+    project_root: &Path,
+) -> Result<Vec<InstallTarget>, SkiloError> {
     // Resolve agents from CLI args
+    let mut generic = false;
     let agents: Option<Vec<Agent>> = if let Some(ref cli_agents) = args.agent {
         let mut resolved = Vec::new();
         for cli_agent in cli_agents {
             match cli_agent.to_selection() {
                 crate::cli::AgentSelection::All => {
                     // "all" means all detected agents
-                    let detected = if args.global {
+                    let detected = if scope.is_global() {
                         Agent::detect_global()
                     } else {
-                        Agent::detect_project(&project_root)
+                        Agent::detect_project(project_root)
                     };
                     if detected.is_empty() {
                         // Fall back to default agent if configured, otherwise use ./skills/
-                        if let Some(default_agent) = config.add.default_agent {
-                            resolved.push(default_agent);
-                        }
+                        resolved.extend(config.add.default_agent_list());
                     } else {
                         resolved.extend(detected);
                     }
                 }
+                crate::cli::AgentSelection::Generic => generic = true,
                 crate::cli::AgentSelection::Single(agent) => {
                     resolved.push(agent);
                 }
@@ -86,70 +217,104 @@ fn resolve_targets(args: &AddArgs, config: &Config) -> Result<Vec<InstallTarget>
         // Deduplicate
         let mut seen = HashSet::new();
         resolved.retain(|a| seen.insert(*a));
-        if resolved.is_empty() {
+        if resolved.is_empty() && !generic {
             None
         } else {
             Some(resolved)
         }
     } else {
-        config.add.default_agent.map(|a| vec![a])
+        let defaults = config.add.default_agent_list();
+        if defaults.is_empty() {
+            None
+        } else {
+            Some(defaults)
+        }
     };
 
     // Build targets
     let targets: Vec<InstallTarget> = match agents {
-        Some(agent_list) => agent_list
-            .into_iter()
-            .filter_map(|agent| {
-                let path = match scope {
-                    Scope::Global => agent.resolve_global_skills_dir()?,
-                    Scope::Project => Some(agent.resolve_project_skills_dir(&project_root))?,
-                };
-                Some(InstallTarget {
-                    agent: Some(agent),
-                    path,
-                    scope,
+        Some(agent_list) => {
+            if generic && scope.is_global() {
+                return Err(SkiloError::Config(
+                    "The generic target has no global directory".to_string(),
+                ));
+            }
+            let mut targets: Vec<InstallTarget> = agent_list
+                .into_iter()
+                .filter_map(|agent| {
+                    let path = scope.resolve_skills_dir(agent, project_root)?;
+                    Some(InstallTarget {
+                        agent: Some(agent),
+                        path,
+                        scope,
+                    })
                 })
-            })
-            .collect(),
+                .collect();
+            if generic {
+                targets.push(InstallTarget {
+                    agent: None,
+                    path: project_root.join("skills"),
+                    scope: Scope::Project,
+                });
+            }
+            targets
+        }
         None => {
             // No agent specified - install to ./skills/ in current directory
-            let path = if args.global {
+            if scope.is_global() {
                 return Err(SkiloError::Config(
                     "Global installation requires an agent (use --agent)".to_string(),
                 ));
-            } else {
-                project_root.join("skills")
-            };
+            }
             vec![InstallTarget {
                 agent: None,
-                path,
+                path: project_root.join("skills"),
                 scope: Scope::Project,
             }]
         }
     };
 
-    if targets.is_empty() {
-        return Err(SkiloError::Config(
-            "No valid install targets found".to_string(),
-        ));
-    }
-
     Ok(targets)
 }
 
 /// Run the add command.
 pub fn run(args: AddArgs, config: &Config, cli: &Cli) -> Result<i32, SkiloError> {
-    let formatter = get_formatter(cli.format, cli.quiet);
+    if let Some(file) = &args.from_file {
+        return run_from_file(file, &args, config, cli);
+    }
+
+    // JSON output reports progress as events instead of text
+    let progress = Progress::new(cli.format, cli.quiet);
+    let quiet = cli.quiet || cli.porcelain || progress.is_enabled();
+
+    let Some(source_arg) = args.source.as_deref() else {
+        return Err(SkiloError::Config(
+            "A source is required unless --from-file is given".to_string(),
+        ));
+    };
 
     // Resolve install targets
     let targets = resolve_targets(&args, config)?;
 
     // Parse the source
-    let mut source =
-        Source::parse_with_options(&args.source, args.branch.clone(), args.tag.clone())?;
-
-    // Apply --path to narrow the source to a specific subdirectory
-    if let Some(ref path) = args.path {
+    let mut source = Source::parse_with_options(
+        source_arg,
+        args.branch.clone(),
+        args.tag.clone(),
+        args.rev.clone(),
+    )?;
+
+    check_allowed(&source, config)?;
+
+    // Apply --path to narrow the source to a specific subdirectory. Glob
+    // patterns are matched against the fetched tree instead.
+    let path_glob = args
+        .path
+        .as_deref()
+        .map(|p| p.trim_matches('/').to_string())
+        .filter(|p| is_glob(Path::new(p)));
+
+    if let Some(path) = args.path.as_deref().filter(|_| path_glob.is_none()) {
         match &mut source {
             Source::Git(ref mut git_source) => {
                 git_source.subdir = Some(match &git_source.subdir {
@@ -157,28 +322,97 @@ pub fn run(args: AddArgs, config: &Config, cli: &Cli) -> Result<i32, SkiloError>
                     None => path.trim_matches('/').to_string(),
                 });
             }
+            Source::Release(ref mut asset) => {
+                asset.subdir = Some(match &asset.subdir {
+                    Some(existing) => format!("{}/{}", existing, path.trim_matches('/')),
+                    None => path.trim_matches('/').to_string(),
+                });
+            }
             Source::Local(ref mut local_path) => {
                 *local_path = local_path.join(path.trim_matches('/'));
             }
         }
     }
 
-    // Extract source path based on source type
-    let (source_path, source_name, _temp_dir) = match source {
+    let ssh_key = args
+        .ssh_key
+        .clone()
+        .or_else(|| config.add.ssh_key.clone())
+        .and_then(|key| expand_tilde(&key.to_string_lossy()));
+    let fetched = fetch_source(
+        source,
+        path_glob.as_deref(),
+        ssh_key,
+        config,
+        quiet,
+        &progress,
+    )?;
+    install(args, fetched, path_glob.as_deref(), targets, config, cli)
+}
+
+/// Reject remote sources that `add.allowed_sources`, in the project or the
+/// global config, does not list.
+pub(crate) fn check_allowed(source: &Source, config: &Config) -> Result<(), SkiloError> {
+    let repository = match source {
+        Source::Git(git_source) => git_source.clone(),
+        Source::Release(asset) => asset.repository(),
+        Source::Local(_) => return Ok(()),
+    };
+    let allowed = |patterns: &[String]| patterns.is_empty() || repository.matches_any(patterns);
+    if !allowed(&config.add.allowed_sources) || !allowed(&config.add.global_allowed_sources) {
+        return Err(SkiloError::SourceNotAllowed(repository.host_path()));
+    }
+    Ok(())
+}
+
+/// A source fetched to a local directory, ready to install from.
+pub(crate) struct Fetched {
+    /// Directory holding the source's files.
+    pub(crate) root: PathBuf,
+    /// Name of the source shown in messages.
+    pub(crate) name: String,
+    /// Where the files came from, recorded in the lockfile.
+    pub(crate) provenance: Provenance,
+    /// Temporary directory holding the files, removed when dropped.
+    pub(crate) temp_dir: Option<TempDir>,
+}
+
+/// Fetch `source` to a local directory.
+///
+/// A `path_glob` given with `--path` is recorded in the provenance.
+pub(crate) fn fetch_source(
+    source: Source,
+    path_glob: Option<&str>,
+    ssh_key: Option<PathBuf>,
+    config: &Config,
+    quiet: bool,
+    progress: &Progress,
+) -> Result<Fetched, SkiloError> {
+    match source {
         Source::Git(git_source) => {
-            let display_name = git_source.display_name();
+            let display_name = git_source.display_name_with_ref();
 
-            if !cli.quiet {
+            if !quiet {
                 print!("Fetching skills from {}...", display_name.cyan());
                 io::stdout().flush().ok();
             }
+            progress.emit(ProgressEvent::FetchStarted {
+                source: &display_name,
+            });
 
             // Fetch the repository (uses cache when possible)
-            let fetch_result = fetch(&git_source)?;
+            let fetch_result = fetch(
+                &git_source,
+                CheckoutOptions {
+                    submodules: config.add.submodules,
+                    lfs: config.add.lfs,
+                },
+                &Credentials::new(ssh_key),
+            )?;
 
-            if !cli.quiet {
+            if !quiet {
                 if fetch_result.from_cache {
-                    if let Some(ref commit) = fetch_result.commit {
+                    if let Some(commit) = fetch_result.short_commit() {
                         println!(" {} ({})", "done".green(), commit.dimmed());
                     } else {
                         println!(" {}", "done".green());
@@ -188,26 +422,141 @@ pub fn run(args: AddArgs, config: &Config, cli: &Cli) -> Result<i32, SkiloError>
                 }
             }
 
-            (
-                fetch_result.root.clone(),
-                display_name,
-                fetch_result.temp_dir,
-            )
+            progress.emit(ProgressEvent::FetchDone {
+                source: &display_name,
+                commit: fetch_result.commit.as_deref(),
+                from_cache: fetch_result.from_cache,
+            });
+
+            warn_lfs_pointers(&fetch_result.lfs_pointers, &fetch_result.root, config);
+
+            let provenance = Provenance {
+                source: git_source.url.clone(),
+                subdir: join_subdir(git_source.subdir.as_deref(), path_glob),
+                branch: git_source.branch.clone(),
+                tag: git_source.tag.clone(),
+                rev: git_source.rev.clone(),
+                commit: fetch_result.commit.clone(),
+            };
+
+            Ok(Fetched {
+                root: fetch_result.root,
+                name: display_name,
+                provenance,
+                temp_dir: fetch_result.temp_dir,
+            })
+        }
+        Source::Release(asset) => {
+            let display_name = asset.display_name();
+
+            if !quiet {
+                print!("Downloading skills from {}...", display_name.cyan());
+                io::stdout().flush().ok();
+            }
+            progress.emit(ProgressEvent::FetchStarted {
+                source: &display_name,
+            });
+
+            let fetch_result = fetch_release_asset(&asset)?;
+
+            if !quiet {
+                println!(" {}", "done".green());
+            }
+            progress.emit(ProgressEvent::FetchDone {
+                source: &display_name,
+                commit: None,
+                from_cache: false,
+            });
+
+            // The tag is part of the asset URL, so it is not recorded apart
+            let provenance = Provenance {
+                source: asset.url.clone(),
+                subdir: join_subdir(asset.subdir.as_deref(), path_glob),
+                branch: None,
+                tag: None,
+                rev: None,
+                commit: None,
+            };
+
+            Ok(Fetched {
+                root: fetch_result.root,
+                name: display_name,
+                provenance,
+                temp_dir: fetch_result.temp_dir,
+            })
         }
         Source::Local(path) => {
             let expanded =
                 expand_tilde(path.to_str().unwrap_or(".")).unwrap_or_else(|| path.clone());
-            (expanded.clone(), expanded.display().to_string(), None)
+            let absolute = expanded.canonicalize().unwrap_or_else(|_| expanded.clone());
+            let provenance = Provenance {
+                source: absolute.display().to_string(),
+                subdir: path_glob.map(str::to_string),
+                branch: None,
+                tag: None,
+                rev: None,
+                commit: None,
+            };
+            Ok(Fetched {
+                name: expanded.display().to_string(),
+                root: expanded,
+                provenance,
+                temp_dir: None,
+            })
         }
-    };
+    }
+}
+
+/// Install the skills of a source fetched by other means than `add`, with
+/// the targets and filters of `args`.
+pub(crate) fn install_fetched(
+    args: AddArgs,
+    fetched: Fetched,
+    config: &Config,
+    cli: &Cli,
+) -> Result<i32, SkiloError> {
+    let targets = resolve_targets(&args, config)?;
+    install(args, fetched, None, targets, config, cli)
+}
+
+/// Discover, confirm, and install the skills of a fetched source.
+fn install(
+    args: AddArgs,
+    fetched: Fetched,
+    path_glob: Option<&str>,
+    targets: Vec<InstallTarget>,
+    config: &Config,
+    cli: &Cli,
+) -> Result<i32, SkiloError> {
+    let formatter = get_formatter(cli.format, cli.quiet);
+    let progress = Progress::new(cli.format, cli.quiet);
+    let quiet = cli.quiet || cli.porcelain || progress.is_enabled();
+    let Fetched {
+        root: source_path,
+        name: source_name,
+        provenance,
+        temp_dir: _temp_dir,
+    } = fetched;
 
     // Discover skills
-    let skills = discover_skills(&source_path, config)?;
+    let skills = match path_glob {
+        Some(pattern) => discover_matching(&source_path, pattern, config)?,
+        None => discover_skills(&source_path, config)?,
+    };
 
     if skills.is_empty() {
-        return Err(SkiloError::NoSkillsFound { path: source_name });
+        let path = match path_glob {
+            Some(pattern) => format!("{} matching '{}'", source_name, pattern),
+            None => source_name,
+        };
+        return Err(SkiloError::NoSkillsFound { path });
     }
 
+    progress.emit(ProgressEvent::ValidationFinished {
+        skills: skills.len(),
+        valid: skills.iter().filter(|s| s.valid).count(),
+    });
+
     // Filter by --skill if provided
     let skills = filter_skills(skills, &args.skill);
 
@@ -222,16 +571,36 @@ pub fn run(args: AddArgs, config: &Config, cli: &Cli) -> Result<i32, SkiloError>
         return Ok(1);
     }
 
+    // Drop skills the project config never wants installed
+    let (skills, excluded) = exclude_skills(skills, &config.add.exclude);
+
+    if !quiet {
+        for skill in &excluded {
+            println!("Skipping {} (excluded by config)", skill.name.yellow());
+        }
+    }
+
+    if skills.is_empty() {
+        formatter.format_error("All matching skills are excluded by config");
+        return Ok(1);
+    }
+
     // List mode
     if args.list {
         print_skill_list(&skills);
         return Ok(0);
     }
 
+    if !quiet {
+        warn_deprecated(&skills);
+    }
+
+    let targets = plan_installs(&args, config, targets, &skills)?;
+
     // Build target descriptions for confirmation
     let target_desc: Vec<String> = targets
         .iter()
-        .map(|t| {
+        .map(|(t, _)| {
             let scope_str = if t.scope.is_global() { " (global)" } else { "" };
             match t.agent {
                 Some(agent) => format!(
@@ -295,27 +664,131 @@ pub fn run(args: AddArgs, config: &Config, cli: &Cli) -> Result<i32, SkiloError>
 
     // Install skills to all targets
     let mut total_installed = 0;
+    let mut added: Vec<HookSkill> = Vec::new();
+    let mut updated: Vec<HookSkill> = Vec::new();
+
+    let header = config.add.provenance_header.then(|| {
+        provenance::render_header(&provenance.source, provenance.commit.as_deref(), now_secs())
+    });
 
-    for target in &targets {
-        if !cli.quiet && targets.len() > 1 {
-            let target_name = target
-                .agent
-                .map(|a| a.display_name().to_string())
-                .unwrap_or_else(|| target.path.display().to_string());
+    // Ask once about every skill that would be overwritten
+    let conflicts: Vec<Conflict> = targets
+        .iter()
+        .flat_map(|(target, skills)| {
+            skills.iter().filter(|s| s.valid).map(|skill| Conflict {
+                skill,
+                dest: target.path.join(&skill.name),
+                agent: target.agent,
+            })
+        })
+        .filter(|c| c.dest.exists())
+        .collect();
+    let keep = if args.yes || conflicts.is_empty() {
+        HashSet::new()
+    } else {
+        resolve_conflicts(&conflicts, header.as_deref())?
+    };
+
+    for (target, skills) in &targets {
+        if !quiet && targets.len() > 1 {
+            let target_name = match target.agent {
+                Some(agent) if target.scope.is_global() => {
+                    format!("{} (global)", agent.display_name())
+                }
+                Some(agent) => agent.display_name().to_string(),
+                None => target.path.display().to_string(),
+            };
             println!("Installing to {}...", target_name.cyan());
         }
 
         // Check for feature compatibility warnings
-        if !cli.quiet {
+        if !quiet {
             if let Some(agent) = target.agent {
-                check_feature_warnings(&skills, agent, &source_path);
+                check_feature_warnings(skills, agent, &source_path);
+                if config.lint.rules.reserved_names {
+                    check_reserved_names(skills, agent);
+                }
             }
         }
 
-        let installed = install_skills(&skills, &target.path, args.yes, cli.quiet)?;
+        let mut records = install_skills(
+            skills,
+            &target.path,
+            header.as_deref(),
+            target.agent,
+            &keep,
+            config.add.store,
+            quiet,
+        )?;
+        records.extend(excluded.iter().map(|skill| InstallRecord {
+            name: skill.name.clone(),
+            status: InstallStatus::Excluded,
+            path: target.path.join(&skill.name),
+            replaced: false,
+        }));
+        let installed = records
+            .iter()
+            .filter(|r| r.status == InstallStatus::Installed)
+            .count();
         total_installed += installed;
 
-        if !cli.quiet {
+        record_installs(&records, target, &provenance)?;
+
+        if (args.register || config.add.register) && installed > 0 {
+            if let Some(agent) = target.agent {
+                register_skills_dir(agent, target, quiet);
+            }
+        }
+
+        for record in records
+            .iter()
+            .filter(|r| r.status == InstallStatus::Installed)
+        {
+            let skill = HookSkill {
+                name: record.name.clone(),
+                path: record.path.clone(),
+                scope: target.scope,
+                agent: target.agent,
+            };
+            if record.replaced {
+                updated.push(skill);
+            } else {
+                added.push(skill);
+            }
+        }
+
+        for record in &records {
+            progress.emit(match record.status {
+                InstallStatus::Installed => ProgressEvent::SkillInstalled {
+                    skill: &record.name,
+                    agent: target.agent,
+                    scope: target.scope,
+                    path: &record.path,
+                    replaced: record.replaced,
+                },
+                status => ProgressEvent::SkillSkipped {
+                    skill: &record.name,
+                    agent: target.agent,
+                    scope: target.scope,
+                    reason: status.as_str(),
+                },
+            });
+        }
+
+        if cli.porcelain {
+            let agent_name = target.agent.map(|a| a.cli_name()).unwrap_or_default();
+            for record in &records {
+                print_porcelain(&[
+                    record.status.as_str(),
+                    &record.name,
+                    agent_name,
+                    target.scope.display_name(),
+                    &record.path.display().to_string(),
+                ]);
+            }
+        }
+
+        if !quiet {
             formatter.format_success(&format!(
                 "Installed {} skill{} to {}/",
                 installed,
@@ -325,7 +798,7 @@ pub fn run(args: AddArgs, config: &Config, cli: &Cli) -> Result<i32, SkiloError>
         }
     }
 
-    if !cli.quiet && targets.len() > 1 {
+    if !quiet && targets.len() > 1 {
         println!();
         formatter.format_success(&format!(
             "Total: {} skill{} installed to {} agent{}",
@@ -336,6 +809,17 @@ pub fn run(args: AddArgs, config: &Config, cli: &Cli) -> Result<i32, SkiloError>
         ));
     }
 
+    progress.emit(ProgressEvent::InstallFinished {
+        installed: total_installed,
+        targets: targets.len(),
+    });
+
+    let source = Some(provenance.source.as_str());
+    history::record(&config.history, Action::Install, &added, source);
+    history::record(&config.history, Action::Update, &updated, source);
+    hooks::run(&config.hooks, HookEvent::PostAdd, &added, source)?;
+    hooks::run(&config.hooks, HookEvent::PostUpdate, &updated, source)?;
+
     if total_installed == 0 {
         Ok(1)
     } else {
@@ -343,6 +827,257 @@ pub fn run(args: AddArgs, config: &Config, cli: &Cli) -> Result<i32, SkiloError>
     }
 }
 
+/// List a target's skills directory in its agent's config file, for agents
+/// that need it.
+///
+/// The skills are already installed at this point, so failures are only
+/// reported as warnings.
+fn register_skills_dir(agent: Agent, target: &InstallTarget, quiet: bool) {
+    let Some(registration) = agent.skills_registration() else {
+        return;
+    };
+    let project_root = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    let Some((file, entry)) = registration_target(agent, target.scope, &project_root, &target.path)
+    else {
+        if !quiet {
+            eprintln!(
+                "{}: {} has no {} config file; add {} to its skill paths manually",
+                "Warning".yellow(),
+                agent.display_name(),
+                target.scope,
+                target.path.display()
+            );
+        }
+        return;
+    };
+
+    match register(&file, registration.key, &entry) {
+        Ok(Registration::Added { backup }) if !quiet => {
+            let backup = backup
+                .map(|b| format!(" (previous version saved as {})", b.display()))
+                .unwrap_or_default();
+            println!(
+                "Registered {} in {}{}",
+                entry.cyan(),
+                file.display(),
+                backup
+            );
+        }
+        Ok(_) => {}
+        Err(e) => eprintln!(
+            "{}: could not register {} in {}: {}",
+            "Warning".yellow(),
+            entry,
+            file.display(),
+            e
+        ),
+    }
+}
+
+/// Warn that Git LFS assets are installed as pointer files.
+fn warn_lfs_pointers(pointers: &[PathBuf], root: &Path, config: &Config) {
+    if pointers.is_empty() {
+        return;
+    }
+
+    eprintln!(
+        "{}: {} Git LFS file(s) were not downloaded and will be installed as pointer files:",
+        "Warning".yellow(),
+        pointers.len()
+    );
+    for path in pointers.iter().take(5) {
+        eprintln!("  {}", path.strip_prefix(root).unwrap_or(path).display());
+    }
+    if pointers.len() > 5 {
+        eprintln!("  ... and {} more", pointers.len() - 5);
+    }
+    if config.add.lfs {
+        eprintln!("  Install git-lfs or check access to the repository's LFS server.");
+    } else {
+        eprintln!("  Set [add] lfs = true to download them.");
+    }
+}
+
+/// A source listed in an `--from-file` list.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct ListedSource {
+    /// The source, as accepted by `skilo add`.
+    pub(crate) source: String,
+    /// Skills named with `--skill` on the same line.
+    pub(crate) skills: Vec<String>,
+}
+
+/// Install from every source listed in `file` (`-` for stdin).
+///
+/// The whole list is parsed before anything is installed. A failing source
+/// is reported and the remaining sources are still installed.
+fn run_from_file(
+    file: &Path,
+    args: &AddArgs,
+    config: &Config,
+    cli: &Cli,
+) -> Result<i32, SkiloError> {
+    let formatter = get_formatter(cli.format, cli.quiet);
+
+    let content = if file == Path::new("-") {
+        io::read_to_string(io::stdin())?
+    } else {
+        fs::read_to_string(file)?
+    };
+    let sources = parse_source_list(&content)
+        .map_err(|e| SkiloError::Config(format!("{}: {}", file.display(), e)))?;
+
+    if sources.is_empty() {
+        formatter.format_message("No sources to install.");
+        return Ok(0);
+    }
+
+    let mut results: Vec<(&str, Result<(), String>)> = Vec::new();
+    for listed in &sources {
+        let mut add_args = args.clone();
+        add_args.source = Some(listed.source.clone());
+        add_args.from_file = None;
+        if !listed.skills.is_empty() {
+            add_args.skill = Some(listed.skills.clone());
+        }
+
+        let result = match run(add_args, config, cli) {
+            Ok(0) => Ok(()),
+            Ok(_) => Err("no skills installed".to_string()),
+            Err(SkiloError::Cancelled) => return Err(SkiloError::Cancelled),
+            Err(e) => Err(e.to_string()),
+        };
+        results.push((&listed.source, result));
+
+        if !cli.quiet && !cli.porcelain {
+            println!();
+        }
+    }
+
+    let failed = results.iter().filter(|(_, r)| r.is_err()).count();
+    if !cli.quiet && !cli.porcelain {
+        println!(
+            "Installed from {} of {} source{}:",
+            results.len() - failed,
+            results.len(),
+            if results.len() == 1 { "" } else { "s" }
+        );
+        for (source, result) in &results {
+            match result {
+                Ok(()) => println!("  {} {}", "✓".green(), source),
+                Err(e) => println!("  {} {}: {}", "✗".red(), source, e),
+            }
+        }
+    }
+
+    Ok(if failed > 0 { 1 } else { 0 })
+}
+
+/// Parse a newline-separated source list.
+///
+/// Each line holds a source optionally followed by `--skill NAME` (or
+/// `-s NAME`) filters.
+pub(crate) fn parse_source_list(content: &str) -> Result<Vec<ListedSource>, String> {
+    let mut sources = Vec::new();
+
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let mut words = line.split_whitespace();
+        let source = words.next().unwrap_or_default().to_string();
+        if source.starts_with('-') {
+            return Err(format!(
+                "line {}: expected a source, found '{}'",
+                i + 1,
+                source
+            ));
+        }
+
+        let mut skills = Vec::new();
+        while let Some(word) = words.next() {
+            let name = match word.split_once('=') {
+                Some(("--skill", name)) => Some(name),
+                _ if word == "--skill" || word == "-s" => words.next(),
+                _ => {
+                    return Err(format!(
+                        "line {}: unexpected '{}' (only --skill is allowed after the source)",
+                        i + 1,
+                        word
+                    ))
+                }
+            };
+            match name {
+                Some(name) if !name.is_empty() => skills.push(name.to_string()),
+                _ => return Err(format!("line {}: --skill needs a skill name", i + 1)),
+            }
+        }
+
+        sources.push(ListedSource { source, skills });
+    }
+
+    Ok(sources)
+}
+
+/// Record installed skills in the lockfile of the target's scope.
+fn record_installs(
+    records: &[InstallRecord],
+    target: &InstallTarget,
+    provenance: &Provenance,
+) -> Result<(), SkiloError> {
+    if !records.iter().any(|r| r.status == InstallStatus::Installed) {
+        return Ok(());
+    }
+
+    let project_root = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    let Some(lock_file) = Lockfile::path_for(target.scope, &project_root) else {
+        return Ok(());
+    };
+    let mut lock = Lockfile::load(&lock_file)?;
+
+    for record in records {
+        if record.status != InstallStatus::Installed {
+            continue;
+        }
+        lock.upsert(LockedSkill {
+            name: record.name.clone(),
+            agent: target.agent,
+            path: lock_path(target.scope, &record.path, &project_root),
+            source: provenance.source.clone(),
+            subdir: provenance.subdir.clone(),
+            branch: provenance.branch.clone(),
+            tag: provenance.tag.clone(),
+            rev: provenance.rev.clone(),
+            commit: provenance.commit.clone(),
+            checksum: checksum_dir(&record.path)?,
+            installed_at: now_secs(),
+        });
+    }
+
+    lock.save(&lock_file)
+}
+
+/// Warn about deprecated skills that are about to be installed.
+fn warn_deprecated(skills: &[SkillInfo]) {
+    for skill in skills.iter().filter(|s| s.valid && s.deprecated) {
+        match skill.superseded_by {
+            Some(ref replacement) => eprintln!(
+                "{}: Skill '{}' is deprecated; use '{}' instead",
+                "Warning".yellow(),
+                skill.name,
+                replacement
+            ),
+            None => eprintln!(
+                "{}: Skill '{}' is deprecated",
+                "Warning".yellow(),
+                skill.name
+            ),
+        }
+    }
+}
+
 /// Check for feature compatibility warnings.
 fn check_feature_warnings(skills: &[SkillInfo], agent: Agent, _source_path: &Path) {
     let features = agent.features();
@@ -352,29 +1087,67 @@ fn check_feature_warnings(skills: &[SkillInfo], agent: Agent, _source_path: &Pat
             continue;
         }
 
-        // Try to read skill manifest to check for feature usage
-        let skill_md = skill.source_path.join("SKILL.md");
-        if let Ok(content) = std::fs::read_to_string(&skill_md) {
-            // Check for context: fork usage
-            if content.contains("context: fork") && !features.context_fork {
-                eprintln!(
-                    "{}: Skill '{}' uses 'context: fork' which is only supported by Claude Code",
-                    "Warning".yellow(),
-                    skill.name.cyan()
-                );
-            }
+        let Ok(manifest) = Manifest::parse(skill.source_path.join("SKILL.md")) else {
+            continue;
+        };
 
-            // Check for hooks usage
-            if content.contains("hooks:") && !features.hooks {
-                eprintln!(
-                    "{}: Skill '{}' uses hooks which may not be supported by {}",
-                    "Warning".yellow(),
-                    skill.name.cyan(),
-                    agent.display_name()
-                );
+        if manifest.frontmatter.is_forked() && !features.context_fork {
+            eprintln!(
+                "{}: Skill '{}' uses 'context: fork' which is not supported by {}",
+                "Warning".yellow(),
+                skill.name.cyan(),
+                agent.display_name()
+            );
+        }
+
+        if manifest.frontmatter.has_hooks() && !features.hooks {
+            eprintln!(
+                "{}: Skill '{}' uses hooks which may not be supported by {}",
+                "Warning".yellow(),
+                skill.name.cyan(),
+                agent.display_name()
+            );
+        }
+    }
+}
+
+/// Warn about skills whose names collide with a built-in command of `agent`.
+fn check_reserved_names(skills: &[SkillInfo], agent: Agent) {
+    for skill in skills.iter().filter(|s| agent.is_reserved_name(&s.name)) {
+        eprintln!(
+            "{}: Skill '{}' has the same name as a built-in {} command",
+            "Warning".yellow(),
+            skill.name.cyan(),
+            agent.display_name()
+        );
+    }
+}
+
+/// Discover skills in every directory under `root` matching `pattern`.
+fn discover_matching(
+    root: &Path,
+    pattern: &str,
+    config: &Config,
+) -> Result<Vec<SkillInfo>, SkiloError> {
+    let mut skills: Vec<SkillInfo> = Vec::new();
+
+    for dir in glob_paths(root, pattern).into_iter().filter(|p| p.is_dir()) {
+        for skill in discover_skills(&dir, config)? {
+            if !skills.iter().any(|s| s.source_path == skill.source_path) {
+                skills.push(skill);
             }
         }
     }
+
+    Ok(skills)
+}
+
+/// Join a fetched subdirectory and a `--path` pattern for the lockfile.
+fn join_subdir(subdir: Option<&str>, pattern: Option<&str>) -> Option<String> {
+    match (subdir, pattern) {
+        (Some(subdir), Some(pattern)) => Some(format!("{}/{}", subdir, pattern)),
+        (subdir, pattern) => subdir.or(pattern).map(str::to_string),
+    }
 }
 
 /// Discover skills in a directory.
@@ -386,7 +1159,7 @@ fn discover_skills(root: &Path, config: &Config) -> Result<Vec<SkillInfo>, Skilo
     let mut seen_paths = HashSet::new();
 
     // Use the existing discovery mechanism
-    let skill_paths = Discovery::find_skills(root, &config.discovery.ignore);
+    let skill_paths = Discovery::find_skills(root, &config.discovery);
 
     if skill_paths.is_empty() {
         // Try looking in common locations and all agent-specific directories
@@ -396,7 +1169,7 @@ fn discover_skills(root: &Path, config: &Config) -> Result<Vec<SkillInfo>, Skilo
         for loc in locations {
             let path = root.join(loc);
             if path.exists() {
-                let found = Discovery::find_skills(&path, &config.discovery.ignore);
+                let found = Discovery::find_skills(&path, &config.discovery);
                 for skill_path in found {
                     if seen_paths.insert(skill_path.clone()) {
                         if let Some(info) = load_skill_info(&skill_path, config) {
@@ -422,6 +1195,19 @@ fn discover_skills(root: &Path, config: &Config) -> Result<Vec<SkillInfo>, Skilo
     Ok(skills)
 }
 
+/// The names and directories of the valid skills in `root`, found the way
+/// `add` finds them.
+pub(crate) fn skill_dirs(
+    root: &Path,
+    config: &Config,
+) -> Result<Vec<(String, PathBuf)>, SkiloError> {
+    Ok(discover_skills(root, config)?
+        .into_iter()
+        .filter(|s| s.valid)
+        .map(|s| (s.name, s.source_path))
+        .collect())
+}
+
 /// Load skill info from a SKILL.md path.
 fn load_skill_info(skill_path: &Path, config: &Config) -> Option<SkillInfo> {
     let manifest = match Manifest::parse(skill_path.to_path_buf()) {
@@ -448,6 +1234,8 @@ fn load_skill_info(skill_path: &Path, config: &Config) -> Option<SkillInfo> {
         source_path,
         valid,
         errors,
+        deprecated: manifest.frontmatter.is_deprecated(),
+        superseded_by: manifest.frontmatter.superseded_by.clone(),
     })
 }
 
@@ -462,6 +1250,21 @@ fn filter_skills(skills: Vec<SkillInfo>, filter: &Option<Vec<String>>) -> Vec<Sk
     }
 }
 
+/// Split off skills whose name matches any of the `exclude` patterns.
+///
+/// Returns the skills to install and the excluded ones.
+fn exclude_skills(skills: Vec<SkillInfo>, exclude: &[String]) -> (Vec<SkillInfo>, Vec<SkillInfo>) {
+    let matchers: Vec<_> = exclude
+        .iter()
+        .filter_map(|pattern| globset::Glob::new(pattern).ok())
+        .map(|glob| glob.compile_matcher())
+        .collect();
+
+    skills
+        .into_iter()
+        .partition(|s| !matchers.iter().any(|m| m.is_match(&s.name)))
+}
+
 /// Print the list of discovered skills.
 fn print_skill_list(skills: &[SkillInfo]) {
     println!();
@@ -474,10 +1277,12 @@ fn print_skill_list(skills: &[SkillInfo]) {
     let max_name_len = skills.iter().map(|s| s.name.len()).max().unwrap_or(20);
 
     for skill in skills {
-        let status = if skill.valid {
-            "".to_string()
-        } else {
+        let status = if !skill.valid {
             format!(" {}", "(invalid)".yellow())
+        } else if skill.deprecated {
+            format!(" {}", "(deprecated)".yellow())
+        } else {
+            "".to_string()
         };
 
         let description = truncate_description(&skill.description, 50);
@@ -504,19 +1309,191 @@ fn truncate_description(s: &str, max_len: usize) -> String {
     }
 }
 
+/// A valid skill that is already installed at one of the targets.
+struct Conflict<'a> {
+    /// The incoming skill.
+    skill: &'a SkillInfo,
+    /// The existing installation.
+    dest: PathBuf,
+    /// The agent of the target, whose overrides apply.
+    agent: Option<Agent>,
+}
+
+/// Ask how to handle skills that are already installed.
+///
+/// All conflicts are listed on one screen with the choice to overwrite or
+/// skip all of them, decide per skill (once for every agent), or show what
+/// would change. Returns the installations to leave untouched.
+fn resolve_conflicts(
+    conflicts: &[Conflict],
+    header: Option<&str>,
+) -> Result<HashSet<PathBuf>, SkiloError> {
+    println!("{} skill(s) already installed:", conflicts.len());
+    for conflict in conflicts {
+        println!(
+            "  {} {}",
+            conflict.skill.name.yellow(),
+            conflict.dest.display().to_string().dimmed()
+        );
+    }
+    println!();
+
+    let options = [
+        "Overwrite all",
+        "Skip all",
+        "Decide per skill",
+        "Show differences",
+    ];
+    loop {
+        let choice = Select::new()
+            .with_prompt("Replace existing skills?")
+            .items(options)
+            .default(0)
+            .interact()
+            .map_err(|_| SkiloError::Cancelled)?;
+
+        match choice {
+            0 => return Ok(HashSet::new()),
+            1 => return Ok(conflicts.iter().map(|c| c.dest.clone()).collect()),
+            2 => {
+                let mut keep = HashSet::new();
+                let mut asked = HashSet::new();
+                for conflict in conflicts {
+                    let name = &conflict.skill.name;
+                    if !asked.insert(name) {
+                        continue;
+                    }
+                    let count = conflicts.iter().filter(|c| &c.skill.name == name).count();
+                    let prompt = if count == 1 {
+                        format!("Overwrite '{}'?", name)
+                    } else {
+                        format!("Overwrite '{}' in {} locations?", name, count)
+                    };
+                    if !Confirm::new()
+                        .with_prompt(prompt)
+                        .interact()
+                        .map_err(|_| SkiloError::Cancelled)?
+                    {
+                        keep.extend(
+                            conflicts
+                                .iter()
+                                .filter(|c| &c.skill.name == name)
+                                .map(|c| c.dest.clone()),
+                        );
+                    }
+                }
+                println!();
+                return Ok(keep);
+            }
+            _ => {
+                for conflict in conflicts {
+                    println!(
+                        "\n{} {}",
+                        conflict.skill.name.bold(),
+                        conflict.dest.display().to_string().dimmed()
+                    );
+                    let changes = conflict_changes(
+                        &conflict.dest,
+                        &conflict.skill.source_path,
+                        header,
+                        conflict.agent,
+                    );
+                    if changes.is_empty() {
+                        println!("  no differences");
+                    }
+                    for line in changes {
+                        println!("  {}", line);
+                    }
+                }
+                println!();
+            }
+        }
+    }
+}
+
+/// Describe how installing `incoming` over `installed` would change it.
+///
+/// Lists added (`A`), modified (`M`), and deleted (`D`) files, followed by
+/// the changed lines of SKILL.md.
+fn conflict_changes(
+    installed: &Path,
+    incoming: &Path,
+    header: Option<&str>,
+    agent: Option<Agent>,
+) -> Vec<String> {
+    let files = |root: &Path| -> BTreeSet<PathBuf> {
+        walkdir::WalkDir::new(root)
+            .into_iter()
+            .filter_entry(|e| e.file_name() != ".git")
+            .filter_map(Result::ok)
+            .filter(|e| e.file_type().is_file())
+            .filter_map(|e| e.path().strip_prefix(root).ok().map(Path::to_path_buf))
+            .collect()
+    };
+    let old_files = files(installed);
+    let new_files = files(incoming);
+
+    let read_new = |file: &Path| -> Option<Vec<u8>> {
+        let content = fs::read(incoming.join(file)).ok()?;
+        if file == Path::new("SKILL.md") {
+            let text = String::from_utf8(content).ok()?;
+            return Some(installed_skill_md(&text, header, agent).into_bytes());
+        }
+        Some(content)
+    };
+
+    let mut changes = Vec::new();
+    let mut skill_md = None;
+    for file in old_files.union(&new_files) {
+        let line = match (old_files.contains(file), new_files.contains(file)) {
+            (true, true) => {
+                let old = fs::read(installed.join(file)).ok();
+                let new = read_new(file);
+                if old == new {
+                    continue;
+                }
+                if file == Path::new("SKILL.md") {
+                    skill_md = old.zip(new);
+                }
+                format!("M {}", file.display())
+            }
+            (false, _) => format!("A {}", file.display()),
+            (_, false) => format!("D {}", file.display()),
+        };
+        changes.push(line);
+    }
+
+    if let Some((old, new)) = skill_md {
+        let diff = minimal_diff(
+            &String::from_utf8_lossy(&old),
+            &String::from_utf8_lossy(&new),
+        );
+        changes.extend(diff.iter().flat_map(|d| d.lines()).map(str::to_string));
+    }
+    changes
+}
+
 /// Install skills to the target directory.
+///
+/// Skills whose destination is in `keep` are left as they are. SKILL.md is
+/// written as [`installed_skill_md`] returns it.
 fn install_skills(
     skills: &[SkillInfo],
     install_dir: &Path,
-    skip_confirm: bool,
+    header: Option<&str>,
+    agent: Option<Agent>,
+    keep: &HashSet<PathBuf>,
+    use_store: bool,
     quiet: bool,
-) -> Result<usize, SkiloError> {
+) -> Result<Vec<InstallRecord>, SkiloError> {
     // Create the install directory if needed
     fs::create_dir_all(install_dir)?;
 
-    let mut installed = 0;
+    let mut records = Vec::new();
 
     for skill in skills {
+        let dest = install_dir.join(&skill.name);
+
         if !skill.valid {
             if !quiet {
                 println!(
@@ -525,30 +1502,27 @@ fn install_skills(
                     skill.errors.join(", ")
                 );
             }
+            records.push(InstallRecord {
+                name: skill.name.clone(),
+                status: InstallStatus::Invalid,
+                path: dest,
+                replaced: false,
+            });
             continue;
         }
 
-        let dest = install_dir.join(&skill.name);
-
-        // Check if already exists
-        if dest.exists() {
-            if skip_confirm {
-                // Overwrite silently in --yes mode
-                fs::remove_dir_all(&dest)?;
-            } else {
-                let prompt = format!("Skill '{}' already exists. Overwrite?", skill.name);
-                if !Confirm::new()
-                    .with_prompt(prompt)
-                    .interact()
-                    .map_err(|_| SkiloError::Cancelled)?
-                {
-                    if !quiet {
-                        println!("Skipping {}...", skill.name);
-                    }
-                    continue;
-                }
-                fs::remove_dir_all(&dest)?;
+        // An existing skill is replaced once the copy is complete
+        if keep.contains(&dest) {
+            if !quiet {
+                println!("Skipping {}...", skill.name);
             }
+            records.push(InstallRecord {
+                name: skill.name.clone(),
+                status: InstallStatus::Skipped,
+                path: dest,
+                replaced: false,
+            });
+            continue;
         }
 
         if !quiet {
@@ -556,21 +1530,55 @@ fn install_skills(
             io::stdout().flush().ok();
         }
 
-        // Copy the skill directory
-        copy_dir_all(&skill.source_path, &dest)?;
+        // Link the skill's files from the store, or copy them
+        let replaced = dest.exists();
+        let staged = StagedDir::begin(OperationKind::Install, &dest)?;
+        if use_store {
+            store::link_into(&store::add(&skill.source_path)?, staged.path())?;
+        } else {
+            copy_dir_all(&skill.source_path, staged.path())?;
+        }
+        if header.is_some() || agent.is_some() {
+            let skill_md = staged.path().join("SKILL.md");
+            let content = fs::read_to_string(&skill_md)?;
+            let installed = installed_skill_md(&content, header, agent);
+            if installed != content {
+                // Replaced rather than overwritten: it may be linked from the store
+                write_atomic(&skill_md, installed)?;
+            }
+        }
+        staged.commit()?;
 
         if !quiet {
             println!(" {}", "done".green());
         }
 
-        installed += 1;
+        records.push(InstallRecord {
+            name: skill.name.clone(),
+            status: InstallStatus::Installed,
+            path: dest,
+            replaced,
+        });
     }
 
-    Ok(installed)
+    Ok(records)
+}
+
+/// SKILL.md `content` as installed: with `agent`'s overrides applied and the
+/// provenance `header` added.
+fn installed_skill_md(content: &str, header: Option<&str>, agent: Option<Agent>) -> String {
+    let content = match agent {
+        Some(agent) => overrides::apply_to_content(content, agent),
+        None => content.to_string(),
+    };
+    match header {
+        Some(header) => provenance::apply_header(&content, header),
+        None => content,
+    }
 }
 
 /// Recursively copy a directory.
-fn copy_dir_all(src: &Path, dst: &Path) -> Result<(), SkiloError> {
+pub(crate) fn copy_dir_all(src: &Path, dst: &Path) -> Result<(), SkiloError> {
     fs::create_dir_all(dst)?;
 
     for entry in fs::read_dir(src)? {
@@ -579,6 +1587,11 @@ fn copy_dir_all(src: &Path, dst: &Path) -> Result<(), SkiloError> {
         let src_path = entry.path();
         let dst_path = dst.join(entry.file_name());
 
+        // Submodule checkouts hold a `.git` file pointing into the parent repo
+        if entry.file_name() == ".git" {
+            continue;
+        }
+
         if ty.is_dir() {
             copy_dir_all(&src_path, &dst_path)?;
         } else {
@@ -594,6 +1607,66 @@ mod tests {
     use super::*;
     use tempfile::TempDir;
 
+    /// Non-interactive `add` arguments for `source`, with no other options.
+    fn args(source: &str) -> AddArgs {
+        AddArgs {
+            source: Some(source.to_string()),
+            from_file: None,
+            skill: None,
+            list: false,
+            yes: true,
+            branch: None,
+            tag: None,
+            rev: None,
+            path: None,
+            agent: None,
+            global: false,
+            scope: None,
+            output: None,
+            register: false,
+            ssh_key: None,
+        }
+    }
+
+    #[test]
+    fn test_conflict_changes() {
+        let temp = TempDir::new().unwrap();
+        let installed = temp.path().join("installed");
+        let incoming = temp.path().join("incoming");
+        for dir in [&installed, &incoming] {
+            fs::create_dir_all(dir.join("scripts")).unwrap();
+            fs::write(dir.join("scripts/run.sh"), "#!/bin/sh\n").unwrap();
+        }
+        fs::write(
+            installed.join("SKILL.md"),
+            "---\nname: demo\n---\n\nOld step.\n",
+        )
+        .unwrap();
+        fs::write(
+            incoming.join("SKILL.md"),
+            "---\nname: demo\n---\n\nNew step.\n",
+        )
+        .unwrap();
+        fs::write(installed.join("notes.md"), "old\n").unwrap();
+        fs::write(incoming.join("guide.md"), "new\n").unwrap();
+
+        let changes = conflict_changes(&installed, &incoming, None, None);
+        assert_eq!(
+            changes,
+            vec![
+                "M SKILL.md",
+                "A guide.md",
+                "D notes.md",
+                "@@ line 5 @@",
+                " ",
+                "-Old step.",
+                "+New step.",
+            ]
+        );
+
+        assert!(conflict_changes(&installed, &installed, None, None).is_empty());
+    }
+
     #[test]
     fn test_discover_skills_empty() {
         let temp = TempDir::new().unwrap();
@@ -603,6 +1676,34 @@ mod tests {
         assert!(skills.is_empty());
     }
 
+    #[test]
+    fn test_discover_matching() {
+        let temp = TempDir::new().unwrap();
+        for name in ["web-forms", "web-api", "data-etl"] {
+            let dir = temp.path().join("skills").join(name);
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(
+                dir.join("SKILL.md"),
+                format!("---\nname: {}\ndescription: Test\n---\n", name),
+            )
+            .unwrap();
+        }
+        let config = Config::default();
+
+        let skills = discover_matching(temp.path(), "skills/web-*", &config).unwrap();
+        let mut names: Vec<&str> = skills.iter().map(|s| s.name.as_str()).collect();
+        names.sort();
+        assert_eq!(names, ["web-api", "web-forms"]);
+
+        assert!(discover_matching(temp.path(), "docs/*", &config)
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            join_subdir(Some("packages"), Some("skills/web-*")).as_deref(),
+            Some("packages/skills/web-*")
+        );
+    }
+
     #[test]
     fn test_filter_skills() {
         let skills = vec![
@@ -612,6 +1713,8 @@ mod tests {
                 source_path: PathBuf::from("/tmp/a"),
                 valid: true,
                 errors: vec![],
+                deprecated: false,
+                superseded_by: None,
             },
             SkillInfo {
                 name: "skill-b".to_string(),
@@ -619,6 +1722,8 @@ mod tests {
                 source_path: PathBuf::from("/tmp/b"),
                 valid: true,
                 errors: vec![],
+                deprecated: false,
+                superseded_by: None,
             },
         ];
 
@@ -629,4 +1734,157 @@ mod tests {
         let filtered = filter_skills(skills, &None);
         assert_eq!(filtered.len(), 2);
     }
+
+    #[test]
+    fn test_exclude_skills() {
+        let skill = |name: &str| SkillInfo {
+            name: name.to_string(),
+            description: "Test".to_string(),
+            source_path: PathBuf::from("/tmp").join(name),
+            valid: true,
+            errors: vec![],
+            deprecated: false,
+            superseded_by: None,
+        };
+        let skills = vec![skill("code-review"), skill("legacy-lint"), skill("danger")];
+
+        let (kept, excluded) =
+            exclude_skills(skills, &["legacy-*".to_string(), "danger".to_string()]);
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].name, "code-review");
+        assert_eq!(excluded.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_source_list() {
+        let sources = parse_source_list(
+            "# Team skills\n\nowner/one\nowner/two --skill a -s b\n  ./local --skill=c\n",
+        )
+        .unwrap();
+        assert_eq!(
+            sources,
+            vec![
+                ListedSource {
+                    source: "owner/one".into(),
+                    skills: vec![],
+                },
+                ListedSource {
+                    source: "owner/two".into(),
+                    skills: vec!["a".into(), "b".into()],
+                },
+                ListedSource {
+                    source: "./local".into(),
+                    skills: vec!["c".into()],
+                },
+            ]
+        );
+
+        assert_eq!(
+            parse_source_list("owner/one --skill\n").unwrap_err(),
+            "line 1: --skill needs a skill name"
+        );
+        assert!(parse_source_list("owner/one\nowner/two --global\n")
+            .unwrap_err()
+            .starts_with("line 2: unexpected '--global'"));
+    }
+
+    #[test]
+    fn test_check_allowed_project_narrows_global() {
+        let mut config = Config::default();
+        config.add.global_allowed_sources = vec!["my-org/*".into()];
+        config.add.allowed_sources = vec!["my-org/skills".into()];
+        let allowed = |source: &str| check_allowed(&Source::parse(source).unwrap(), &config);
+
+        assert!(allowed("my-org/skills").is_ok());
+        assert!(allowed("my-org/other").is_err());
+
+        // A project list cannot allow what the global list does not
+        config.add.allowed_sources = vec!["*/*".into()];
+        let allowed = |source: &str| check_allowed(&Source::parse(source).unwrap(), &config);
+        assert!(allowed("other-org/skills").is_err());
+        assert!(allowed("./local").is_ok());
+    }
+
+    #[test]
+    fn test_resolve_scope_targets_both() {
+        let temp = TempDir::new().unwrap();
+        let config = Config::default();
+        let args = AddArgs {
+            agent: Some(vec![crate::cli::Agent::Claude]),
+            scope: Some(ScopeArg::Both),
+            ..args("owner/repo")
+        };
+
+        let mut targets = Vec::new();
+        for scope in ScopeArg::resolve(args.scope, args.global, config.add.default_scope) {
+            targets.extend(resolve_scope_targets(&args, &config, scope, temp.path()).unwrap());
+        }
+
+        assert_eq!(targets.len(), 2);
+        assert_eq!(targets[0].scope, Scope::Project);
+        assert_eq!(targets[0].path, temp.path().join(".claude/skills"));
+        assert_eq!(targets[1].scope, Scope::Global);
+    }
+
+    #[test]
+    fn test_resolve_scope_targets_generic() {
+        let temp = TempDir::new().unwrap();
+        let config = Config::default();
+        let args = AddArgs {
+            agent: Some(vec![crate::cli::Agent::Claude, crate::cli::Agent::Generic]),
+            ..args("owner/repo")
+        };
+
+        let targets = resolve_scope_targets(&args, &config, Scope::Project, temp.path()).unwrap();
+        assert_eq!(targets.len(), 2);
+        assert_eq!(targets[0].agent, Some(Agent::Claude));
+        assert_eq!(targets[1].agent, None);
+        assert_eq!(targets[1].path, temp.path().join("skills"));
+
+        assert!(resolve_scope_targets(&args, &config, Scope::Global, temp.path()).is_err());
+    }
+
+    #[test]
+    fn test_plan_installs_overrides() {
+        let skill = |name: &str| SkillInfo {
+            name: name.to_string(),
+            description: "Test".to_string(),
+            source_path: PathBuf::from("/tmp").join(name),
+            valid: true,
+            errors: vec![],
+            deprecated: false,
+            superseded_by: None,
+        };
+        let mut config = Config::default();
+        config.add.overrides.insert(
+            "deploy-helper".to_string(),
+            SkillOverride {
+                agents: vec![Agent::Claude],
+                scope: None,
+            },
+        );
+        let mut args = args("owner/repo");
+        let defaults = || {
+            vec![InstallTarget {
+                agent: None,
+                path: PathBuf::from("skills"),
+                scope: Scope::Project,
+            }]
+        };
+        let skills = vec![skill("code-review"), skill("deploy-helper")];
+
+        let plan = plan_installs(&args, &config, defaults(), &skills).unwrap();
+        assert_eq!(plan.len(), 2);
+        assert_eq!(plan[0].0.path, PathBuf::from("skills"));
+        assert_eq!(plan[0].1.len(), 1);
+        assert_eq!(plan[0].1[0].name, "code-review");
+        assert_eq!(plan[1].0.agent, Some(Agent::Claude));
+        assert_eq!(plan[1].1[0].name, "deploy-helper");
+
+        // Explicit target flags take precedence over overrides
+        args.output = Some(PathBuf::from("out"));
+        let plan = plan_installs(&args, &config, defaults(), &skills).unwrap();
+        assert_eq!(plan.len(), 1);
+        assert_eq!(plan[0].1.len(), 2);
+    }
 }
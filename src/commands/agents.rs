@@ -1,13 +1,59 @@
 //! List detected AI coding agents.
 
-use crate::agent::{Agent, AgentFeatures, DetectedAgent};
-use crate::cli::{AgentsArgs, Cli};
+use crate::agent::{Agent, AgentFeatures, Confidence, DetectedAgent};
+use crate::cli::{AgentsArgs, Cli, OutputFormat};
 use crate::config::Config;
 use crate::error::SkiloError;
-use crate::output::get_formatter;
+use crate::output::{get_formatter, print_porcelain, Align, Table};
+use crate::scope::Scope;
 use colored::Colorize;
+use dialoguer::Confirm;
+use serde::Serialize;
+use std::collections::HashSet;
 use std::path::PathBuf;
 
+/// JSON output for the agents command.
+#[derive(Serialize)]
+struct AgentsOutput {
+    /// Agents detected at project or global level.
+    detected: Vec<DetectedAgentEntry>,
+    /// Configured default agent, if any.
+    default_agent: Option<&'static str>,
+    /// All configured default agents.
+    default_agents: Vec<&'static str>,
+    /// Feature support for every known agent.
+    features: Vec<AgentFeatureEntry>,
+}
+
+/// A detected agent in JSON output.
+#[derive(Serialize)]
+struct DetectedAgentEntry {
+    agent: &'static str,
+    name: &'static str,
+    scope: &'static str,
+    skills_path: PathBuf,
+    skill_count: usize,
+    confidence: Confidence,
+    signals: Vec<SignalEntry>,
+}
+
+/// A detection signal in JSON output.
+#[derive(Serialize)]
+struct SignalEntry {
+    kind: &'static str,
+    path: PathBuf,
+}
+
+/// A row of the feature matrix in JSON output.
+#[derive(Serialize)]
+struct AgentFeatureEntry {
+    agent: &'static str,
+    name: &'static str,
+    skills_dir: &'static str,
+    global_skills_dir: &'static str,
+    features: AgentFeatures,
+}
+
 /// Run the agents command.
 ///
 /// Lists all detected agents at project and global levels.
@@ -18,9 +64,31 @@ pub fn run(args: AgentsArgs, config: &Config, cli: &Cli) -> Result<i32, SkiloErr
     // Detect all agents
     let detected = Agent::detect_all(&project_root);
 
+    if args.fix_missing {
+        return fix_missing(&detected, &args, cli.quiet);
+    }
+
+    if matches!(cli.format, OutputFormat::Json) {
+        print_json(&detected, config)?;
+        return Ok(0);
+    }
+
+    if cli.porcelain {
+        for agent in &detected {
+            let scope = if agent.is_global { "global" } else { "project" };
+            print_porcelain(&[
+                scope,
+                agent.agent.cli_name(),
+                &agent.skill_count.to_string(),
+                &agent.skills_path.display().to_string(),
+            ]);
+        }
+        return Ok(0);
+    }
+
     if detected.is_empty() {
         formatter.format_message("No agents detected.");
-        match config.add.default_agent {
+        match config.add.primary_agent() {
             Some(agent) => {
                 formatter.format_message(&format!(
                     "\nDefault agent: {} ({})",
@@ -74,6 +142,147 @@ pub fn run(args: AgentsArgs, config: &Config, cli: &Cli) -> Result<i32, SkiloErr
     Ok(0)
 }
 
+/// Create the missing skills directories of detected agents.
+fn fix_missing(
+    detected: &[DetectedAgent],
+    args: &AgentsArgs,
+    quiet: bool,
+) -> Result<i32, SkiloError> {
+    let scopes = args.scope.scopes();
+    let mut missing: Vec<&DetectedAgent> = detected
+        .iter()
+        .filter(|d| {
+            let scope = if d.is_global {
+                Scope::Global
+            } else {
+                Scope::Project
+            };
+            scopes.contains(&scope) && !d.skills_path.exists()
+        })
+        .collect();
+    // Agents can share a skills directory
+    let mut seen = HashSet::new();
+    missing.retain(|d| seen.insert(d.skills_path.clone()));
+
+    if missing.is_empty() {
+        if !quiet {
+            println!("Every detected agent has a skills directory.");
+        }
+        return Ok(0);
+    }
+
+    if !quiet {
+        println!("{}", "Missing skills directories:".bold());
+        for agent in &missing {
+            println!(
+                "  {:<14} {}",
+                agent.agent.display_name().cyan(),
+                agent.skills_path.display()
+            );
+        }
+        println!();
+    }
+
+    if !args.yes
+        && !Confirm::new()
+            .with_prompt(format!(
+                "Create {} director{}?",
+                missing.len(),
+                if missing.len() == 1 { "y" } else { "ies" }
+            ))
+            .default(true)
+            .interact()
+            .map_err(|_| SkiloError::Cancelled)?
+    {
+        return Err(SkiloError::Cancelled);
+    }
+
+    for agent in &missing {
+        std::fs::create_dir_all(&agent.skills_path)?;
+        if args.readme {
+            std::fs::write(
+                agent.skills_path.join("README.md"),
+                readme(agent.agent, agent.is_global),
+            )?;
+        }
+        if !quiet {
+            println!("{} Created {}", "✓".green(), agent.skills_path.display());
+        }
+    }
+
+    Ok(0)
+}
+
+/// README placed in a new skills directory.
+fn readme(agent: Agent, is_global: bool) -> String {
+    let flags = format!(
+        "--agent {}{}",
+        agent.cli_name(),
+        if is_global { " --global" } else { "" }
+    );
+    format!(
+        "# {} Skills\n\n\
+         Each subdirectory holds an Agent Skill: a `SKILL.md` file with optional\n\
+         `scripts/`, `references/`, and `assets/`.\n\n\
+         Skills here are managed with [skilo](https://github.com/manuelmauro/skilo):\n\n\
+         ```sh\n\
+         skilo add owner/repo {flags}   # Install skills\n\
+         skilo list {flags}             # Show installed skills\n\
+         skilo remove <name> {flags}    # Remove a skill\n\
+         ```\n",
+        agent.display_name(),
+        flags = flags
+    )
+}
+
+/// Print detected agents and the feature matrix as JSON.
+fn print_json(detected: &[DetectedAgent], config: &Config) -> Result<(), SkiloError> {
+    let output = AgentsOutput {
+        detected: detected
+            .iter()
+            .map(|d| DetectedAgentEntry {
+                agent: d.agent.cli_name(),
+                name: d.agent.display_name(),
+                scope: if d.is_global { "global" } else { "project" },
+                skills_path: d.skills_path.clone(),
+                skill_count: d.skill_count,
+                confidence: d.confidence(),
+                signals: d
+                    .signals
+                    .iter()
+                    .map(|s| SignalEntry {
+                        kind: s.kind(),
+                        path: s.path().to_path_buf(),
+                    })
+                    .collect(),
+            })
+            .collect(),
+        default_agent: config.add.primary_agent().map(|a| a.cli_name()),
+        default_agents: config
+            .add
+            .default_agent_list()
+            .iter()
+            .map(|a| a.cli_name())
+            .collect(),
+        features: Agent::all()
+            .iter()
+            .map(|a| AgentFeatureEntry {
+                agent: a.cli_name(),
+                name: a.display_name(),
+                skills_dir: a.skills_dir(),
+                global_skills_dir: a.global_skills_dir(),
+                features: a.features(),
+            })
+            .collect(),
+    };
+
+    let json = serde_json::to_string_pretty(&output)
+        .map_err(|e| SkiloError::Config(format!("JSON serialization failed: {}", e)))?;
+    println!("{}", json);
+
+    Ok(())
+}
+
 /// Print information about a detected agent.
 fn print_agent_info(agent: &DetectedAgent, verbose: bool) {
     let skill_text = if agent.skill_count == 1 {
@@ -90,6 +299,20 @@ fn print_agent_info(agent: &DetectedAgent, verbose: bool) {
     );
 
     if verbose {
+        println!(
+            "    {} {}",
+            "Confidence:".dimmed(),
+            agent.confidence().to_string().dimmed()
+        );
+        for signal in &agent.signals {
+            println!(
+                "    {} {} {}",
+                "Detected:".dimmed(),
+                signal.kind().dimmed(),
+                signal.path().display().to_string().dimmed()
+            );
+        }
+
         let features = agent.agent.features();
         print_features(&features);
     }
@@ -122,28 +345,24 @@ fn print_features(features: &AgentFeatures) {
 
 /// Print the full feature matrix for all agents.
 fn print_feature_matrix() {
-    println!();
-    println!(
-        "  {:<14} {:^12} {:^8} {:^14} {:^8}",
-        "Agent".bold(),
-        "context:fork".bold(),
-        "hooks".bold(),
-        "allowed-tools".bold(),
-        "scripts".bold()
-    );
-    println!("  {}", "-".repeat(60));
+    let mut table = Table::new(["Agent", "context:fork", "hooks", "allowed-tools", "scripts"]);
+    for column in 1..5 {
+        table = table.align(column, Align::Center);
+    }
 
     for agent in Agent::all() {
         let features = agent.features();
-        println!(
-            "  {:<14} {:^12} {:^8} {:^14} {:^8}",
+        table.row([
             agent.display_name(),
             feature_mark(features.context_fork),
             feature_mark(features.hooks),
             feature_mark(features.allowed_tools),
             feature_mark(features.scripts),
-        );
+        ]);
     }
+
+    println!();
+    table.print();
 }
 
 /// Return a mark for feature support.
@@ -0,0 +1,461 @@
+//! JSON-RPC 2.0 API over stdin and stdout for editors and bots.
+//!
+//! Each line on stdin is one request and each line on stdout is one
+//! response or notification. While a request runs, anything the command
+//! would have printed is sent as a `progress` notification carrying the
+//! request id, so stdout only ever contains protocol messages:
+//!
+//! ```text
+//! → {"jsonrpc":"2.0","id":1,"method":"add","params":{"source":"owner/repo"}}
+//! ← {"jsonrpc":"2.0","method":"progress","params":{"id":1,"message":"Installing ..."}}
+//! ← {"jsonrpc":"2.0","id":1,"result":{"exit_code":0,"success":true}}
+//! ```
+//!
+//! Methods are `discover`, `lint`, `list`, `add`, and `remove`. Capturing
+//! command output is only supported on Unix; elsewhere commands run with
+//! `--quiet` and no progress is reported.
+
+use crate::cli::{ApiArgs, Cli, Command};
+use crate::config::Config;
+use crate::error::SkiloError;
+use crate::output::{JsonFormatter, OutputFormatter};
+use crate::skill::{Discovery, Manifest, ValidationResult, Validator};
+use clap::Parser;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::io::{BufRead, Write};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+/// Line written to the captured stdout to mark the end of a request's output.
+const SYNC_MARKER: &str = "\u{0}skilo-api-sync";
+
+/// JSON-RPC error codes.
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+/// Start of the implementation-defined server error range.
+const SERVER_ERROR: i64 = -32000;
+
+/// Run the api command.
+pub fn run(_args: ApiArgs, config: &Config, _cli: &Cli) -> Result<i32, SkiloError> {
+    // Progress messages are read by programs, not terminals
+    colored::control::set_override(false);
+
+    #[cfg(unix)]
+    let (writer, capture) = {
+        let (protocol, captured) = unix::redirect_stdout()?;
+        let writer = Arc::new(Writer::new(Box::new(protocol)));
+        let capture = Capture::spawn(captured, Arc::clone(&writer))?;
+        (writer, Some(capture))
+    };
+    #[cfg(not(unix))]
+    let (writer, capture): (_, Option<Capture>) =
+        (Arc::new(Writer::new(Box::new(std::io::stdout()))), None);
+
+    let stdin = std::io::stdin();
+    for line in stdin.lock().lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let (id, outcome) = match serde_json::from_str::<Value>(&line) {
+            Ok(message) => {
+                let id = message.get("id").cloned();
+                writer.set_current(id.clone().unwrap_or(Value::Null));
+                let outcome = handle(&message, config, capture.is_some());
+                if let Some(capture) = &capture {
+                    capture.sync();
+                }
+                (id, outcome)
+            }
+            Err(e) => (
+                Some(Value::Null),
+                Err(RpcError::new(PARSE_ERROR, e.to_string())),
+            ),
+        };
+
+        // Requests without an id are notifications and get no response
+        let Some(id) = id else {
+            continue;
+        };
+        let response = match outcome {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err(e) => json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": { "code": e.code, "message": e.message },
+            }),
+        };
+        writer.send(&response)?;
+    }
+
+    Ok(0)
+}
+
+/// A JSON-RPC error.
+#[derive(Debug)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<SkiloError> for RpcError {
+    fn from(e: SkiloError) -> Self {
+        Self::new(SERVER_ERROR, e.to_string())
+    }
+}
+
+/// Dispatch a single request.
+fn handle(message: &Value, config: &Config, verbose: bool) -> Result<Value, RpcError> {
+    if message.get("jsonrpc").and_then(Value::as_str) != Some("2.0") {
+        return Err(RpcError::new(INVALID_REQUEST, "expected jsonrpc \"2.0\""));
+    }
+    let Some(method) = message.get("method").and_then(Value::as_str) else {
+        return Err(RpcError::new(INVALID_REQUEST, "missing method"));
+    };
+    let params = message.get("params").cloned().unwrap_or(json!({}));
+
+    match method {
+        "discover" => discover(parse_params(params)?, config),
+        "lint" => lint(parse_params(params)?, config),
+        "list" => list(parse_params(params)?, config),
+        "add" => add(parse_params(params)?, config, verbose),
+        "remove" => remove(parse_params(params)?, config, verbose),
+        _ => Err(RpcError::new(
+            METHOD_NOT_FOUND,
+            format!("unknown method '{}'", method),
+        )),
+    }
+}
+
+fn parse_params<T: DeserializeOwned>(params: Value) -> Result<T, RpcError> {
+    serde_json::from_value(params).map_err(|e| RpcError::new(INVALID_PARAMS, e.to_string()))
+}
+
+/// Parameters for `discover` and `lint`.
+#[derive(Deserialize)]
+#[serde(default)]
+struct PathsParams {
+    paths: Vec<PathBuf>,
+}
+
+impl Default for PathsParams {
+    fn default() -> Self {
+        Self {
+            paths: vec![PathBuf::from(".")],
+        }
+    }
+}
+
+/// Parameters for `list`.
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct ListParams {
+    path: Option<String>,
+    agent: Option<String>,
+    scope: Option<String>,
+}
+
+/// Parameters for `add`.
+#[derive(Deserialize)]
+struct AddParams {
+    source: String,
+    #[serde(default)]
+    skills: Vec<String>,
+    #[serde(default)]
+    agents: Vec<String>,
+    scope: Option<String>,
+    branch: Option<String>,
+    tag: Option<String>,
+    path: Option<String>,
+}
+
+/// Parameters for `remove`.
+#[derive(Deserialize)]
+struct RemoveParams {
+    skills: Vec<String>,
+    agent: Option<String>,
+    scope: Option<String>,
+}
+
+fn discover(params: PathsParams, config: &Config) -> Result<Value, RpcError> {
+    let skills: Vec<Value> = Discovery::find_skills_in(&params.paths, &config.discovery)
+        .into_iter()
+        .map(|path| match Manifest::parse(path.clone()) {
+            Ok(manifest) => json!({
+                "name": manifest.frontmatter.name,
+                "description": manifest.frontmatter.description,
+                "path": path,
+            }),
+            Err(e) => json!({ "path": path, "error": e.to_string() }),
+        })
+        .collect();
+    Ok(Value::Array(skills))
+}
+
+fn lint(params: PathsParams, config: &Config) -> Result<Value, RpcError> {
+    let validator = Validator::with_agents(&config.lint, &config.lint_agents());
+    let mut results: Vec<(String, ValidationResult)> = Vec::new();
+    let mut parse_errors = Vec::new();
+
+    for path in Discovery::find_skills_in(&params.paths, &config.discovery) {
+        match Manifest::parse(path.clone()) {
+            Ok(manifest) => {
+                results.push((path.display().to_string(), validator.validate(&manifest)))
+            }
+            Err(e) => parse_errors.push(json!({ "path": path, "error": e.to_string() })),
+        }
+    }
+
+    let mut output: Value =
+        serde_json::from_str(&JsonFormatter::new(true).format_validation(&results))
+            .map_err(|e| RpcError::new(SERVER_ERROR, e.to_string()))?;
+    output["parse_errors"] = Value::Array(parse_errors);
+    Ok(output)
+}
+
+fn list(params: ListParams, config: &Config) -> Result<Value, RpcError> {
+    let mut argv = vec!["list".to_string()];
+    push_opt(&mut argv, "--agent", params.agent);
+    push_opt(&mut argv, "--scope", params.scope);
+    argv.extend(["--".into(), params.path.unwrap_or_else(|| ".".into())]);
+
+    let Command::List(args) = parse_cli(&argv)?.command else {
+        unreachable!("parsed a list command");
+    };
+    let json = super::list::installed_json(args, config)?;
+    serde_json::from_str(&json).map_err(|e| RpcError::new(SERVER_ERROR, e.to_string()))
+}
+
+fn add(params: AddParams, config: &Config, verbose: bool) -> Result<Value, RpcError> {
+    delegate(add_argv(params), config, verbose)
+}
+
+/// The `add` command line for `params`.
+fn add_argv(params: AddParams) -> Vec<String> {
+    let mut argv = vec!["add".to_string(), "--yes".into()];
+    for skill in params.skills {
+        push_opt(&mut argv, "--skill", Some(skill));
+    }
+    for agent in params.agents {
+        push_opt(&mut argv, "--agent", Some(agent));
+    }
+    push_opt(&mut argv, "--scope", params.scope);
+    push_opt(&mut argv, "--branch", params.branch);
+    push_opt(&mut argv, "--tag", params.tag);
+    push_opt(&mut argv, "--path", params.path);
+    argv.extend(["--".into(), params.source]);
+    argv
+}
+
+fn remove(params: RemoveParams, config: &Config, verbose: bool) -> Result<Value, RpcError> {
+    let mut argv = vec!["remove".to_string(), "--yes".into()];
+    push_opt(&mut argv, "--agent", params.agent);
+    push_opt(&mut argv, "--scope", params.scope);
+    argv.push("--".into());
+    argv.extend(params.skills);
+
+    delegate(argv, config, verbose)
+}
+
+/// Add `--flag=value` to `argv`. Values are attached to their flag, and
+/// positionals follow `--`, so no param can be read as another option.
+fn push_opt(argv: &mut Vec<String>, flag: &str, value: Option<String>) {
+    if let Some(value) = value {
+        argv.push(format!("{}={}", flag, value));
+    }
+}
+
+/// Parse a skilo command line, reporting bad values as invalid params.
+fn parse_cli(argv: &[String]) -> Result<Cli, RpcError> {
+    let full = std::iter::once("skilo").chain(argv.iter().map(String::as_str));
+    Cli::try_parse_from(full).map_err(|e| RpcError::new(INVALID_PARAMS, e.to_string().trim()))
+}
+
+/// Run an `add` or `remove` command line and report its exit code.
+fn delegate(mut argv: Vec<String>, config: &Config, verbose: bool) -> Result<Value, RpcError> {
+    if !verbose {
+        // Before the `--` ending the options
+        argv.insert(1, "--quiet".into());
+    }
+    let cli = parse_cli(&argv)?;
+    let code = match &cli.command {
+        Command::Add(args) => super::add::run(args.clone(), config, &cli)?,
+        Command::Remove(args) => super::remove::run(args.clone(), config, &cli)?,
+        _ => unreachable!("only add and remove are delegated"),
+    };
+    Ok(json!({ "success": code == 0, "exit_code": code }))
+}
+
+/// Writes protocol messages, one per line.
+struct Writer {
+    out: Mutex<Box<dyn Write + Send>>,
+    /// Id of the request being handled, attached to progress notifications.
+    current: Mutex<Value>,
+}
+
+impl Writer {
+    fn new(out: Box<dyn Write + Send>) -> Self {
+        Self {
+            out: Mutex::new(out),
+            current: Mutex::new(Value::Null),
+        }
+    }
+
+    fn set_current(&self, id: Value) {
+        *self.current.lock().unwrap_or_else(|e| e.into_inner()) = id;
+    }
+
+    fn send(&self, message: &Value) -> std::io::Result<()> {
+        let mut out = self.out.lock().unwrap_or_else(|e| e.into_inner());
+        writeln!(out, "{}", message)?;
+        out.flush()
+    }
+
+    fn progress(&self, message: &str) -> std::io::Result<()> {
+        let id = self
+            .current
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        self.send(&json!({
+            "jsonrpc": "2.0",
+            "method": "progress",
+            "params": { "id": id, "message": message },
+        }))
+    }
+}
+
+/// Forwards captured stdout lines as progress notifications.
+struct Capture {
+    synced: std::sync::mpsc::Receiver<()>,
+}
+
+impl Capture {
+    fn spawn(
+        captured: impl std::io::Read + Send + 'static,
+        writer: Arc<Writer>,
+    ) -> std::io::Result<Self> {
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::Builder::new()
+            .name("skilo-api-progress".into())
+            .spawn(move || {
+                for line in std::io::BufReader::new(captured).lines() {
+                    let Ok(line) = line else { break };
+                    if line == SYNC_MARKER {
+                        let _ = tx.send(());
+                    } else if !line.trim().is_empty() {
+                        let _ = writer.progress(line.trim_end());
+                    }
+                }
+            })?;
+        Ok(Self { synced: rx })
+    }
+
+    /// Wait until everything printed so far has been forwarded.
+    fn sync(&self) {
+        println!("{}", SYNC_MARKER);
+        let _ = std::io::stdout().flush();
+        let _ = self.synced.recv();
+    }
+}
+
+#[cfg(unix)]
+mod unix {
+    use std::fs::File;
+    use std::os::fd::FromRawFd;
+
+    /// Point stdout at a pipe, returning the original stdout for protocol
+    /// messages and the read end of the pipe.
+    pub(super) fn redirect_stdout() -> std::io::Result<(File, File)> {
+        let _ = std::io::Write::flush(&mut std::io::stdout());
+
+        let original = unsafe { libc::dup(libc::STDOUT_FILENO) };
+        if original < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+
+        let mut fds = [0 as libc::c_int; 2];
+        if unsafe { libc::pipe(fds.as_mut_ptr()) } != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        let [read_fd, write_fd] = fds;
+
+        if unsafe { libc::dup2(write_fd, libc::STDOUT_FILENO) } < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        unsafe { libc::close(write_fd) };
+
+        // Safety: both descriptors were just created and are owned here
+        Ok(unsafe { (File::from_raw_fd(original), File::from_raw_fd(read_fd)) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_handle_errors() {
+        let config = Config::default();
+
+        let err = handle(&json!({ "id": 1, "method": "list" }), &config, false).unwrap_err();
+        assert_eq!(err.code, INVALID_REQUEST);
+
+        let err = handle(
+            &json!({ "jsonrpc": "2.0", "id": 1, "method": "publish" }),
+            &config,
+            false,
+        )
+        .unwrap_err();
+        assert_eq!(err.code, METHOD_NOT_FOUND);
+
+        let err = handle(
+            &json!({ "jsonrpc": "2.0", "id": 1, "method": "add", "params": {} }),
+            &config,
+            false,
+        )
+        .unwrap_err();
+        assert_eq!(err.code, INVALID_PARAMS);
+
+        let err = handle(
+            &json!({ "jsonrpc": "2.0", "id": 1, "method": "list", "params": { "scope": "nowhere" } }),
+            &config,
+            false,
+        )
+        .unwrap_err();
+        assert_eq!(err.code, INVALID_PARAMS);
+    }
+
+    #[test]
+    fn test_add_argv_keeps_params_out_of_options() {
+        let params: AddParams = serde_json::from_value(json!({
+            "source": "--config=/tmp/x",
+            "skills": ["--global"],
+            "tag": "--help",
+        }))
+        .unwrap();
+        let cli = parse_cli(&add_argv(params)).unwrap();
+        assert!(cli.config.is_none());
+        let Command::Add(args) = cli.command else {
+            panic!("Expected add command");
+        };
+        assert_eq!(args.source.as_deref(), Some("--config=/tmp/x"));
+        assert_eq!(args.skill, Some(vec!["--global".to_string()]));
+        assert!(!args.global);
+        assert_eq!(args.tag.as_deref(), Some("--help"));
+    }
+}
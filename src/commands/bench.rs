@@ -0,0 +1,162 @@
+//! Times discovery, parsing, validation, and formatting over a tree of skills.
+
+use crate::cli::{BenchArgs, Cli, OutputFormat};
+use crate::config::Config;
+use crate::error::SkiloError;
+use crate::output::{Align, Table};
+use crate::skill::{Discovery, Formatter, FormatterConfig, Manifest, Validator};
+use serde::Serialize;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// Time spent in one phase or rule, averaged over the iterations.
+#[derive(Debug, Serialize)]
+pub struct Timing {
+    /// Phase or rule name.
+    pub name: String,
+    /// Mean time per iteration, in milliseconds.
+    pub millis: f64,
+    /// Skills processed per second.
+    pub skills_per_second: f64,
+}
+
+/// Results of a benchmark run.
+#[derive(Debug, Serialize)]
+pub struct BenchReport {
+    /// Number of skills found.
+    pub skills: usize,
+    /// Number of skills that failed to parse.
+    pub parse_errors: usize,
+    /// Number of iterations each phase ran.
+    pub iterations: u32,
+    /// Time per phase: discovery, parse, validate, and format.
+    pub phases: Vec<Timing>,
+    /// Time per lint rule, slowest first.
+    pub rules: Vec<Timing>,
+}
+
+/// Run the bench command.
+pub fn run(args: BenchArgs, config: &Config, cli: &Cli) -> Result<i32, SkiloError> {
+    let roots = Discovery::roots(std::slice::from_ref(&args.path), &config.discovery);
+    let validator = Validator::with_agents(&config.lint, &config.lint_agents());
+    let formatter = Formatter::new(FormatterConfig::from(&config.fmt));
+    let rules: Vec<_> = validator.rules().collect();
+
+    let mut discovery = Duration::ZERO;
+    let mut parsing = Duration::ZERO;
+    let mut formatting = Duration::ZERO;
+    let mut per_rule = vec![Duration::ZERO; rules.len()];
+    let mut skills = 0;
+    let mut parse_errors = 0;
+
+    for _ in 0..args.iterations {
+        let start = Instant::now();
+        let paths: Vec<_> = Discovery::find_skills_by_root(&roots, &config.discovery)
+            .into_iter()
+            .flat_map(|(_, paths)| paths)
+            .collect();
+        discovery += start.elapsed();
+
+        let start = Instant::now();
+        let (manifests, errors): (Vec<_>, Vec<_>) = paths
+            .iter()
+            .map(|path| Manifest::parse(path.clone()))
+            .partition(Result::is_ok);
+        parsing += start.elapsed();
+        let manifests: Vec<Manifest> = manifests.into_iter().flatten().collect();
+        skills = paths.len();
+        parse_errors = errors.len();
+
+        for (rule, elapsed) in rules.iter().zip(&mut per_rule) {
+            let start = Instant::now();
+            for manifest in &manifests {
+                black_box(rule.check(manifest));
+            }
+            *elapsed += start.elapsed();
+        }
+
+        let start = Instant::now();
+        for manifest in &manifests {
+            let _ = black_box(formatter.format(manifest));
+        }
+        formatting += start.elapsed();
+    }
+
+    if skills == 0 {
+        return Err(SkiloError::NoSkillsFound {
+            path: args.path.display().to_string(),
+        });
+    }
+
+    let timing = |name: &str, total: Duration| {
+        let mean = total.as_secs_f64() / f64::from(args.iterations);
+        Timing {
+            name: name.to_string(),
+            millis: mean * 1000.0,
+            skills_per_second: if mean > 0.0 {
+                skills as f64 / mean
+            } else {
+                f64::INFINITY
+            },
+        }
+    };
+
+    let mut rule_timings: Vec<Timing> = rules
+        .iter()
+        .zip(&per_rule)
+        .map(|(rule, total)| timing(rule.name(), *total))
+        .collect();
+    rule_timings.sort_by(|a, b| b.millis.total_cmp(&a.millis));
+
+    let report = BenchReport {
+        skills,
+        parse_errors,
+        iterations: args.iterations,
+        phases: vec![
+            timing("discovery", discovery),
+            timing("parse", parsing),
+            timing("validate", per_rule.iter().sum()),
+            timing("format", formatting),
+        ],
+        rules: rule_timings,
+    };
+
+    if matches!(cli.format, OutputFormat::Json) {
+        let json = serde_json::to_string_pretty(&report)
+            .map_err(|e| SkiloError::Config(format!("JSON serialization failed: {}", e)))?;
+        println!("{}", json);
+        return Ok(0);
+    }
+
+    println!(
+        "{} skill(s) in {}, {} iteration(s){}\n",
+        report.skills,
+        args.path.display(),
+        report.iterations,
+        if report.parse_errors > 0 {
+            format!(", {} failed to parse", report.parse_errors)
+        } else {
+            String::new()
+        }
+    );
+    print_timings("PHASE", &report.phases);
+    println!();
+    print_timings("RULE", &report.rules);
+
+    Ok(0)
+}
+
+/// Print timings as a table.
+fn print_timings(header: &str, timings: &[Timing]) {
+    let mut table = Table::new([header, "TIME (ms)", "SKILLS/S"])
+        .align(1, Align::Right)
+        .align(2, Align::Right);
+    for timing in timings {
+        table.row([
+            timing.name.clone(),
+            format!("{:.3}", timing.millis),
+            format!("{:.0}", timing.skills_per_second),
+        ]);
+    }
+    table.print();
+}
@@ -0,0 +1,221 @@
+//! Export skills to a bundle and install from one without network access.
+
+use crate::agent::expand_tilde;
+use crate::bundle::{self, BundleIndex, BundledSkill, BundledSource, BUNDLE_VERSION};
+use crate::cli::{AddArgs, BundleArgs, BundleCommand, Cli};
+use crate::commands::add::{
+    check_allowed, copy_dir_all, fetch_source, install_fetched, parse_source_list, skill_dirs,
+    Fetched, Provenance,
+};
+use crate::config::Config;
+use crate::error::SkiloError;
+use crate::git::Source;
+use crate::lockfile::{checksum_dir, now_secs};
+use crate::output::{get_formatter, Progress};
+use colored::Colorize;
+use std::fs;
+use std::io;
+use std::path::Path;
+use tempfile::TempDir;
+
+/// Run the bundle command.
+pub fn run(args: BundleArgs, config: &Config, cli: &Cli) -> Result<i32, SkiloError> {
+    match args.command {
+        BundleCommand::Export { sources, output } => export(&sources, &output, config, cli),
+        BundleCommand::Import {
+            file,
+            skill,
+            agent,
+            scope,
+            yes,
+        } => {
+            let add_args = AddArgs {
+                source: None,
+                from_file: None,
+                skill,
+                list: false,
+                yes,
+                branch: None,
+                tag: None,
+                rev: None,
+                path: None,
+                agent,
+                global: false,
+                scope,
+                output: None,
+                register: false,
+                ssh_key: None,
+            };
+            import(&file, add_args, config, cli)
+        }
+    }
+}
+
+/// Fetch every source listed in `sources` and write their skills to the
+/// bundle `output`.
+///
+/// Unlike `add --from-file`, a failing source fails the export: a bundle
+/// missing skills would only be noticed on the machine it is carried to.
+fn export(sources: &Path, output: &Path, config: &Config, cli: &Cli) -> Result<i32, SkiloError> {
+    let formatter = get_formatter(cli.format, cli.quiet);
+    let progress = Progress::new(cli.format, cli.quiet);
+    let quiet = cli.quiet || cli.porcelain || progress.is_enabled();
+
+    let content = if sources == Path::new("-") {
+        io::read_to_string(io::stdin())?
+    } else {
+        fs::read_to_string(sources)?
+    };
+    let listed = parse_source_list(&content)
+        .map_err(|e| SkiloError::Config(format!("{}: {}", sources.display(), e)))?;
+
+    if listed.is_empty() {
+        formatter.format_message("No sources to bundle.");
+        return Ok(0);
+    }
+
+    let ssh_key = config
+        .add
+        .ssh_key
+        .clone()
+        .and_then(|key| expand_tilde(&key.to_string_lossy()));
+
+    let staging = TempDir::new()?;
+    let mut index = BundleIndex {
+        version: BUNDLE_VERSION,
+        created_at: now_secs(),
+        sources: Vec::new(),
+    };
+
+    for (i, entry) in listed.iter().enumerate() {
+        let source = Source::parse(&entry.source)?;
+        check_allowed(&source, config)?;
+        let fetched = fetch_source(source, None, ssh_key.clone(), config, quiet, &progress)?;
+
+        let skills: Vec<_> = skill_dirs(&fetched.root, config)?
+            .into_iter()
+            .filter(|(name, _)| entry.skills.is_empty() || entry.skills.contains(name))
+            .collect();
+        if skills.is_empty() {
+            return Err(SkiloError::NoSkillsFound { path: fetched.name });
+        }
+
+        let path = format!("sources/{}", i);
+        let mut bundled = Vec::new();
+        for (name, dir) in skills {
+            let dest = staging.path().join(&path).join(&name);
+            copy_dir_all(&dir, &dest)?;
+            bundled.push(BundledSkill {
+                checksum: checksum_dir(&dest)?,
+                name,
+            });
+        }
+
+        let provenance = fetched.provenance;
+        index.sources.push(BundledSource {
+            source: provenance.source,
+            subdir: provenance.subdir,
+            branch: provenance.branch,
+            tag: provenance.tag,
+            rev: provenance.rev,
+            commit: provenance.commit,
+            path,
+            skills: bundled,
+        });
+    }
+
+    bundle::pack(staging.path(), &index, output)?;
+
+    let count: usize = index.sources.iter().map(|s| s.skills.len()).sum();
+    if !cli.quiet {
+        println!(
+            "{} {} skill{} from {} source{} to {}",
+            "Bundled".green(),
+            count,
+            if count == 1 { "" } else { "s" },
+            index.sources.len(),
+            if index.sources.len() == 1 { "" } else { "s" },
+            output.display()
+        );
+    }
+
+    Ok(0)
+}
+
+/// Install the skills in the bundle `file`, recording their original
+/// sources in the lockfile.
+///
+/// A failing source is reported and the remaining sources are still
+/// installed.
+fn import(file: &Path, args: AddArgs, config: &Config, cli: &Cli) -> Result<i32, SkiloError> {
+    let formatter = get_formatter(cli.format, cli.quiet);
+
+    // Verified before anything is installed; removed once all sources are
+    let (temp_dir, index) = bundle::unpack(file)?;
+
+    let mut attempted = 0;
+    let mut failed = 0;
+
+    for source in index.sources {
+        let names: Vec<String> = source
+            .skills
+            .iter()
+            .map(|s| s.name.clone())
+            .filter(|name| args.skill.as_ref().is_none_or(|s| s.contains(name)))
+            .collect();
+        if names.is_empty() {
+            continue;
+        }
+        attempted += 1;
+
+        let fetched = Fetched {
+            root: temp_dir.path().join(&source.path),
+            name: source.source.clone(),
+            provenance: Provenance {
+                source: source.source.clone(),
+                subdir: source.subdir,
+                branch: source.branch,
+                tag: source.tag,
+                rev: source.rev,
+                commit: source.commit,
+            },
+            temp_dir: None,
+        };
+        let mut add_args = args.clone();
+        add_args.skill = Some(names);
+
+        match install_fetched(add_args, fetched, config, cli) {
+            Ok(0) => {}
+            Ok(_) => failed += 1,
+            Err(SkiloError::Cancelled) => return Err(SkiloError::Cancelled),
+            Err(e) => {
+                failed += 1;
+                formatter.format_error(&format!("Failed to import from {}: {}", source.source, e));
+            }
+        }
+
+        if !cli.quiet && !cli.porcelain {
+            println!();
+        }
+    }
+
+    if attempted == 0 {
+        formatter.format_message("No skills to import.");
+        return Ok(0);
+    }
+
+    if failed > 0 {
+        if !cli.quiet {
+            eprintln!(
+                "{}: {} of {} source{} failed to import",
+                "Warning".yellow(),
+                failed,
+                attempted,
+                if attempted == 1 { "" } else { "s" }
+            );
+        }
+        Ok(1)
+    } else {
+        Ok(0)
+    }
+}
@@ -1,6 +1,9 @@
 //! Cache management commands.
 
-use crate::cache::{clean_all, clean_old_checkouts, format_size, git_dir, CacheStats};
+use crate::cache::{
+    clean_all, clean_old_checkouts, find_duplicates, format_size, git_dir, CacheStats,
+    DuplicateFile, DUPLICATE_MIN_SIZE,
+};
 use crate::cli::{CacheArgs, CacheCommand, Cli};
 use crate::config::Config;
 use crate::error::SkiloError;
@@ -51,7 +54,7 @@ fn show_status(cli: &Cli) -> Result<i32, SkiloError> {
         format_size(stats.db_size)
     );
     for repo in &stats.repos {
-        println!("    {}", repo.name);
+        println!("    {} {}", repo.name, format_size(repo.size).dimmed());
     }
 
     if !stats.repos.is_empty() && !stats.checkouts.is_empty() {
@@ -67,7 +70,12 @@ fn show_status(cli: &Cli) -> Result<i32, SkiloError> {
     );
     for checkout in &stats.checkouts {
         let age = format_age(checkout.modified);
-        println!("    {} {}", checkout.name, age.dimmed());
+        println!(
+            "    {} {} {}",
+            checkout.name,
+            format_size(checkout.size).dimmed(),
+            age.dimmed()
+        );
     }
 
     if !stats.checkouts.is_empty() || !stats.repos.is_empty() {
@@ -75,9 +83,45 @@ fn show_status(cli: &Cli) -> Result<i32, SkiloError> {
         println!("Total: {}", format_size(stats.total_size()).cyan());
     }
 
+    let checkouts: Vec<_> = stats.checkouts.iter().map(|c| c.path.clone()).collect();
+    let duplicates = find_duplicates(&checkouts, DUPLICATE_MIN_SIZE);
+    if !duplicates.is_empty() {
+        println!();
+        print_duplicates(&duplicates, &git);
+        println!(
+            "Old checkouts often hold the same assets; run {} to remove them.",
+            "skilo cache clean".cyan()
+        );
+    }
+
     Ok(0)
 }
 
+/// Print duplicated files with paths relative to `base`, and the space
+/// keeping one copy of each would free.
+pub(crate) fn print_duplicates(duplicates: &[DuplicateFile], base: &std::path::Path) {
+    println!(
+        "Duplicate files ({} or larger):",
+        format_size(DUPLICATE_MIN_SIZE)
+    );
+    for duplicate in duplicates {
+        println!(
+            "  {} x {}",
+            duplicate.paths.len(),
+            format_size(duplicate.size).bold()
+        );
+        for path in &duplicate.paths {
+            let path = path.strip_prefix(base).unwrap_or(path);
+            println!("    {}", path.display());
+        }
+    }
+    let wasted: u64 = duplicates.iter().map(DuplicateFile::wasted).sum();
+    println!(
+        "{} could be freed by keeping one copy of each.",
+        format_size(wasted).yellow()
+    );
+}
+
 /// Format age as a human-readable string.
 fn format_age(modified: Option<SystemTime>) -> String {
     let Some(modified) = modified else {
@@ -1,23 +1,39 @@
 //! Runs both lint and format check in sequence.
 
-use crate::cli::{CheckArgs, Cli, FmtArgs, LintArgs};
+use crate::cli::{CheckArgs, Cli, ExitArgs, FailOn, FmtArgs, LintArgs};
 use crate::config::Config;
 use crate::error::SkiloError;
 use crate::output::get_formatter;
 
 /// Run the check command, which executes lint and format check.
 ///
-/// Returns 0 if all checks pass, 1 if any fail.
+/// Returns 0 if all checks pass, 1 if any fail. `--fail-on never` reports
+/// failures but always returns 0.
 pub fn run(args: CheckArgs, config: &Config, cli: &Cli) -> Result<i32, SkiloError> {
     let formatter = get_formatter(cli.format, cli.quiet);
 
+    // Run both checks with real thresholds so the final message is accurate
+    let report_only = args.exit.fail_on == Some(FailOn::Never);
+    let exit = ExitArgs {
+        fail_on: args.exit.fail_on.filter(|_| !report_only),
+        ..args.exit
+    };
+
     formatter.format_message("Running lint...");
 
     // Run lint with strict mode
     let lint_args = LintArgs {
-        path: args.path.clone(),
+        paths: args.paths.clone(),
         strict: true,
         fix: false,
+        diff: false,
+        summary_only: args.summary_only,
+        max_diagnostics: args.max_diagnostics,
+        explain_failures: false,
+        only: Vec::new(),
+        skip: Vec::new(),
+        github_pr_review: false,
+        exit: exit.clone(),
     };
     let lint_result = super::lint::run(lint_args, config, cli)?;
 
@@ -25,15 +41,17 @@ pub fn run(args: CheckArgs, config: &Config, cli: &Cli) -> Result<i32, SkiloErro
 
     // Run format check
     let fmt_args = FmtArgs {
-        path: args.path,
+        paths: args.paths,
         check: true,
         diff: false,
+        verify: true,
+        exit,
     };
     let fmt_result = super::fmt::run(fmt_args, config, cli)?;
 
     // Return non-zero if either failed
     if lint_result != 0 || fmt_result != 0 {
-        Ok(1)
+        Ok(if report_only { 0 } else { 1 })
     } else {
         formatter.format_success("\nAll checks passed!");
         Ok(0)
@@ -0,0 +1,84 @@
+//! Verify installed skills against the lockfile.
+
+use crate::cli::{CiArgs, Cli, OutputFormat};
+use crate::config::Config;
+use crate::error::SkiloError;
+use crate::lockfile::{check_drift, Drift, DriftKind};
+use crate::output::{get_formatter, print_porcelain};
+use colored::Colorize;
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// JSON output for the ci command.
+#[derive(Serialize)]
+struct CiOutput<'a> {
+    /// True if installed skills match the lockfile exactly.
+    clean: bool,
+    /// Every difference found.
+    drift: &'a [Drift],
+}
+
+/// Run the ci command.
+///
+/// Exits with 1 if any drift is found.
+pub fn run(args: CiArgs, _config: &Config, cli: &Cli) -> Result<i32, SkiloError> {
+    let formatter = get_formatter(cli.format, cli.quiet);
+    let project_root = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+
+    let mut drift = Vec::new();
+    for scope in args.scope.scopes() {
+        drift.extend(check_drift(scope, &project_root)?);
+    }
+
+    let exit_code = if drift.is_empty() { 0 } else { 1 };
+
+    if matches!(cli.format, OutputFormat::Json) {
+        let output = CiOutput {
+            clean: drift.is_empty(),
+            drift: &drift,
+        };
+        let json = serde_json::to_string_pretty(&output)
+            .map_err(|e| SkiloError::Config(format!("JSON serialization failed: {}", e)))?;
+        println!("{}", json);
+        return Ok(exit_code);
+    }
+
+    if cli.porcelain {
+        for d in &drift {
+            print_porcelain(&[
+                d.kind.as_str(),
+                d.scope.display_name(),
+                &d.name,
+                &d.path.display().to_string(),
+            ]);
+        }
+        return Ok(exit_code);
+    }
+
+    if drift.is_empty() {
+        formatter.format_success("Installed skills match skills.lock");
+        return Ok(0);
+    }
+
+    for d in &drift {
+        let label = match d.kind {
+            DriftKind::Missing => "missing".red(),
+            DriftKind::Modified => "modified".yellow(),
+            DriftKind::Extra => "extra".yellow(),
+        };
+        println!(
+            "  {:<8} {} {}",
+            label,
+            d.name.cyan(),
+            d.path.display().to_string().dimmed()
+        );
+    }
+    println!();
+    formatter.format_error(&format!(
+        "{} skill{} out of sync with skills.lock",
+        drift.len(),
+        if drift.len() == 1 { " is" } else { "s are" }
+    ));
+
+    Ok(exit_code)
+}
@@ -0,0 +1,217 @@
+//! Remove artifacts left behind by skilo.
+
+use crate::agent::Agent;
+use crate::cli::{CleanArgs, Cli, OutputFormat};
+use crate::commands::self_update::{UPDATE_BACKUP_NAME, UPDATE_TEMP_NAME};
+use crate::config::Config;
+use crate::error::SkiloError;
+use crate::output::{get_formatter, print_porcelain};
+use colored::Colorize;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// Kind of artifact removed by `clean`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ArtifactKind {
+    /// Partially written binary from an interrupted self-update.
+    UpdateTemp,
+    /// Backup of the previous binary from an interrupted self-update.
+    UpdateBackup,
+    /// A skills directory with nothing in it.
+    EmptyDir,
+}
+
+impl ArtifactKind {
+    /// Stable identifier used in porcelain output.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::UpdateTemp => "update-temp",
+            Self::UpdateBackup => "update-backup",
+            Self::EmptyDir => "empty-dir",
+        }
+    }
+}
+
+/// A generated file or directory that can be removed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Artifact {
+    /// What kind of artifact this is.
+    pub kind: ArtifactKind,
+    /// Path to the artifact.
+    pub path: PathBuf,
+}
+
+/// JSON output for the clean command.
+#[derive(Serialize)]
+struct CleanOutput<'a> {
+    /// True if nothing was actually removed.
+    dry_run: bool,
+    /// Artifacts removed (or that would be removed).
+    artifacts: &'a [Artifact],
+}
+
+/// Run the clean command.
+pub fn run(args: CleanArgs, _config: &Config, cli: &Cli) -> Result<i32, SkiloError> {
+    let formatter = get_formatter(cli.format, cli.quiet);
+    let project_root = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    let exe_dir = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(Path::to_path_buf));
+
+    let artifacts = find_artifacts(&project_root, exe_dir.as_deref(), args.empty_dirs);
+
+    let mut removed = Vec::new();
+    let mut failed = 0;
+    for artifact in artifacts {
+        if !args.dry_run {
+            let result = match artifact.kind {
+                ArtifactKind::EmptyDir => std::fs::remove_dir(&artifact.path),
+                _ => std::fs::remove_file(&artifact.path),
+            };
+            if let Err(e) = result {
+                failed += 1;
+                formatter.format_error(&format!(
+                    "Failed to remove {}: {}",
+                    artifact.path.display(),
+                    e
+                ));
+                continue;
+            }
+        }
+        removed.push(artifact);
+    }
+
+    let exit_code = if failed > 0 { 1 } else { 0 };
+
+    if matches!(cli.format, OutputFormat::Json) {
+        let output = CleanOutput {
+            dry_run: args.dry_run,
+            artifacts: &removed,
+        };
+        let json = serde_json::to_string_pretty(&output)
+            .map_err(|e| SkiloError::Config(format!("JSON serialization failed: {}", e)))?;
+        println!("{}", json);
+        return Ok(exit_code);
+    }
+
+    if cli.porcelain {
+        for artifact in &removed {
+            print_porcelain(&[artifact.kind.as_str(), &artifact.path.display().to_string()]);
+        }
+        return Ok(exit_code);
+    }
+
+    if removed.is_empty() {
+        if failed == 0 {
+            formatter.format_success("Nothing to clean");
+        }
+        return Ok(exit_code);
+    }
+
+    let verb = if args.dry_run {
+        "Would remove"
+    } else {
+        "Removed"
+    };
+    if !cli.quiet {
+        for artifact in &removed {
+            println!(
+                "  {} {} {}",
+                verb,
+                artifact.path.display(),
+                format!("({})", artifact.kind.as_str()).dimmed()
+            );
+        }
+        println!();
+    }
+
+    let count = format!(
+        "{} artifact{}",
+        removed.len(),
+        if removed.len() == 1 { "" } else { "s" }
+    );
+    if args.dry_run {
+        formatter.format_message(&format!("{} would be removed (dry run)", count));
+    } else {
+        formatter.format_success(&format!("Removed {}", count));
+    }
+
+    Ok(exit_code)
+}
+
+/// Collect the artifacts `clean` would remove.
+///
+/// Self-update leftovers are looked up next to the running executable.
+/// Empty skills directories are only included when `empty_dirs` is set.
+pub fn find_artifacts(
+    project_root: &Path,
+    exe_dir: Option<&Path>,
+    empty_dirs: bool,
+) -> Vec<Artifact> {
+    let mut artifacts = Vec::new();
+
+    if let Some(exe_dir) = exe_dir {
+        for (name, kind) in [
+            (UPDATE_TEMP_NAME, ArtifactKind::UpdateTemp),
+            (UPDATE_BACKUP_NAME, ArtifactKind::UpdateBackup),
+        ] {
+            let path = exe_dir.join(name);
+            if path.is_file() {
+                artifacts.push(Artifact { kind, path });
+            }
+        }
+    }
+
+    if empty_dirs {
+        let mut dirs = vec![project_root.join("skills")];
+        dirs.extend(
+            Agent::all()
+                .iter()
+                .map(|a| project_root.join(a.skills_dir())),
+        );
+
+        for path in dirs {
+            let is_empty = std::fs::read_dir(&path).is_ok_and(|mut e| e.next().is_none());
+            if is_empty && !artifacts.iter().any(|a| a.path == path) {
+                artifacts.push(Artifact {
+                    kind: ArtifactKind::EmptyDir,
+                    path,
+                });
+            }
+        }
+    }
+
+    artifacts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_find_artifacts() {
+        let temp = TempDir::new().unwrap();
+        let bin = temp.path().join("bin");
+        std::fs::create_dir(&bin).unwrap();
+        std::fs::write(bin.join(UPDATE_BACKUP_NAME), "old").unwrap();
+
+        let project = temp.path().join("project");
+        std::fs::create_dir_all(project.join(".claude/skills")).unwrap();
+        std::fs::create_dir_all(project.join("skills/my-skill")).unwrap();
+
+        let artifacts = find_artifacts(&project, Some(&bin), false);
+        assert_eq!(
+            artifacts,
+            vec![Artifact {
+                kind: ArtifactKind::UpdateBackup,
+                path: bin.join(UPDATE_BACKUP_NAME),
+            }]
+        );
+
+        let artifacts = find_artifacts(&project, Some(&bin), true);
+        assert_eq!(artifacts.len(), 2);
+        assert_eq!(artifacts[1].path, project.join(".claude/skills"));
+    }
+}
@@ -0,0 +1,405 @@
+//! Find skills installed more than once under different names.
+
+use crate::agent::Agent;
+use crate::cli::{Cli, DedupeArgs, OutputFormat};
+use crate::commands::remove::forget_install;
+use crate::config::Config;
+use crate::error::SkiloError;
+use crate::lockfile::checksum_dir_with;
+use crate::output::{get_formatter, print_porcelain};
+use crate::scope::{list_skills, list_skills_from_path, InstalledSkill, Scope};
+use crate::skill::provenance::HEADER_PREFIX;
+use crate::skill::Manifest;
+use colored::Colorize;
+use dialoguer::Confirm;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// How closely the skills in a duplicate group match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DuplicateKind {
+    /// Same files and frontmatter apart from the skill name.
+    Identical,
+    /// Same files and body; the frontmatter differs.
+    Similar,
+}
+
+impl DuplicateKind {
+    /// Stable identifier used in porcelain output.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DuplicateKind::Identical => "identical",
+            DuplicateKind::Similar => "similar",
+        }
+    }
+}
+
+/// One installed copy of a duplicated skill.
+#[derive(Debug, Serialize)]
+pub struct DuplicateSkill {
+    /// The skill name.
+    pub name: String,
+    /// The agent the copy is installed for (None for `./skills/`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agent: Option<Agent>,
+    /// Installation scope.
+    pub scope: Scope,
+    /// Path to the skill directory.
+    pub path: PathBuf,
+}
+
+/// Installed skills with the same content under at least two names.
+#[derive(Debug, Serialize)]
+pub struct DuplicateGroup {
+    /// How closely the copies match.
+    pub kind: DuplicateKind,
+    /// The copies, in discovery order; the first one is kept on consolidation.
+    pub skills: Vec<DuplicateSkill>,
+}
+
+impl DuplicateGroup {
+    /// Copies whose name differs from the kept copy.
+    fn redundant(&self) -> impl Iterator<Item = &DuplicateSkill> {
+        let keep = &self.skills[0].name;
+        self.skills.iter().filter(move |s| &s.name != keep)
+    }
+}
+
+/// Run the dedupe command.
+pub fn run(args: DedupeArgs, _config: &Config, cli: &Cli) -> Result<i32, SkiloError> {
+    let formatter = get_formatter(cli.format, cli.quiet);
+    let project_root = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    let quiet = cli.quiet || cli.porcelain || !matches!(cli.format, OutputFormat::Text);
+
+    let groups = find_duplicates(installed_skills(&project_root));
+
+    if matches!(cli.format, OutputFormat::Json) {
+        let json = serde_json::to_string_pretty(&groups)
+            .map_err(|e| SkiloError::Config(format!("JSON serialization failed: {}", e)))?;
+        println!("{}", json);
+    } else if cli.porcelain {
+        for (i, group) in groups.iter().enumerate() {
+            for skill in &group.skills {
+                print_porcelain(&[
+                    group.kind.as_str(),
+                    &(i + 1).to_string(),
+                    &skill.name,
+                    skill.agent.map(|a| a.cli_name()).unwrap_or_default(),
+                    skill.scope.display_name(),
+                    &skill.path.display().to_string(),
+                ]);
+            }
+        }
+    } else if !cli.quiet {
+        for group in &groups {
+            print_group(group, &project_root);
+            println!();
+        }
+    }
+
+    if groups.is_empty() {
+        if !quiet {
+            formatter.format_success("No duplicate skills found");
+        }
+        return Ok(0);
+    }
+
+    if !args.consolidate {
+        if !quiet {
+            formatter.format_error(&format!(
+                "Found {} duplicate group{}",
+                groups.len(),
+                if groups.len() == 1 { "" } else { "s" }
+            ));
+            println!(
+                "Run {} to keep the first copy of each",
+                "skilo dedupe --consolidate".cyan()
+            );
+        }
+        return Ok(1);
+    }
+
+    let redundant: Vec<(&DuplicateSkill, &DuplicateSkill)> = groups
+        .iter()
+        .flat_map(|g| g.redundant().map(move |s| (&g.skills[0], s)))
+        .collect();
+
+    if !args.yes {
+        let prompt = format!(
+            "{} {} duplicate skill{}?",
+            if args.link { "Link" } else { "Remove" },
+            redundant.len(),
+            if redundant.len() == 1 { "" } else { "s" }
+        );
+        if !Confirm::new()
+            .with_prompt(prompt)
+            .interact()
+            .map_err(|_| SkiloError::Cancelled)?
+        {
+            return Err(SkiloError::Cancelled);
+        }
+        if !quiet {
+            println!();
+        }
+    }
+
+    let mut done = 0;
+    for (keep, skill) in &redundant {
+        if !quiet {
+            print!(
+                "{} {}...",
+                if args.link { "Linking" } else { "Removing" },
+                skill.name.cyan()
+            );
+        }
+
+        match consolidate(keep, skill, args.link) {
+            Ok(()) => {
+                done += 1;
+                forget_install(skill.scope, &skill.path, &project_root)?;
+                if !quiet {
+                    println!(" {}", "done".green());
+                }
+                if cli.porcelain {
+                    print_porcelain(&[
+                        if args.link { "linked" } else { "removed" },
+                        &skill.path.display().to_string(),
+                    ]);
+                }
+            }
+            Err(e) => {
+                if !quiet {
+                    println!(" {}", "failed".red());
+                }
+                formatter.format_error(&format!("Failed to consolidate '{}': {}", skill.name, e));
+            }
+        }
+    }
+
+    if !quiet {
+        println!();
+        formatter.format_success(&format!(
+            "{} {} duplicate skill{}",
+            if args.link { "Linked" } else { "Removed" },
+            done,
+            if done == 1 { "" } else { "s" }
+        ));
+    }
+
+    if done == redundant.len() {
+        Ok(0)
+    } else {
+        Ok(1)
+    }
+}
+
+/// Collect every installed skill across agents and scopes.
+///
+/// Skills reached through more than one path (shared skills directories or
+/// symlinks) are listed once.
+fn installed_skills(project_root: &Path) -> Vec<InstalledSkill> {
+    let mut skills = Vec::new();
+    for scope in [Scope::Project, Scope::Global] {
+        for agent in Agent::all() {
+            skills.extend(list_skills(*agent, scope, project_root));
+        }
+    }
+    skills.extend(list_skills_from_path(
+        &project_root.join("skills"),
+        None,
+        Scope::Project,
+    ));
+
+    let mut seen = HashSet::new();
+    skills.retain(|s| seen.insert(s.path.canonicalize().unwrap_or_else(|_| s.path.clone())));
+    skills
+}
+
+/// Group skills with the same content under at least two different names.
+pub fn find_duplicates(skills: Vec<InstalledSkill>) -> Vec<DuplicateGroup> {
+    let mut by_body: BTreeMap<String, Vec<(String, InstalledSkill)>> = BTreeMap::new();
+    let mut order = Vec::new();
+
+    for skill in skills {
+        let Ok(similar) = checksum_dir_with(&skill.path, |p, b| normalize(p, b, false)) else {
+            continue;
+        };
+        let Ok(identical) = checksum_dir_with(&skill.path, |p, b| normalize(p, b, true)) else {
+            continue;
+        };
+        if !by_body.contains_key(&similar) {
+            order.push(similar.clone());
+        }
+        by_body.entry(similar).or_default().push((identical, skill));
+    }
+
+    order
+        .into_iter()
+        .filter_map(|key| {
+            let members = by_body.remove(&key)?;
+            let names: HashSet<&str> = members.iter().map(|(_, s)| s.name.as_str()).collect();
+            if names.len() < 2 {
+                return None;
+            }
+
+            let kind = if members.iter().all(|(id, _)| id == &members[0].0) {
+                DuplicateKind::Identical
+            } else {
+                DuplicateKind::Similar
+            };
+
+            Some(DuplicateGroup {
+                kind,
+                skills: members
+                    .into_iter()
+                    .map(|(_, s)| DuplicateSkill {
+                        name: s.name,
+                        agent: s.agent,
+                        scope: s.scope,
+                        path: s.path,
+                    })
+                    .collect(),
+            })
+        })
+        .collect()
+}
+
+/// Normalize file contents before hashing.
+///
+/// For SKILL.md, the provenance header and the skill name are dropped. With
+/// `keep_frontmatter` false, the frontmatter is dropped entirely.
+fn normalize(relative: &Path, bytes: Vec<u8>, keep_frontmatter: bool) -> Vec<u8> {
+    if relative != Path::new("SKILL.md") {
+        return bytes;
+    }
+    let Ok(content) = String::from_utf8(bytes.clone()) else {
+        return bytes;
+    };
+    let Ok(manifest) = Manifest::parse_content(relative.to_path_buf(), &content) else {
+        return bytes;
+    };
+
+    let body = match manifest.body.strip_prefix(HEADER_PREFIX) {
+        Some(rest) => rest.split_once("-->").map_or(rest, |(_, b)| b).trim_start(),
+        None => manifest.body.as_str(),
+    };
+
+    if !keep_frontmatter {
+        return body.as_bytes().to_vec();
+    }
+
+    let mut frontmatter = manifest.frontmatter.clone();
+    frontmatter.name.clear();
+    let yaml = frontmatter.to_yaml().unwrap_or_default();
+    format!("{}\0{}", yaml, body).into_bytes()
+}
+
+/// Remove `skill`, or replace it with a symlink to `keep`.
+fn consolidate(keep: &DuplicateSkill, skill: &DuplicateSkill, link: bool) -> std::io::Result<()> {
+    std::fs::remove_dir_all(&skill.path)?;
+    if !link {
+        return Ok(());
+    }
+
+    let target = keep.path.canonicalize()?;
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(&target, &skill.path)?;
+    #[cfg(windows)]
+    std::os::windows::fs::symlink_dir(&target, &skill.path)?;
+    Ok(())
+}
+
+/// Print a duplicate group as an indented list.
+fn print_group(group: &DuplicateGroup, project_root: &Path) {
+    let title = match group.kind {
+        DuplicateKind::Identical => "Identical skills:",
+        DuplicateKind::Similar => "Similar skills (frontmatter differs):",
+    };
+    println!("{}", title.bold());
+
+    let paths: Vec<String> = group
+        .skills
+        .iter()
+        .map(|s| {
+            s.path
+                .strip_prefix(project_root)
+                .unwrap_or(&s.path)
+                .display()
+                .to_string()
+        })
+        .collect();
+    let name_width = group.skills.iter().map(|s| s.name.len()).max().unwrap_or(0);
+    let path_width = paths.iter().map(|p| p.len()).max().unwrap_or(0);
+
+    for (i, (skill, path)) in group.skills.iter().zip(&paths).enumerate() {
+        let location = match skill.agent {
+            Some(agent) => format!("{}, {}", agent.display_name(), skill.scope.display_name()),
+            None => skill.scope.display_name().to_string(),
+        };
+        let marker = if i == 0 {
+            format!(" {}", "(keep)".green())
+        } else {
+            String::new()
+        };
+        println!(
+            "  {:<name_width$}  {:<path_width$}  {}{}",
+            skill.name.cyan(),
+            path.dimmed(),
+            format!("({})", location).dimmed(),
+            marker,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn install(root: &Path, name: &str, description: &str, body: &str) -> InstalledSkill {
+        let path = root.join(name);
+        std::fs::create_dir_all(&path).unwrap();
+        std::fs::write(
+            path.join("SKILL.md"),
+            format!(
+                "---\nname: {}\ndescription: {}\n---\n\n{}\n",
+                name, description, body
+            ),
+        )
+        .unwrap();
+        InstalledSkill {
+            name: name.to_string(),
+            description: description.to_string(),
+            path,
+            agent: None,
+            scope: Scope::Project,
+            deprecated: false,
+            superseded_by: None,
+            tags: Vec::new(),
+            version: None,
+        }
+    }
+
+    #[test]
+    fn test_find_duplicates() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+
+        let skills = vec![
+            install(root, "code-review", "Review code", "# Review"),
+            install(root, "review", "Review code", "# Review"),
+            install(root, "lint", "Lint code", "# Lint"),
+            install(root, "lint-fast", "Lint code quickly", "# Lint"),
+            install(root, "unique", "Something else", "# Unique"),
+        ];
+
+        let groups = find_duplicates(skills);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].kind, DuplicateKind::Identical);
+        assert_eq!(groups[0].skills[0].name, "code-review");
+        assert_eq!(groups[0].redundant().count(), 1);
+        assert_eq!(groups[1].kind, DuplicateKind::Similar);
+    }
+}
@@ -0,0 +1,519 @@
+//! Diagnose and repair problems left behind by interrupted operations, and
+//! find out why fetching a source fails to authenticate.
+
+use crate::agent::expand_tilde;
+use crate::cache::is_offline;
+use crate::cli::{Cli, DoctorArgs, OutputFormat};
+use crate::config::Config;
+use crate::error::SkiloError;
+use crate::git::auth::{is_encrypted, probe, Probe};
+use crate::git::{Credentials, Source};
+use crate::journal::{load_journal, Operation};
+use crate::output::{get_formatter, print_porcelain, EMPTY_FIELD};
+use colored::Colorize;
+use git2::{Cred, CredentialType};
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// An operation found in the journal and what happened to it.
+#[derive(Serialize)]
+struct Finding<'a> {
+    /// The journaled operation.
+    #[serde(flatten)]
+    operation: &'a Operation,
+    /// `running`, `interrupted`, `failed`, or how it was repaired.
+    status: &'static str,
+}
+
+/// JSON output for the doctor command.
+#[derive(Serialize)]
+struct DoctorOutput<'a> {
+    /// True if no interrupted operations remain.
+    healthy: bool,
+    /// Every operation found in the journal.
+    operations: &'a [Finding<'a>],
+}
+
+/// The result of trying one way to authenticate.
+#[derive(Serialize)]
+struct AuthCheck {
+    /// `https`, `credential-helper`, `ssh-agent`, or `ssh-key`.
+    method: &'static str,
+    /// The key file tried, for `ssh-key`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    key: Option<PathBuf>,
+    /// The URL connected to.
+    url: String,
+    /// `ok`, `failed`, or `skipped`.
+    status: &'static str,
+    /// What happened.
+    detail: String,
+    /// How to make the method work.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hint: Option<String>,
+}
+
+/// JSON output for `doctor --auth`.
+#[derive(Serialize)]
+struct AuthOutput<'a> {
+    /// The source tested, as `host/owner/repo`.
+    source: String,
+    /// True if at least one method can fetch the source.
+    accessible: bool,
+    /// Every method tried, in the order fetches try them.
+    checks: &'a [AuthCheck],
+}
+
+/// Run the doctor command.
+///
+/// Exits with 1 if interrupted operations remain after the run, or, with
+/// `--auth`, if no method can fetch the source.
+pub fn run(args: DoctorArgs, config: &Config, cli: &Cli) -> Result<i32, SkiloError> {
+    if let Some(source) = &args.auth {
+        return run_auth(source, args.ssh_key.clone(), config, cli);
+    }
+
+    let formatter = get_formatter(cli.format, cli.quiet);
+    let operations = load_journal();
+
+    let mut findings = Vec::new();
+    let mut remaining = 0;
+
+    for op in &operations {
+        let status = if op.is_running() {
+            "running"
+        } else if !args.repair {
+            remaining += 1;
+            "interrupted"
+        } else {
+            match op.recover() {
+                Ok(recovery) => recovery.as_str(),
+                Err(e) => {
+                    remaining += 1;
+                    formatter.format_error(&format!(
+                        "Failed to repair {}: {}",
+                        op.target.display(),
+                        e
+                    ));
+                    "failed"
+                }
+            }
+        };
+        findings.push(Finding {
+            operation: op,
+            status,
+        });
+    }
+
+    let exit_code = if remaining == 0 { 0 } else { 1 };
+
+    if matches!(cli.format, OutputFormat::Json) {
+        let output = DoctorOutput {
+            healthy: remaining == 0,
+            operations: &findings,
+        };
+        let json = serde_json::to_string_pretty(&output)
+            .map_err(|e| SkiloError::Config(format!("JSON serialization failed: {}", e)))?;
+        println!("{}", json);
+        return Ok(exit_code);
+    }
+
+    if cli.porcelain {
+        for f in &findings {
+            print_porcelain(&[
+                f.status,
+                f.operation.kind.as_str(),
+                &f.operation.target.display().to_string(),
+            ]);
+        }
+        return Ok(exit_code);
+    }
+
+    if findings.is_empty() {
+        formatter.format_success("No interrupted operations found");
+        return Ok(0);
+    }
+
+    if !cli.quiet {
+        for f in &findings {
+            let status = match f.status {
+                "interrupted" | "failed" => f.status.red(),
+                "running" => f.status.yellow(),
+                _ => f.status.green(),
+            };
+            println!(
+                "  {:<12} {:<8} {}",
+                status,
+                f.operation.kind.as_str(),
+                f.operation.target.display().to_string().dimmed()
+            );
+        }
+        println!();
+    }
+
+    if remaining == 0 {
+        if args.repair {
+            formatter.format_success("Repaired all interrupted operations");
+        }
+    } else if args.repair {
+        formatter.format_error(&format!(
+            "{} operation{} could not be repaired",
+            remaining,
+            if remaining == 1 { "" } else { "s" }
+        ));
+    } else {
+        formatter.format_error(&format!(
+            "{} interrupted operation{} found. Run 'skilo doctor --repair' to recover",
+            remaining,
+            if remaining == 1 { "" } else { "s" }
+        ));
+    }
+
+    Ok(exit_code)
+}
+
+/// Test each way of authenticating against `source` and report which work.
+fn run_auth(
+    source: &str,
+    ssh_key: Option<PathBuf>,
+    config: &Config,
+    cli: &Cli,
+) -> Result<i32, SkiloError> {
+    if is_offline() {
+        return Err(SkiloError::Network {
+            message: "Cannot test authentication in offline mode".to_string(),
+        });
+    }
+    let git_source = match Source::parse(source)? {
+        Source::Git(git_source) => git_source,
+        Source::Release(_) | Source::Local(_) => {
+            return Err(SkiloError::InvalidSource(
+                source.to_string(),
+                "Authentication can only be tested for git sources".to_string(),
+            ))
+        }
+    };
+
+    // Test both protocols, whichever the source was given in
+    let host_path = git_source.host_path();
+    let (authority, repo_path) = host_path.split_once('/').unwrap_or((&host_path, ""));
+    let host = authority.split(':').next().unwrap_or(authority).to_string();
+    let is_https = git_source.url.starts_with("https://") || git_source.url.starts_with("http://");
+    let https_url = if is_https {
+        git_source.url.clone()
+    } else {
+        format!("https://{}/{}.git", host, repo_path)
+    };
+    let ssh_url = if is_https {
+        format!("git@{}:{}.git", host, repo_path)
+    } else {
+        git_source.url.clone()
+    };
+
+    let check = |method, url: &str, status, detail: String, hint: Option<String>| AuthCheck {
+        method,
+        key: None,
+        url: url.to_string(),
+        status,
+        detail,
+        hint,
+    };
+    let unreachable = |e: git2::Error| {
+        Some(format!(
+            "Check that the repository exists and {} is reachable ({})",
+            host,
+            e.message()
+        ))
+    };
+    let mut checks = Vec::new();
+
+    // Anonymous HTTPS
+    checks.push(match probe(&https_url, |_, _, _| None) {
+        Probe::Connected => check(
+            "https",
+            &https_url,
+            "ok",
+            "Public repository, no credentials needed".to_string(),
+            None,
+        ),
+        Probe::NoCredentials | Probe::Rejected => check(
+            "https",
+            &https_url,
+            "failed",
+            "The repository requires authentication".to_string(),
+            None,
+        ),
+        Probe::Failed(e) => check(
+            "https",
+            &https_url,
+            "failed",
+            "Could not connect".to_string(),
+            unreachable(e),
+        ),
+    });
+
+    // HTTPS with the git credential helper
+    let helper = git2::Config::open_default()
+        .ok()
+        .and_then(|git_config| git_config.get_string("credential.helper").ok())
+        .filter(|helper| !helper.is_empty());
+    checks.push(match helper {
+        None => check(
+            "credential-helper",
+            &https_url,
+            "skipped",
+            "No git credential helper is configured".to_string(),
+            Some(
+                "Configure one with `git config --global credential.helper <helper>` \
+                 (for GitHub, `gh auth setup-git`)"
+                    .to_string(),
+            ),
+        ),
+        Some(helper) => {
+            let mut asked = false;
+            let result = probe(&https_url, |url, username, _| {
+                if std::mem::replace(&mut asked, true) {
+                    return None;
+                }
+                let git_config = git2::Config::open_default().ok()?;
+                Cred::credential_helper(&git_config, url, username).ok()
+            });
+            match result {
+                Probe::Connected if asked => check(
+                    "credential-helper",
+                    &https_url,
+                    "ok",
+                    format!("Credentials from '{}' were accepted", helper),
+                    None,
+                ),
+                Probe::Connected => check(
+                    "credential-helper",
+                    &https_url,
+                    "ok",
+                    "Connected without asking for credentials".to_string(),
+                    None,
+                ),
+                Probe::NoCredentials => check(
+                    "credential-helper",
+                    &https_url,
+                    "failed",
+                    format!("'{}' has no credentials for {}", helper, host),
+                    Some(format!(
+                        "Sign in once with `git ls-remote {}` so the helper stores a token",
+                        https_url
+                    )),
+                ),
+                Probe::Rejected => check(
+                    "credential-helper",
+                    &https_url,
+                    "failed",
+                    format!("{} rejected the credentials from '{}'", host, helper),
+                    Some(
+                        "The stored token may have expired or lack read access to the \
+                         repository; erase it with `git credential reject` and sign in again"
+                            .to_string(),
+                    ),
+                ),
+                Probe::Failed(e) => check(
+                    "credential-helper",
+                    &https_url,
+                    "failed",
+                    "Could not connect".to_string(),
+                    unreachable(e),
+                ),
+            }
+        }
+    });
+
+    // SSH agent
+    checks.push(if std::env::var_os("SSH_AUTH_SOCK").is_none() {
+        check(
+            "ssh-agent",
+            &ssh_url,
+            "skipped",
+            "No SSH agent is running (SSH_AUTH_SOCK is not set)".to_string(),
+            Some(
+                "Start one with `eval \"$(ssh-agent)\"` and add your key with `ssh-add`"
+                    .to_string(),
+            ),
+        )
+    } else {
+        let mut asked = false;
+        let result = probe(&ssh_url, |_, username, allowed| {
+            if !allowed.contains(CredentialType::SSH_KEY) || std::mem::replace(&mut asked, true) {
+                return None;
+            }
+            Cred::ssh_key_from_agent(username.unwrap_or("git")).ok()
+        });
+        match result {
+            Probe::Connected => check(
+                "ssh-agent",
+                &ssh_url,
+                "ok",
+                "A key in the SSH agent was accepted".to_string(),
+                None,
+            ),
+            Probe::NoCredentials | Probe::Rejected => check(
+                "ssh-agent",
+                &ssh_url,
+                "failed",
+                format!("{} accepted none of the keys in the SSH agent", host),
+                Some(format!(
+                    "Add your key with `ssh-add <key>` (`ssh-add -l` lists loaded keys) and \
+                     register its public key with your account on {}",
+                    host
+                )),
+            ),
+            Probe::Failed(e) => check(
+                "ssh-agent",
+                &ssh_url,
+                "failed",
+                "Could not connect".to_string(),
+                unreachable(e),
+            ),
+        }
+    });
+
+    // SSH key files
+    let ssh_key = ssh_key
+        .or_else(|| config.add.ssh_key.clone())
+        .and_then(|key| expand_tilde(&key.to_string_lossy()));
+    let identities = Credentials::new(ssh_key).ssh_identities(&ssh_url);
+    if identities.is_empty() {
+        checks.push(check(
+            "ssh-key",
+            &ssh_url,
+            "skipped",
+            "No SSH key found in --ssh-key, add.ssh_key, ~/.ssh/config, or ~/.ssh/id_*".to_string(),
+            Some(format!(
+                "Create one with `ssh-keygen -t ed25519` and register ~/.ssh/id_ed25519.pub \
+                 with your account on {}",
+                host
+            )),
+        ));
+    }
+    for key in identities {
+        let mut key_check = if is_encrypted(&key) {
+            check(
+                "ssh-key",
+                &ssh_url,
+                "skipped",
+                "The key is protected by a passphrase".to_string(),
+                Some(format!(
+                    "Load it into the SSH agent with `ssh-add {}` to test it",
+                    key.display()
+                )),
+            )
+        } else {
+            let mut asked = false;
+            let result = probe(&ssh_url, |_, username, allowed| {
+                if !allowed.contains(CredentialType::SSH_KEY) || std::mem::replace(&mut asked, true)
+                {
+                    return None;
+                }
+                Cred::ssh_key(username.unwrap_or("git"), None, &key, None).ok()
+            });
+            match result {
+                Probe::Connected => check(
+                    "ssh-key",
+                    &ssh_url,
+                    "ok",
+                    "The key was accepted".to_string(),
+                    None,
+                ),
+                Probe::NoCredentials | Probe::Rejected => check(
+                    "ssh-key",
+                    &ssh_url,
+                    "failed",
+                    format!("{} did not accept the key", host),
+                    Some(format!(
+                        "Register {}.pub with your account on {}",
+                        key.display(),
+                        host
+                    )),
+                ),
+                Probe::Failed(e) => check(
+                    "ssh-key",
+                    &ssh_url,
+                    "failed",
+                    "Could not connect".to_string(),
+                    unreachable(e),
+                ),
+            }
+        };
+        key_check.key = Some(key);
+        checks.push(key_check);
+    }
+
+    let accessible = checks.iter().any(|c| c.status == "ok");
+    let exit_code = if accessible { 0 } else { 1 };
+
+    if matches!(cli.format, OutputFormat::Json) {
+        let output = AuthOutput {
+            source: host_path.trim_end_matches(".git").to_string(),
+            accessible,
+            checks: &checks,
+        };
+        let json = serde_json::to_string_pretty(&output)
+            .map_err(|e| SkiloError::Config(format!("JSON serialization failed: {}", e)))?;
+        println!("{}", json);
+        return Ok(exit_code);
+    }
+
+    if cli.porcelain {
+        for c in &checks {
+            let key = c.key.as_ref().map(|k| k.display().to_string());
+            print_porcelain(&[
+                c.status,
+                c.method,
+                key.as_deref().unwrap_or(EMPTY_FIELD),
+                &c.url,
+                &c.detail,
+            ]);
+        }
+        return Ok(exit_code);
+    }
+
+    let formatter = get_formatter(cli.format, cli.quiet);
+    if !cli.quiet {
+        println!("Authentication for {}", host_path.bold());
+        println!();
+        for c in &checks {
+            let mark = match c.status {
+                "ok" => "✓".green(),
+                "failed" => "✗".red(),
+                _ => "-".dimmed(),
+            };
+            let label = match &c.key {
+                Some(key) => format!("{} {}", c.method, key.display()),
+                None => format!("{} {}", c.method, c.url),
+            };
+            println!("  {} {}", mark, label);
+            println!("      {}", c.detail);
+            if let Some(hint) = &c.hint {
+                println!("      {} {}", "→".cyan(), hint.dimmed());
+            }
+        }
+        println!();
+    }
+
+    if accessible {
+        let methods: Vec<&str> = checks
+            .iter()
+            .filter(|c| c.status == "ok")
+            .map(|c| c.method)
+            .collect();
+        formatter.format_success(&format!(
+            "{} can be fetched using {}",
+            host_path,
+            methods.join(", ")
+        ));
+    } else {
+        formatter.format_error(&format!(
+            "No method could authenticate to {}; see the hints above",
+            host_path
+        ));
+    }
+
+    Ok(exit_code)
+}
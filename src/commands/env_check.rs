@@ -0,0 +1,133 @@
+//! Checks that the current machine satisfies skills' runtime requirements.
+
+use crate::cli::{Cli, EnvCheckArgs, OutputFormat};
+use crate::config::Config;
+use crate::error::SkiloError;
+use crate::output::{get_formatter, print_porcelain};
+use crate::skill::discovery::Discovery;
+use crate::skill::requirements::{RequirementCheck, Requirements};
+use crate::skill::Manifest;
+use colored::Colorize;
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// The requirement checks of one skill.
+#[derive(Debug, Serialize)]
+struct SkillEnv {
+    /// The skill name.
+    name: String,
+    /// Path to the SKILL.md.
+    path: PathBuf,
+    /// Whether every requirement is met.
+    ok: bool,
+    /// Problems with the `requires` section itself.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    invalid: Vec<String>,
+    /// One entry per requirement.
+    requirements: Vec<RequirementCheck>,
+}
+
+/// Run the env-check command.
+///
+/// Exits with 1 if any requirement is unmet or a `requires` section is
+/// invalid.
+pub fn run(args: EnvCheckArgs, config: &Config, cli: &Cli) -> Result<i32, SkiloError> {
+    let formatter = get_formatter(cli.format, cli.quiet);
+    let skill_paths = Discovery::find_skills_in(&args.paths, &config.discovery);
+
+    if skill_paths.is_empty() {
+        return Err(SkiloError::NoSkillsFound {
+            path: args
+                .paths
+                .iter()
+                .map(|p| p.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+        });
+    }
+
+    let mut skills = Vec::new();
+    for path in skill_paths {
+        let manifest = match Manifest::parse(path.clone()) {
+            Ok(manifest) => manifest,
+            Err(e) => {
+                formatter.format_error(&format!("{}: {}", path.display(), e));
+                continue;
+            }
+        };
+        let (requirements, invalid) = match &manifest.frontmatter.requires {
+            None => (Requirements::default(), Vec::new()),
+            Some(requires) => match Requirements::parse(requires) {
+                Ok(requirements) => (requirements, Vec::new()),
+                Err(problems) => (Requirements::default(), problems),
+            },
+        };
+        let checks = requirements.check();
+        skills.push(SkillEnv {
+            name: manifest.frontmatter.name,
+            path,
+            ok: invalid.is_empty() && checks.iter().all(|c| c.ok),
+            invalid,
+            requirements: checks,
+        });
+    }
+
+    let exit_code = if skills.iter().all(|s| s.ok) { 0 } else { 1 };
+
+    if matches!(cli.format, OutputFormat::Json) {
+        let json = serde_json::to_string_pretty(&skills)
+            .map_err(|e| SkiloError::Config(format!("JSON serialization failed: {}", e)))?;
+        println!("{}", json);
+        return Ok(exit_code);
+    }
+
+    if cli.porcelain {
+        for skill in &skills {
+            for check in &skill.requirements {
+                print_porcelain(&[
+                    &skill.name,
+                    check.kind,
+                    &check.name,
+                    if check.ok { "ok" } else { "missing" },
+                    check.version.as_deref().unwrap_or("-"),
+                ]);
+            }
+        }
+        return Ok(exit_code);
+    }
+
+    for skill in &skills {
+        if cli.quiet && skill.ok {
+            continue;
+        }
+        println!("{}", skill.name.bold());
+        if skill.requirements.is_empty() && skill.invalid.is_empty() {
+            println!("  {}", "no requirements".dimmed());
+        }
+        for problem in &skill.invalid {
+            println!("  {} invalid requires: {}", "✗".red(), problem);
+        }
+        for check in &skill.requirements {
+            println!("  {}", describe(check));
+        }
+    }
+
+    Ok(exit_code)
+}
+
+/// One line describing a requirement check.
+fn describe(check: &RequirementCheck) -> String {
+    let mut name = match (check.kind, &check.min_version) {
+        ("env", _) => format!("${}", check.name),
+        (_, Some(min)) => format!("{} >= {}", check.name, min),
+        _ => check.name.clone(),
+    };
+    if let Some(version) = check.version.as_ref().filter(|_| check.ok) {
+        name.push_str(&format!(" {}", format!("({})", version).dimmed()));
+    }
+
+    match &check.problem {
+        None => format!("{} {}", "✓".green(), name),
+        Some(problem) => format!("{} {}: {}", "✗".red(), name, problem),
+    }
+}
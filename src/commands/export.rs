@@ -0,0 +1,116 @@
+//! Export installed skills to a portable manifest.
+
+use crate::agent::Agent;
+use crate::cli::{Cli, ExportArgs};
+use crate::config::Config;
+use crate::error::SkiloError;
+use crate::lockfile::{untracked_skills, Lockfile};
+use crate::output::get_formatter;
+use crate::scope::Scope;
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Current export manifest format version.
+pub const MANIFEST_VERSION: u32 = 1;
+
+/// A portable list of installed skills, written by `export` and read by `import`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportManifest {
+    /// Format version.
+    pub version: u32,
+    /// Exported skills.
+    pub skills: Vec<ExportedSkill>,
+}
+
+/// A skill entry in an export manifest.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportedSkill {
+    /// The skill name.
+    pub name: String,
+    /// The agent the skill is installed for (None for `./skills/`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent: Option<Agent>,
+    /// The installation scope.
+    pub scope: Scope,
+    /// The source the skill was installed from.
+    pub source: String,
+    /// Subdirectory within the source.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subdir: Option<String>,
+    /// The requested branch, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    /// The requested tag, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    /// The requested `@ref` (branch, tag, or commit), if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rev: Option<String>,
+    /// The commit that was installed, for git sources.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+}
+
+/// Run the export command.
+pub fn run(args: ExportArgs, _config: &Config, cli: &Cli) -> Result<i32, SkiloError> {
+    let formatter = get_formatter(cli.format, cli.quiet);
+    let project_root = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+
+    let mut skills = Vec::new();
+    let mut untracked = Vec::new();
+
+    for scope in args.scope.scopes() {
+        let lock = Lockfile::load_scope(scope, &project_root)?;
+
+        for entry in &lock.skills {
+            skills.push(ExportedSkill {
+                name: entry.name.clone(),
+                agent: entry.agent,
+                scope,
+                source: entry.source.clone(),
+                subdir: entry.subdir.clone(),
+                branch: entry.branch.clone(),
+                tag: entry.tag.clone(),
+                rev: entry.rev.clone(),
+                commit: entry.commit.clone(),
+            });
+        }
+
+        untracked.extend(untracked_skills(&lock, scope, &project_root));
+    }
+
+    let manifest = ExportManifest {
+        version: MANIFEST_VERSION,
+        skills,
+    };
+    let json = serde_json::to_string_pretty(&manifest)
+        .map_err(|e| SkiloError::Config(format!("JSON serialization failed: {}", e)))?;
+
+    if !cli.quiet {
+        for path in &untracked {
+            eprintln!(
+                "{}: {} has no recorded source and was not exported",
+                "Warning".yellow(),
+                path.display()
+            );
+        }
+    }
+
+    match args.output {
+        Some(ref output) => {
+            std::fs::write(output, format!("{}\n", json))?;
+            if !cli.quiet {
+                formatter.format_success(&format!(
+                    "Exported {} skill{} to {}",
+                    manifest.skills.len(),
+                    if manifest.skills.len() == 1 { "" } else { "s" },
+                    output.display()
+                ));
+            }
+        }
+        None => println!("{}", json),
+    }
+
+    Ok(0)
+}
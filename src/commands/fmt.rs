@@ -1,11 +1,17 @@
 //! Formats SKILL.md files with consistent YAML frontmatter and table alignment.
 
+use crate::cli::OutputFormat;
 use crate::cli::{Cli, FmtArgs};
 use crate::config::Config;
 use crate::error::SkiloError;
-use crate::output::get_formatter;
-use crate::skill::{Discovery, Formatter, FormatterConfig, Manifest};
+use crate::journal::write_atomic;
+use crate::output::{get_formatter, Align, OutputFormatter, Table};
+use crate::skill::rules::scripts;
+use crate::skill::{
+    minimal_diff, normalize_line_endings, Discovery, Formatter, FormatterConfig, Manifest,
+};
 use colored::Colorize;
+use std::path::Path;
 
 /// Run the format command.
 ///
@@ -15,63 +21,120 @@ pub fn run(args: FmtArgs, config: &Config, cli: &Cli) -> Result<i32, SkiloError>
     let skill_formatter = Formatter::new(FormatterConfig::from(&config.fmt));
 
     // Find all skills
-    let skill_paths = Discovery::find_skills(&args.path, &config.discovery.ignore);
+    let roots = Discovery::roots(&args.paths, &config.discovery);
+    let by_root = Discovery::find_skills_by_root(&roots, &config.discovery);
 
-    if skill_paths.is_empty() {
+    if by_root.iter().all(|(_, skills)| skills.is_empty()) {
         return Err(SkiloError::NoSkillsFound {
-            path: args.path.display().to_string(),
+            path: roots
+                .iter()
+                .map(|p| p.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
         });
     }
 
     let mut files_changed = 0;
     let mut files_checked = 0;
+    let mut unstable = 0;
+    let mut root_summaries = Vec::new();
 
-    for path in &skill_paths {
-        match Manifest::parse(path.clone()) {
-            Ok(manifest) => {
-                files_checked += 1;
-
-                // Get formatted content
-                let formatted = match skill_formatter.format(&manifest) {
-                    Ok(f) => f,
-                    Err(e) => {
-                        output_formatter.format_error(&format!("{}: {}", path.display(), e));
-                        continue;
-                    }
-                };
-
-                // Read current content
-                let current = std::fs::read_to_string(path)?;
+    for (root, skill_paths) in &by_root {
+        let changed_before = files_changed;
+        for path in skill_paths {
+            match Manifest::parse(path.clone()) {
+                Ok(manifest) => {
+                    files_checked += 1;
 
-                if formatted != current {
-                    files_changed += 1;
+                    // Get formatted content
+                    let formatted = match skill_formatter.format(&manifest) {
+                        Ok(f) => f,
+                        Err(e) => {
+                            output_formatter.format_error(&format!("{}: {}", path.display(), e));
+                            continue;
+                        }
+                    };
 
-                    if args.check {
-                        output_formatter.format_message(&format!(
-                            "{} {} needs formatting",
-                            "!".yellow(),
-                            path.display()
-                        ));
-                    } else if args.diff {
-                        // Show diff
-                        println!("{}", format!("--- {}", path.display()).dimmed());
-                        println!("{}", format!("+++ {}", path.display()).dimmed());
-                        print_diff(&current, &formatted);
-                    } else {
-                        // Write formatted content
-                        std::fs::write(path, &formatted)?;
-                        output_formatter.format_message(&format!(
-                            "{} Formatted {}",
-                            "✓".green(),
-                            path.display()
+                    if args.verify {
+                        if let Some(diff) =
+                            verify_idempotent(&skill_formatter, &manifest, &formatted)
+                        {
+                            unstable += 1;
+                            output_formatter.format_error(&format!(
+                            "{}: formatting is not idempotent (this is a skilo bug, please report it)\n{}",
+                            path.display(),
+                            diff.trim_end()
                         ));
+                        }
+                    }
+
+                    // Read current content
+                    let current = std::fs::read_to_string(path)?;
+                    if update_file(path, &current, &formatted, &args, output_formatter.as_ref())? {
+                        files_changed += 1;
+                    }
+
+                    // Scripts only change with normalized line endings
+                    if config.fmt.normalize_line_endings {
+                        for script in scripts(&manifest) {
+                            let Ok(current) = std::fs::read_to_string(&script) else {
+                                continue;
+                            };
+                            if current.contains('\0') {
+                                continue;
+                            }
+                            files_checked += 1;
+                            let normalized = normalize_line_endings(&current);
+                            if update_file(
+                                &script,
+                                &current,
+                                &normalized,
+                                &args,
+                                output_formatter.as_ref(),
+                            )? {
+                                files_changed += 1;
+                            }
+                        }
                     }
                 }
-            }
-            Err(e) => {
-                output_formatter.format_error(&format!("{}: {}", path.display(), e));
+                Err(e) => {
+                    output_formatter.format_error(&format!("{}: {}", path.display(), e));
+                }
             }
         }
+        root_summaries.push(vec![
+            root.display().to_string(),
+            skill_paths.len().to_string(),
+            (files_changed - changed_before).to_string(),
+        ]);
+    }
+
+    // Per-root summary when formatting several roots
+    let text = matches!(cli.format, OutputFormat::Text | OutputFormat::Table);
+    if by_root.len() > 1 && text && !cli.quiet {
+        let changed = if args.check || args.diff {
+            "UNFORMATTED"
+        } else {
+            "FORMATTED"
+        };
+        let mut table = Table::new(["ROOT", "FILES", changed])
+            .align(1, Align::Right)
+            .align(2, Align::Right);
+        for row in root_summaries {
+            table.row(row);
+        }
+        println!();
+        table.print();
+    }
+
+    // Unstable and (with --check) unformatted files count as errors
+    if unstable > 0 {
+        output_formatter.format_message(&format!(
+            "\n{} {} file(s) format differently on a second pass",
+            "!".yellow(),
+            unstable
+        ));
+        return Ok(args.exit.exit_code(unstable, 0, false));
     }
 
     if args.check {
@@ -81,14 +144,13 @@ pub fn run(args: FmtArgs, config: &Config, cli: &Cli) -> Result<i32, SkiloError>
                 "!".yellow(),
                 files_changed
             ));
-            Ok(1)
         } else {
             output_formatter.format_success(&format!(
                 "{} file(s) checked, all formatted correctly",
                 files_checked
             ));
-            Ok(0)
         }
+        Ok(args.exit.exit_code(files_changed, 0, false))
     } else {
         if files_changed > 0 {
             output_formatter.format_success(&format!("Formatted {} file(s)", files_changed));
@@ -102,6 +164,58 @@ pub fn run(args: FmtArgs, config: &Config, cli: &Cli) -> Result<i32, SkiloError>
     }
 }
 
+/// Write `formatted` to `path` if it differs from `current`, or report or
+/// show the difference with `--check` and `--diff`.
+///
+/// Returns true if the content differs.
+fn update_file(
+    path: &Path,
+    current: &str,
+    formatted: &str,
+    args: &FmtArgs,
+    output_formatter: &dyn OutputFormatter,
+) -> Result<bool, SkiloError> {
+    if formatted == current {
+        return Ok(false);
+    }
+
+    if args.check {
+        output_formatter.format_message(&format!(
+            "{} {} needs formatting",
+            "!".yellow(),
+            path.display()
+        ));
+    } else if args.diff {
+        // Show diff
+        println!("{}", format!("--- {}", path.display()).dimmed());
+        println!("{}", format!("+++ {}", path.display()).dimmed());
+        print_diff(current, formatted);
+    } else {
+        // Write formatted content
+        write_atomic(path, formatted)?;
+        output_formatter.format_message(&format!("{} Formatted {}", "✓".green(), path.display()));
+    }
+    Ok(true)
+}
+
+/// Format `formatted` a second time and describe any change.
+///
+/// Returns `None` when the second pass leaves the output unchanged.
+fn verify_idempotent(
+    formatter: &Formatter,
+    manifest: &Manifest,
+    formatted: &str,
+) -> Option<String> {
+    let reparsed = match Manifest::parse_content(manifest.path.clone(), formatted) {
+        Ok(m) => m,
+        Err(e) => return Some(format!("formatted output no longer parses: {}", e)),
+    };
+    match formatter.format(&reparsed) {
+        Ok(again) => minimal_diff(formatted, &again),
+        Err(e) => Some(format!("formatted output cannot be formatted again: {}", e)),
+    }
+}
+
 /// Print a simple line-by-line diff between two strings.
 fn print_diff(old: &str, new: &str) {
     let old_lines: Vec<&str> = old.lines().collect();
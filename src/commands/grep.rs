@@ -0,0 +1,247 @@
+//! Searches the content of installed skills.
+
+use crate::agent::Agent;
+use crate::cli::{AgentSelection, Cli, GrepArgs, OutputFormat, ScopeArg};
+use crate::config::Config;
+use crate::error::SkiloError;
+use crate::output::{print_porcelain, EMPTY_FIELD};
+use crate::scope::{list_skills, list_skills_from_path, InstalledSkill, Scope};
+use colored::Colorize;
+use regex::{Regex, RegexBuilder};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// A line matching the pattern.
+#[derive(Debug, Serialize)]
+struct GrepMatch {
+    /// Name of the skill containing the match.
+    skill: String,
+    /// Agent the skill is installed for, if any.
+    agent: Option<Agent>,
+    /// Installation scope.
+    scope: Scope,
+    /// File containing the match.
+    path: PathBuf,
+    /// 1-based line number.
+    line: usize,
+    /// The matching line.
+    text: String,
+}
+
+/// Run the grep command.
+///
+/// Searches SKILL.md files (names, descriptions, and bodies) and scripts of
+/// installed skills, printing matches as `file:line:text`.
+pub fn run(args: GrepArgs, _config: &Config, cli: &Cli) -> Result<i32, SkiloError> {
+    let pattern = if args.fixed_strings {
+        regex::escape(&args.pattern)
+    } else {
+        args.pattern.clone()
+    };
+    let regex = RegexBuilder::new(&pattern)
+        .case_insensitive(args.ignore_case)
+        .build()
+        .map_err(|e| SkiloError::Config(format!("Invalid pattern '{}': {}", args.pattern, e)))?;
+
+    let project_root = args
+        .path
+        .canonicalize()
+        .unwrap_or_else(|_| args.path.clone());
+    let scopes = match args.scope {
+        Some(ScopeArg::Both) => vec![Scope::Project, Scope::Global],
+        Some(ScopeArg::Global) => vec![Scope::Global],
+        Some(ScopeArg::Project) => vec![Scope::Project],
+        None if args.all => vec![Scope::Project, Scope::Global],
+        None if args.global => vec![Scope::Global],
+        None => vec![Scope::Project],
+    };
+    let selection = args
+        .agent
+        .as_ref()
+        .map(|a| a.to_selection())
+        .unwrap_or(AgentSelection::All);
+
+    let matches: Vec<GrepMatch> = installed_skills(selection, &scopes, &project_root)
+        .iter()
+        .flat_map(|skill| search_skill(skill, &regex))
+        .collect();
+
+    if cli.quiet {
+        return Ok(if matches.is_empty() { 1 } else { 0 });
+    }
+
+    if matches!(cli.format, OutputFormat::Json) {
+        let json = serde_json::to_string_pretty(&matches)
+            .map_err(|e| SkiloError::Config(format!("JSON serialization failed: {}", e)))?;
+        println!("{}", json);
+    } else if cli.porcelain {
+        for m in &matches {
+            print_porcelain(&[
+                m.scope.display_name(),
+                m.agent.map_or(EMPTY_FIELD, |a| a.cli_name()),
+                &m.skill,
+                &m.path.display().to_string(),
+                &m.line.to_string(),
+                &m.text,
+            ]);
+        }
+    } else if args.files_with_matches {
+        let mut seen = HashSet::new();
+        for m in matches.iter().filter(|m| seen.insert(&m.path)) {
+            println!("{}", display_path(&m.path).magenta());
+        }
+    } else {
+        for m in &matches {
+            println!(
+                "{}:{}:{}",
+                display_path(&m.path).magenta(),
+                m.line.to_string().green(),
+                highlight(&m.text, &regex)
+            );
+        }
+    }
+
+    Ok(if matches.is_empty() { 1 } else { 0 })
+}
+
+/// Collect installed skills for the selected agents and scopes.
+///
+/// Skills reached through more than one path (shared skills directories or
+/// symlinks) are returned once.
+pub(crate) fn installed_skills(
+    selection: AgentSelection,
+    scopes: &[Scope],
+    project_root: &Path,
+) -> Vec<InstalledSkill> {
+    let agents: Vec<Agent> = match selection {
+        AgentSelection::Single(agent) => vec![agent],
+        AgentSelection::Generic => Vec::new(),
+        AgentSelection::All => Agent::all().to_vec(),
+    };
+
+    let mut skills = Vec::new();
+    for &scope in scopes {
+        for &agent in &agents {
+            skills.extend(list_skills(agent, scope, project_root));
+        }
+    }
+    let generic = matches!(selection, AgentSelection::All | AgentSelection::Generic);
+    if generic && scopes.contains(&Scope::Project) {
+        skills.extend(list_skills_from_path(
+            &project_root.join("skills"),
+            None,
+            Scope::Project,
+        ));
+    }
+
+    let mut seen = HashSet::new();
+    skills.retain(|s| seen.insert(s.path.canonicalize().unwrap_or_else(|_| s.path.clone())));
+    skills
+}
+
+/// Search a skill's SKILL.md and scripts for lines matching `regex`.
+fn search_skill(skill: &InstalledSkill, regex: &Regex) -> Vec<GrepMatch> {
+    let mut files = vec![skill.path.join("SKILL.md")];
+    files.extend(
+        WalkDir::new(skill.path.join("scripts"))
+            .sort_by_file_name()
+            .into_iter()
+            .filter_map(Result::ok)
+            .filter(|e| e.file_type().is_file())
+            .map(|e| e.into_path()),
+    );
+
+    let mut matches = Vec::new();
+    for file in files {
+        // Skips missing and binary files
+        let Ok(content) = std::fs::read_to_string(&file) else {
+            continue;
+        };
+        for (i, text) in content.lines().enumerate() {
+            if regex.is_match(text) {
+                matches.push(GrepMatch {
+                    skill: skill.name.clone(),
+                    agent: skill.agent,
+                    scope: skill.scope,
+                    path: file.clone(),
+                    line: i + 1,
+                    text: text.to_string(),
+                });
+            }
+        }
+    }
+    matches
+}
+
+/// Show paths under the current directory relative to it.
+fn display_path(path: &Path) -> String {
+    std::env::current_dir()
+        .ok()
+        .and_then(|cwd| path.strip_prefix(cwd).ok())
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
+
+/// Highlight every match of `regex` in `text`.
+fn highlight(text: &str, regex: &Regex) -> String {
+    let mut out = String::new();
+    let mut last = 0;
+    for m in regex.find_iter(text) {
+        out.push_str(&text[last..m.start()]);
+        out.push_str(&m.as_str().red().bold().to_string());
+        last = m.end();
+    }
+    out.push_str(&text[last..]);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_search_skill() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path().join("my-skill");
+        std::fs::create_dir_all(dir.join("scripts")).unwrap();
+        std::fs::write(
+            dir.join("SKILL.md"),
+            "---\nname: my-skill\ndescription: Parses TOML\n---\n\n# My Skill\n\nRun the toml parser.\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join("scripts/run.sh"), "#!/bin/sh\nparse-toml \"$1\"\n").unwrap();
+        std::fs::write(dir.join("scripts/data.bin"), [0xff, 0xfe, 0x00]).unwrap();
+
+        let skill = InstalledSkill {
+            name: "my-skill".into(),
+            description: "Parses TOML".into(),
+            path: dir.clone(),
+            agent: Some(Agent::Claude),
+            scope: Scope::Project,
+            deprecated: false,
+            superseded_by: None,
+            tags: Vec::new(),
+            version: None,
+        };
+
+        let regex = RegexBuilder::new("toml")
+            .case_insensitive(true)
+            .build()
+            .unwrap();
+        let matches = search_skill(&skill, &regex);
+        let found: Vec<_> = matches
+            .iter()
+            .map(|m| (m.path.file_name().unwrap().to_str().unwrap(), m.line))
+            .collect();
+        assert_eq!(found, vec![("SKILL.md", 3), ("SKILL.md", 8), ("run.sh", 2)]);
+
+        assert_eq!(
+            highlight("a toml b", &Regex::new("toml").unwrap()),
+            format!("a {} b", "toml".red().bold())
+        );
+    }
+}
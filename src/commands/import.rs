@@ -0,0 +1,154 @@
+//! Reinstall skills from an export manifest.
+
+use crate::cli::{AddArgs, Cli, ImportArgs, ScopeArg};
+use crate::commands::export::{ExportManifest, ExportedSkill, MANIFEST_VERSION};
+use crate::config::Config;
+use crate::error::SkiloError;
+use crate::output::get_formatter;
+use colored::Colorize;
+
+/// Run the import command.
+///
+/// Skills that share a source, version, agent, and scope are installed
+/// together with a single `add`. A failing group is reported and the
+/// remaining groups are still installed.
+pub fn run(args: ImportArgs, config: &Config, cli: &Cli) -> Result<i32, SkiloError> {
+    let formatter = get_formatter(cli.format, cli.quiet);
+
+    let content = std::fs::read_to_string(&args.file)?;
+    let manifest: ExportManifest = serde_json::from_str(&content).map_err(|e| {
+        SkiloError::Config(format!("Invalid manifest {}: {}", args.file.display(), e))
+    })?;
+
+    if manifest.version > MANIFEST_VERSION {
+        return Err(SkiloError::Config(format!(
+            "Manifest version {} is newer than supported version {}",
+            manifest.version, MANIFEST_VERSION
+        )));
+    }
+
+    if manifest.skills.is_empty() {
+        formatter.format_message("No skills to import.");
+        return Ok(0);
+    }
+
+    let groups = group_skills(&manifest.skills);
+    let mut failed = 0;
+
+    for group in &groups {
+        let first = group[0];
+        let source = match &first.rev {
+            Some(rev) => format!("{}@{}", first.source, rev),
+            None => first.source.clone(),
+        };
+        let add_args = AddArgs {
+            source: Some(source),
+            from_file: None,
+            skill: Some(group.iter().map(|s| s.name.clone()).collect()),
+            list: false,
+            yes: args.yes,
+            branch: first.branch.clone(),
+            tag: first.tag.clone(),
+            rev: None,
+            path: first.subdir.clone(),
+            agent: first.agent.map(|a| vec![a.into()]),
+            global: false,
+            scope: Some(ScopeArg::from(first.scope)),
+            output: None,
+            register: false,
+            ssh_key: None,
+        };
+
+        match crate::commands::add::run(add_args, config, cli) {
+            Ok(0) => {}
+            Ok(_) => failed += 1,
+            Err(SkiloError::Cancelled) => return Err(SkiloError::Cancelled),
+            Err(e) => {
+                failed += 1;
+                formatter.format_error(&format!("Failed to import from {}: {}", first.source, e));
+            }
+        }
+
+        if !cli.quiet && !cli.porcelain {
+            println!();
+        }
+    }
+
+    if failed > 0 {
+        if !cli.quiet {
+            eprintln!(
+                "{}: {} of {} source{} failed to import",
+                "Warning".yellow(),
+                failed,
+                groups.len(),
+                if groups.len() == 1 { "" } else { "s" }
+            );
+        }
+        Ok(1)
+    } else {
+        Ok(0)
+    }
+}
+
+/// Group skills that can be installed with a single `add`, preserving order.
+pub(crate) fn group_skills(skills: &[ExportedSkill]) -> Vec<Vec<&ExportedSkill>> {
+    let mut groups: Vec<Vec<&ExportedSkill>> = Vec::new();
+
+    for skill in skills {
+        let existing = groups.iter_mut().find(|g| {
+            let first = g[0];
+            first.source == skill.source
+                && first.subdir == skill.subdir
+                && first.branch == skill.branch
+                && first.tag == skill.tag
+                && first.rev == skill.rev
+                && first.agent == skill.agent
+                && first.scope == skill.scope
+        });
+
+        match existing {
+            Some(group) => group.push(skill),
+            None => groups.push(vec![skill]),
+        }
+    }
+
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::Agent;
+    use crate::scope::Scope;
+
+    fn skill(name: &str, source: &str, agent: Agent) -> ExportedSkill {
+        ExportedSkill {
+            name: name.to_string(),
+            agent: Some(agent),
+            scope: Scope::Project,
+            source: source.to_string(),
+            subdir: None,
+            branch: None,
+            tag: None,
+            rev: None,
+            commit: None,
+        }
+    }
+
+    #[test]
+    fn test_group_skills() {
+        let skills = vec![
+            skill("a", "owner/one", Agent::Claude),
+            skill("b", "owner/two", Agent::Claude),
+            skill("c", "owner/one", Agent::Claude),
+            skill("d", "owner/one", Agent::Cursor),
+        ];
+
+        let groups = group_skills(&skills);
+        let names: Vec<Vec<&str>> = groups
+            .iter()
+            .map(|g| g.iter().map(|s| s.name.as_str()).collect())
+            .collect();
+        assert_eq!(names, vec![vec!["a", "c"], vec!["b"], vec!["d"]]);
+    }
+}
@@ -0,0 +1,250 @@
+//! Scaffolds repositories for collections of skills.
+
+use crate::cli::{Cli, InitArgs, InitCommand};
+use crate::config::Config;
+use crate::error::SkiloError;
+use crate::journal::write_atomic;
+use crate::output::get_formatter;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Skilo configuration searching `skills/` when no path is given.
+const CONFIG: &str = r#"# skilo configuration: https://github.com/manuelmauro/skilo
+
+[discovery]
+roots = ["skills"]
+
+[lint]
+strict = true
+"#;
+
+/// Workflow checking the skills and their index on every push.
+const WORKFLOW: &str = r#"name: Skills
+
+on:
+  push:
+    branches: [main]
+  pull_request:
+
+jobs:
+  check:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+
+      - name: Install skilo
+        run: curl -sSfL https://raw.githubusercontent.com/manuelmauro/skilo/main/install.sh | sh
+
+      - name: Check skills
+        run: skilo ci-check skills/
+
+      - name: Check the skill index is up to date
+        run: |
+          skilo to-prompt skills/ --output AGENTS.md
+          git diff --exit-code AGENTS.md
+"#;
+
+/// Agent instructions file the skill index is written to.
+const AGENTS: &str = r#"# Skills
+
+This repository is a collection of agent skills, one directory per skill
+under `skills/`. Install them with `skilo add <owner>/<repo>`.
+
+The index below is generated by `skilo to-prompt skills/ --output AGENTS.md`;
+CI fails when it is out of date.
+"#;
+
+/// Attributes keeping scripts runnable and checksums stable across
+/// platforms.
+const GITATTRIBUTES: &[&str] = &[
+    "* text=auto eol=lf",
+    "*.sh text eol=lf",
+    "*.py text eol=lf",
+    "*.js text eol=lf",
+    "*.ts text eol=lf",
+    "*.rb text eol=lf",
+    "*.png binary",
+    "*.jpg binary",
+    "*.gif binary",
+    "*.pdf binary",
+    "*.zip binary",
+];
+
+/// Files ignored in skill repositories.
+const GITIGNORE: &[&str] = &[
+    ".skilo-new-*/",
+    "__pycache__/",
+    "*.pyc",
+    ".venv/",
+    "node_modules/",
+    ".DS_Store",
+];
+
+/// What `init` did with a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FileStatus {
+    /// The file was written.
+    Created,
+    /// Missing lines were appended to an existing file.
+    Updated,
+    /// The file exists and was left as is.
+    Skipped,
+}
+
+/// Run the init command.
+pub fn run(args: InitArgs, _config: &Config, cli: &Cli) -> Result<i32, SkiloError> {
+    let formatter = get_formatter(cli.format, cli.quiet);
+
+    match args.command {
+        InitCommand::Repo { path, force, no_ci } => {
+            let files = scaffold_repo(&path, force, !no_ci)?;
+
+            for (file, status) in &files {
+                let file = file.strip_prefix(&path).unwrap_or(file).display();
+                match status {
+                    FileStatus::Created => formatter.format_success(&format!("Created {}", file)),
+                    FileStatus::Updated => formatter.format_success(&format!("Updated {}", file)),
+                    FileStatus::Skipped => {
+                        formatter.format_message(&format!("  {} exists, skipped", file))
+                    }
+                }
+            }
+
+            formatter.format_message("\nNext steps:");
+            formatter.format_message("  1. Create a skill with `skilo new <name> --output skills`");
+            formatter.format_message("  2. Check it with `skilo check skills/`");
+            formatter
+                .format_message("  3. Index it with `skilo to-prompt skills/ --output AGENTS.md`");
+            Ok(0)
+        }
+    }
+}
+
+/// Write the layout of a skill repository under `root`.
+///
+/// `.gitattributes` and `.gitignore` get the lines they lack appended; other
+/// existing files are only replaced with `force`.
+fn scaffold_repo(
+    root: &Path,
+    force: bool,
+    ci: bool,
+) -> Result<Vec<(PathBuf, FileStatus)>, SkiloError> {
+    let mut files = Vec::new();
+
+    let skills = root.join("skills");
+    fs::create_dir_all(&skills)?;
+    // Keep the empty directory in git until the first skill is added
+    if fs::read_dir(&skills)?.next().is_none() {
+        fs::write(skills.join(".gitkeep"), "")?;
+    }
+
+    let mut templates = vec![(".skilorc.toml", CONFIG), ("AGENTS.md", AGENTS)];
+    if ci {
+        templates.push((".github/workflows/skills.yml", WORKFLOW));
+    }
+    for (name, content) in templates {
+        let file = root.join(name);
+        let status = write_file(&file, content, force)?;
+        files.push((file, status));
+    }
+
+    for (name, lines) in [(".gitattributes", GITATTRIBUTES), (".gitignore", GITIGNORE)] {
+        let file = root.join(name);
+        let status = append_lines(&file, lines)?;
+        files.push((file, status));
+    }
+
+    Ok(files)
+}
+
+/// Write `content` to `file` unless it exists and `force` is not set.
+fn write_file(file: &Path, content: &str, force: bool) -> Result<FileStatus, SkiloError> {
+    let exists = file.exists();
+    if exists && !force {
+        return Ok(FileStatus::Skipped);
+    }
+    if let Some(parent) = file.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    write_atomic(file, content)?;
+    Ok(if exists {
+        FileStatus::Updated
+    } else {
+        FileStatus::Created
+    })
+}
+
+/// Append the `lines` that `file` lacks, creating it if needed.
+fn append_lines(file: &Path, lines: &[&str]) -> Result<FileStatus, SkiloError> {
+    let existing = match fs::read_to_string(file) {
+        Ok(existing) => Some(existing),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => return Err(e.into()),
+    };
+    let present: Vec<&str> = existing
+        .as_deref()
+        .map(|e| e.lines().map(str::trim).collect())
+        .unwrap_or_default();
+    let missing: Vec<&str> = lines
+        .iter()
+        .copied()
+        .filter(|line| !present.contains(line))
+        .collect();
+    if missing.is_empty() {
+        return Ok(FileStatus::Skipped);
+    }
+
+    let mut content = existing.clone().unwrap_or_default();
+    if !content.is_empty() {
+        if !content.ends_with('\n') {
+            content.push('\n');
+        }
+        content.push('\n');
+    }
+    content.push_str("# Added by skilo init\n");
+    for line in missing {
+        content.push_str(line);
+        content.push('\n');
+    }
+    write_atomic(file, content)?;
+
+    Ok(if existing.is_some() {
+        FileStatus::Updated
+    } else {
+        FileStatus::Created
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_scaffold_repo() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        fs::write(root.join(".gitignore"), "target/\n*.pyc").unwrap();
+        fs::write(root.join("AGENTS.md"), "# Mine\n").unwrap();
+
+        let files = scaffold_repo(root, false, true).unwrap();
+        let status = |name: &str| files.iter().find(|(f, _)| f == &root.join(name)).unwrap().1;
+        assert_eq!(status(".skilorc.toml"), FileStatus::Created);
+        assert_eq!(status(".github/workflows/skills.yml"), FileStatus::Created);
+        assert_eq!(status("AGENTS.md"), FileStatus::Skipped);
+        assert_eq!(status(".gitignore"), FileStatus::Updated);
+        assert!(root.join("skills/.gitkeep").is_file());
+        assert_eq!(
+            fs::read_to_string(root.join("AGENTS.md")).unwrap(),
+            "# Mine\n"
+        );
+
+        let gitignore = fs::read_to_string(root.join(".gitignore")).unwrap();
+        assert!(gitignore.starts_with("target/\n*.pyc\n\n# Added by skilo init\n"));
+        assert_eq!(gitignore.matches("*.pyc").count(), 1);
+
+        // Running again changes nothing
+        let files = scaffold_repo(root, false, false).unwrap();
+        assert!(files.iter().all(|(_, s)| *s == FileStatus::Skipped));
+    }
+}
@@ -1,10 +1,18 @@
 //! Validates skills against the Agent Skills specification rules.
 
 use crate::cli::{Cli, LintArgs};
+use crate::cli::{OutputFormat, PathStyle};
 use crate::config::Config;
 use crate::error::SkiloError;
-use crate::output::get_formatter;
-use crate::skill::{Discovery, Manifest, ValidationResult, Validator};
+use crate::github::{self, PullRequest};
+use crate::lockfile;
+use crate::output::{get_formatter, Align, PathNormalizer, ReportOptions, Table};
+use crate::skill::{
+    fix, Diagnostic, DiagnosticCode, Discovery, Manifest, ValidationResult, Validator,
+};
+use colored::Colorize;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
 
 /// Run the lint command.
 ///
@@ -12,50 +20,233 @@ use crate::skill::{Discovery, Manifest, ValidationResult, Validator};
 pub fn run(args: LintArgs, config: &Config, cli: &Cli) -> Result<i32, SkiloError> {
     let formatter = get_formatter(cli.format, cli.quiet);
     let strict = args.strict || config.lint.strict;
+    let pull_request = if args.github_pr_review {
+        Some(PullRequest::from_env()?)
+    } else {
+        None
+    };
+
+    for warning in config.lint.code_warnings() {
+        eprintln!("{}: {}", "Warning".yellow(), warning);
+    }
 
     // Find all skills
-    let skill_paths = Discovery::find_skills(&args.path, &config.discovery.ignore);
+    let roots = Discovery::roots(&args.paths, &config.discovery);
+    let by_root = Discovery::find_skills_by_root(&roots, &config.discovery);
 
-    if skill_paths.is_empty() {
+    if by_root.iter().all(|(_, skills)| skills.is_empty()) {
         return Err(SkiloError::NoSkillsFound {
-            path: args.path.display().to_string(),
+            path: roots
+                .iter()
+                .map(|p| p.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
         });
     }
 
+    let mut lint_config = config.lint.clone();
+    if let Some(name_fix) = args.name_fix {
+        lint_config.name_fix = name_fix;
+    }
+    let mut validator = Validator::with_agents(&lint_config, &config.lint_agents());
+    validator
+        .select(&args.only, &args.skip)
+        .map_err(|e| SkiloError::Config(format!("--only/--skip: {}", e)))?;
+
+    if args.fix || args.diff {
+        fix_skills(&by_root, &validator, args.diff, cli.quiet)?;
+    }
+
     // Load and validate skills
-    let validator = Validator::new(&config.lint);
     let mut results: Vec<(String, ValidationResult)> = Vec::new();
+    let mut root_summaries = Vec::new();
     let mut parse_errors = 0;
 
-    for path in &skill_paths {
-        match Manifest::parse(path.clone()) {
-            Ok(manifest) => {
-                let result = validator.validate(&manifest);
-                results.push((path.display().to_string(), result));
-            }
-            Err(e) => {
-                parse_errors += 1;
-                formatter.format_error(&format!("{}: {}", path.display(), e));
+    for (root, skill_paths) in &by_root {
+        let first = results.len();
+        let mut root_parse_errors = 0;
+        for path in skill_paths {
+            match Manifest::parse(path.clone()) {
+                Ok(manifest) => {
+                    let result = validator.validate(&manifest);
+                    results.push((path.display().to_string(), result));
+                }
+                Err(e) => match Diagnostic::for_parse_error(path, &e) {
+                    Some(diag) => results.push((
+                        path.display().to_string(),
+                        ValidationResult {
+                            errors: vec![diag],
+                            warnings: Vec::new(),
+                        },
+                    )),
+                    None => {
+                        root_parse_errors += 1;
+                        formatter.format_error(&format!("{}: {}", path.display(), e));
+                    }
+                },
             }
         }
+        parse_errors += root_parse_errors;
+
+        let root_results = &results[first..];
+        root_summaries.push(vec![
+            root.display().to_string(),
+            skill_paths.len().to_string(),
+            (root_parse_errors
+                + root_results
+                    .iter()
+                    .map(|(_, r)| r.errors.len())
+                    .sum::<usize>())
+            .to_string(),
+            root_results
+                .iter()
+                .map(|(_, r)| r.warnings.len())
+                .sum::<usize>()
+                .to_string(),
+        ]);
     }
 
+    // Review comments need repository-relative paths whatever the style
+    let review_results = pull_request.as_ref().map(|_| {
+        let mut review_results = results.clone();
+        PathNormalizer::new(PathStyle::Root).normalize_results(&mut review_results);
+        review_results
+    });
+
     // Output results
-    let output = formatter.format_validation(&results);
+    let normalizer = PathNormalizer::new(cli.path_style);
+    let sources = if args.explain_failures {
+        read_sources(&results, &normalizer)
+    } else {
+        BTreeMap::new()
+    };
+    normalizer.normalize_results(&mut results);
+    let options = ReportOptions {
+        group_by_rule: args.summary_only,
+        max_diagnostics: args.max_diagnostics,
+        sources,
+    };
+    let output = formatter.format_report(&results, &options);
     if !output.is_empty() {
         print!("{}", output);
     }
 
+    if let (Some(pr), Some(review_results)) = (&pull_request, &review_results) {
+        let outcome = github::review(pr, review_results)?;
+        formatter.format_message(&format!(
+            "Reviewed pull request #{}: {} comment(s) posted, {} kept, {} removed",
+            pr.number, outcome.posted, outcome.kept, outcome.removed
+        ));
+    }
+
+    // Per-root summary when linting several roots
+    let text = matches!(cli.format, OutputFormat::Text | OutputFormat::Table);
+    if by_root.len() > 1 && text && !cli.quiet {
+        let mut table = Table::new(["ROOT", "SKILLS", "ERRORS", "WARNINGS"])
+            .align(1, Align::Right)
+            .align(2, Align::Right)
+            .align(3, Align::Right);
+        for row in root_summaries {
+            table.row(row);
+        }
+        println!();
+        table.print();
+    }
+
     // Calculate exit code
     let total_errors: usize = results.iter().map(|(_, r)| r.errors.len()).sum();
     let total_warnings: usize = results.iter().map(|(_, r)| r.warnings.len()).sum();
 
-    let has_errors = parse_errors > 0 || total_errors > 0;
-    let has_strict_warnings = strict && total_warnings > 0;
+    Ok(args
+        .exit
+        .exit_code(parse_errors + total_errors, total_warnings, strict))
+}
+
+/// Apply the fixes the validator offers for the skills in `by_root`, or
+/// print them as diffs with `preview`.
+///
+/// Files that do not parse are left for the linter to report. Install
+/// receipts of skills renamed by the E003 fix follow the new name and
+/// directory.
+fn fix_skills(
+    by_root: &[(PathBuf, Vec<PathBuf>)],
+    validator: &Validator,
+    preview: bool,
+    quiet: bool,
+) -> Result<(), SkiloError> {
+    let project_root = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    for path in by_root.iter().flat_map(|(_, paths)| paths) {
+        let Ok(manifest) = Manifest::parse(path.clone()) else {
+            continue;
+        };
+        let fixes = validator.fixes(&manifest);
+        let changes = fix::plan(&fixes)?;
+        if changes.is_empty() {
+            continue;
+        }
 
-    if has_errors || has_strict_warnings {
-        Ok(1)
-    } else {
-        Ok(0)
+        if preview {
+            for change in &changes {
+                println!("{}", format!("--- {}", change.path.display()).dimmed());
+                print!("{}", change.diff());
+            }
+            continue;
+        }
+
+        fix::apply(&changes)?;
+        if fixes.iter().any(|f| f.code == DiagnosticCode::E003) {
+            if let Some(dir) = path.parent() {
+                let renamed = changes
+                    .iter()
+                    .find(|c| c.path == dir)
+                    .and_then(|c| c.renamed_to.clone())
+                    .unwrap_or_else(|| dir.to_path_buf());
+                let manifest = Manifest::parse(renamed.join("SKILL.md"))?;
+                lockfile::rename_receipts(
+                    dir,
+                    &renamed,
+                    &manifest.frontmatter.name,
+                    &project_root,
+                )?;
+            }
+        }
+        if !quiet {
+            for fix in &fixes {
+                eprintln!(
+                    "{} {}: {} ({})",
+                    "Fixed".green(),
+                    path.display(),
+                    fix.message,
+                    fix.code
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Read the files the diagnostics in `results` point at, keyed by their
+/// paths as `normalizer` will show them.
+///
+/// Files that cannot be read as text are left out; their diagnostics are
+/// shown without a snippet.
+fn read_sources(
+    results: &[(String, ValidationResult)],
+    normalizer: &PathNormalizer,
+) -> BTreeMap<String, String> {
+    let mut sources = BTreeMap::new();
+    let diagnostics = results
+        .iter()
+        .flat_map(|(_, r)| r.errors.iter().chain(&r.warnings))
+        .filter(|d| d.line.is_some());
+    for diag in diagnostics {
+        let key = normalizer.normalize(&diag.path);
+        if sources.contains_key(&key) {
+            continue;
+        }
+        if let Ok(source) = std::fs::read_to_string(&diag.path) {
+            sources.insert(key, source);
+        }
     }
+    sources
 }
@@ -1,17 +1,33 @@
 //! List installed skills.
 
 use crate::agent::Agent;
-use crate::cli::{AgentSelection, Cli, ListArgs};
+use crate::cache::{dir_size, find_duplicates, format_size, DuplicateFile, DUPLICATE_MIN_SIZE};
+use crate::cli::{AgentSelection, Cli, ListArgs, ListSort, OutputFormat, ScopeArg};
+use crate::commands::cache::print_duplicates;
 use crate::config::Config;
 use crate::error::SkiloError;
-use crate::output::get_formatter;
-use crate::scope::{list_skills, InstalledSkill, Scope};
+use crate::lockfile::{lock_path, LockedSkill, Lockfile};
+use crate::output::{csv_line, get_formatter, print_porcelain, Align, Table};
+use crate::scope::{list_skills, list_skills_from_path, InstalledSkill, Scope};
+use crate::skill::provenance::format_date;
+use crate::skill::{Discovery, Manifest, Validator};
 use colored::Colorize;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 
 /// Run the list command.
 ///
 /// Lists installed skills at project or global level.
-pub fn run(args: ListArgs, _config: &Config, cli: &Cli) -> Result<i32, SkiloError> {
+pub fn run(mut args: ListArgs, config: &Config, cli: &Cli) -> Result<i32, SkiloError> {
+    // Fold --scope into the --global/--all shorthands
+    match args.scope {
+        Some(ScopeArg::Project) => {}
+        Some(ScopeArg::Global) => args.global = true,
+        Some(ScopeArg::Both) => args.all = true,
+        None => {}
+    }
+
     let formatter = get_formatter(cli.format, cli.quiet);
     let project_root = args
         .path
@@ -25,30 +41,52 @@ pub fn run(args: ListArgs, _config: &Config, cli: &Cli) -> Result<i32, SkiloErro
         .map(|a| a.to_selection())
         .unwrap_or(AgentSelection::All);
 
+    let roots = workspace_roots(&args, selection, &project_root, config);
+
+    if args.du {
+        return run_disk_usage(&args, selection, &project_root, &roots, cli);
+    }
+
+    if cli.porcelain {
+        return run_porcelain(&args, selection, &project_root, &roots);
+    }
+
+    if matches!(cli.format, OutputFormat::Json) {
+        return run_json(&args, selection, &project_root, config);
+    }
+
+    if matches!(cli.format, OutputFormat::Table) {
+        return run_table(&args, selection, &project_root, &roots, formatter.as_ref());
+    }
+
+    if matches!(cli.format, OutputFormat::Csv) {
+        return run_csv(&args, selection, &project_root, &roots);
+    }
+
     // Handle --agent all (or default): iterate over all detected agents
-    if matches!(selection, AgentSelection::All) {
-        return run_for_all_agents(&args, &project_root, formatter.as_ref());
+    if matches!(selection, AgentSelection::All | AgentSelection::Generic) {
+        return run_for_all_agents(&args, selection, &project_root, &roots, formatter.as_ref());
     }
 
     // Single agent specified
     let agent: Agent = match selection {
         AgentSelection::Single(a) => a,
-        AgentSelection::All => unreachable!(), // handled above
+        AgentSelection::All | AgentSelection::Generic => unreachable!(), // handled above
     };
 
     // Collect skills based on flags (specific agent was requested)
     let (project_skills, global_skills) = if args.all {
         // List both project and global
-        let project = list_skills(agent, Scope::Project, &project_root);
-        let global = list_skills(agent, Scope::Global, &project_root);
+        let project = list_tagged(agent, Scope::Project, &project_root, &args.tag);
+        let global = list_tagged(agent, Scope::Global, &project_root, &args.tag);
         (project, global)
     } else if args.global {
         // List only global
-        let global = list_skills(agent, Scope::Global, &project_root);
+        let global = list_tagged(agent, Scope::Global, &project_root, &args.tag);
         (Vec::new(), global)
     } else {
         // List only project (default)
-        let project = list_skills(agent, Scope::Project, &project_root);
+        let project = list_tagged(agent, Scope::Project, &project_root, &args.tag);
         (project, Vec::new())
     };
 
@@ -102,16 +140,26 @@ pub fn run(args: ListArgs, _config: &Config, cli: &Cli) -> Result<i32, SkiloErro
     Ok(0)
 }
 
-/// Run the list command for all detected agents.
+/// Run the list command for all detected agents, or only the `skills/`
+/// directory for the generic target.
 fn run_for_all_agents(
     args: &ListArgs,
+    selection: AgentSelection,
     project_root: &std::path::Path,
+    roots: &[PathBuf],
     formatter: &dyn crate::output::OutputFormatter,
 ) -> Result<i32, SkiloError> {
-    let detected = Agent::detect_all(project_root);
+    let detected = match selection {
+        AgentSelection::All => Agent::detect_all(project_root),
+        _ => Vec::new(),
+    };
 
-    if detected.is_empty() {
-        formatter.format_message("No agents detected with installed skills.");
+    if detected.is_empty() && roots.is_empty() {
+        formatter.format_message(if selection == AgentSelection::Generic {
+            "No skills in skills/."
+        } else {
+            "No agents detected with installed skills."
+        });
         return Ok(0);
     }
 
@@ -129,7 +177,12 @@ fn run_for_all_agents(
             // Collect skills first to check if any exist
             let mut project_skills_by_agent = Vec::new();
             for detected_agent in &project_agents {
-                let skills = list_skills(detected_agent.agent, Scope::Project, project_root);
+                let skills = list_tagged(
+                    detected_agent.agent,
+                    Scope::Project,
+                    project_root,
+                    &args.tag,
+                );
                 if !skills.is_empty() {
                     project_skills_by_agent.push((detected_agent, skills));
                 }
@@ -148,7 +201,12 @@ fn run_for_all_agents(
                     );
                     for skill in &skills {
                         let description = truncate_description(&skill.description, 50);
-                        println!("    {}  {}", skill.name.cyan(), description);
+                        println!(
+                            "    {}  {}{}",
+                            skill.name.cyan(),
+                            description,
+                            deprecation_note(skill)
+                        );
                     }
                     total_skills += skills.len();
                 }
@@ -157,6 +215,34 @@ fn run_for_all_agents(
         }
     }
 
+    // Display skills in workspace roots from discovery.roots
+    let root_skills: Vec<_> = roots
+        .iter()
+        .map(|root| (root, list_root(root, &args.tag)))
+        .filter(|(_, skills)| !skills.is_empty())
+        .collect();
+    if !root_skills.is_empty() {
+        if !first {
+            println!();
+        }
+        println!("{}", "Workspace skills:".bold());
+        for (root, skills) in root_skills {
+            let root = root.strip_prefix(project_root).unwrap_or(root);
+            println!("  {}:", root.display().to_string().cyan());
+            for skill in &skills {
+                let description = truncate_description(&skill.description, 50);
+                println!(
+                    "    {}  {}{}",
+                    skill.name.cyan(),
+                    description,
+                    deprecation_note(skill)
+                );
+            }
+            total_skills += skills.len();
+        }
+        first = false;
+    }
+
     // Collect and display global-level skills
     if show_global {
         let global_agents: Vec<_> = detected.iter().filter(|d| d.is_global).collect();
@@ -164,7 +250,8 @@ fn run_for_all_agents(
             // Collect skills first to check if any exist
             let mut global_skills_by_agent = Vec::new();
             for detected_agent in &global_agents {
-                let skills = list_skills(detected_agent.agent, Scope::Global, project_root);
+                let skills =
+                    list_tagged(detected_agent.agent, Scope::Global, project_root, &args.tag);
                 if !skills.is_empty() {
                     global_skills_by_agent.push((detected_agent, skills));
                 }
@@ -183,7 +270,12 @@ fn run_for_all_agents(
                     );
                     for skill in &skills {
                         let description = truncate_description(&skill.description, 50);
-                        println!("    {}  {}", skill.name.cyan(), description);
+                        println!(
+                            "    {}  {}{}",
+                            skill.name.cyan(),
+                            description,
+                            deprecation_note(skill)
+                        );
                     }
                     total_skills += skills.len();
                 }
@@ -199,15 +291,502 @@ fn run_for_all_agents(
         } else {
             "in project"
         };
-        formatter.format_message(&format!(
-            "No skills installed {} for any detected agent.",
-            scope_desc
-        ));
+        if selection == AgentSelection::Generic {
+            formatter.format_message("No skills in skills/.");
+        } else {
+            formatter.format_message(&format!(
+                "No skills installed {} for any detected agent.",
+                scope_desc
+            ));
+        }
+    }
+
+    Ok(0)
+}
+
+/// Print installed skills as porcelain records.
+fn run_porcelain(
+    args: &ListArgs,
+    selection: AgentSelection,
+    project_root: &Path,
+    roots: &[PathBuf],
+) -> Result<i32, SkiloError> {
+    for skill in collect_skills(args, selection, project_root, roots) {
+        print_porcelain(&[
+            skill.scope.display_name(),
+            skill.agent.map(|a| a.cli_name()).unwrap_or_default(),
+            &skill.name,
+            &skill.path.display().to_string(),
+            &skill.description,
+        ]);
+    }
+
+    Ok(0)
+}
+
+/// An installed skill with its lockfile metadata, for table and JSON output.
+#[derive(Serialize)]
+struct ListEntry {
+    name: String,
+    description: String,
+    path: PathBuf,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    agent: Option<Agent>,
+    scope: Scope,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    deprecated: bool,
+    /// Where the skill was installed from, if it is tracked in a lockfile.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source: Option<SourceInfo>,
+    size: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    validation: Option<ValidationStatus>,
+}
+
+/// Lockfile metadata describing where a skill came from.
+#[derive(Serialize)]
+struct SourceInfo {
+    source: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    subdir: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    branch: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tag: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rev: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    commit: Option<String>,
+    checksum: String,
+    installed_at: u64,
+}
+
+impl From<&LockedSkill> for SourceInfo {
+    fn from(locked: &LockedSkill) -> Self {
+        Self {
+            source: locked.source.clone(),
+            subdir: locked.subdir.clone(),
+            branch: locked.branch.clone(),
+            tag: locked.tag.clone(),
+            rev: locked.rev.clone(),
+            commit: locked.commit.clone(),
+            checksum: locked.checksum.clone(),
+            installed_at: locked.installed_at,
+        }
+    }
+}
+
+/// Lint outcome for an installed skill.
+#[derive(Serialize)]
+struct ValidationStatus {
+    valid: bool,
+    errors: usize,
+    warnings: usize,
+}
+
+impl ValidationStatus {
+    /// Validate the skill in `skill_dir`. A SKILL.md that does not parse
+    /// counts as a single error.
+    fn check(skill_dir: &Path, validator: &Validator) -> Self {
+        match Manifest::parse(skill_dir.join("SKILL.md")) {
+            Ok(manifest) => {
+                let result = validator.validate(&manifest);
+                Self {
+                    valid: result.errors.is_empty(),
+                    errors: result.errors.len(),
+                    warnings: result.warnings.len(),
+                }
+            }
+            Err(_) => Self {
+                valid: false,
+                errors: 1,
+                warnings: 0,
+            },
+        }
+    }
+}
+
+/// Collect installed skills with lockfile metadata and size.
+fn collect_entries(
+    args: &ListArgs,
+    selection: AgentSelection,
+    project_root: &Path,
+    roots: &[PathBuf],
+) -> Result<Vec<ListEntry>, SkiloError> {
+    let project_lock = Lockfile::load_scope(Scope::Project, project_root)?;
+    let global_lock = Lockfile::load_scope(Scope::Global, project_root)?;
+
+    let mut entries: Vec<ListEntry> = collect_skills(args, selection, project_root, roots)
+        .into_iter()
+        .map(|skill| {
+            let lock = match skill.scope {
+                Scope::Project => &project_lock,
+                Scope::Global => &global_lock,
+            };
+            let locked = lock.find_path(&lock_path(skill.scope, &skill.path, project_root));
+            ListEntry {
+                source: locked.map(SourceInfo::from),
+                size: dir_size(&skill.path),
+                name: skill.name,
+                description: skill.description,
+                path: skill.path,
+                agent: skill.agent,
+                scope: skill.scope,
+                version: skill.version,
+                tags: skill.tags,
+                deprecated: skill.deprecated,
+                validation: None,
+            }
+        })
+        .collect();
+
+    sort_entries(&mut entries, args.sort);
+    Ok(entries)
+}
+
+/// Print installed skills as an aligned table.
+fn run_table(
+    args: &ListArgs,
+    selection: AgentSelection,
+    project_root: &Path,
+    roots: &[PathBuf],
+    formatter: &dyn crate::output::OutputFormatter,
+) -> Result<i32, SkiloError> {
+    let entries = collect_entries(args, selection, project_root, roots)?;
+
+    if entries.is_empty() {
+        formatter.format_message("No skills installed.");
+        return Ok(0);
+    }
+
+    let mut table = Table::new([
+        "Name",
+        "Agent",
+        "Scope",
+        "Version",
+        "Source",
+        "Size",
+        "Installed",
+    ])
+    .align(5, Align::Right);
+
+    for entry in &entries {
+        table.row([
+            entry.name.clone(),
+            entry
+                .agent
+                .map(|a| a.cli_name().to_string())
+                .unwrap_or_else(|| "-".into()),
+            entry.scope.display_name().to_string(),
+            entry.version.clone().unwrap_or_else(|| "-".into()),
+            entry
+                .source
+                .as_ref()
+                .map(|l| l.source.clone())
+                .unwrap_or_else(|| "-".into()),
+            format_size(entry.size),
+            entry
+                .source
+                .as_ref()
+                .map(|l| format_date(l.installed_at))
+                .unwrap_or_else(|| "-".into()),
+        ]);
+    }
+
+    print!("{}", table.render(""));
+    Ok(0)
+}
+
+/// Disk usage of the skills installed for one agent in one scope.
+#[derive(Serialize)]
+struct UsageEntry {
+    agent: Option<Agent>,
+    scope: Scope,
+    skills: usize,
+    size: u64,
+}
+
+/// Output of `list --du --format json`.
+#[derive(Serialize)]
+struct DiskUsage {
+    usage: Vec<UsageEntry>,
+    /// Size of all skills, counting directories shared by agents once.
+    total: u64,
+    duplicates: Vec<DuplicateFile>,
+}
+
+/// Print disk usage per agent and scope, and files duplicated across the
+/// installed skills.
+fn run_disk_usage(
+    args: &ListArgs,
+    selection: AgentSelection,
+    project_root: &Path,
+    roots: &[PathBuf],
+    cli: &Cli,
+) -> Result<i32, SkiloError> {
+    let entries = collect_entries(args, selection, project_root, roots)?;
+
+    let mut usage: Vec<UsageEntry> = Vec::new();
+    for entry in &entries {
+        match usage
+            .iter_mut()
+            .find(|u| u.agent == entry.agent && u.scope == entry.scope)
+        {
+            Some(u) => {
+                u.skills += 1;
+                u.size += entry.size;
+            }
+            None => usage.push(UsageEntry {
+                agent: entry.agent,
+                scope: entry.scope,
+                skills: 1,
+                size: entry.size,
+            }),
+        }
     }
+    usage.sort_by(|a, b| b.size.cmp(&a.size));
 
+    // Agents sharing a skills directory list the same skills
+    let mut seen = HashSet::new();
+    let mut total = 0;
+    let mut skill_dirs = Vec::new();
+    for entry in &entries {
+        if seen.insert(
+            entry
+                .path
+                .canonicalize()
+                .unwrap_or_else(|_| entry.path.clone()),
+        ) {
+            total += entry.size;
+            skill_dirs.push(entry.path.clone());
+        }
+    }
+    let duplicates = find_duplicates(&skill_dirs, DUPLICATE_MIN_SIZE);
+
+    if matches!(cli.format, OutputFormat::Json) {
+        let output = DiskUsage {
+            usage,
+            total,
+            duplicates,
+        };
+        let json = serde_json::to_string_pretty(&output)
+            .map_err(|e| SkiloError::Config(format!("JSON serialization failed: {}", e)))?;
+        println!("{}", json);
+        return Ok(0);
+    }
+
+    if usage.is_empty() {
+        println!("No skills installed.");
+        return Ok(0);
+    }
+
+    let mut table = Table::new(["Agent", "Scope", "Skills", "Size"])
+        .align(2, Align::Right)
+        .align(3, Align::Right);
+    for u in &usage {
+        table.row([
+            u.agent
+                .map(|a| a.display_name().to_string())
+                .unwrap_or_else(|| "skills/".into()),
+            u.scope.display_name().to_string(),
+            u.skills.to_string(),
+            format_size(u.size),
+        ]);
+    }
+    print!("{}", table.render(""));
+    println!("Total: {}", format_size(total).cyan());
+
+    if !duplicates.is_empty() {
+        println!();
+        print_duplicates(&duplicates, project_root);
+        println!(
+            "Keep shared assets in one skill, or run {} to find skills installed twice.",
+            "skilo dedupe".cyan()
+        );
+    }
+
+    Ok(0)
+}
+
+/// Print installed skills as CSV rows.
+fn run_csv(
+    args: &ListArgs,
+    selection: AgentSelection,
+    project_root: &Path,
+    roots: &[PathBuf],
+) -> Result<i32, SkiloError> {
+    let mut output = csv_line(&["name", "agent", "scope", "source", "version", "path"]);
+    for entry in collect_entries(args, selection, project_root, roots)? {
+        output.push_str(&csv_line(&[
+            entry.name.as_str(),
+            entry.agent.map(|a| a.cli_name()).unwrap_or_default(),
+            entry.scope.display_name(),
+            entry
+                .source
+                .as_ref()
+                .map(|s| s.source.as_str())
+                .unwrap_or_default(),
+            entry.version.as_deref().unwrap_or_default(),
+            &entry.path.display().to_string(),
+        ]));
+    }
+    print!("{}", output);
     Ok(0)
 }
 
+/// Print installed skills as JSON, including their lint status.
+fn run_json(
+    args: &ListArgs,
+    selection: AgentSelection,
+    project_root: &Path,
+    config: &Config,
+) -> Result<i32, SkiloError> {
+    println!("{}", entries_json(args, selection, project_root, config)?);
+    Ok(0)
+}
+
+/// The output of `list --format json` for `args`, for callers other than the CLI.
+pub(crate) fn installed_json(mut args: ListArgs, config: &Config) -> Result<String, SkiloError> {
+    match args.scope {
+        Some(ScopeArg::Global) => args.global = true,
+        Some(ScopeArg::Both) => args.all = true,
+        Some(ScopeArg::Project) | None => {}
+    }
+    let project_root = args
+        .path
+        .canonicalize()
+        .unwrap_or_else(|_| args.path.clone());
+    let selection = args
+        .agent
+        .as_ref()
+        .map(|a| a.to_selection())
+        .unwrap_or(AgentSelection::All);
+
+    entries_json(&args, selection, &project_root, config)
+}
+
+/// Installed skills with lockfile metadata and lint status, as a JSON array.
+fn entries_json(
+    args: &ListArgs,
+    selection: AgentSelection,
+    project_root: &Path,
+    config: &Config,
+) -> Result<String, SkiloError> {
+    let validator = Validator::new(&config.lint);
+    let roots = workspace_roots(args, selection, project_root, config);
+    let mut entries = collect_entries(args, selection, project_root, &roots)?;
+    for entry in &mut entries {
+        entry.validation = Some(ValidationStatus::check(&entry.path, &validator));
+    }
+
+    serde_json::to_string_pretty(&entries)
+        .map_err(|e| SkiloError::Config(format!("JSON serialization failed: {}", e)))
+}
+
+/// Sort entries by `--sort`; ties are broken by name.
+fn sort_entries(entries: &mut [ListEntry], sort: ListSort) {
+    let installed_at = |e: &ListEntry| e.source.as_ref().map(|l| l.installed_at);
+    entries.sort_by(|a, b| {
+        let primary = match sort {
+            ListSort::Name => std::cmp::Ordering::Equal,
+            ListSort::Size => b.size.cmp(&a.size),
+            ListSort::InstalledAt => installed_at(b).cmp(&installed_at(a)),
+        };
+        primary.then_with(|| a.name.cmp(&b.name))
+    });
+}
+
+/// Skill roots to list alongside agent skills: the generic `skills/`
+/// directory and roots from `discovery.roots`.
+///
+/// Roots are project skills, so they are only listed for the project scope
+/// and when no single agent is selected. The generic target lists only
+/// `skills/`.
+fn workspace_roots(
+    args: &ListArgs,
+    selection: AgentSelection,
+    project_root: &Path,
+    config: &Config,
+) -> Vec<PathBuf> {
+    if args.global || matches!(selection, AgentSelection::Single(_)) {
+        return Vec::new();
+    }
+
+    // Skip `skills/` when it is a detected agent's own directory (Clawdbot)
+    let generic = project_root.join("skills");
+    let listed_by_agent = selection == AgentSelection::All
+        && Agent::detect_all(project_root)
+            .iter()
+            .any(|d| !d.is_global && d.skills_path == generic);
+    let mut roots: Vec<PathBuf> = Some(generic)
+        .filter(|dir| dir.is_dir() && !listed_by_agent)
+        .into_iter()
+        .collect();
+    if selection == AgentSelection::All {
+        for root in Discovery::configured_roots(project_root, &config.discovery) {
+            if !roots.contains(&root) {
+                roots.push(root);
+            }
+        }
+    }
+    roots
+}
+
+/// Collect installed skills for the selected agents and scopes, and skills
+/// in workspace `roots`.
+fn collect_skills(
+    args: &ListArgs,
+    selection: AgentSelection,
+    project_root: &Path,
+    roots: &[PathBuf],
+) -> Vec<InstalledSkill> {
+    let scopes = if args.all {
+        vec![Scope::Project, Scope::Global]
+    } else if args.global {
+        vec![Scope::Global]
+    } else {
+        vec![Scope::Project]
+    };
+
+    let detected = match selection {
+        AgentSelection::All => Agent::detect_all(project_root),
+        AgentSelection::Generic | AgentSelection::Single(_) => Vec::new(),
+    };
+
+    let mut skills = Vec::new();
+    for scope in scopes {
+        let agents: Vec<Agent> = match selection {
+            AgentSelection::Single(agent) => vec![agent],
+            AgentSelection::Generic => Vec::new(),
+            AgentSelection::All => detected
+                .iter()
+                .filter(|d| d.is_global == scope.is_global())
+                .map(|d| d.agent)
+                .collect(),
+        };
+
+        for agent in agents {
+            skills.extend(list_tagged(agent, scope, project_root, &args.tag));
+        }
+    }
+    for root in roots {
+        skills.extend(list_root(root, &args.tag));
+    }
+    skills
+}
+
+/// List skills in a workspace root, filtered by tag.
+fn list_root(root: &Path, tags: &[String]) -> Vec<InstalledSkill> {
+    let mut skills = list_skills_from_path(root, None, Scope::Project);
+    if !tags.is_empty() {
+        skills.retain(|s| s.tags.iter().any(|t| tags.contains(t)));
+    }
+    skills
+}
+
 /// Print shadowed skills warning.
 fn print_shadowed_skills(project_skills: &[InstalledSkill], global_skills: &[InstalledSkill]) {
     let project_names: std::collections::HashSet<_> =
@@ -243,14 +822,42 @@ fn print_skills(skills: &[InstalledSkill]) {
     for skill in skills {
         let description = truncate_description(&skill.description, 50);
         println!(
-            "  {:<width$}  {}",
+            "  {:<width$}  {}{}",
             skill.name.cyan(),
             description,
+            deprecation_note(skill),
             width = max_name_len
         );
     }
 }
 
+/// List installed skills, keeping only those with one of `tags` (if any are given).
+fn list_tagged(
+    agent: Agent,
+    scope: Scope,
+    project_root: &Path,
+    tags: &[String],
+) -> Vec<InstalledSkill> {
+    let mut skills = list_skills(agent, scope, project_root);
+    if !tags.is_empty() {
+        skills.retain(|skill| skill.tags.iter().any(|t| tags.contains(t)));
+    }
+    skills
+}
+
+/// Describe a skill's deprecation status, or an empty string if not deprecated.
+fn deprecation_note(skill: &InstalledSkill) -> String {
+    if !skill.deprecated {
+        return String::new();
+    }
+
+    let note = match skill.superseded_by {
+        Some(ref replacement) => format!(" (deprecated, use {})", replacement),
+        None => " (deprecated)".to_string(),
+    };
+    note.yellow().to_string()
+}
+
 /// Truncate a description to a maximum length, adding ellipsis if needed.
 fn truncate_description(s: &str, max_len: usize) -> String {
     if s.is_empty() {
@@ -0,0 +1,473 @@
+//! Combine several skills into one.
+
+use crate::cli::{Cli, MergeArgs};
+use crate::commands::new::check_name;
+use crate::config::Config;
+use crate::error::SkiloError;
+use crate::output::{get_formatter, PathNormalizer, ReportOptions};
+use crate::skill::frontmatter::HookMatcher;
+use crate::skill::provenance::HEADER_PREFIX;
+use crate::skill::requirements::Requirements;
+use crate::skill::{Frontmatter, Manifest, Validator};
+use crate::templates::{split_title, to_title_case};
+use colored::Colorize;
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Longest description the specification allows.
+const MAX_DESCRIPTION_LEN: usize = 1024;
+
+/// A source skill and the files it brings along.
+struct Source {
+    /// The parsed SKILL.md.
+    manifest: Manifest,
+    /// The skill directory.
+    dir: PathBuf,
+    /// Paths of the other files, relative to `dir`, and where they go in the
+    /// merged skill.
+    files: Vec<(String, String)>,
+}
+
+/// Run the merge command.
+pub fn run(args: MergeArgs, config: &Config, cli: &Cli) -> Result<i32, SkiloError> {
+    let formatter = get_formatter(cli.format, cli.quiet);
+    check_name(&args.name)?;
+
+    let sources = args
+        .skills
+        .iter()
+        .map(|path| load_source(path))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let output_dir = match &args.output {
+        Some(output) => output.clone(),
+        None => sources[0]
+            .dir
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| PathBuf::from(".")),
+    };
+    let skill_dir = output_dir.join(&args.name);
+    if skill_dir.exists() {
+        return Err(SkiloError::SkillExists {
+            name: args.name,
+            path: skill_dir.display().to_string(),
+        });
+    }
+
+    let mut frontmatter = merge_frontmatter(&sources, &args.name);
+    if let Some(description) = args.description {
+        frontmatter.description = description;
+    } else if frontmatter.description.len() > MAX_DESCRIPTION_LEN {
+        return Err(SkiloError::Config(format!(
+            "Combined description is {} characters (max {}); pass --description",
+            frontmatter.description.len(),
+            MAX_DESCRIPTION_LEN
+        )));
+    }
+
+    let yaml = frontmatter
+        .to_yaml()
+        .map_err(|e| SkiloError::Config(format!("Failed to serialize frontmatter: {}", e)))?;
+    let content = format!(
+        "---\n{}---\n\n{}",
+        yaml,
+        merge_bodies(&sources, &to_title_case(&args.name))
+    );
+
+    if let Err(e) = write_skill(&skill_dir, &content, &sources) {
+        let _ = fs::remove_dir_all(&skill_dir);
+        return Err(e.into());
+    }
+
+    formatter.format_success(&format!(
+        "Merged {} skills into '{}' at {}",
+        sources.len(),
+        args.name,
+        skill_dir.display()
+    ));
+
+    // Validate the result
+    let skill_md = skill_dir.join("SKILL.md");
+    let manifest = Manifest::parse(skill_md.clone())?;
+    let validator = Validator::with_agents(&config.lint, &config.lint_agents());
+    let result = validator.validate(&manifest);
+    let errors = result.errors.len();
+
+    let mut results = [(skill_md.display().to_string(), result)];
+    PathNormalizer::new(cli.path_style).normalize_results(&mut results);
+    let output = formatter.format_report(
+        &results,
+        &ReportOptions {
+            group_by_rule: false,
+            max_diagnostics: None,
+            sources: BTreeMap::new(),
+        },
+    );
+    if !output.is_empty() {
+        print!("{}", output);
+    }
+
+    Ok(if errors > 0 { 1 } else { 0 })
+}
+
+/// Parse a skill from its directory or SKILL.md path.
+fn load_source(path: &Path) -> Result<Source, SkiloError> {
+    let (dir, skill_md) = if path.is_dir() {
+        (path.to_path_buf(), path.join("SKILL.md"))
+    } else {
+        let dir = path.parent().unwrap_or(Path::new(".")).to_path_buf();
+        (dir, path.to_path_buf())
+    };
+    let manifest = Manifest::parse(skill_md)?;
+
+    let mut files = Vec::new();
+    let walker = WalkDir::new(&dir)
+        .min_depth(1)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|e| !e.file_name().to_string_lossy().starts_with('.'));
+    for entry in walker {
+        let entry = entry.map_err(|e| SkiloError::Io(e.into()))?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let relative = entry.path().strip_prefix(&dir).unwrap_or(entry.path());
+        if relative == Path::new("SKILL.md") {
+            continue;
+        }
+        let relative = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        let target = prefixed_path(&relative, &manifest.frontmatter.name);
+        files.push((relative, target));
+    }
+
+    Ok(Source {
+        manifest,
+        dir,
+        files,
+    })
+}
+
+/// Where a file of skill `name` goes in the merged skill.
+///
+/// The name is prefixed to the entry inside the top-level directory, so
+/// `scripts/run.sh` becomes `scripts/<name>-run.sh`; files at the skill
+/// root are prefixed directly.
+fn prefixed_path(relative: &str, name: &str) -> String {
+    match relative.split_once('/') {
+        Some((dir, rest)) => format!("{}/{}-{}", dir, name, rest),
+        None => format!("{}-{}", name, relative),
+    }
+}
+
+/// Combine the frontmatter of the sources.
+///
+/// List-like fields are unioned in source order; metadata keeps the first
+/// value of each key. Other fields are kept only if every source agrees.
+fn merge_frontmatter(sources: &[Source], name: &str) -> Frontmatter {
+    let all: Vec<&Frontmatter> = sources.iter().map(|s| &s.manifest.frontmatter).collect();
+
+    let mut tools: Vec<&str> = Vec::new();
+    let mut tags: Vec<String> = Vec::new();
+    let mut examples: Vec<String> = Vec::new();
+    let mut metadata: BTreeMap<String, String> = BTreeMap::new();
+    let mut hooks: BTreeMap<String, Vec<HookMatcher>> = BTreeMap::new();
+    let mut requirements = Requirements::default();
+
+    for frontmatter in &all {
+        for tool in frontmatter
+            .allowed_tools
+            .iter()
+            .flat_map(|t| t.split_whitespace())
+        {
+            if !tools.contains(&tool) {
+                tools.push(tool);
+            }
+        }
+        for tag in frontmatter.tags() {
+            if !tags.contains(tag) {
+                tags.push(tag.clone());
+            }
+        }
+        for example in frontmatter.examples() {
+            if !examples.contains(example) {
+                examples.push(example.clone());
+            }
+        }
+        for (key, value) in frontmatter.metadata.iter().flatten() {
+            match metadata.get(key) {
+                Some(kept) if kept != value => eprintln!(
+                    "{}: '{}' sets metadata.{} to '{}'; keeping '{}'",
+                    "Warning".yellow(),
+                    frontmatter.name,
+                    key,
+                    value,
+                    kept
+                ),
+                Some(_) => {}
+                None => {
+                    metadata.insert(key.clone(), value.clone());
+                }
+            }
+        }
+        if let Some(Ok(requires)) = frontmatter.requires.as_ref().map(Requirements::parse) {
+            requirements.union(&requires);
+        }
+        for (event, matchers) in frontmatter.hooks.iter().flatten() {
+            hooks
+                .entry(event.clone())
+                .or_default()
+                .extend(matchers.iter().cloned());
+        }
+    }
+
+    Frontmatter {
+        name: name.to_string(),
+        description: all
+            .iter()
+            .map(|f| {
+                f.description
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect::<Vec<_>>()
+            .join(" "),
+        summary: None,
+        license: agree(&all, "license", |f| f.license.clone()),
+        compatibility: agree(&all, "compatibility", |f| f.compatibility.clone()),
+        requires: (!requirements.is_empty()).then(|| requirements.to_value()),
+        metadata: (!metadata.is_empty()).then_some(metadata),
+        allowed_tools: (!tools.is_empty()).then(|| tools.join(" ")),
+        tags: (!tags.is_empty()).then_some(tags),
+        examples: (!examples.is_empty()).then_some(examples),
+        deprecated: None,
+        superseded_by: None,
+        context: agree(&all, "context", |f| f.context.clone()),
+        agent: agree(&all, "agent", |f| f.agent.clone()),
+        model: agree(&all, "model", |f| f.model.clone()),
+        hooks: (!hooks.is_empty()).then_some(hooks),
+        user_invocable: agree(&all, "user-invocable", |f| f.user_invocable),
+        disable_model_invocation: agree(&all, "disable-model-invocation", |f| {
+            f.disable_model_invocation
+        }),
+        argument_hint: agree(&all, "argument-hint", |f| f.argument_hint.clone()),
+        agents: agree(&all, "agents", |f| f.agents.clone()),
+    }
+}
+
+/// The value of a field if every source sets it the same way.
+///
+/// Prints a warning and returns `None` when the sources disagree.
+fn agree<T: PartialEq>(
+    all: &[&Frontmatter],
+    key: &str,
+    field: impl Fn(&Frontmatter) -> Option<T>,
+) -> Option<T> {
+    let mut values = all.iter().map(|f| field(f));
+    let first = values.next()?;
+    if values.all(|v| v == first) {
+        return first;
+    }
+    eprintln!(
+        "{}: skills set different values for '{}'; leaving it unset",
+        "Warning".yellow(),
+        key
+    );
+    None
+}
+
+/// Build the merged body, with one `##` section per source.
+fn merge_bodies(sources: &[Source], title: &str) -> String {
+    let mut body = format!("# {}\n", title);
+
+    for source in sources {
+        let content = source.manifest.body.trim_start();
+        let content = match content.strip_prefix(HEADER_PREFIX) {
+            Some(rest) => rest.split_once("-->").map_or(rest, |(_, b)| b),
+            None => content,
+        };
+        let (heading, content) = split_title(content);
+        let heading = heading
+            .map(str::to_string)
+            .unwrap_or_else(|| to_title_case(&source.manifest.frontmatter.name));
+
+        let content = demote_headings(&relink(content, &source.files));
+        body.push_str(&format!("\n## {}\n", heading));
+        if !content.trim().is_empty() {
+            body.push_str(&format!("\n{}\n", content.trim()));
+        }
+    }
+
+    body
+}
+
+/// Rewrite mentions of a source's files to their merged paths.
+///
+/// A mention only counts if it is not part of a longer path.
+fn relink(content: &str, files: &[(String, String)]) -> String {
+    if files.is_empty() {
+        return content.to_string();
+    }
+
+    // Longest first, so a path wins over any path it starts with
+    let mut paths: Vec<&(String, String)> = files.iter().collect();
+    paths.sort_by_key(|(from, _)| std::cmp::Reverse(from.len()));
+    let pattern = paths
+        .iter()
+        .map(|(from, _)| regex::escape(from))
+        .collect::<Vec<_>>()
+        .join("|");
+    let re = Regex::new(&pattern).expect("escaped paths form a valid pattern");
+    let is_path_char = |c: char| c.is_alphanumeric() || matches!(c, '_' | '-' | '/' | '.');
+
+    let mut out = String::new();
+    let mut last = 0;
+    for m in re.find_iter(content) {
+        // A leading `./` is part of the mention; a trailing `.` may end a sentence
+        let before = content[..m.start()]
+            .trim_end_matches("./")
+            .chars()
+            .next_back();
+        let rest = &content[m.end()..];
+        let after = rest.strip_prefix('.').unwrap_or(rest).chars().next();
+        if before.is_some_and(is_path_char) || after.is_some_and(is_path_char) {
+            continue;
+        }
+        let target = &paths
+            .iter()
+            .find(|(from, _)| from == m.as_str())
+            .expect("match comes from the pattern")
+            .1;
+        out.push_str(&content[last..m.start()]);
+        out.push_str(target);
+        last = m.end();
+    }
+    out.push_str(&content[last..]);
+    out
+}
+
+/// Move every ATX heading outside code fences one level down.
+fn demote_headings(content: &str) -> String {
+    let mut out = String::new();
+    let mut fence: Option<&str> = None;
+
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        let marker = ["```", "~~~"].into_iter().find(|m| trimmed.starts_with(m));
+        match (fence, marker) {
+            (None, Some(marker)) => fence = Some(marker),
+            (Some(open), Some(marker)) if open == marker => fence = None,
+            _ => {}
+        }
+
+        let level = line.chars().take_while(|&c| c == '#').count();
+        let is_heading = fence.is_none()
+            && marker.is_none()
+            && (1..6).contains(&level)
+            && line[level..].starts_with(' ');
+        if is_heading {
+            out.push('#');
+        }
+        out.push_str(line);
+        out.push('\n');
+    }
+    out
+}
+
+/// Write the merged SKILL.md and copy the sources' files.
+fn write_skill(skill_dir: &Path, content: &str, sources: &[Source]) -> std::io::Result<()> {
+    fs::create_dir_all(skill_dir)?;
+    fs::write(skill_dir.join("SKILL.md"), content)?;
+
+    for source in sources {
+        for (from, to) in &source.files {
+            let target = skill_dir.join(to);
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::copy(source.dir.join(from), target)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_source(root: &Path, name: &str, frontmatter: &str, body: &str) -> PathBuf {
+        let dir = root.join(name);
+        fs::create_dir_all(dir.join("scripts")).unwrap();
+        fs::write(
+            dir.join("SKILL.md"),
+            format!(
+                "---\nname: {}\ndescription: The {} skill.\n{}---\n\n{}",
+                name, name, frontmatter, body
+            ),
+        )
+        .unwrap();
+        fs::write(dir.join("scripts/run.sh"), format!("echo {}\n", name)).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_merge_sources() {
+        let temp = TempDir::new().unwrap();
+        let first = write_source(
+            temp.path(),
+            "lint-py",
+            "allowed-tools: Bash Read\nmetadata:\n  owner: a\n",
+            "# Python Linting\n\nRun `scripts/run.sh`.\n\n## Steps\n\n```sh\n# not a heading\n```\n",
+        );
+        let second = write_source(
+            temp.path(),
+            "lint-js",
+            "allowed-tools: Read Write\nlicense: MIT\nmetadata:\n  owner: b\n  tier: \"1\"\n",
+            "Use ./scripts/run.sh, not scripts/run.sh.bak.\n",
+        );
+        let sources = vec![
+            load_source(&first).unwrap(),
+            load_source(&second.join("SKILL.md")).unwrap(),
+        ];
+        assert_eq!(
+            sources[0].files,
+            vec![(
+                "scripts/run.sh".to_string(),
+                "scripts/lint-py-run.sh".to_string()
+            )]
+        );
+
+        let frontmatter = merge_frontmatter(&sources, "lint");
+        assert_eq!(
+            frontmatter.description,
+            "The lint-py skill. The lint-js skill."
+        );
+        assert_eq!(
+            frontmatter.allowed_tools.as_deref(),
+            Some("Bash Read Write")
+        );
+        assert_eq!(frontmatter.license, None);
+        let metadata = frontmatter.metadata.unwrap();
+        assert_eq!(metadata["owner"], "a");
+        assert_eq!(metadata["tier"], "1");
+
+        assert_eq!(
+            merge_bodies(&sources, "Lint"),
+            "# Lint\n\n\
+             ## Python Linting\n\n\
+             Run `scripts/lint-py-run.sh`.\n\n\
+             ### Steps\n\n```sh\n# not a heading\n```\n\n\
+             ## Lint Js\n\n\
+             Use ./scripts/lint-js-run.sh, not scripts/run.sh.bak.\n"
+        );
+    }
+}
@@ -1,28 +1,77 @@
 //! Command implementations.
 
+/// The `activity` command implementation.
+pub mod activity;
 /// The `add` command implementation.
 pub mod add;
 /// The `agents` command implementation.
 pub mod agents;
+/// The `api` command implementation.
+pub mod api;
+/// The `bench` command implementation.
+pub mod bench;
+/// The `bundle` command implementation.
+pub mod bundle;
 /// The `cache` command implementation.
 pub mod cache;
 /// The `check` command implementation.
 pub mod check;
+/// The `ci` command implementation.
+pub mod ci;
+/// The `ci-check` command implementation.
+pub mod ci_check;
+/// The `clean` command implementation.
+pub mod clean;
 /// The `completions` command implementation.
 pub mod completions;
+/// The `dedupe` command implementation.
+pub mod dedupe;
+/// The `doctor` command implementation.
+pub mod doctor;
+/// The `env-check` command implementation.
+pub mod env_check;
+/// The `export` command implementation.
+pub mod export;
 /// The `fmt` command implementation.
 pub mod fmt;
+/// The `grep` command implementation.
+pub mod grep;
+/// The `import` command implementation.
+pub mod import;
+/// The `init` command implementation.
+pub mod init;
 /// The `lint` command implementation.
 pub mod lint;
 /// The `list` command implementation.
 pub mod list;
+/// The `merge` command implementation.
+pub mod merge;
 /// The `new` command implementation.
 pub mod new;
+/// The `permissions` command implementation.
+pub mod permissions;
+/// The `plugin` command implementation.
+pub mod plugin;
+/// The `preview` command implementation.
+pub mod preview;
 /// The `read-properties` command implementation.
 pub mod read_properties;
 /// The `remove` command implementation.
 pub mod remove;
 /// The `self update` command implementation.
 pub mod self_update;
+/// The `selftest` command implementation.
+pub mod selftest;
+/// The `serve` command implementation.
+#[cfg(feature = "serve")]
+pub mod serve;
+/// The `store` command implementation.
+pub mod store;
 /// The `to-prompt` command implementation.
 pub mod to_prompt;
+/// The `tree` command implementation.
+pub mod tree;
+/// The `vendor` command implementation.
+pub mod vendor;
+/// The `which` command implementation.
+pub mod which;
@@ -1,14 +1,23 @@
 //! Creates new skills from templates.
 
-use crate::cli::{Cli, NewArgs};
+use crate::cli::{Cli, NewArgs, OutputFormat};
+use crate::commands::add::copy_dir_all;
 use crate::config::Config;
 use crate::error::SkiloError;
-use crate::output::get_formatter;
+use crate::output::{get_formatter, OutputFormatter};
 use crate::scope::{ensure_skills_dir, Scope};
-use crate::templates::{get_template, TemplateContext};
+use crate::skill::{Formatter, FormatterConfig, Manifest, ValidationResult, Validator};
+use crate::templates::{
+    describe, get_template, split_title, to_kebab_case, to_title_case, PromptTemplate,
+    SkillTemplate, TemplateContext, BUILTIN_VARS,
+};
+use colored::Colorize;
+use dialoguer::Confirm;
 use once_cell::sync::Lazy;
 use regex::Regex;
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
 
 /// Pattern for valid skill names.
 static NAME_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^[a-z0-9]+(-[a-z0-9]+)*$").unwrap());
@@ -19,58 +28,346 @@ static NAME_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^[a-z0-9]+(-[a-z0-9]+
 pub fn run(args: NewArgs, config: &Config, cli: &Cli) -> Result<i32, SkiloError> {
     let formatter = get_formatter(cli.format, cli.quiet);
 
-    // Validate name
-    if !NAME_REGEX.is_match(&args.name) {
-        return Err(SkiloError::InvalidName(args.name));
-    }
+    let prompt = args.from_prompt.as_deref().map(read_prompt).transpose()?;
 
-    if args.name.len() > 64 {
-        return Err(SkiloError::InvalidName(format!(
-            "{} (name too long, max 64 chars)",
-            args.name
-        )));
-    }
+    let (name, mut title) = match (&args.name, &args.from_title) {
+        (Some(name), _) => (name.clone(), to_title_case(name)),
+        (None, Some(title)) => (
+            derive_name(title, args.yes || cli.quiet)?,
+            title.trim().to_string(),
+        ),
+        (None, None) => unreachable!("clap requires a name or --from-title"),
+    };
+
+    check_name(&name)?;
+    let vars = template_vars(&config.new.vars, &args.vars)?;
 
     // Determine output directory based on --output, --agent, --global flags
     let output_dir = resolve_output_dir(&args, config)?;
-    let skill_dir = output_dir.join(&args.name);
+    let skill_dir = output_dir.join(&name);
 
-    // Check if skill already exists
-    if skill_dir.exists() {
+    // Existing skills are only touched with --force or --merge
+    let existing = skill_dir.exists();
+    if existing && !args.force && !args.merge {
         return Err(SkiloError::SkillExists {
-            name: args.name,
+            name,
             path: skill_dir.display().to_string(),
         });
     }
+    if existing && args.force {
+        confirm_replace(&skill_dir, args.yes || cli.quiet)?;
+    }
+    let skill_md = skill_dir.join("SKILL.md");
+    let keep_skill_md = args.merge && skill_md.exists();
 
     // Get license (from args or config)
     let license = args.license.or_else(|| config.new.default_license.clone());
 
+    // A prompt's own `# Title` becomes the heading unless one was given
+    let mut prompt_body = None;
+    if let Some(prompt) = &prompt {
+        let (prompt_title, body) = split_title(prompt);
+        if let (Some(prompt_title), None) = (prompt_title, &args.from_title) {
+            title = prompt_title.to_string();
+        }
+        prompt_body = Some(body);
+    }
+
     // Build template context
     let ctx = TemplateContext {
-        name: args.name.clone(),
+        name: name.clone(),
+        title,
         description: args
             .description
-            .unwrap_or_else(|| format!("A {} skill.", args.name.replace('-', " "))),
+            .or_else(|| prompt_body.and_then(describe))
+            .unwrap_or_else(|| format!("A {} skill.", name.replace('-', " "))),
         license,
         lang: args.lang,
         include_optional_dirs: !args.no_optional_dirs,
         include_scripts: !args.no_scripts,
+        vars,
     };
 
     // Render template
-    let template = get_template(args.template);
-    template.render(&ctx, &output_dir)?;
+    let template: Box<dyn SkillTemplate> = match prompt_body {
+        Some(body) => {
+            let max_lines = config.lint.rules.body_length.resolve(500);
+            Box::new(PromptTemplate::new(
+                &ctx.expand(body),
+                max_lines.unwrap_or(usize::MAX),
+            ))
+        }
+        None => get_template(args.template),
+    };
+    let mut replaced = None;
+    let mut added = Vec::new();
+    if existing {
+        // Render next to the skill, then swap it in or copy what is missing
+        let staging = tempfile::Builder::new()
+            .prefix(".skilo-new-")
+            .tempdir_in(&output_dir)?;
+        template.render(&ctx, staging.path())?;
+        let rendered = staging.path().join(&name);
+        if args.force {
+            replaced = Some(back_up(&skill_dir, &name)?);
+            std::fs::rename(&rendered, &skill_dir)?;
+        } else {
+            added = add_missing(&rendered, &skill_dir)?;
+        }
+    } else {
+        template.render(&ctx, &output_dir)?;
+    }
 
-    formatter.format_success(&format!(
-        "Created skill '{}' at {}",
-        args.name,
-        skill_dir.display()
-    ));
+    let result = if keep_skill_md {
+        Validator::with_agents(&config.lint, &config.lint_agents())
+            .validate(&Manifest::parse(skill_md.clone())?)
+    } else {
+        format_and_validate(&skill_md, config)?
+    };
+    if !result.errors.is_empty() {
+        print!(
+            "{}",
+            formatter.format_validation(&[(skill_md.display().to_string(), result.clone())])
+        );
+        formatter.format_error(&format!(
+            "The generated skill at {} is invalid; check the template, variables, and description",
+            skill_dir.display()
+        ));
+        return Err(SkiloError::ValidationFailed(result.errors.len()));
+    }
+
+    if let Some(backup) = &replaced {
+        formatter.format_success(&format!(
+            "Replaced skill '{}' at {} (previous content backed up to {})",
+            name,
+            skill_dir.display(),
+            backup.display()
+        ));
+    } else if existing {
+        formatter.format_success(&format!(
+            "Added {} missing file(s) to skill '{}' at {}",
+            added.len(),
+            name,
+            skill_dir.display()
+        ));
+        for file in &added {
+            formatter.format_message(&format!("  + {}", file.display()));
+        }
+    } else {
+        formatter.format_success(&format!(
+            "Created skill '{}' at {}",
+            name,
+            skill_dir.display()
+        ));
+    }
+
+    if !result.warnings.is_empty() {
+        print!(
+            "{}",
+            formatter.format_validation(&[(skill_md.display().to_string(), result.clone())])
+        );
+    }
+    if matches!(cli.format, OutputFormat::Text | OutputFormat::Table) {
+        print_next_steps(formatter.as_ref(), &skill_dir, &result);
+    }
 
     Ok(0)
 }
 
+/// Ask before replacing an existing skill directory.
+///
+/// The prompt is skipped with `yes` or when stdin is not a terminal.
+fn confirm_replace(skill_dir: &Path, yes: bool) -> Result<(), SkiloError> {
+    if yes || !std::io::stdin().is_terminal() {
+        return Ok(());
+    }
+    if Confirm::new()
+        .with_prompt(format!(
+            "Replace {}? Its current content is backed up first",
+            skill_dir.display()
+        ))
+        .default(false)
+        .interact()
+        .map_err(|_| SkiloError::Cancelled)?
+    {
+        Ok(())
+    } else {
+        Err(SkiloError::Cancelled)
+    }
+}
+
+/// Move `skill_dir` to `~/.skilo/backups/<name>-<timestamp>` and return the
+/// backup's path.
+fn back_up(skill_dir: &Path, name: &str) -> Result<PathBuf, SkiloError> {
+    let backups = crate::cache::backups_dir()
+        .ok_or_else(|| SkiloError::Config("Cannot determine the backup directory".to_string()))?;
+    std::fs::create_dir_all(&backups)?;
+    let secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let mut backup = backups.join(format!("{}-{}", name, secs));
+    let mut n = 1;
+    while backup.exists() {
+        n += 1;
+        backup = backups.join(format!("{}-{}-{}", name, secs, n));
+    }
+
+    // Backups may be on another file system, where renaming fails
+    if std::fs::rename(skill_dir, &backup).is_err() {
+        copy_dir_all(skill_dir, &backup)?;
+        std::fs::remove_dir_all(skill_dir)?;
+    }
+    Ok(backup)
+}
+
+/// Copy the files of `rendered` that `skill_dir` lacks, returning their
+/// paths relative to the skill.
+fn add_missing(rendered: &Path, skill_dir: &Path) -> Result<Vec<PathBuf>, SkiloError> {
+    let mut added = Vec::new();
+    for entry in walkdir::WalkDir::new(rendered).sort_by_file_name() {
+        let entry = entry.map_err(std::io::Error::from)?;
+        let Ok(relative) = entry.path().strip_prefix(rendered) else {
+            continue;
+        };
+        let target = skill_dir.join(relative);
+        if entry.file_type().is_dir() {
+            std::fs::create_dir_all(&target)?;
+        } else if !target.exists() {
+            std::fs::copy(entry.path(), &target)?;
+            added.push(relative.to_path_buf());
+        }
+    }
+    Ok(added)
+}
+
+/// Format a generated SKILL.md in place and validate its skill.
+fn format_and_validate(path: &Path, config: &Config) -> Result<ValidationResult, SkiloError> {
+    let manifest = Manifest::parse(path.to_path_buf())?;
+    let formatted = Formatter::new(FormatterConfig::from(&config.fmt))
+        .format(&manifest)
+        .map_err(|e| SkiloError::Config(format!("Failed to format {}: {}", path.display(), e)))?;
+    if formatted != std::fs::read_to_string(path)? {
+        std::fs::write(path, formatted)?;
+    }
+
+    let manifest = Manifest::parse(path.to_path_buf())?;
+    Ok(Validator::with_agents(&config.lint, &config.lint_agents()).validate(&manifest))
+}
+
+/// Print what to do with a new skill, with its lint status.
+fn print_next_steps(formatter: &dyn OutputFormatter, skill_dir: &Path, result: &ValidationResult) {
+    let status = if result.warnings.is_empty() {
+        format!("{} no issues", "✓".green())
+    } else {
+        format!("{} {} warning(s)", "!".yellow(), result.warnings.len())
+    };
+    let dir = skill_dir.display();
+    formatter.format_message(&format!("  Lint: {}", status));
+    formatter.format_message("\nNext steps:");
+    formatter.format_message(&format!(
+        "  1. Describe when to use the skill in {}/SKILL.md",
+        dir
+    ));
+    formatter.format_message(&format!("  2. Check it with `skilo check {}`", dir));
+    formatter.format_message(&format!("  3. Try it in an agent with `skilo add {}`", dir));
+}
+
+/// Check that `name` is a valid skill name.
+pub(crate) fn check_name(name: &str) -> Result<(), SkiloError> {
+    if !NAME_REGEX.is_match(name) {
+        return Err(SkiloError::InvalidName(name.to_string()));
+    }
+
+    if name.len() > 64 {
+        return Err(SkiloError::InvalidName(format!(
+            "{} (name too long, max 64 chars)",
+            name
+        )));
+    }
+    Ok(())
+}
+
+/// Merge `[new.vars]` with `--var KEY=VALUE` arguments, which take
+/// precedence.
+fn template_vars(
+    configured: &BTreeMap<String, String>,
+    args: &[String],
+) -> Result<BTreeMap<String, String>, SkiloError> {
+    let mut vars = configured.clone();
+    for arg in args {
+        let (key, value) = arg.split_once('=').ok_or_else(|| {
+            SkiloError::Config(format!("Invalid --var '{}': expected KEY=VALUE", arg))
+        })?;
+        vars.insert(key.trim().to_string(), value.to_string());
+    }
+
+    for key in vars.keys() {
+        if BUILTIN_VARS.contains(&key.as_str()) {
+            return Err(SkiloError::Config(format!(
+                "Template variable '{}' is set from the skill and cannot be redefined",
+                key
+            )));
+        }
+        if key.is_empty()
+            || !key
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        {
+            return Err(SkiloError::Config(format!(
+                "Invalid template variable name '{}': use letters, digits, '_' and '-'",
+                key
+            )));
+        }
+    }
+    Ok(vars)
+}
+
+/// Read a prompt from a file, or from stdin for `-`.
+fn read_prompt(path: &Path) -> Result<String, SkiloError> {
+    let content = if path == Path::new("-") {
+        std::io::read_to_string(std::io::stdin())?
+    } else {
+        std::fs::read_to_string(path)?
+    };
+    if content.trim().is_empty() {
+        return Err(SkiloError::Config(format!(
+            "Prompt file {} is empty",
+            path.display()
+        )));
+    }
+    Ok(content)
+}
+
+/// Derive a skill name from a title and show it for confirmation.
+///
+/// The prompt is skipped with `yes` or when stdin is not a terminal.
+fn derive_name(title: &str, yes: bool) -> Result<String, SkiloError> {
+    let name = to_kebab_case(title);
+    if name.is_empty() {
+        return Err(SkiloError::InvalidName(format!(
+            "{} (no letters or digits to derive a name from)",
+            title
+        )));
+    }
+
+    if yes || !std::io::stdin().is_terminal() {
+        eprintln!("Derived name: {}", name.cyan());
+        return Ok(name);
+    }
+
+    if !Confirm::new()
+        .with_prompt(format!("Use the name '{}'?", name))
+        .default(true)
+        .interact()
+        .map_err(|_| SkiloError::Cancelled)?
+    {
+        return Err(SkiloError::Cancelled);
+    }
+
+    Ok(name)
+}
+
 /// Resolve the output directory based on CLI arguments.
 fn resolve_output_dir(args: &NewArgs, config: &Config) -> Result<PathBuf, SkiloError> {
     let project_root = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
@@ -84,17 +381,18 @@ fn resolve_output_dir(args: &NewArgs, config: &Config) -> Result<PathBuf, SkiloE
     let scope = if args.global {
         Scope::Global
     } else {
-        Scope::Project
+        config.add.default_scope
     };
 
     // Determine agent
     let agent: Option<crate::agent::Agent> = if let Some(ref cli_agent) = args.agent {
         match cli_agent.to_selection() {
             crate::cli::AgentSelection::Single(a) => Some(a),
-            crate::cli::AgentSelection::All => config.add.default_agent,
+            crate::cli::AgentSelection::Generic => None,
+            crate::cli::AgentSelection::All => config.add.primary_agent(),
         }
     } else {
-        config.add.default_agent
+        config.add.primary_agent()
     };
 
     // Ensure skills directory exists and return it
@@ -102,7 +400,7 @@ fn resolve_output_dir(args: &NewArgs, config: &Config) -> Result<PathBuf, SkiloE
         Some(agent) => ensure_skills_dir(agent, scope, &project_root)
             .map_err(|e| SkiloError::Config(format!("Failed to create skills directory: {}", e))),
         None => {
-            if args.global {
+            if scope.is_global() {
                 return Err(SkiloError::Config(
                     "Global installation requires an agent (use --agent)".to_string(),
                 ));
@@ -115,3 +413,53 @@ fn resolve_output_dir(args: &NewArgs, config: &Config) -> Result<PathBuf, SkiloE
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::{ScriptLang, Template};
+    use clap::ValueEnum;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_templates_produce_valid_skills() {
+        let config = Config::default();
+        for template in Template::value_variants() {
+            let temp = TempDir::new().unwrap();
+            let ctx = TemplateContext {
+                name: "demo".to_string(),
+                title: "Demo".to_string(),
+                description: "A demo skill.".to_string(),
+                license: Some("MIT".to_string()),
+                lang: ScriptLang::Python,
+                include_optional_dirs: true,
+                include_scripts: true,
+                vars: BTreeMap::new(),
+            };
+            get_template(*template).render(&ctx, temp.path()).unwrap();
+
+            let result = format_and_validate(&temp.path().join("demo/SKILL.md"), &config).unwrap();
+            assert!(result.errors.is_empty(), "{:?}", result.errors);
+        }
+    }
+
+    #[test]
+    fn test_add_missing() {
+        let rendered = TempDir::new().unwrap();
+        let skill = TempDir::new().unwrap();
+        std::fs::create_dir_all(rendered.path().join("scripts")).unwrap();
+        std::fs::write(rendered.path().join("SKILL.md"), "template").unwrap();
+        std::fs::write(rendered.path().join("scripts/run.py"), "template").unwrap();
+        std::fs::write(skill.path().join("SKILL.md"), "mine").unwrap();
+
+        let added = add_missing(rendered.path(), skill.path()).unwrap();
+        assert_eq!(added, vec![PathBuf::from("scripts/run.py")]);
+        assert_eq!(
+            std::fs::read_to_string(skill.path().join("SKILL.md")).unwrap(),
+            "mine"
+        );
+        assert!(add_missing(rendered.path(), skill.path())
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,145 @@
+//! Syncs Claude Code tool permissions with installed skills.
+
+use crate::agent::{expand_tilde, Agent};
+use crate::agent_config::sync_permissions;
+use crate::cli::{Cli, OutputFormat, PermissionsArgs};
+use crate::config::Config;
+use crate::error::SkiloError;
+use crate::scope::{list_skills, Scope};
+use crate::skill::Manifest;
+use colored::Colorize;
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// Name of the Claude Code settings file.
+const SETTINGS_FILE: &str = "settings.json";
+
+/// JSON output of the permissions command.
+#[derive(Serialize)]
+struct PermissionsOutput {
+    /// The settings file.
+    settings: PathBuf,
+    /// Every rule needed by installed skills, with the skills needing it.
+    rules: Vec<RuleEntry>,
+    /// Rules added to `permissions.allow`.
+    added: Vec<String>,
+    /// Managed rules removed from `permissions.allow`.
+    removed: Vec<String>,
+    /// Whether the settings file was written.
+    written: bool,
+}
+
+/// A permission rule and the skills needing it.
+#[derive(Serialize)]
+struct RuleEntry {
+    /// The rule, such as `Bash(git log:*)`.
+    rule: String,
+    /// Names of the skills listing it in `allowed-tools`.
+    skills: Vec<String>,
+}
+
+/// Run the permissions command.
+///
+/// Without `--write`, only reports the changes that would be made.
+pub fn run(args: PermissionsArgs, _config: &Config, cli: &Cli) -> Result<i32, SkiloError> {
+    let project_root = args
+        .path
+        .canonicalize()
+        .unwrap_or_else(|_| args.path.clone());
+    let scope = if args.global {
+        Scope::Global
+    } else {
+        Scope::Project
+    };
+
+    let settings = match (&args.settings, scope) {
+        (Some(settings), _) => settings.clone(),
+        (None, Scope::Project) => project_root
+            .join(Agent::Claude.detection_dir())
+            .join(SETTINGS_FILE),
+        (None, Scope::Global) => expand_tilde(Agent::Claude.global_detection_dir())
+            .ok_or_else(|| SkiloError::Config("Could not determine home directory".into()))?
+            .join(SETTINGS_FILE),
+    };
+
+    let mut skills = list_skills(Agent::Claude, scope, &project_root);
+    skills.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut rules: Vec<RuleEntry> = Vec::new();
+    for skill in &skills {
+        let Ok(manifest) = Manifest::parse(skill.path.join("SKILL.md")) else {
+            continue;
+        };
+        for rule in manifest.frontmatter.allowed_tool_list() {
+            match rules.iter_mut().find(|entry| entry.rule == rule) {
+                Some(entry) => entry.skills.push(skill.name.clone()),
+                None => rules.push(RuleEntry {
+                    rule,
+                    skills: vec![skill.name.clone()],
+                }),
+            }
+        }
+    }
+
+    let needed: Vec<String> = rules.iter().map(|entry| entry.rule.clone()).collect();
+    let sync = sync_permissions(&settings, &needed, !args.write)?;
+
+    if matches!(cli.format, OutputFormat::Json) {
+        let output = PermissionsOutput {
+            settings,
+            rules,
+            written: args.write && sync.is_changed(),
+            added: sync.added,
+            removed: sync.removed,
+        };
+        let json = serde_json::to_string_pretty(&output)
+            .map_err(|e| SkiloError::Config(format!("JSON serialization failed: {}", e)))?;
+        println!("{}", json);
+        return Ok(0);
+    }
+
+    if cli.quiet {
+        return Ok(0);
+    }
+
+    if !sync.is_changed() {
+        println!(
+            "{} Permissions in {} are up to date",
+            "✓".green(),
+            settings.display()
+        );
+        return Ok(0);
+    }
+
+    for rule in &sync.added {
+        let needed_by = rules
+            .iter()
+            .find(|entry| &entry.rule == rule)
+            .map(|entry| entry.skills.join(", "))
+            .unwrap_or_default();
+        println!(
+            "  {} {} {}",
+            "+".green(),
+            rule,
+            format!("({})", needed_by).dimmed()
+        );
+    }
+    for rule in &sync.removed {
+        println!("  {} {}", "-".red(), rule);
+    }
+
+    if args.write {
+        println!("{} Updated {}", "✓".green(), settings.display());
+        if let Some(backup) = &sync.backup {
+            println!("  Previous settings saved to {}", backup.display());
+        }
+    } else {
+        println!(
+            "Run with {} to update {}",
+            "--write".cyan(),
+            settings.display()
+        );
+    }
+
+    Ok(0)
+}
@@ -0,0 +1,388 @@
+//! Generates Claude Code plugin and marketplace manifests for a set of skills.
+//!
+//! A plugin is a directory with the skills under `skills/` and its metadata
+//! in `.claude-plugin/plugin.json`. A marketplace lists one or more plugins
+//! in `.claude-plugin/marketplace.json`.
+
+use crate::cli::{Cli, PluginArgs};
+use crate::commands::new::check_name;
+use crate::config::Config;
+use crate::error::SkiloError;
+use crate::output::{get_formatter, PathNormalizer, ReportOptions};
+use crate::skill::{Diagnostic, Discovery, Manifest, ValidationResult, Validator};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directory holding the plugin metadata.
+pub const PLUGIN_DIR: &str = ".claude-plugin";
+
+/// Longest plugin description accepted.
+const MAX_DESCRIPTION_LEN: usize = 1024;
+
+/// The contents of `plugin.json`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct PluginManifest {
+    /// Plugin name, in kebab-case.
+    pub name: String,
+    /// Plugin version.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// What the plugin provides.
+    #[serde(default)]
+    pub description: String,
+    /// Who maintains the plugin.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<Author>,
+    /// License identifier.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    /// Search keywords.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keywords: Vec<String>,
+    /// Fields skilo does not manage, kept as they are.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// A plugin or marketplace author.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Author {
+    /// Author name.
+    pub name: String,
+    /// Author email.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+}
+
+/// The contents of `marketplace.json`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Marketplace {
+    /// Marketplace name.
+    pub name: String,
+    /// Who maintains the marketplace.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<Author>,
+    /// The plugins listed.
+    #[serde(default)]
+    pub plugins: Vec<serde_json::Map<String, serde_json::Value>>,
+    /// Fields skilo does not manage, kept as they are.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl Marketplace {
+    /// Add or update the entry for `plugin`, keeping other fields of an
+    /// existing entry.
+    pub fn upsert(&mut self, plugin: &PluginManifest) {
+        let position = self
+            .plugins
+            .iter()
+            .position(|entry| entry.get("name").and_then(|n| n.as_str()) == Some(&plugin.name));
+        let entry = match position {
+            Some(i) => &mut self.plugins[i],
+            None => {
+                self.plugins.push(serde_json::Map::new());
+                self.plugins.last_mut().unwrap()
+            }
+        };
+
+        entry.insert("name".into(), plugin.name.clone().into());
+        entry
+            .entry("source")
+            .or_insert_with(|| serde_json::Value::from("./"));
+        entry.insert("description".into(), plugin.description.clone().into());
+        match &plugin.version {
+            Some(version) => entry.insert("version".into(), version.clone().into()),
+            None => entry.remove("version"),
+        };
+    }
+}
+
+/// Run the plugin command.
+///
+/// Returns 1 if a skill fails validation or the metadata breaks the
+/// marketplace constraints; nothing is written in that case.
+pub fn run(args: PluginArgs, config: &Config, cli: &Cli) -> Result<i32, SkiloError> {
+    let formatter = get_formatter(cli.format, cli.quiet);
+    let skills_dir = args.dir.join("skills");
+    let mut skill_paths = Discovery::find_skills(&skills_dir, &config.discovery);
+    skill_paths.sort();
+    if skill_paths.is_empty() {
+        return Err(SkiloError::NoSkillsFound {
+            path: skills_dir.display().to_string(),
+        });
+    }
+
+    // Skills outside skills/ are not picked up by Claude Code
+    for path in Discovery::find_skills(&args.dir, &config.discovery) {
+        if !path.starts_with(&skills_dir) && !path.starts_with(args.dir.join(PLUGIN_DIR)) {
+            eprintln!(
+                "{}: {} is outside {} and will not be part of the plugin",
+                "Warning".yellow(),
+                path.display(),
+                skills_dir.display()
+            );
+        }
+    }
+
+    // Validate the skills
+    let validator = Validator::with_agents(&config.lint, &config.lint_agents());
+    let mut results: Vec<(String, ValidationResult)> = Vec::new();
+    let mut manifests = Vec::new();
+    let mut problems = 0;
+    for path in &skill_paths {
+        match Manifest::parse(path.clone()) {
+            Ok(manifest) => {
+                results.push((path.display().to_string(), validator.validate(&manifest)));
+                manifests.push(manifest);
+            }
+            Err(e) => match Diagnostic::for_parse_error(path, &e) {
+                Some(diag) => results.push((
+                    path.display().to_string(),
+                    ValidationResult {
+                        errors: vec![diag],
+                        warnings: Vec::new(),
+                    },
+                )),
+                None => {
+                    problems += 1;
+                    formatter.format_error(&format!("{}: {}", path.display(), e));
+                }
+            },
+        }
+    }
+    problems += results.iter().map(|(_, r)| r.errors.len()).sum::<usize>();
+
+    PathNormalizer::new(cli.path_style).normalize_results(&mut results);
+    let output = formatter.format_report(
+        &results,
+        &ReportOptions {
+            group_by_rule: false,
+            max_diagnostics: None,
+            sources: BTreeMap::new(),
+        },
+    );
+    if !output.is_empty() {
+        print!("{}", output);
+    }
+
+    let mut seen: BTreeMap<&str, &Path> = BTreeMap::new();
+    for manifest in &manifests {
+        let name = manifest.frontmatter.name.as_str();
+        if let Some(first) = seen.insert(name, &manifest.path) {
+            problems += 1;
+            formatter.format_error(&format!(
+                "Skill name '{}' is used by both {} and {}",
+                name,
+                first.display(),
+                manifest.path.display()
+            ));
+        }
+    }
+
+    // Build plugin.json, on top of the existing one
+    let metadata_dir = args.dir.join(PLUGIN_DIR);
+    let plugin_path = metadata_dir.join("plugin.json");
+    let mut plugin: PluginManifest = read_json(&plugin_path)?.unwrap_or_default();
+
+    if let Some(name) = args.name {
+        plugin.name = name;
+    } else if plugin.name.is_empty() {
+        plugin.name = dir_name(&args.dir);
+    }
+    if let Err(e) = check_name(&plugin.name) {
+        problems += 1;
+        formatter.format_error(&format!("Plugin name: {}", e));
+    }
+
+    if let Some(description) = args.description {
+        plugin.description = description;
+    } else if plugin.description.is_empty() {
+        plugin.description = default_description(&manifests);
+    }
+    if plugin.description.trim().is_empty() {
+        problems += 1;
+        formatter.format_error("Plugin description is empty; pass --description");
+    } else if plugin.description.len() > MAX_DESCRIPTION_LEN {
+        problems += 1;
+        formatter.format_error(&format!(
+            "Plugin description is {} characters (max {}); pass --description",
+            plugin.description.len(),
+            MAX_DESCRIPTION_LEN
+        ));
+    }
+
+    if args.plugin_version.is_some() {
+        plugin.version = args.plugin_version;
+    }
+    if let Some(name) = args.author.or_else(git_user_name) {
+        match &mut plugin.author {
+            Some(author) => author.name = name,
+            None => plugin.author = Some(Author { name, email: None }),
+        }
+    }
+    if plugin.license.is_none() {
+        plugin.license = shared_license(&manifests);
+    }
+    plugin.keywords = keywords(&plugin.keywords, &manifests);
+
+    if problems > 0 {
+        formatter.format_error(&format!("{} problem(s) found; no files written", problems));
+        return Ok(1);
+    }
+    if args.check {
+        formatter.format_success(&format!(
+            "Plugin '{}' is ready to publish ({} skill(s))",
+            plugin.name,
+            manifests.len()
+        ));
+        return Ok(0);
+    }
+
+    fs::create_dir_all(&metadata_dir)?;
+    write_json(&plugin_path, &plugin)?;
+    formatter.format_success(&format!(
+        "Wrote {} ({} skill(s))",
+        plugin_path.display(),
+        manifests.len()
+    ));
+
+    if args.marketplace {
+        let marketplace_path = metadata_dir.join("marketplace.json");
+        let mut marketplace: Marketplace = read_json(&marketplace_path)?.unwrap_or_default();
+        if marketplace.name.is_empty() {
+            marketplace.name = plugin.name.clone();
+        }
+        if marketplace.owner.is_none() {
+            marketplace.owner = plugin.author.clone();
+        }
+        marketplace.upsert(&plugin);
+        write_json(&marketplace_path, &marketplace)?;
+        formatter.format_success(&format!("Wrote {}", marketplace_path.display()));
+    }
+
+    Ok(0)
+}
+
+/// Read a JSON file, or `None` if it does not exist.
+fn read_json<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<Option<T>, SkiloError> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(path)?;
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| SkiloError::Config(format!("{}: {}", path.display(), e)))
+}
+
+/// Write a value as pretty-printed JSON with a trailing newline.
+fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<(), SkiloError> {
+    let json = serde_json::to_string_pretty(value)
+        .map_err(|e| SkiloError::Config(format!("JSON serialization failed: {}", e)))?;
+    fs::write(path, json + "\n")?;
+    Ok(())
+}
+
+/// The final component of `dir`, resolving `.` and the like.
+fn dir_name(dir: &Path) -> String {
+    dir.canonicalize()
+        .unwrap_or_else(|_| PathBuf::from(dir))
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// A description for the plugin: the skill's own for a single skill,
+/// otherwise a list of the skills.
+fn default_description(manifests: &[Manifest]) -> String {
+    match manifests {
+        [only] => only.frontmatter.description.trim().to_string(),
+        _ => {
+            let names: Vec<&str> = manifests
+                .iter()
+                .map(|m| m.frontmatter.name.as_str())
+                .collect();
+            format!("Skills: {}", names.join(", "))
+        }
+    }
+}
+
+/// The license every skill declares, if they agree.
+fn shared_license(manifests: &[Manifest]) -> Option<String> {
+    let licenses: BTreeSet<Option<&str>> = manifests
+        .iter()
+        .map(|m| m.frontmatter.license.as_deref())
+        .collect();
+    match licenses.into_iter().collect::<Vec<_>>().as_slice() {
+        [Some(license)] => Some(license.to_string()),
+        _ => None,
+    }
+}
+
+/// Existing keywords plus the tags of every skill, deduplicated.
+fn keywords(existing: &[String], manifests: &[Manifest]) -> Vec<String> {
+    let mut keywords = existing.to_vec();
+    let tags = manifests
+        .iter()
+        .flat_map(|m| m.frontmatter.tags.iter().flatten());
+    for tag in tags {
+        if !keywords.contains(tag) {
+            keywords.push(tag.clone());
+        }
+    }
+    keywords
+}
+
+/// The user name from the git configuration.
+fn git_user_name() -> Option<String> {
+    git2::Config::open_default()
+        .ok()?
+        .get_string("user.name")
+        .ok()
+        .filter(|name| !name.trim().is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_keeps_unknown_fields() {
+        let plugin: PluginManifest = serde_json::from_str(
+            r#"{"name": "demo", "description": "Old", "commands": ["./cmd"], "keywords": ["a"]}"#,
+        )
+        .unwrap();
+        let json = serde_json::to_value(&plugin).unwrap();
+        assert_eq!(json["commands"][0], "./cmd");
+        assert_eq!(json["keywords"][0], "a");
+        assert!(json.get("author").is_none());
+
+        let mut marketplace: Marketplace = serde_json::from_str(
+            r#"{"name": "market", "plugins": [{"name": "demo", "source": "./demo", "category": "dev"}]}"#,
+        )
+        .unwrap();
+        let updated = PluginManifest {
+            name: "demo".into(),
+            description: "New".into(),
+            version: Some("1.0.0".into()),
+            ..Default::default()
+        };
+        marketplace.upsert(&updated);
+        marketplace.upsert(&PluginManifest {
+            name: "other".into(),
+            description: "Other".into(),
+            ..Default::default()
+        });
+
+        assert_eq!(marketplace.plugins.len(), 2);
+        let entry = &marketplace.plugins[0];
+        assert_eq!(entry["source"], "./demo");
+        assert_eq!(entry["category"], "dev");
+        assert_eq!(entry["description"], "New");
+        assert_eq!(entry["version"], "1.0.0");
+        assert_eq!(marketplace.plugins[1]["source"], "./");
+    }
+}
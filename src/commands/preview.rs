@@ -0,0 +1,366 @@
+//! Renders a skill as styled markdown in the terminal.
+
+use crate::cli::{Cli, PreviewArgs};
+use crate::config::Config;
+use crate::error::SkiloError;
+use crate::output::Table;
+use crate::skill::discovery::Discovery;
+use crate::skill::{Frontmatter, Manifest};
+use colored::Colorize;
+use comrak::nodes::{AstNode, ListType, NodeValue};
+use comrak::{parse_document, Arena, Options};
+use std::path::{Path, PathBuf};
+
+/// Widest the text is wrapped to, even on very wide terminals.
+const MAX_WIDTH: usize = 100;
+
+/// Run the preview command.
+pub fn run(args: PreviewArgs, config: &Config, _cli: &Cli) -> Result<i32, SkiloError> {
+    let skills = Discovery::find_skills_in(std::slice::from_ref(&args.path), &config.discovery);
+
+    let skill_md = match skills.as_slice() {
+        [] => {
+            return Err(SkiloError::NoSkillsFound {
+                path: args.path.display().to_string(),
+            })
+        }
+        [one] => one,
+        many => {
+            return Err(SkiloError::Config(format!(
+                "{} contains {} skills; pass the path of a single skill",
+                args.path.display(),
+                many.len()
+            )))
+        }
+    };
+
+    let manifest = Manifest::parse(skill_md.clone())?;
+    let width = args
+        .width
+        .or_else(|| terminal_size::terminal_size().map(|(w, _)| w.0 as usize))
+        .unwrap_or(80)
+        .clamp(20, MAX_WIDTH);
+
+    print!("{}", render_summary(&manifest.frontmatter, width));
+    println!();
+    print!("{}", render_markdown(&manifest.body, width));
+
+    if let Some(reference) = &args.reference {
+        let skill_dir = skill_md.parent().unwrap_or(Path::new("."));
+        let path = resolve_reference(skill_dir, reference).ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("reference '{}' not found", reference.display()),
+            )
+        })?;
+        let content = std::fs::read_to_string(&path)?;
+
+        println!();
+        println!("{}", "─".repeat(width).dimmed());
+        println!("{}", path.display().to_string().dimmed());
+        println!();
+        print!("{}", render_markdown(&content, width));
+    }
+
+    Ok(0)
+}
+
+/// Find a reference document relative to the skill or its references/ directory.
+fn resolve_reference(skill_dir: &Path, reference: &Path) -> Option<PathBuf> {
+    [
+        reference.to_path_buf(),
+        skill_dir.join(reference),
+        skill_dir.join("references").join(reference),
+    ]
+    .into_iter()
+    .find(|p| p.is_file())
+}
+
+/// Render the frontmatter as a short header block.
+fn render_summary(frontmatter: &Frontmatter, width: usize) -> String {
+    let mut out = String::new();
+
+    let mut title = frontmatter.name.bold().to_string();
+    if let Some(version) = frontmatter.metadata.as_ref().and_then(|m| m.get("version")) {
+        title.push_str(&format!(" {}", format!("v{}", version).dimmed()));
+    }
+    if frontmatter.is_deprecated() {
+        title.push_str(&format!(" {}", "deprecated".yellow()));
+    }
+    out.push_str(&title);
+    out.push('\n');
+
+    for line in textwrap::wrap(&frontmatter.description, width) {
+        out.push_str(&line);
+        out.push('\n');
+    }
+
+    let mut fields: Vec<(&str, String)> = Vec::new();
+    let optional = [
+        ("license", &frontmatter.license),
+        ("compatibility", &frontmatter.compatibility),
+        ("allowed-tools", &frontmatter.allowed_tools),
+        ("context", &frontmatter.context),
+        ("agent", &frontmatter.agent),
+        ("model", &frontmatter.model),
+        ("superseded-by", &frontmatter.superseded_by),
+    ];
+    for (key, value) in optional {
+        if let Some(value) = value {
+            fields.push((key, value.clone()));
+        }
+    }
+    if !frontmatter.tags().is_empty() {
+        fields.push(("tags", frontmatter.tags().join(", ")));
+    }
+    for (i, example) in frontmatter.examples().iter().enumerate() {
+        let key = if i == 0 { "examples" } else { "" };
+        fields.push((key, format!("\"{}\"", example.trim())));
+    }
+    if frontmatter.has_hooks() {
+        let events: Vec<&str> = frontmatter
+            .hooks
+            .iter()
+            .flatten()
+            .map(|(e, _)| e.as_str())
+            .collect();
+        fields.push(("hooks", events.join(", ")));
+    }
+    if let Some(metadata) = &frontmatter.metadata {
+        let mut entries: Vec<_> = metadata.iter().filter(|(k, _)| *k != "version").collect();
+        entries.sort();
+        for (key, value) in entries {
+            fields.push((key, value.clone()));
+        }
+    }
+
+    if !fields.is_empty() {
+        out.push('\n');
+        let label_width = fields.iter().map(|(k, _)| k.len()).max().unwrap_or(0);
+        for (key, value) in fields {
+            out.push_str(&format!(
+                "  {}  {}\n",
+                format!("{:<w$}", key, w = label_width).dimmed(),
+                value
+            ));
+        }
+    }
+
+    out.push_str(&"─".repeat(width).dimmed().to_string());
+    out.push('\n');
+    out
+}
+
+/// Render markdown with terminal styling, wrapped to `width` columns.
+fn render_markdown(markdown: &str, width: usize) -> String {
+    let arena = Arena::new();
+    let mut options = Options::default();
+    options.extension.table = true;
+    options.extension.strikethrough = true;
+    options.extension.tasklist = true;
+    options.extension.footnotes = true;
+
+    let root = parse_document(&arena, markdown, &options);
+    let mut out = String::new();
+    for line in render_blocks(root, width) {
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+/// Render the block children of `node` as lines, separated by blank lines.
+fn render_blocks<'a>(node: &'a AstNode<'a>, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    for child in node.children() {
+        let block = render_block(child, width);
+        if block.is_empty() {
+            continue;
+        }
+        if !lines.is_empty() {
+            lines.push(String::new());
+        }
+        lines.extend(block);
+    }
+    lines
+}
+
+fn render_block<'a>(node: &'a AstNode<'a>, width: usize) -> Vec<String> {
+    let value = node.data.borrow().value.clone();
+    match value {
+        NodeValue::Heading(heading) => {
+            let text = inline(node, false);
+            let styled = match heading.level {
+                1 => text.to_uppercase().bold().underline(),
+                2 => text.bold().underline(),
+                _ => text.bold(),
+            };
+            vec![styled.to_string()]
+        }
+        NodeValue::Paragraph => wrap(&inline(node, true), width),
+        NodeValue::List(list) => render_list(node, list.list_type, list.start, list.tight, width),
+        NodeValue::BlockQuote => prefix(render_blocks(node, width.saturating_sub(2)), "│ "),
+        NodeValue::CodeBlock(code) => {
+            let mut lines = Vec::new();
+            if !code.info.is_empty() {
+                lines.push(format!("    {}", code.info.dimmed()));
+            }
+            for line in code.literal.lines() {
+                lines.push(format!("    {}", line.cyan()));
+            }
+            lines
+        }
+        NodeValue::HtmlBlock(html) => html
+            .literal
+            .lines()
+            .map(|l| l.dimmed().to_string())
+            .collect(),
+        NodeValue::ThematicBreak => vec!["─".repeat(width.min(40)).dimmed().to_string()],
+        NodeValue::Table(_) => render_table(node),
+        NodeValue::FootnoteDefinition(footnote) => {
+            let label = format!("[^{}]: ", footnote.name);
+            let body = render_blocks(node, width.saturating_sub(label.len()));
+            hang(body, &label.dimmed().to_string(), label.len())
+        }
+        _ => render_blocks(node, width),
+    }
+}
+
+fn render_list<'a>(
+    node: &'a AstNode<'a>,
+    list_type: ListType,
+    start: usize,
+    tight: bool,
+    width: usize,
+) -> Vec<String> {
+    let mut lines = Vec::new();
+    for (i, item) in node.children().enumerate() {
+        let marker = match &item.data.borrow().value {
+            NodeValue::TaskItem(task) if task.symbol.is_some() => "☑".green().to_string(),
+            NodeValue::TaskItem(_) => "☐".to_string(),
+            _ if list_type == ListType::Ordered => format!("{}.", start + i),
+            _ => "•".to_string(),
+        };
+        let indent = colored_len(&marker) + 1;
+        let body = render_blocks(item, width.saturating_sub(indent));
+
+        if !tight && !lines.is_empty() {
+            lines.push(String::new());
+        }
+        lines.extend(hang(body, &format!("{} ", marker), indent));
+    }
+    lines
+}
+
+fn render_table<'a>(node: &'a AstNode<'a>) -> Vec<String> {
+    let mut rows = node.children().map(|row| {
+        row.children()
+            .map(|cell| inline(cell, false))
+            .collect::<Vec<_>>()
+    });
+
+    let Some(headers) = rows.next() else {
+        return Vec::new();
+    };
+    let mut table = Table::new(headers);
+    for row in rows {
+        table.row(row);
+    }
+    table.render("").lines().map(str::to_string).collect()
+}
+
+/// Flatten inline content to a string, optionally with terminal styling.
+fn inline<'a>(node: &'a AstNode<'a>, styled: bool) -> String {
+    let mut out = String::new();
+    for child in node.children() {
+        let value = child.data.borrow().value.clone();
+        let text = match value {
+            NodeValue::Text(text) => text.to_string(),
+            NodeValue::SoftBreak => " ".to_string(),
+            NodeValue::LineBreak => "\n".to_string(),
+            NodeValue::Code(code) if styled => code.literal.cyan().to_string(),
+            NodeValue::Code(code) => code.literal,
+            NodeValue::HtmlInline(html) if styled => html.dimmed().to_string(),
+            NodeValue::HtmlInline(html) => html,
+            NodeValue::Emph if styled => inline(child, styled).italic().to_string(),
+            NodeValue::Strong if styled => inline(child, styled).bold().to_string(),
+            NodeValue::Strikethrough if styled => inline(child, styled).strikethrough().to_string(),
+            NodeValue::Link(link) if styled => {
+                let text = inline(child, styled);
+                if text == link.url {
+                    text.underline().to_string()
+                } else {
+                    format!(
+                        "{} {}",
+                        text.underline(),
+                        format!("({})", link.url).dimmed()
+                    )
+                }
+            }
+            NodeValue::Image(_) => format!("[image: {}]", inline(child, false)),
+            NodeValue::FootnoteReference(footnote) => format!("[^{}]", footnote.name),
+            _ => inline(child, styled),
+        };
+        out.push_str(&text);
+    }
+    out
+}
+
+/// Wrap styled text to `width` columns, keeping explicit line breaks.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    textwrap::wrap(text, width.max(1))
+        .into_iter()
+        .map(|l| l.into_owned())
+        .collect()
+}
+
+/// Prefix every line, including blank ones.
+fn prefix(lines: Vec<String>, prefix: &str) -> Vec<String> {
+    let prefix = prefix.dimmed().to_string();
+    lines
+        .into_iter()
+        .map(|l| format!("{}{}", prefix, l))
+        .collect()
+}
+
+/// Put `first` before the first line and indent the rest by `indent` spaces.
+fn hang(lines: Vec<String>, first: &str, indent: usize) -> Vec<String> {
+    if lines.is_empty() {
+        return vec![first.to_string()];
+    }
+    let pad = " ".repeat(indent);
+    lines
+        .into_iter()
+        .enumerate()
+        .map(|(i, l)| match (i, l.is_empty()) {
+            (0, _) => format!("{}{}", first, l),
+            (_, true) => l,
+            _ => format!("{}{}", pad, l),
+        })
+        .collect()
+}
+
+/// Display width of a string, ignoring ANSI escape sequences.
+fn colored_len(s: &str) -> usize {
+    textwrap::core::display_width(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_markdown_plain() {
+        let markdown = "# Title\n\nSome *text* with `code`.\n\n- [x] done\n- [ ] todo\n\n1. first\n2. second\n\n> quoted\n\n| A | B |\n|---|---|\n| 1 | 2 |\n";
+
+        let ansi = regex::Regex::new("\x1b\\[[0-9;]*m").unwrap();
+        let rendered = ansi
+            .replace_all(&render_markdown(markdown, 40), "")
+            .to_string();
+
+        assert_eq!(
+            rendered,
+            "TITLE\n\nSome text with code.\n\n☑ done\n☐ todo\n\n1. first\n2. second\n\n│ quoted\n\nA  B\n----\n1  2\n"
+        );
+    }
+}
@@ -3,10 +3,13 @@
 use crate::cli::{Cli, ReadPropertiesArgs};
 use crate::config::Config;
 use crate::error::SkiloError;
-use crate::skill::{Discovery, Manifest};
+use crate::output::csv_line;
+use crate::skill::rules::{estimate_tokens, reference_tokens};
+use crate::skill::{Discovery, Manifest, Outline};
 use serde::Serialize;
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
 
 /// JSON output structure for a single skill's properties.
 #[derive(Serialize)]
@@ -27,12 +30,32 @@ pub struct SkillProperties {
 
     /// Additional metadata key-value pairs
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub metadata: Option<HashMap<String, String>>,
+    pub metadata: Option<BTreeMap<String, String>>,
 
     /// Pre-approved tools (space-delimited string)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub allowed_tools: Option<String>,
 
+    /// Tags for grouping skills by capability
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+
+    /// Example user requests the skill handles
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub examples: Option<Vec<String>>,
+
+    /// Number of lines in the body
+    pub body_lines: usize,
+
+    /// Estimated tokens in the body
+    pub body_tokens: usize,
+
+    /// Estimated tokens across files in references/
+    pub reference_tokens: usize,
+
+    /// Headings and mentioned scripts and references in the body
+    pub outline: Outline,
+
     /// Path to the SKILL.md file
     pub path: PathBuf,
 }
@@ -46,6 +69,12 @@ impl From<&Manifest> for SkillProperties {
             compatibility: manifest.frontmatter.compatibility.clone(),
             metadata: manifest.frontmatter.metadata.clone(),
             allowed_tools: manifest.frontmatter.allowed_tools.clone(),
+            tags: manifest.frontmatter.tags.clone(),
+            examples: manifest.frontmatter.examples.clone(),
+            body_lines: manifest.body_line_count(),
+            body_tokens: estimate_tokens(&manifest.body),
+            reference_tokens: manifest.path.parent().map_or(0, reference_tokens),
+            outline: manifest.outline(),
             path: manifest.path.clone(),
         }
     }
@@ -56,12 +85,7 @@ impl From<&Manifest> for SkillProperties {
 /// Outputs JSON with skill metadata from frontmatter.
 pub fn run(args: ReadPropertiesArgs, config: &Config, cli: &Cli) -> Result<i32, SkiloError> {
     // Collect all skill paths from all input paths
-    let mut all_skill_paths: Vec<PathBuf> = Vec::new();
-
-    for path in &args.paths {
-        let paths = Discovery::find_skills(path, &config.discovery.ignore);
-        all_skill_paths.extend(paths);
-    }
+    let all_skill_paths = Discovery::find_skills_in(&args.paths, &config.discovery);
 
     if all_skill_paths.is_empty() {
         return Err(SkiloError::NoSkillsFound {
@@ -94,8 +118,31 @@ pub fn run(args: ReadPropertiesArgs, config: &Config, cli: &Cli) -> Result<i32,
         eprintln!("Error: {}", error);
     }
 
+    let report = MetadataReport::new(&properties, &args.require);
+    if !args.aggregate {
+        for missing in &report.missing {
+            eprintln!(
+                "Error: {}: missing metadata {}",
+                missing.path.display(),
+                missing.keys.join(", ")
+            );
+        }
+    }
+
+    let csv_to_stdout = args.csv.as_deref() == Some(Path::new("-"));
+    if let Some(csv_path) = &args.csv {
+        let csv = metadata_csv(&properties, &report);
+        if csv_to_stdout {
+            print!("{}", csv);
+        } else {
+            fs::write(csv_path, csv)?;
+        }
+    }
+
     // Output JSON (always JSON for this command, ignoring --format)
-    let output = if properties.len() == 1 {
+    let output = if args.aggregate {
+        serde_json::to_string_pretty(&report)
+    } else if properties.len() == 1 {
         // Single skill: output object directly
         serde_json::to_string_pretty(&properties[0])
     } else {
@@ -105,7 +152,7 @@ pub fn run(args: ReadPropertiesArgs, config: &Config, cli: &Cli) -> Result<i32,
 
     match output {
         Ok(json) => {
-            if !cli.quiet {
+            if !cli.quiet && !csv_to_stdout {
                 println!("{}", json);
             }
         }
@@ -117,10 +164,188 @@ pub fn run(args: ReadPropertiesArgs, config: &Config, cli: &Cli) -> Result<i32,
         }
     }
 
-    // Return error code if there were parsing failures
-    if errors.is_empty() {
+    // Return error code if there were parsing failures or missing keys
+    if errors.is_empty() && report.missing.is_empty() {
         Ok(0)
     } else {
         Ok(1)
     }
 }
+
+/// How often a metadata key is set across skills.
+#[derive(Debug, Serialize)]
+pub struct KeyUsage {
+    /// The metadata key.
+    pub key: String,
+    /// Number of skills that set the key.
+    pub skills: usize,
+    /// Fraction of skills that set the key, from 0 to 1.
+    pub coverage: f64,
+    /// Number of skills per value.
+    pub values: BTreeMap<String, usize>,
+}
+
+/// A skill that lacks required metadata keys.
+#[derive(Debug, Serialize)]
+pub struct MissingKeys {
+    /// Name of the skill.
+    pub name: String,
+    /// Path to the SKILL.md file.
+    pub path: PathBuf,
+    /// The required keys it does not set.
+    pub keys: Vec<String>,
+}
+
+/// Metadata usage across a set of skills.
+#[derive(Debug, Serialize)]
+pub struct MetadataReport {
+    /// Number of skills examined.
+    pub skills: usize,
+    /// Every key used or required, most widely set first.
+    pub keys: Vec<KeyUsage>,
+    /// Skills missing required keys.
+    pub missing: Vec<MissingKeys>,
+}
+
+impl MetadataReport {
+    /// Aggregate the metadata of `properties`, checking for `required` keys.
+    pub fn new(properties: &[SkillProperties], required: &[String]) -> Self {
+        let mut usage: BTreeMap<&str, BTreeMap<String, usize>> = required
+            .iter()
+            .map(|key| (key.as_str(), BTreeMap::new()))
+            .collect();
+        for (key, value) in properties.iter().flat_map(|p| p.metadata.iter().flatten()) {
+            *usage
+                .entry(key.as_str())
+                .or_default()
+                .entry(value.clone())
+                .or_default() += 1;
+        }
+
+        let mut keys: Vec<KeyUsage> = usage
+            .into_iter()
+            .map(|(key, values)| {
+                let skills = values.values().sum();
+                KeyUsage {
+                    key: key.to_string(),
+                    skills,
+                    coverage: if properties.is_empty() {
+                        0.0
+                    } else {
+                        skills as f64 / properties.len() as f64
+                    },
+                    values,
+                }
+            })
+            .collect();
+        keys.sort_by(|a, b| b.skills.cmp(&a.skills).then_with(|| a.key.cmp(&b.key)));
+
+        let missing = properties
+            .iter()
+            .filter_map(|p| {
+                let keys: Vec<String> = required
+                    .iter()
+                    .filter(|key| !p.metadata.as_ref().is_some_and(|m| m.contains_key(*key)))
+                    .cloned()
+                    .collect();
+                (!keys.is_empty()).then(|| MissingKeys {
+                    name: p.name.clone(),
+                    path: p.path.clone(),
+                    keys,
+                })
+            })
+            .collect();
+
+        Self {
+            skills: properties.len(),
+            keys,
+            missing,
+        }
+    }
+}
+
+/// One CSV row per skill: its name, path, the value of every key in
+/// `report`, and the required keys it lacks (separated by `;`).
+fn metadata_csv(properties: &[SkillProperties], report: &MetadataReport) -> String {
+    let mut header = vec!["name".to_string(), "path".to_string()];
+    header.extend(report.keys.iter().map(|k| k.key.clone()));
+    header.push("missing".to_string());
+
+    let mut rows = vec![header];
+    for p in properties {
+        let mut row = vec![p.name.clone(), p.path.display().to_string()];
+        row.extend(report.keys.iter().map(|k| {
+            p.metadata
+                .as_ref()
+                .and_then(|m| m.get(&k.key))
+                .cloned()
+                .unwrap_or_default()
+        }));
+        let missing = report
+            .missing
+            .iter()
+            .find(|m| m.path == p.path)
+            .map(|m| m.keys.join(";"))
+            .unwrap_or_default();
+        row.push(missing);
+        rows.push(row);
+    }
+
+    rows.iter().map(|row| csv_line(row)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn properties(name: &str, metadata: &str) -> SkillProperties {
+        let content = format!(
+            "---\nname: {}\ndescription: Test\nmetadata:\n{}---\n\nBody\n",
+            name, metadata
+        );
+        let manifest =
+            Manifest::parse_content(PathBuf::from(format!("{}/SKILL.md", name)), &content).unwrap();
+        SkillProperties::from(&manifest)
+    }
+
+    #[test]
+    fn test_metadata_report() {
+        let skills = [
+            properties("a", "  owner: team-a\n  tier: gold\n"),
+            properties("b", "  owner: team-a\n"),
+            properties("c", "  note: \"x, y\"\n"),
+        ];
+        let report = MetadataReport::new(&skills, &["owner".into(), "reviewed".into()]);
+
+        let keys: Vec<(&str, usize)> = report
+            .keys
+            .iter()
+            .map(|k| (k.key.as_str(), k.skills))
+            .collect();
+        assert_eq!(
+            keys,
+            [("owner", 2), ("note", 1), ("tier", 1), ("reviewed", 0)]
+        );
+        assert_eq!(report.keys[0].values["team-a"], 2);
+
+        let missing: Vec<(&str, Vec<String>)> = report
+            .missing
+            .iter()
+            .map(|m| (m.name.as_str(), m.keys.clone()))
+            .collect();
+        assert_eq!(
+            missing,
+            [
+                ("a", vec!["reviewed".to_string()]),
+                ("b", vec!["reviewed".to_string()]),
+                ("c", vec!["owner".to_string(), "reviewed".to_string()]),
+            ]
+        );
+
+        let csv = metadata_csv(&skills, &report);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "name,path,owner,note,tier,reviewed,missing");
+        assert_eq!(lines[1], "a,a/SKILL.md,team-a,,gold,,reviewed");
+        assert_eq!(lines[3], "c,c/SKILL.md,,\"x, y\",,,owner;reviewed");
+    }
+}
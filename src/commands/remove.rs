@@ -1,72 +1,133 @@
 //! Remove installed skills.
 
 use crate::agent::Agent;
-use crate::cli::{Cli, RemoveArgs};
+use crate::cli::{AgentSelection, Cli, RemoveArgs, ScopeArg};
 use crate::config::Config;
 use crate::error::SkiloError;
+use crate::history::{self, Action};
+use crate::hooks::{self, HookEvent, HookSkill};
+use crate::lockfile::{lock_path, Lockfile};
 use crate::output::get_formatter;
 use crate::scope::Scope;
 use colored::Colorize;
 use dialoguer::Confirm;
-use std::path::PathBuf;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 
-/// Run the remove command.
-pub fn run(args: RemoveArgs, config: &Config, cli: &Cli) -> Result<i32, SkiloError> {
-    let formatter = get_formatter(cli.format, cli.quiet);
-    let project_root = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+/// A skills directory to remove skills from.
+struct RemoveTarget {
+    agent: Option<Agent>,
+    scope: Scope,
+    path: PathBuf,
+}
 
-    // Determine scope
-    let scope = if args.global {
-        Scope::Global
-    } else {
-        Scope::Project
-    };
+impl RemoveTarget {
+    /// Name of the target for progress and summary messages.
+    fn display_name(&self) -> String {
+        match self.agent {
+            Some(agent) if self.scope.is_global() => format!("{} (global)", agent.display_name()),
+            Some(agent) => agent.display_name().to_string(),
+            None => "skills/".to_string(),
+        }
+    }
+}
 
-    // Determine agent (None means use ./skills/)
-    let agent: Option<Agent> = match args.agent.as_ref().map(|a| a.to_selection()) {
-        Some(crate::cli::AgentSelection::Single(a)) => Some(a),
-        Some(crate::cli::AgentSelection::All) => config.add.default_agent,
-        None => config.add.default_agent,
-    };
+/// Resolve the skills directories to remove from, mirroring `add`.
+///
+/// `--agent all` covers every agent and, in project scope, `./skills/`.
+/// Without `--agent` the configured default agents are used. Directories
+/// shared by several agents are listed once.
+fn resolve_targets(
+    args: &RemoveArgs,
+    config: &Config,
+    project_root: &Path,
+) -> Result<Vec<RemoveTarget>, SkiloError> {
+    let scopes = ScopeArg::resolve(args.scope, args.global, config.add.default_scope);
 
-    // Resolve skills directory
-    let skills_dir = match agent {
-        Some(agent) => match scope {
-            Scope::Global => agent.resolve_global_skills_dir().ok_or_else(|| {
-                SkiloError::Config("Could not determine global skills directory".to_string())
-            })?,
-            Scope::Project => agent.resolve_project_skills_dir(&project_root),
-        },
-        None => {
-            if args.global {
-                return Err(SkiloError::Config(
-                    "Global removal requires an agent (use --agent)".to_string(),
-                ));
+    let mut include_local = false;
+    let mut agents: Vec<Agent> = match &args.agent {
+        Some(cli_agents) => {
+            let mut resolved = Vec::new();
+            for cli_agent in cli_agents {
+                match cli_agent.to_selection() {
+                    AgentSelection::All => {
+                        resolved.extend(Agent::all());
+                        include_local = true;
+                    }
+                    AgentSelection::Generic => include_local = true,
+                    AgentSelection::Single(agent) => resolved.push(agent),
+                }
             }
-            project_root.join("skills")
+            resolved
         }
+        None => config.add.default_agent_list(),
     };
+    let mut seen = HashSet::new();
+    agents.retain(|a| seen.insert(*a));
+
+    let mut targets = Vec::new();
+    for scope in scopes {
+        if agents.is_empty() && scope.is_global() {
+            return Err(SkiloError::Config(if include_local {
+                "The generic target has no global directory".to_string()
+            } else {
+                "Global removal requires an agent (use --agent)".to_string()
+            }));
+        }
+        for &agent in &agents {
+            if let Some(path) = scope.resolve_skills_dir(agent, project_root) {
+                targets.push(RemoveTarget {
+                    agent: Some(agent),
+                    scope,
+                    path,
+                });
+            }
+        }
+        if (agents.is_empty() || include_local) && !scope.is_global() {
+            targets.push(RemoveTarget {
+                agent: None,
+                scope,
+                path: project_root.join("skills"),
+            });
+        }
+    }
+
+    let mut seen = HashSet::new();
+    targets.retain(|t| t.path.exists() && seen.insert(t.path.canonicalize().ok()));
+    Ok(targets)
+}
 
-    if !skills_dir.exists() {
-        let target = agent
-            .map(|a| a.display_name().to_string())
-            .unwrap_or_else(|| "skills/".to_string());
-        formatter.format_error(&format!("Skills directory does not exist for {}", target));
+/// Run the remove command.
+pub fn run(args: RemoveArgs, config: &Config, cli: &Cli) -> Result<i32, SkiloError> {
+    let formatter = get_formatter(cli.format, cli.quiet);
+    let project_root = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+
+    let targets = resolve_targets(&args, config, &project_root)?;
+    if targets.is_empty() {
+        formatter.format_error("No skills directory exists for the selected agents");
         return Ok(1);
     }
 
     // Find skills to remove
-    let mut to_remove: Vec<(String, PathBuf)> = Vec::new();
+    // (index into targets, skill name, skill path)
+    let mut to_remove: Vec<(usize, String, PathBuf)> = Vec::new();
     let mut not_found: Vec<String> = Vec::new();
 
     for skill_name in &args.skills {
-        let skill_path = skills_dir.join(skill_name);
-        if skill_path.exists() && skill_path.join("SKILL.md").exists() {
-            to_remove.push((skill_name.clone(), skill_path));
-        } else {
+        let mut found = false;
+        for (index, target) in targets.iter().enumerate() {
+            let skill_path = target.path.join(skill_name);
+            if skill_path.exists() && skill_path.join("SKILL.md").exists() {
+                to_remove.push((index, skill_name.clone(), skill_path));
+                found = true;
+            }
+        }
+        if !found {
             not_found.push(skill_name.clone());
         }
     }
+    // Keep each target's skills together for reporting
+    to_remove.sort_by_key(|(index, _, _)| *index);
 
     // Report not found skills
     if !not_found.is_empty() && !cli.quiet {
@@ -84,7 +145,7 @@ pub fn run(args: RemoveArgs, config: &Config, cli: &Cli) -> Result<i32, SkiloErr
     if !args.yes {
         println!();
         println!("Skills to remove:");
-        for (name, path) in &to_remove {
+        for (_, name, path) in &to_remove {
             println!(
                 "  {} ({})",
                 name.cyan(),
@@ -109,35 +170,80 @@ pub fn run(args: RemoveArgs, config: &Config, cli: &Cli) -> Result<i32, SkiloErr
         println!();
     }
 
-    // Remove skills
-    let mut removed = 0;
-    for (name, path) in &to_remove {
-        if !cli.quiet {
-            print!("Removing {}...", name.cyan());
+    let affected: Vec<HookSkill> = to_remove
+        .iter()
+        .map(|(index, name, path)| HookSkill {
+            name: name.clone(),
+            path: path.clone(),
+            scope: targets[*index].scope,
+            agent: targets[*index].agent,
+        })
+        .collect();
+    hooks::run(&config.hooks, HookEvent::PreRemove, &affected, None)?;
+
+    // Remove skills, reporting per target
+    let mut removed_skills = Vec::new();
+    let mut target_count = 0;
+    for (index, target) in targets.iter().enumerate() {
+        let entries: Vec<_> = to_remove
+            .iter()
+            .zip(&affected)
+            .filter(|((i, _, _), _)| *i == index)
+            .collect();
+        if entries.is_empty() {
+            continue;
         }
+        target_count += 1;
 
-        match std::fs::remove_dir_all(path) {
-            Ok(()) => {
-                removed += 1;
-                if !cli.quiet {
-                    println!(" {}", "done".green());
-                }
+        if !cli.quiet && targets.len() > 1 {
+            println!("Removing from {}...", target.display_name().cyan());
+        }
+
+        let mut removed = 0;
+        for ((_, name, path), skill) in entries {
+            if !cli.quiet {
+                print!("Removing {}...", name.cyan());
             }
-            Err(e) => {
-                if !cli.quiet {
-                    println!(" {}", "failed".red());
+
+            match std::fs::remove_dir_all(path) {
+                Ok(()) => {
+                    removed += 1;
+                    removed_skills.push(skill.clone());
+                    forget_install(target.scope, path, &project_root)?;
+                    if !cli.quiet {
+                        println!(" {}", "done".green());
+                    }
+                }
+                Err(e) => {
+                    if !cli.quiet {
+                        println!(" {}", "failed".red());
+                    }
+                    formatter.format_error(&format!("Failed to remove '{}': {}", name, e));
                 }
-                formatter.format_error(&format!("Failed to remove '{}': {}", name, e));
             }
         }
+
+        if !cli.quiet {
+            formatter.format_success(&format!(
+                "Removed {} skill{} from {}/",
+                removed,
+                if removed == 1 { "" } else { "s" },
+                target.path.display()
+            ));
+        }
     }
 
-    if !cli.quiet {
+    history::record(&config.history, Action::Remove, &removed_skills, None);
+
+    let removed = removed_skills.len();
+    if !cli.quiet && target_count > 1 {
         println!();
         formatter.format_success(&format!(
-            "Removed {} skill{}",
+            "Total: {} skill{} removed from {} target{}",
             removed,
-            if removed == 1 { "" } else { "s" }
+            if removed == 1 { "" } else { "s" },
+            target_count,
+            if target_count == 1 { "" } else { "s" }
         ));
     }
 
@@ -147,3 +253,65 @@ pub fn run(args: RemoveArgs, config: &Config, cli: &Cli) -> Result<i32, SkiloErr
         Ok(1)
     }
 }
+
+/// Drop a removed skill from the lockfile of its scope.
+pub(crate) fn forget_install(
+    scope: Scope,
+    path: &Path,
+    project_root: &Path,
+) -> Result<(), SkiloError> {
+    let Some(lock_file) = Lockfile::path_for(scope, project_root) else {
+        return Ok(());
+    };
+    if !lock_file.exists() {
+        return Ok(());
+    }
+
+    let mut lock = Lockfile::load(&lock_file)?;
+    if lock.remove_path(&lock_path(scope, path, project_root)) {
+        lock.save(&lock_file)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_resolve_targets_all_agents() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        let claude = Scope::Project
+            .resolve_skills_dir(Agent::Claude, root)
+            .unwrap();
+        let cursor = Scope::Project
+            .resolve_skills_dir(Agent::Cursor, root)
+            .unwrap();
+        for dir in [&claude, &cursor, &root.join("skills")] {
+            std::fs::create_dir_all(dir).unwrap();
+        }
+
+        let mut args = RemoveArgs {
+            skills: vec!["my-skill".into()],
+            global: false,
+            scope: Some(ScopeArg::Project),
+            agent: Some(vec![crate::cli::Agent::All]),
+            yes: true,
+        };
+        let config = Config::default();
+
+        let targets = resolve_targets(&args, &config, root).unwrap();
+        let paths: Vec<&PathBuf> = targets.iter().map(|t| &t.path).collect();
+        assert_eq!(paths.len(), 3);
+        assert!(paths.contains(&&claude));
+        assert!(paths.contains(&&cursor));
+        assert!(paths.contains(&&root.join("skills")));
+
+        args.agent = Some(vec![crate::cli::Agent::Claude, crate::cli::Agent::Cursor]);
+        let targets = resolve_targets(&args, &config, root).unwrap();
+        assert_eq!(targets.len(), 2);
+        assert!(targets.iter().all(|t| t.agent.is_some()));
+    }
+}
@@ -12,6 +12,11 @@ use std::io::{self, Read, Write};
 const GITHUB_API_URL: &str = "https://api.github.com/repos/manuelmauro/skilo/releases/latest";
 const USER_AGENT: &str = concat!("skilo/", env!("CARGO_PKG_VERSION"));
 
+/// Temporary file the new binary is written to, next to the executable.
+pub(crate) const UPDATE_TEMP_NAME: &str = ".skilo-update-tmp";
+/// Backup of the previous binary, kept until the update completes.
+pub(crate) const UPDATE_BACKUP_NAME: &str = ".skilo-backup";
+
 /// GitHub release response structure.
 #[derive(Debug, Deserialize)]
 struct GitHubRelease {
@@ -214,8 +219,8 @@ fn replace_binary(new_binary: &[u8]) -> Result<()> {
         ))
     })?;
 
-    let temp_path = exe_dir.join(".skilo-update-tmp");
-    let backup_path = exe_dir.join(".skilo-backup");
+    let temp_path = exe_dir.join(UPDATE_TEMP_NAME);
+    let backup_path = exe_dir.join(UPDATE_BACKUP_NAME);
 
     // Write new binary to temp file
     {
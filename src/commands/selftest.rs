@@ -0,0 +1,67 @@
+//! Runs golden tests for lint rules.
+
+use crate::cli::{Cli, SelftestArgs};
+use crate::config::Config;
+use crate::error::SkiloError;
+use crate::output::get_formatter;
+use crate::skill::golden::{find_cases, run_case, EXPECTED_FILE};
+use crate::skill::minimal_diff;
+use colored::Colorize;
+
+/// Run the selftest command.
+///
+/// Returns 0 if every case matches its expected diagnostics, 1 otherwise.
+/// With `--bless`, mismatching cases are updated and 0 is returned.
+pub fn run(args: SelftestArgs, _config: &Config, cli: &Cli) -> Result<i32, SkiloError> {
+    let formatter = get_formatter(cli.format, cli.quiet);
+
+    let cases = find_cases(&args.dir)?;
+    if cases.is_empty() {
+        return Err(SkiloError::Config(format!(
+            "No test cases found in {}",
+            args.dir.display()
+        )));
+    }
+
+    let mut failed = 0;
+    for case in &cases {
+        let result = run_case(case)?;
+        if result.passed() {
+            formatter.format_message(&format!("{} {}", "✓".green(), case.display()));
+            continue;
+        }
+
+        if args.bless {
+            result.bless()?;
+            formatter.format_message(&format!(
+                "{} Updated {}",
+                "✓".green(),
+                case.join(EXPECTED_FILE).display()
+            ));
+            continue;
+        }
+
+        failed += 1;
+        formatter.format_message(&format!("{} {}", "✗".red(), case.display()));
+        match &result.expected {
+            Some(expected) => {
+                if let Some(diff) = minimal_diff(expected, &result.actual) {
+                    formatter.format_message(diff.trim_end());
+                }
+            }
+            None => formatter.format_message(&format!("  missing {}", EXPECTED_FILE)),
+        }
+    }
+
+    if failed > 0 {
+        formatter.format_error(&format!(
+            "{} of {} case(s) failed; run with --bless to accept the new output",
+            failed,
+            cases.len()
+        ));
+        return Ok(1);
+    }
+
+    formatter.format_success(&format!("{} case(s) passed", cases.len()));
+    Ok(0)
+}
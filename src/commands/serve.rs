@@ -0,0 +1,494 @@
+//! Local web UI and REST API for managing installed skills.
+//!
+//! Requests are handled one at a time on a plain `TcpListener`, so installs
+//! and removals never run concurrently; a client that stops sending is cut
+//! off after [`READ_TIMEOUT`]. Mutating requests are delegated to the `add`
+//! and `remove` commands, whose output goes to the server's terminal, and
+//! must carry an `Origin` header matching `Host`, as browsers send.
+//!
+//! | Method   | Path                | Description                          |
+//! | -------- | ------------------- | ------------------------------------ |
+//! | `GET`    | `/`                 | Web UI                               |
+//! | `GET`    | `/api/skills`       | Installed skills, as `list --format json` |
+//! | `GET`    | `/api/skill?path=`  | Frontmatter, rendered HTML, and lint |
+//! | `POST`   | `/api/install`      | Install from `{source, skill?, agent?, global?}` |
+//! | `DELETE` | `/api/skill?path=`  | Remove an installed skill            |
+
+use crate::cli::{Cli, Command, ServeArgs};
+use crate::config::Config;
+use crate::error::SkiloError;
+use crate::output::{JsonFormatter, OutputFormatter};
+use crate::skill::{Manifest, Validator};
+use clap::Parser;
+use colored::Colorize;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{IpAddr, TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Largest request body accepted, in bytes.
+const MAX_BODY: usize = 64 * 1024;
+
+/// Longest request or header line accepted, in bytes.
+const MAX_LINE: usize = 8 * 1024;
+
+/// How long a client may take to send its request.
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+const INDEX_HTML: &str = include_str!("serve.html");
+
+/// Run the serve command.
+pub fn run(args: ServeArgs, config: &Config, cli: &Cli) -> Result<i32, SkiloError> {
+    let listener = TcpListener::bind((args.bind.as_str(), args.port))?;
+    let address = listener.local_addr()?.ip();
+    if !address.is_loopback() && !args.allow_remote {
+        return Err(SkiloError::Config(format!(
+            "Refusing to serve on {}, which is not a loopback address; pass --allow-remote to expose skill installs to the network",
+            address
+        )));
+    }
+    let project_root = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+
+    if !cli.quiet {
+        println!(
+            "{} Serving skills for {} at {}",
+            "✓".green(),
+            project_root.display(),
+            format!("http://{}", listener.local_addr()?).cyan()
+        );
+        println!("  Press Ctrl+C to stop");
+    }
+
+    let server = Server {
+        config,
+        project_root,
+        address,
+    };
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                if let Err(e) = server.handle(stream) {
+                    eprintln!("{}: {}", "Warning".yellow(), e);
+                }
+            }
+            Err(e) => eprintln!("{}: {}", "Warning".yellow(), e),
+        }
+    }
+
+    Ok(0)
+}
+
+/// A parsed HTTP request.
+struct Request {
+    method: String,
+    path: String,
+    query: Vec<(String, String)>,
+    origin: Option<String>,
+    host: Option<String>,
+    body: Vec<u8>,
+}
+
+impl Request {
+    fn query(&self, key: &str) -> Option<&str> {
+        self.query
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// An HTTP response.
+struct Response {
+    status: u16,
+    content_type: &'static str,
+    body: String,
+}
+
+impl Response {
+    fn json(status: u16, body: String) -> Self {
+        Self {
+            status,
+            content_type: "application/json",
+            body,
+        }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        Self::json(status, json!({ "error": message }).to_string())
+    }
+
+    fn write(&self, stream: &mut TcpStream) -> std::io::Result<()> {
+        let reason = match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            403 => "Forbidden",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            413 => "Payload Too Large",
+            431 => "Request Header Fields Too Large",
+            _ => "Internal Server Error",
+        };
+        write!(
+            stream,
+            "HTTP/1.1 {} {}\r\nContent-Type: {}; charset=utf-8\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n{}",
+            self.status,
+            reason,
+            self.content_type,
+            self.body.len(),
+            self.body
+        )?;
+        stream.flush()
+    }
+}
+
+/// Body of `POST /api/install`.
+#[derive(Deserialize)]
+struct InstallRequest {
+    source: String,
+    #[serde(default)]
+    skill: Vec<String>,
+    agent: Option<String>,
+    #[serde(default)]
+    global: bool,
+}
+
+struct Server<'a> {
+    config: &'a Config,
+    project_root: PathBuf,
+    /// Address the server listens on.
+    address: IpAddr,
+}
+
+impl Server<'_> {
+    fn handle(&self, mut stream: TcpStream) -> std::io::Result<()> {
+        // A stalled client would otherwise block every other request
+        stream.set_read_timeout(Some(READ_TIMEOUT))?;
+        stream.set_write_timeout(Some(READ_TIMEOUT))?;
+        let response = match read_request(&mut stream) {
+            Ok(request) => self.route(&request),
+            Err(response) => response,
+        };
+        response.write(&mut stream)
+    }
+
+    fn route(&self, request: &Request) -> Response {
+        // Reject cross-site requests, and requests from outside a browser
+        // that omit Origin, so other pages and local processes can't drive
+        // the API; reject foreign hosts to block DNS rebinding
+        let host = request.host.as_deref().unwrap_or_default();
+        if request.method != "GET" && !same_origin(request.origin.as_deref(), host) {
+            return Response::error(403, "cross-origin requests are not allowed");
+        }
+        if !is_expected_host(host, self.address) {
+            return Response::error(403, "unexpected Host header");
+        }
+
+        let result = match (request.method.as_str(), request.path.as_str()) {
+            ("GET", "/") => Ok(Response {
+                status: 200,
+                content_type: "text/html",
+                body: INDEX_HTML.to_string(),
+            }),
+            ("GET", "/api/skills") => self.skills().map(|body| Response::json(200, body)),
+            ("GET", "/api/skill") => self.skill(request),
+            ("DELETE", "/api/skill") => self.remove(request),
+            ("POST", "/api/install") => self.install(request),
+            (_, "/" | "/api/skills" | "/api/skill" | "/api/install") => {
+                Ok(Response::error(405, "method not allowed"))
+            }
+            _ => Ok(Response::error(404, "not found")),
+        };
+
+        result.unwrap_or_else(|e| Response::error(500, &e.to_string()))
+    }
+
+    /// Installed skills at both scopes for every detected agent.
+    fn skills(&self) -> Result<String, SkiloError> {
+        let root = self.project_root.display().to_string();
+        let Command::List(args) = parse_command(&["list", "--all", &root])? else {
+            unreachable!("parsed a list command");
+        };
+        super::list::installed_json(args, self.config)
+    }
+
+    /// Find the installed skill at `?path=`, refusing paths that are not
+    /// listed so the API can't be used to read arbitrary files.
+    fn installed(&self, request: &Request) -> Result<Option<Value>, SkiloError> {
+        let Some(path) = request.query("path") else {
+            return Ok(None);
+        };
+        let skills: Vec<Value> = serde_json::from_str(&self.skills()?)
+            .map_err(|e| SkiloError::Config(format!("JSON serialization failed: {}", e)))?;
+        Ok(skills
+            .into_iter()
+            .find(|s| s["path"].as_str() == Some(path)))
+    }
+
+    fn skill(&self, request: &Request) -> Result<Response, SkiloError> {
+        let Some(entry) = self.installed(request)? else {
+            return Ok(Response::error(404, "skill not found"));
+        };
+        let dir = Path::new(entry["path"].as_str().unwrap_or_default());
+
+        let manifest = Manifest::parse(dir.join("SKILL.md"))?;
+        let mut options = comrak::Options::default();
+        options.extension.table = true;
+        options.extension.strikethrough = true;
+        options.extension.tasklist = true;
+        options.extension.footnotes = true;
+        let html = comrak::markdown_to_html(&manifest.body, &options);
+
+        let result = Validator::with_agents(&self.config.lint, &self.config.lint_agents())
+            .validate(&manifest);
+        let lint: Value = serde_json::from_str(
+            &JsonFormatter::new(true)
+                .format_validation(&[(manifest.path.display().to_string(), result)]),
+        )
+        .unwrap_or(Value::Null);
+
+        let body = json!({
+            "skill": entry,
+            "frontmatter": manifest.frontmatter_raw,
+            "html": html,
+            "lint": lint,
+        });
+        Ok(Response::json(200, body.to_string()))
+    }
+
+    fn install(&self, request: &Request) -> Result<Response, SkiloError> {
+        let install: InstallRequest = match serde_json::from_slice(&request.body) {
+            Ok(install) => install,
+            Err(e) => return Ok(Response::error(400, &format!("invalid request: {}", e))),
+        };
+
+        match add_argv(install) {
+            Ok(argv) => self.delegate(&argv),
+            Err(value) => Ok(Response::error(400, &format!("invalid value '{}'", value))),
+        }
+    }
+
+    fn remove(&self, request: &Request) -> Result<Response, SkiloError> {
+        let Some(entry) = self.installed(request)? else {
+            return Ok(Response::error(404, "skill not found"));
+        };
+        let name = entry["name"].as_str().unwrap_or_default().to_string();
+        let scope = entry["scope"].as_str().unwrap_or("project").to_string();
+
+        let mut argv = vec![
+            "remove".to_string(),
+            "--yes".to_string(),
+            format!("--scope={}", scope),
+        ];
+        if let Some(agent) = entry["agent"].as_str() {
+            argv.push(format!("--agent={}", agent));
+        }
+        argv.extend(["--".to_string(), name]);
+
+        self.delegate(&argv)
+    }
+
+    /// Run an `add` or `remove` command line and report its exit code.
+    fn delegate(&self, argv: &[String]) -> Result<Response, SkiloError> {
+        let full = std::iter::once("skilo").chain(argv.iter().map(String::as_str));
+        let cli = match Cli::try_parse_from(full) {
+            Ok(cli) => cli,
+            Err(e) => return Ok(Response::error(400, &e.to_string())),
+        };
+
+        let result = match &cli.command {
+            Command::Add(args) => super::add::run(args.clone(), self.config, &cli),
+            Command::Remove(args) => super::remove::run(args.clone(), self.config, &cli),
+            _ => unreachable!("only add and remove are delegated"),
+        };
+
+        let body = match result {
+            Ok(code) => json!({ "success": code == 0, "exit_code": code }),
+            Err(e) => json!({ "success": false, "error": e.to_string() }),
+        };
+        Ok(Response::json(200, body.to_string()))
+    }
+}
+
+/// The `add` command line for an install request, or the value that
+/// looks like an option.
+///
+/// Values are attached to their flags, and the source follows `--`, so none
+/// of them can be read as another option.
+fn add_argv(install: InstallRequest) -> Result<Vec<String>, String> {
+    let mut values = std::iter::once(&install.source)
+        .chain(&install.skill)
+        .chain(&install.agent);
+    if let Some(value) = values.find(|v| v.starts_with('-')) {
+        return Err(value.clone());
+    }
+
+    let mut argv = vec!["add".to_string(), "--yes".to_string()];
+    argv.extend(
+        install
+            .skill
+            .iter()
+            .map(|skill| format!("--skill={}", skill)),
+    );
+    if let Some(agent) = &install.agent {
+        argv.push(format!("--agent={}", agent));
+    }
+    if install.global {
+        argv.push("--global".to_string());
+    }
+    argv.extend(["--".to_string(), install.source]);
+    Ok(argv)
+}
+
+/// Parse a skilo command line (without the program name).
+fn parse_command(argv: &[&str]) -> Result<Command, SkiloError> {
+    let full = std::iter::once("skilo").chain(argv.iter().copied());
+    Cli::try_parse_from(full)
+        .map(|cli| cli.command)
+        .map_err(|e| SkiloError::Config(e.to_string()))
+}
+
+/// Whether a request's `Origin` is present and matches its `Host`.
+fn same_origin(origin: Option<&str>, host: &str) -> bool {
+    let Some(origin) = origin else {
+        return false;
+    };
+    origin
+        .strip_prefix("http://")
+        .or_else(|| origin.strip_prefix("https://"))
+        .is_some_and(|authority| authority.eq_ignore_ascii_case(host))
+}
+
+/// Whether a `Host` header names the address the server listens on.
+///
+/// On loopback that is `localhost` or the loopback IP; otherwise only the
+/// IP itself, so no DNS name can be rebound to the server. Servers on an
+/// unspecified address (`0.0.0.0`) accept any host.
+fn is_expected_host(host: &str, address: IpAddr) -> bool {
+    let name = match host.rsplit_once(':') {
+        Some((name, port)) if !name.is_empty() && port.bytes().all(|b| b.is_ascii_digit()) => name,
+        _ => host,
+    };
+    let ip = name
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .parse::<IpAddr>();
+    if address.is_loopback() {
+        return name == "localhost" || ip.is_ok_and(|ip| ip.is_loopback());
+    }
+    address.is_unspecified() || ip.is_ok_and(|ip| ip == address)
+}
+
+/// Read and parse a request from `stream`.
+fn read_request(stream: &mut TcpStream) -> Result<Request, Response> {
+    let bad = |msg: &str| Response::error(400, msg);
+    let mut reader = BufReader::new(stream);
+
+    // Read the head up to the blank line
+    let mut head = Vec::new();
+    loop {
+        let mut line = Vec::new();
+        let n = reader
+            .by_ref()
+            .take(MAX_LINE as u64)
+            .read_until(b'\n', &mut line)
+            .map_err(|_| bad("failed to read request"))?;
+        if n == MAX_LINE && !line.ends_with(b"\n") {
+            return Err(Response::error(431, "request header line too long"));
+        }
+        head.extend_from_slice(&line);
+        if n == 0 || line == b"\r\n" || line == b"\n" {
+            break;
+        }
+        if head.len() > MAX_BODY {
+            return Err(Response::error(413, "request headers too large"));
+        }
+    }
+
+    let mut headers = [httparse::EMPTY_HEADER; 32];
+    let mut parsed = httparse::Request::new(&mut headers);
+    match parsed.parse(&head) {
+        Ok(httparse::Status::Complete(_)) => {}
+        _ => return Err(bad("malformed request")),
+    }
+
+    let header = |name: &str| {
+        parsed
+            .headers
+            .iter()
+            .find(|h| h.name.eq_ignore_ascii_case(name))
+            .and_then(|h| std::str::from_utf8(h.value).ok())
+            .map(str::to_string)
+    };
+
+    let length: usize = header("content-length")
+        .and_then(|v| v.trim().parse().ok())
+        .unwrap_or(0);
+    if length > MAX_BODY {
+        return Err(Response::error(413, "request body too large"));
+    }
+    let mut body = vec![0; length];
+    reader
+        .read_exact(&mut body)
+        .map_err(|_| bad("truncated request body"))?;
+
+    let target = parsed.path.unwrap_or("/");
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let query = url::form_urlencoded::parse(query.as_bytes())
+        .into_owned()
+        .collect();
+
+    Ok(Request {
+        method: parsed.method.unwrap_or("GET").to_string(),
+        path: path.to_string(),
+        query,
+        origin: header("origin"),
+        host: header("host"),
+        body,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_origin_checks() {
+        assert!(!same_origin(None, "localhost:4747"));
+        assert!(same_origin(Some("http://localhost:4747"), "localhost:4747"));
+        assert!(!same_origin(Some("https://example.com"), "localhost:4747"));
+        assert!(!same_origin(Some("null"), "localhost:4747"));
+
+        let loopback: IpAddr = "127.0.0.1".parse().unwrap();
+        assert!(is_expected_host("127.0.0.1:4747", loopback));
+        assert!(is_expected_host("[::1]:4747", loopback));
+        assert!(is_expected_host("localhost", loopback));
+        assert!(!is_expected_host("attacker.example:4747", loopback));
+
+        let lan: IpAddr = "192.168.1.5".parse().unwrap();
+        assert!(is_expected_host("192.168.1.5:4747", lan));
+        assert!(!is_expected_host("attacker.example:4747", lan));
+        assert!(!is_expected_host("localhost:4747", lan));
+    }
+
+    #[test]
+    fn test_add_argv() {
+        let install = |body: &str| add_argv(serde_json::from_str(body).unwrap());
+
+        let argv =
+            install(r#"{"source": "owner/repo", "skill": ["a"], "agent": "claude"}"#).unwrap();
+        let argv: Vec<&str> = argv.iter().map(String::as_str).collect();
+        let Command::Add(args) = parse_command(&argv).unwrap() else {
+            panic!("Expected add command");
+        };
+        assert_eq!(args.source.as_deref(), Some("owner/repo"));
+        assert_eq!(args.skill, Some(vec!["a".to_string()]));
+
+        assert_eq!(
+            install(r#"{"source": "--config=/tmp/x"}"#).unwrap_err(),
+            "--config=/tmp/x"
+        );
+        assert!(install(r#"{"source": "owner/repo", "skill": ["-a"]}"#).is_err());
+    }
+}
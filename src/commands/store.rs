@@ -0,0 +1,106 @@
+//! Skill store management commands.
+
+use crate::cache::format_size;
+use crate::cli::{Cli, StoreArgs, StoreCommand};
+use crate::config::Config;
+use crate::error::SkiloError;
+use crate::store::{entries, gc, store_dir};
+use colored::Colorize;
+
+/// Characters of an entry's hash shown.
+const SHORT_HASH_LEN: usize = 12;
+
+/// Run the store command.
+pub fn run(args: StoreArgs, _config: &Config, cli: &Cli) -> Result<i32, SkiloError> {
+    match args.command {
+        Some(StoreCommand::Path) => show_path(),
+        Some(StoreCommand::Gc { dry_run }) => collect_garbage(dry_run, cli),
+        None => show_status(cli),
+    }
+}
+
+/// Show store directory path.
+fn show_path() -> Result<i32, SkiloError> {
+    let store = store_dir()
+        .ok_or_else(|| SkiloError::Config("Could not determine store directory".to_string()))?;
+
+    println!("{}", store.display());
+
+    Ok(0)
+}
+
+/// Show the store entries and whether installs still use them.
+fn show_status(cli: &Cli) -> Result<i32, SkiloError> {
+    let store = store_dir()
+        .ok_or_else(|| SkiloError::Config("Could not determine store directory".to_string()))?;
+
+    if !store.exists() {
+        if !cli.quiet {
+            println!("Store directory: {} (not created yet)", store.display());
+        }
+        return Ok(0);
+    }
+
+    let entries = entries();
+    let unused: Vec<_> = entries.iter().filter(|e| !e.in_use).collect();
+
+    println!("Store directory: {}", store.display().to_string().cyan());
+    println!();
+    println!("  {} entries, {} unused", entries.len(), unused.len());
+    for entry in &entries {
+        let hash = &entry.hash[..SHORT_HASH_LEN.min(entry.hash.len())];
+        let status = if entry.in_use { "" } else { "(unused)" };
+        println!(
+            "    {} {} {}",
+            hash,
+            format_size(entry.size).dimmed(),
+            status.yellow()
+        );
+    }
+
+    if !entries.is_empty() {
+        println!();
+        let total: u64 = entries.iter().map(|e| e.size).sum();
+        println!("Total: {}", format_size(total).cyan());
+    }
+
+    if !unused.is_empty() {
+        let size: u64 = unused.iter().map(|e| e.size).sum();
+        println!(
+            "{} is no longer linked from installed skills; run {} to remove it.",
+            format_size(size).yellow(),
+            "skilo store gc".cyan()
+        );
+    }
+
+    Ok(0)
+}
+
+/// Remove unused store entries.
+fn collect_garbage(dry_run: bool, cli: &Cli) -> Result<i32, SkiloError> {
+    let (removed, freed) = gc(dry_run).map_err(SkiloError::Io)?;
+
+    if cli.quiet {
+        return Ok(0);
+    }
+
+    if removed.is_empty() {
+        println!("No unused store entries found");
+        return Ok(0);
+    }
+
+    for entry in &removed {
+        let hash = &entry.hash[..SHORT_HASH_LEN.min(entry.hash.len())];
+        println!("  {} {}", hash, format_size(entry.size).dimmed());
+    }
+    println!(
+        "{} {} entr{} ({} {})",
+        if dry_run { "Would remove" } else { "Removed" },
+        removed.len(),
+        if removed.len() == 1 { "y" } else { "ies" },
+        format_size(freed).green(),
+        if dry_run { "would be freed" } else { "freed" }
+    );
+
+    Ok(0)
+}
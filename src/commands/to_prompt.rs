@@ -1,17 +1,54 @@
 //! Generates XML for embedding skill information in agent prompts.
+//!
+//! Output is deterministic: skills are sorted by name (or priority) with
+//! their location as a tie-breaker, and groups are sorted by name, so the
+//! same skills always produce byte-identical output and prompt caches stay
+//! valid across runs.
 
-use crate::cli::{Cli, ToPromptArgs};
+use crate::cli::{Cli, OutputFormat, PromptGroup, PromptSort, ToPromptArgs};
 use crate::config::Config;
 use crate::error::SkiloError;
-use crate::skill::{Discovery, Manifest};
+use crate::journal::write_atomic;
+use crate::skill::{Discovery, Manifest, Outline};
+use colored::Colorize;
 use serde::Serialize;
-use std::path::PathBuf;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use walkdir::WalkDir;
+
+/// Metadata key holding a skill's priority for `--sort priority`.
+const PRIORITY_KEY: &str = "priority";
+
+/// Line opening the generated section of an `--output` file.
+const BEGIN_MARKER: &str = "<!-- BEGIN skilo to-prompt (generated, do not edit) -->";
+
+/// Line closing the generated section of an `--output` file.
+const END_MARKER: &str = "<!-- END skilo to-prompt -->";
+
+/// How often `--watch` checks the skills for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
 
 /// Root element for XML output.
 #[derive(Serialize)]
 #[serde(rename = "available_skills")]
 struct AvailableSkills {
-    /// List of skills.
+    /// List of skills, when not grouped.
+    #[serde(rename = "skill", skip_serializing_if = "Vec::is_empty")]
+    skills: Vec<SkillEntry>,
+    /// Groups of skills, with `--group-by`.
+    #[serde(rename = "group", skip_serializing_if = "Vec::is_empty")]
+    groups: Vec<GroupEntry>,
+}
+
+/// A group of skills in XML output.
+#[derive(Serialize)]
+struct GroupEntry {
+    /// The tag or directory, absent for untagged skills.
+    #[serde(rename = "@name", skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    /// Skills in the group.
     #[serde(rename = "skill")]
     skills: Vec<SkillEntry>,
 }
@@ -23,8 +60,76 @@ struct SkillEntry {
     name: String,
     /// Skill description.
     description: String,
+    /// Comma-separated tags, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tags: Option<String>,
+    /// Example user requests, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    examples: Option<ExamplesEntry>,
     /// Path to the SKILL.md file.
     location: String,
+    /// Body summary, with `--summary` or `--summary-lines`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    summary: Option<String>,
+    /// Body outline, with `--with-outline`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    outline: Option<OutlineEntry>,
+    /// The skill's first tag.
+    #[serde(skip)]
+    first_tag: Option<String>,
+    /// The `metadata.priority` value, 0 if unset or not an integer.
+    #[serde(skip)]
+    priority: i64,
+}
+
+/// Example requests in XML output.
+#[derive(Serialize)]
+struct ExamplesEntry {
+    /// Examples in frontmatter order.
+    #[serde(rename = "example")]
+    examples: Vec<String>,
+}
+
+/// Outline of a skill body in XML output.
+#[derive(Serialize)]
+struct OutlineEntry {
+    /// Headings in document order.
+    #[serde(rename = "heading")]
+    headings: Vec<HeadingEntry>,
+    /// Mentioned scripts.
+    #[serde(rename = "script")]
+    scripts: Vec<String>,
+    /// Mentioned references.
+    #[serde(rename = "reference")]
+    references: Vec<String>,
+}
+
+/// A heading in XML output.
+#[derive(Serialize)]
+struct HeadingEntry {
+    /// Heading level.
+    #[serde(rename = "@level")]
+    level: u8,
+    /// Heading text.
+    #[serde(rename = "$text")]
+    text: String,
+}
+
+impl From<Outline> for OutlineEntry {
+    fn from(outline: Outline) -> Self {
+        Self {
+            headings: outline
+                .headings
+                .into_iter()
+                .map(|h| HeadingEntry {
+                    level: h.level,
+                    text: h.text,
+                })
+                .collect(),
+            scripts: outline.scripts,
+            references: outline.references,
+        }
+    }
 }
 
 impl From<&Manifest> for SkillEntry {
@@ -32,22 +137,177 @@ impl From<&Manifest> for SkillEntry {
         Self {
             name: manifest.frontmatter.name.clone(),
             description: manifest.frontmatter.description.clone(),
+            tags: manifest
+                .frontmatter
+                .tags
+                .as_ref()
+                .filter(|tags| !tags.is_empty())
+                .map(|tags| tags.join(", ")),
+            examples: Some(manifest.frontmatter.examples())
+                .filter(|examples| !examples.is_empty())
+                .map(|examples| ExamplesEntry {
+                    examples: examples.iter().map(|e| e.trim().to_string()).collect(),
+                }),
             location: manifest.path.display().to_string(),
+            summary: None,
+            outline: None,
+            first_tag: manifest
+                .frontmatter
+                .tags
+                .as_ref()
+                .and_then(|tags| tags.first().cloned()),
+            priority: manifest
+                .frontmatter
+                .metadata
+                .as_ref()
+                .and_then(|metadata| metadata.get(PRIORITY_KEY))
+                .and_then(|priority| priority.trim().parse().ok())
+                .unwrap_or(0),
         }
     }
 }
 
+impl SkillEntry {
+    /// The entry as a JSON object.
+    fn to_json(&self) -> serde_json::Value {
+        let mut value = json!({
+            "name": self.name,
+            "description": self.description,
+            "location": self.location,
+        });
+        if let Some(tags) = &self.tags {
+            value["tags"] = json!(tags);
+        }
+        if let Some(examples) = &self.examples {
+            value["examples"] = json!(examples.examples);
+        }
+        if let Some(summary) = &self.summary {
+            value["summary"] = json!(summary);
+        }
+        if let Some(outline) = &self.outline {
+            value["outline"] = json!({
+                "headings": outline
+                    .headings
+                    .iter()
+                    .map(|h| json!({ "level": h.level, "text": h.text }))
+                    .collect::<Vec<_>>(),
+                "scripts": outline.scripts,
+                "references": outline.references,
+            });
+        }
+        value
+    }
+}
+
+/// Sort skills by `sort`, breaking ties by name and then location.
+fn sort_skills(skills: &mut [SkillEntry], sort: PromptSort) {
+    skills.sort_by(|a, b| {
+        let order = match sort {
+            PromptSort::Name => std::cmp::Ordering::Equal,
+            PromptSort::Priority => b.priority.cmp(&a.priority),
+        };
+        order
+            .then_with(|| a.name.cmp(&b.name))
+            .then_with(|| a.location.cmp(&b.location))
+    });
+}
+
+/// Split sorted skills into groups ordered by name, keeping the order of
+/// skills within each group. Skills without a group come last.
+fn group_skills(skills: Vec<SkillEntry>, by: PromptGroup) -> Vec<GroupEntry> {
+    let mut groups: BTreeMap<(bool, Option<String>), Vec<SkillEntry>> = BTreeMap::new();
+    for skill in skills {
+        let name = match by {
+            PromptGroup::Tag => skill.first_tag.clone(),
+            PromptGroup::Directory => Some(skill_directory(&skill.location)),
+        };
+        groups
+            .entry((name.is_none(), name))
+            .or_default()
+            .push(skill);
+    }
+    groups
+        .into_iter()
+        .map(|((_, name), skills)| GroupEntry { name, skills })
+        .collect()
+}
+
+/// The directory containing the skill whose SKILL.md is at `location`.
+fn skill_directory(location: &str) -> String {
+    let dir = Path::new(location)
+        .parent()
+        .and_then(|skill| skill.parent())
+        .map(|dir| dir.display().to_string())
+        .unwrap_or_default();
+    if dir.is_empty() {
+        ".".to_string()
+    } else {
+        dir
+    }
+}
+
 /// Run the to-prompt command.
 ///
-/// Generates `<available_skills>` XML for agent prompts.
+/// Generates `<available_skills>` XML for agent prompts, or JSON with
+/// `--format json`, and prints it or writes it to `--output`. With
+/// `--watch`, keeps rewriting the output file as the skills change.
 pub fn run(args: ToPromptArgs, config: &Config, cli: &Cli) -> Result<i32, SkiloError> {
-    // Collect all skill paths from all input paths
-    let mut all_skill_paths: Vec<PathBuf> = Vec::new();
+    let json = matches!(cli.format, OutputFormat::Json);
+    let (output, failures) = generate(&args, config, json)?;
+    // Return error code if there were parsing failures
+    let exit_code = if failures == 0 { 0 } else { 1 };
+
+    let Some(file) = &args.output else {
+        if json || !cli.quiet {
+            println!("{}", output);
+        }
+        return Ok(exit_code);
+    };
+
+    write_output(file, &output, json)?;
+    if !cli.quiet {
+        println!("{} Wrote {}", "✓".green(), file.display());
+    }
+    if !args.watch {
+        return Ok(exit_code);
+    }
 
-    for path in &args.paths {
-        let paths = Discovery::find_skills(path, &config.discovery.ignore);
-        all_skill_paths.extend(paths);
+    if !cli.quiet {
+        println!("Watching for changes (Ctrl-C to stop)");
+    }
+    let mut last = fingerprint(&args.paths, config, file);
+    loop {
+        std::thread::sleep(POLL_INTERVAL);
+        let current = fingerprint(&args.paths, config, file);
+        if current == last {
+            continue;
+        }
+        last = current;
+
+        // Keep watching through errors; the next change may fix them
+        let written =
+            generate(&args, config, json).and_then(|(output, _)| write_output(file, &output, json));
+        match written {
+            Ok(true) if !cli.quiet => {
+                println!("{} Updated {}", "✓".green(), file.display());
+            }
+            Ok(_) => {}
+            Err(e) => eprintln!("Error: {}", e),
+        }
     }
+}
+
+/// Render the skills under `args.paths` as XML or JSON.
+///
+/// Parse failures are reported on stderr and counted; the skills that
+/// parsed are still rendered.
+fn generate(
+    args: &ToPromptArgs,
+    config: &Config,
+    json: bool,
+) -> Result<(String, usize), SkiloError> {
+    // Collect all skill paths from all input paths
+    let all_skill_paths = Discovery::find_skills_in(&args.paths, &config.discovery);
 
     if all_skill_paths.is_empty() {
         return Err(SkiloError::NoSkillsFound {
@@ -67,7 +327,14 @@ pub fn run(args: ToPromptArgs, config: &Config, cli: &Cli) -> Result<i32, SkiloE
     for path in &all_skill_paths {
         match Manifest::parse(path.clone()) {
             Ok(manifest) => {
-                skills.push(SkillEntry::from(&manifest));
+                let mut entry = SkillEntry::from(&manifest);
+                if args.summary || args.summary_lines.is_some() {
+                    entry.summary = Some(manifest.summary(args.summary_lines));
+                }
+                if args.with_outline {
+                    entry.outline = Some(OutlineEntry::from(manifest.outline()));
+                }
+                skills.push(entry);
             }
             Err(e) => {
                 errors.push(format!("{}: {}", path.display(), e));
@@ -80,22 +347,194 @@ pub fn run(args: ToPromptArgs, config: &Config, cli: &Cli) -> Result<i32, SkiloE
         eprintln!("Error: {}", error);
     }
 
-    // Generate and output XML
-    if !cli.quiet {
-        let available_skills = AvailableSkills { skills };
-        let mut buffer = String::new();
-        let mut serializer = quick_xml::se::Serializer::new(&mut buffer);
-        serializer.indent(' ', 2);
-        available_skills
-            .serialize(serializer)
-            .map_err(|e| SkiloError::Config(format!("XML serialization failed: {}", e)))?;
-        println!("{}", buffer);
+    sort_skills(&mut skills, args.sort);
+    let (skills, groups) = match args.group_by {
+        Some(by) => (Vec::new(), group_skills(skills, by)),
+        None => (skills, Vec::new()),
+    };
+
+    if json {
+        let entries = |skills: &[SkillEntry]| -> Vec<serde_json::Value> {
+            skills.iter().map(SkillEntry::to_json).collect()
+        };
+        let value = if args.group_by.is_some() {
+            let groups: Vec<_> = groups
+                .iter()
+                .map(|group| json!({ "name": group.name, "skills": entries(&group.skills) }))
+                .collect();
+            json!({ "groups": groups })
+        } else {
+            json!({ "skills": entries(&skills) })
+        };
+        let output = serde_json::to_string_pretty(&value)
+            .map_err(|e| SkiloError::Config(format!("JSON serialization failed: {}", e)))?;
+        return Ok((output, errors.len()));
     }
 
-    // Return error code if there were parsing failures
-    if errors.is_empty() {
-        Ok(0)
+    let available_skills = AvailableSkills { skills, groups };
+    let mut buffer = String::new();
+    let mut serializer = quick_xml::se::Serializer::new(&mut buffer);
+    serializer.indent(' ', 2);
+    available_skills
+        .serialize(serializer)
+        .map_err(|e| SkiloError::Config(format!("XML serialization failed: {}", e)))?;
+    Ok((buffer, errors.len()))
+}
+
+/// Write `output` to `file`, returning whether the file changed.
+///
+/// XML goes between the managed markers, leaving the rest of an existing
+/// file (such as an agent instructions file) untouched. JSON replaces the
+/// whole file.
+fn write_output(file: &Path, output: &str, json: bool) -> Result<bool, SkiloError> {
+    let existing = match std::fs::read_to_string(file) {
+        Ok(existing) => Some(existing),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => return Err(e.into()),
+    };
+    let content = if json {
+        format!("{}\n", output.trim_end())
     } else {
-        Ok(1)
+        splice_managed(existing.as_deref().unwrap_or_default(), output)
+    };
+    if existing.as_deref() == Some(content.as_str()) {
+        return Ok(false);
+    }
+
+    if let Some(parent) = file.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    write_atomic(file, content)?;
+    Ok(true)
+}
+
+/// Replace the managed section of `existing` with `output`, or append a
+/// managed section if there is none.
+fn splice_managed(existing: &str, output: &str) -> String {
+    let section = format!("{}\n{}\n{}", BEGIN_MARKER, output.trim_end(), END_MARKER);
+
+    if let Some(begin) = existing.find(BEGIN_MARKER) {
+        if let Some(end) = existing[begin..].find(END_MARKER) {
+            let end = begin + end + END_MARKER.len();
+            return format!("{}{}{}", &existing[..begin], section, &existing[end..]);
+        }
+    }
+
+    if existing.trim().is_empty() {
+        format!("{}\n", section)
+    } else {
+        format!("{}\n\n{}\n", existing.trim_end(), section)
+    }
+}
+
+/// Paths, sizes, and modification times of every file in the skills under
+/// `paths`, except `output`, in a stable order.
+fn fingerprint(
+    paths: &[PathBuf],
+    config: &Config,
+    output: &Path,
+) -> Vec<(PathBuf, u64, Option<SystemTime>)> {
+    let output = output.canonicalize().ok();
+    let mut files = Vec::new();
+
+    for skill_md in Discovery::find_skills_in(paths, &config.discovery) {
+        let Some(dir) = skill_md.parent() else {
+            continue;
+        };
+        let entries = WalkDir::new(dir)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_map(Result::ok);
+        for entry in entries {
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            if !metadata.is_file() || entry.path().canonicalize().ok() == output {
+                continue;
+            }
+            files.push((
+                entry.path().to_path_buf(),
+                metadata.len(),
+                metadata.modified().ok(),
+            ));
+        }
+    }
+
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, location: &str, tag: Option<&str>, priority: i64) -> SkillEntry {
+        SkillEntry {
+            name: name.to_string(),
+            description: "Test".to_string(),
+            tags: tag.map(String::from),
+            examples: None,
+            location: location.to_string(),
+            summary: None,
+            outline: None,
+            first_tag: tag.map(String::from),
+            priority,
+        }
+    }
+
+    fn names(skills: &[SkillEntry]) -> Vec<&str> {
+        skills.iter().map(|s| s.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_sort_skills() {
+        let mut skills = vec![
+            entry("b", "x/b/SKILL.md", None, 0),
+            entry("a", "y/a/SKILL.md", None, 0),
+            entry("c", "x/c/SKILL.md", None, 5),
+            entry("a", "x/a/SKILL.md", None, 0),
+        ];
+        sort_skills(&mut skills, PromptSort::Name);
+        assert_eq!(names(&skills), vec!["a", "a", "b", "c"]);
+        assert_eq!(skills[0].location, "x/a/SKILL.md");
+
+        sort_skills(&mut skills, PromptSort::Priority);
+        assert_eq!(names(&skills), vec!["c", "a", "a", "b"]);
+    }
+
+    #[test]
+    fn test_group_skills() {
+        let skills = vec![
+            entry("a", "x/a/SKILL.md", None, 0),
+            entry("b", "b/SKILL.md", Some("testing"), 0),
+            entry("c", "x/c/SKILL.md", Some("docs"), 0),
+        ];
+        let groups = group_skills(skills, PromptGroup::Tag);
+        let summary: Vec<_> = groups
+            .iter()
+            .map(|g| (g.name.as_deref(), names(&g.skills)))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (Some("docs"), vec!["c"]),
+                (Some("testing"), vec!["b"]),
+                (None, vec!["a"]),
+            ]
+        );
+
+        let skills = vec![
+            entry("a", "x/a/SKILL.md", None, 0),
+            entry("b", "b/SKILL.md", None, 0),
+            entry("c", "x/c/SKILL.md", None, 0),
+        ];
+        let groups = group_skills(skills, PromptGroup::Directory);
+        let summary: Vec<_> = groups
+            .iter()
+            .map(|g| (g.name.as_deref(), names(&g.skills)))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![(Some("."), vec!["b"]), (Some("x"), vec!["a", "c"])]
+        );
     }
 }
@@ -0,0 +1,317 @@
+//! Prints a tree view of a skill's files.
+
+use crate::cache::format_size;
+use crate::cli::{Cli, OutputFormat, TreeArgs};
+use crate::config::Config;
+use crate::error::SkiloError;
+use crate::output::print_porcelain;
+use crate::skill::discovery::Discovery;
+use crate::skill::rules::estimate_tokens;
+use crate::skill::{Manifest, Validator};
+use colored::Colorize;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// A file or directory inside a skill.
+#[derive(Debug, Serialize)]
+pub struct TreeEntry {
+    /// Path relative to the skill directory.
+    pub path: PathBuf,
+    /// Depth below the skill directory (1 for top-level entries).
+    pub depth: usize,
+    /// Whether the entry is a directory.
+    pub is_dir: bool,
+    /// File size in bytes (0 for directories).
+    pub size: u64,
+    /// Interpreter detected for files under `scripts/`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interpreter: Option<String>,
+}
+
+/// Structure and validation summary of a single skill.
+#[derive(Debug, Serialize)]
+pub struct SkillTree {
+    /// Name of the skill, or its directory name if SKILL.md does not parse.
+    pub name: String,
+    /// Path to the skill directory.
+    pub path: PathBuf,
+    /// Number of lines in the SKILL.md body.
+    pub body_lines: usize,
+    /// Estimated tokens in the SKILL.md body.
+    pub body_tokens: usize,
+    /// Number of validation errors.
+    pub errors: usize,
+    /// Number of validation warnings.
+    pub warnings: usize,
+    /// Files and directories in the skill, in display order.
+    pub entries: Vec<TreeEntry>,
+}
+
+/// Run the tree command.
+pub fn run(args: TreeArgs, config: &Config, cli: &Cli) -> Result<i32, SkiloError> {
+    let skill_paths = Discovery::find_skills_in(&args.paths, &config.discovery);
+
+    if skill_paths.is_empty() {
+        return Err(SkiloError::NoSkillsFound {
+            path: args
+                .paths
+                .iter()
+                .map(|p| p.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+        });
+    }
+
+    let validator = Validator::new(&config.lint);
+    let trees: Vec<SkillTree> = skill_paths
+        .iter()
+        .map(|path| build_tree(path, &validator))
+        .collect();
+
+    if matches!(cli.format, OutputFormat::Json) {
+        let json = serde_json::to_string_pretty(&trees)
+            .map_err(|e| SkiloError::Config(format!("JSON serialization failed: {}", e)))?;
+        println!("{}", json);
+        return Ok(0);
+    }
+
+    if cli.porcelain {
+        for tree in &trees {
+            for entry in &tree.entries {
+                print_porcelain(&[
+                    &tree.name,
+                    &entry.path.display().to_string(),
+                    if entry.is_dir { "dir" } else { "file" },
+                    &entry.size.to_string(),
+                    entry.interpreter.as_deref().unwrap_or("-"),
+                ]);
+            }
+        }
+        return Ok(0);
+    }
+
+    for (i, tree) in trees.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        print_tree(tree);
+    }
+
+    Ok(0)
+}
+
+/// Collect the structure and validation results of the skill at `skill_md`.
+fn build_tree(skill_md: &Path, validator: &Validator) -> SkillTree {
+    let skill_dir = skill_md.parent().unwrap_or(Path::new("."));
+    let dir_name = skill_dir
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| skill_dir.display().to_string());
+
+    let mut tree = SkillTree {
+        name: dir_name,
+        path: skill_dir.to_path_buf(),
+        body_lines: 0,
+        body_tokens: 0,
+        errors: 0,
+        warnings: 0,
+        entries: Vec::new(),
+    };
+
+    match Manifest::parse(skill_md.to_path_buf()) {
+        Ok(manifest) => {
+            let result = validator.validate(&manifest);
+            tree.name = manifest.frontmatter.name.clone();
+            tree.body_lines = manifest.body_line_count();
+            tree.body_tokens = estimate_tokens(&manifest.body);
+            tree.errors = result.errors.len();
+            tree.warnings = result.warnings.len();
+        }
+        Err(_) => tree.errors = 1,
+    }
+
+    let walker = WalkDir::new(skill_dir)
+        .min_depth(1)
+        .sort_by(|a, b| {
+            // SKILL.md first, then directories before files, then by name
+            let key = |e: &walkdir::DirEntry| {
+                (
+                    e.file_name() != "SKILL.md",
+                    !e.file_type().is_dir(),
+                    e.file_name().to_os_string(),
+                )
+            };
+            key(a).cmp(&key(b))
+        })
+        .into_iter()
+        .filter_entry(|e| !e.file_name().to_string_lossy().starts_with('.'));
+
+    for entry in walker.filter_map(|e| e.ok()) {
+        let Ok(relative) = entry.path().strip_prefix(skill_dir) else {
+            continue;
+        };
+        let is_dir = entry.file_type().is_dir();
+        let in_scripts = relative.starts_with("scripts");
+        tree.entries.push(TreeEntry {
+            path: relative.to_path_buf(),
+            depth: entry.depth(),
+            is_dir,
+            size: if is_dir {
+                0
+            } else {
+                entry.metadata().map(|m| m.len()).unwrap_or(0)
+            },
+            interpreter: if in_scripts && !is_dir {
+                detect_interpreter(entry.path())
+            } else {
+                None
+            },
+        });
+    }
+
+    tree
+}
+
+/// Print a skill tree with box-drawing connectors.
+fn print_tree(tree: &SkillTree) {
+    let badge = if tree.errors > 0 {
+        format!(
+            "{} error{}",
+            tree.errors,
+            if tree.errors == 1 { "" } else { "s" }
+        )
+        .red()
+    } else if tree.warnings > 0 {
+        format!(
+            "{} warning{}",
+            tree.warnings,
+            if tree.warnings == 1 { "" } else { "s" }
+        )
+        .yellow()
+    } else {
+        "valid".green()
+    };
+    println!("{} [{}]", tree.name.bold(), badge);
+
+    // An entry is the last child of its parent if no later sibling follows
+    // before the tree climbs back above its depth.
+    let last: Vec<bool> = tree
+        .entries
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            tree.entries[i + 1..]
+                .iter()
+                .take_while(|e| e.depth >= entry.depth)
+                .all(|e| e.depth > entry.depth)
+        })
+        .collect();
+
+    for (i, entry) in tree.entries.iter().enumerate() {
+        let mut prefix = String::new();
+        for depth in 1..entry.depth {
+            let ancestor = (0..i).rev().find(|&j| tree.entries[j].depth == depth);
+            let ancestor_last = ancestor.is_some_and(|j| last[j]);
+            prefix.push_str(if ancestor_last { "    " } else { "│   " });
+        }
+        prefix.push_str(if last[i] { "└── " } else { "├── " });
+
+        let name = entry
+            .path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let (name, detail) = if entry.is_dir {
+            (format!("{}/", name).blue().to_string(), String::new())
+        } else if entry.depth == 1 && name == "SKILL.md" {
+            (
+                name,
+                format!(
+                    "{} lines, ~{} tokens, {}",
+                    tree.body_lines,
+                    tree.body_tokens,
+                    format_size(entry.size)
+                ),
+            )
+        } else {
+            let mut detail = format_size(entry.size);
+            if let Some(interpreter) = &entry.interpreter {
+                detail = format!("{}, {}", interpreter, detail);
+            }
+            (name, detail)
+        };
+
+        if detail.is_empty() {
+            println!("{}{}", prefix.dimmed(), name);
+        } else {
+            println!(
+                "{}{}  {}",
+                prefix.dimmed(),
+                name,
+                format!("({})", detail).dimmed()
+            );
+        }
+    }
+}
+
+/// Detect the interpreter of a script from its shebang or file extension.
+pub fn detect_interpreter(path: &Path) -> Option<String> {
+    let first_line = std::fs::read_to_string(path)
+        .ok()
+        .and_then(|content| content.lines().next().map(str::to_string));
+
+    if let Some(shebang) = first_line.as_deref().and_then(|l| l.strip_prefix("#!")) {
+        let mut parts = shebang.split_whitespace();
+        let program = parts.next()?;
+        let program = program.rsplit('/').next().unwrap_or(program);
+        if program != "env" {
+            return Some(program.to_string());
+        }
+        return parts.find(|p| !p.starts_with('-')).map(str::to_string);
+    }
+
+    let interpreter = match path.extension()?.to_str()? {
+        "py" => "python",
+        "sh" => "sh",
+        "bash" => "bash",
+        "js" | "mjs" => "node",
+        "ts" => "ts-node",
+        "rb" => "ruby",
+        "pl" => "perl",
+        _ => return None,
+    };
+    Some(interpreter.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_detect_interpreter() {
+        let temp = TempDir::new().unwrap();
+        let script = |name: &str, content: &str| {
+            let path = temp.path().join(name);
+            std::fs::write(&path, content).unwrap();
+            path
+        };
+
+        let env = script("a", "#!/usr/bin/env python3\nprint()\n");
+        assert_eq!(detect_interpreter(&env).as_deref(), Some("python3"));
+
+        let env_flags = script("b", "#!/usr/bin/env -S npx ts-node\n");
+        assert_eq!(detect_interpreter(&env_flags).as_deref(), Some("npx"));
+
+        let direct = script("c", "#!/bin/bash\n");
+        assert_eq!(detect_interpreter(&direct).as_deref(), Some("bash"));
+
+        let by_extension = script("d.js", "console.log(1)\n");
+        assert_eq!(detect_interpreter(&by_extension).as_deref(), Some("node"));
+
+        let unknown = script("e.txt", "hello\n");
+        assert_eq!(detect_interpreter(&unknown), None);
+    }
+}
@@ -0,0 +1,147 @@
+//! Vendor remote skills into the repository.
+
+use crate::cli::{AddArgs, Cli, VendorArgs};
+use crate::commands::export::ExportedSkill;
+use crate::commands::import::group_skills;
+use crate::config::Config;
+use crate::error::SkiloError;
+use crate::lockfile::Lockfile;
+use crate::output::get_formatter;
+use crate::scope::Scope;
+use colored::Colorize;
+use std::path::{Path, PathBuf};
+
+/// Directory vendored skills are installed into, relative to the project root.
+pub const VENDOR_DIR: &str = "vendor/skills";
+
+/// Run the vendor command.
+///
+/// Vendored skills are installed like `add --output vendor/skills` and
+/// recorded in the project `skills.lock`, which `--update` reads back to
+/// refetch every vendored skill from its recorded source.
+pub fn run(args: VendorArgs, config: &Config, cli: &Cli) -> Result<i32, SkiloError> {
+    let project_root = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    let vendor_dir = project_root.join(VENDOR_DIR);
+
+    if args.update {
+        return update(&args, config, cli, &project_root);
+    }
+
+    let Some(source) = args.source.clone() else {
+        return Err(SkiloError::Config(
+            "A source is required unless --update is given".to_string(),
+        ));
+    };
+
+    let add_args = AddArgs {
+        source: Some(source),
+        from_file: None,
+        skill: args.skill.clone(),
+        list: false,
+        yes: args.yes,
+        branch: args.branch.clone(),
+        tag: args.tag.clone(),
+        rev: args.rev.clone(),
+        path: args.path.clone(),
+        agent: None,
+        global: false,
+        scope: None,
+        output: Some(vendor_dir),
+        register: false,
+        ssh_key: None,
+    };
+
+    crate::commands::add::run(add_args, config, cli)
+}
+
+/// Refetch every vendored skill recorded in the project lockfile.
+fn update(
+    args: &VendorArgs,
+    config: &Config,
+    cli: &Cli,
+    project_root: &Path,
+) -> Result<i32, SkiloError> {
+    let formatter = get_formatter(cli.format, cli.quiet);
+    let lock = Lockfile::load_scope(Scope::Project, project_root)?;
+
+    let vendored: Vec<ExportedSkill> = lock
+        .skills
+        .iter()
+        .filter(|s| s.path.starts_with(VENDOR_DIR))
+        .filter(|s| {
+            args.skill
+                .as_ref()
+                .is_none_or(|names| names.contains(&s.name))
+        })
+        .map(|s| ExportedSkill {
+            name: s.name.clone(),
+            agent: None,
+            scope: Scope::Project,
+            source: s.source.clone(),
+            subdir: s.subdir.clone(),
+            branch: s.branch.clone(),
+            tag: s.tag.clone(),
+            rev: s.rev.clone(),
+            commit: s.commit.clone(),
+        })
+        .collect();
+
+    if vendored.is_empty() {
+        formatter.format_message("No vendored skills to update.");
+        return Ok(0);
+    }
+
+    let groups = group_skills(&vendored);
+    let mut failed = 0;
+
+    for group in &groups {
+        let first = group[0];
+        let source = match &first.rev {
+            Some(rev) => format!("{}@{}", first.source, rev),
+            None => first.source.clone(),
+        };
+        let add_args = AddArgs {
+            source: Some(source),
+            from_file: None,
+            skill: Some(group.iter().map(|s| s.name.clone()).collect()),
+            list: false,
+            // Overwriting the vendored copy is the point of an update
+            yes: true,
+            branch: first.branch.clone(),
+            tag: first.tag.clone(),
+            rev: None,
+            path: first.subdir.clone(),
+            agent: None,
+            global: false,
+            scope: None,
+            output: Some(project_root.join(VENDOR_DIR)),
+            register: false,
+            ssh_key: None,
+        };
+
+        match crate::commands::add::run(add_args, config, cli) {
+            Ok(0) => {}
+            Ok(_) => failed += 1,
+            Err(SkiloError::Cancelled) => return Err(SkiloError::Cancelled),
+            Err(e) => {
+                failed += 1;
+                formatter.format_error(&format!("Failed to update from {}: {}", first.source, e));
+            }
+        }
+    }
+
+    if failed > 0 {
+        if !cli.quiet {
+            eprintln!(
+                "{}: {} of {} source{} failed to update",
+                "Warning".yellow(),
+                failed,
+                groups.len(),
+                if groups.len() == 1 { "" } else { "s" }
+            );
+        }
+        Ok(1)
+    } else {
+        Ok(0)
+    }
+}
@@ -0,0 +1,231 @@
+//! Finds the installed skills that provide a script or request a tool.
+
+use crate::agent::Agent;
+use crate::cli::{AgentSelection, Cli, OutputFormat, ScopeArg, WhichArgs};
+use crate::commands::grep::installed_skills;
+use crate::config::Config;
+use crate::error::SkiloError;
+use crate::lockfile::{lock_path, Lockfile};
+use crate::output::{print_porcelain, EMPTY_FIELD};
+use crate::scope::{InstalledSkill, Scope};
+use crate::skill::Manifest;
+use colored::Colorize;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// A skill providing the script or requesting the tool.
+#[derive(Debug, Serialize)]
+struct WhichMatch {
+    /// Name of the skill.
+    skill: String,
+    /// Agent the skill is installed for, if any.
+    agent: Option<Agent>,
+    /// Installation scope.
+    scope: Scope,
+    /// `script` or `tool`.
+    kind: &'static str,
+    /// The script path within the skill, or the `allowed-tools` entry.
+    entry: String,
+    /// The script, or the SKILL.md requesting the tool.
+    path: PathBuf,
+    /// The source the skill was installed from, per `skills.lock`.
+    source: Option<String>,
+    /// The installed commit, for git sources.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    commit: Option<String>,
+}
+
+/// Run the which command.
+///
+/// Exits with 1 when no installed skill matches.
+pub fn run(args: WhichArgs, _config: &Config, cli: &Cli) -> Result<i32, SkiloError> {
+    let project_root = args
+        .path
+        .canonicalize()
+        .unwrap_or_else(|_| args.path.clone());
+    let scopes = match args.scope {
+        Some(scope) => scope.scopes(),
+        None if args.all => ScopeArg::Both.scopes(),
+        None if args.global => vec![Scope::Global],
+        None => vec![Scope::Project],
+    };
+    let selection = args
+        .agent
+        .as_ref()
+        .map(|a| a.to_selection())
+        .unwrap_or(AgentSelection::All);
+
+    // A path to an existing file matches the script at that path
+    let query_path = Path::new(&args.name).canonicalize().ok();
+
+    let mut matches = Vec::new();
+    for &scope in &scopes {
+        let lockfile = Lockfile::load_scope(scope, &project_root).unwrap_or_default();
+        for skill in installed_skills(selection, &[scope], &project_root) {
+            let receipt = lockfile.find_path(&lock_path(scope, &skill.path, &project_root));
+            for (kind, entry, path) in find_in_skill(&skill, &args.name, query_path.as_deref()) {
+                matches.push(WhichMatch {
+                    skill: skill.name.clone(),
+                    agent: skill.agent,
+                    scope,
+                    kind,
+                    entry,
+                    path,
+                    source: receipt.map(|r| r.source.clone()),
+                    commit: receipt.and_then(|r| r.commit.clone()),
+                });
+            }
+        }
+    }
+
+    let exit_code = if matches.is_empty() { 1 } else { 0 };
+    if cli.quiet {
+        return Ok(exit_code);
+    }
+
+    if matches!(cli.format, OutputFormat::Json) {
+        let json = serde_json::to_string_pretty(&matches)
+            .map_err(|e| SkiloError::Config(format!("JSON serialization failed: {}", e)))?;
+        println!("{}", json);
+        return Ok(exit_code);
+    }
+
+    if cli.porcelain {
+        for m in &matches {
+            print_porcelain(&[
+                m.scope.display_name(),
+                m.agent.map_or(EMPTY_FIELD, |a| a.cli_name()),
+                &m.skill,
+                m.kind,
+                &m.entry,
+                &m.path.display().to_string(),
+                m.source.as_deref().unwrap_or(EMPTY_FIELD),
+            ]);
+        }
+        return Ok(exit_code);
+    }
+
+    if matches.is_empty() {
+        eprintln!("No installed skill provides or requests '{}'", args.name);
+        return Ok(exit_code);
+    }
+
+    for m in &matches {
+        let installed_for = match m.agent {
+            Some(agent) => format!("{}, {}", agent.display_name(), m.scope),
+            None => format!("skills/, {}", m.scope),
+        };
+        println!(
+            "{} {}",
+            m.skill.cyan().bold(),
+            format!("({})", installed_for).dimmed()
+        );
+        println!("  {:<7}{}", m.kind, m.entry);
+        println!("  {:<7}{}", "path", m.path.display());
+        let source = match (&m.source, &m.commit) {
+            (Some(source), Some(commit)) => {
+                format!("{} @ {}", source, &commit[..commit.len().min(7)])
+            }
+            (Some(source), None) => source.clone(),
+            (None, _) => "unknown (not in skills.lock)".dimmed().to_string(),
+        };
+        println!("  {:<7}{}", "source", source);
+    }
+
+    Ok(exit_code)
+}
+
+/// Scripts of `skill` named `name` (or at `query_path`), and its
+/// `allowed-tools` entries for the tool `name`.
+fn find_in_skill(
+    skill: &InstalledSkill,
+    name: &str,
+    query_path: Option<&Path>,
+) -> Vec<(&'static str, String, PathBuf)> {
+    let mut found = Vec::new();
+
+    let scripts = WalkDir::new(skill.path.join("scripts"))
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|e| e.file_type().is_file());
+    for script in scripts {
+        let relative = script
+            .path()
+            .strip_prefix(&skill.path)
+            .unwrap_or(script.path())
+            .to_path_buf();
+        let same_file = query_path
+            .is_some_and(|query| script.path().canonicalize().ok().as_deref() == Some(query));
+        if script.file_name() == name || relative == Path::new(name) || same_file {
+            found.push(("script", relative.display().to_string(), script.into_path()));
+        }
+    }
+
+    let skill_md = skill.path.join("SKILL.md");
+    if let Ok(manifest) = Manifest::parse(skill_md.clone()) {
+        for tool in manifest.frontmatter.allowed_tool_list() {
+            if tool_matches(&tool, name) {
+                found.push(("tool", tool, skill_md.clone()));
+            }
+        }
+    }
+
+    found
+}
+
+/// Returns true if the `allowed-tools` entry `tool` is `name`, or is a
+/// rule for the tool `name` (`Bash(git:*)` matches `Bash`).
+fn tool_matches(tool: &str, name: &str) -> bool {
+    let tool_name = tool.split('(').next().unwrap_or(tool);
+    tool == name || tool_name.eq_ignore_ascii_case(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_find_in_skill() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path().join("my-skill");
+        std::fs::create_dir_all(dir.join("scripts/lib")).unwrap();
+        std::fs::write(
+            dir.join("SKILL.md"),
+            "---\nname: my-skill\ndescription: Test\nallowed-tools: Read Bash(git log:*)\n---\n\n# My Skill\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join("scripts/run.sh"), "#!/bin/sh\n").unwrap();
+        std::fs::write(dir.join("scripts/lib/util.sh"), "#!/bin/sh\n").unwrap();
+
+        let skill = InstalledSkill {
+            name: "my-skill".into(),
+            description: "Test".into(),
+            path: dir.clone(),
+            agent: None,
+            scope: Scope::Project,
+            deprecated: false,
+            superseded_by: None,
+            tags: Vec::new(),
+            version: None,
+        };
+
+        let found = find_in_skill(&skill, "util.sh", None);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].0, "script");
+        assert_eq!(
+            found[0].1,
+            Path::new("scripts/lib/util.sh").display().to_string()
+        );
+
+        let run = dir.join("scripts/run.sh").canonicalize().unwrap();
+        assert_eq!(find_in_skill(&skill, "x", Some(&run)).len(), 1);
+
+        let found = find_in_skill(&skill, "bash", None);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].1, "Bash(git log:*)");
+        assert!(find_in_skill(&skill, "Write", None).is_empty());
+    }
+}
@@ -1,8 +1,14 @@
 //! Configuration file handling.
 
 use crate::agent::Agent;
+use crate::scope::Scope;
+use crate::skill::formatter::{DescriptionStyle, TableStyle};
+use crate::skill::rules::{NameFix, DEFAULT_TRIGGERS};
+use crate::skill::DiagnosticCode;
+use colored::Colorize;
 use serde::{Deserialize, Deserializer};
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 
 /// A configurable threshold that can be default, disabled, or a specific value.
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
@@ -59,20 +65,93 @@ pub struct Config {
     pub add: AddConfig,
     /// Discovery configuration.
     pub discovery: DiscoveryConfig,
+    /// Commands run around install and remove operations.
+    pub hooks: HooksConfig,
+    /// Local operation history.
+    pub history: HistoryConfig,
 }
 
-/// Configuration for the lint command.
+/// Configuration for the local operation history.
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct HistoryConfig {
+    /// Record installs, updates, and removals in `~/.skilo/history.jsonl`.
+    pub enabled: bool,
+}
+
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+/// Commands run around install and remove operations.
+///
+/// Only read from the global config (or an explicit `--config` file): a
+/// cloned repository's `.skilorc.toml` cannot run commands. See
+/// [`crate::hooks`] for the environment passed to them.
 #[derive(Debug, Default, Deserialize)]
 #[serde(default)]
+pub struct HooksConfig {
+    /// Run after `add` installs new skills.
+    pub post_add: Option<String>,
+    /// Run before `remove` deletes skills; a failure aborts the removal.
+    pub pre_remove: Option<String>,
+    /// Run after `add` replaces skills that were already installed.
+    pub post_update: Option<String>,
+}
+
+/// Configuration for the lint command.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default)]
 pub struct LintConfig {
     /// Treat warnings as errors.
     pub strict: bool,
+    /// Agents the skills target, for feature compatibility (W010) and reserved
+    /// name (W011) warnings.
+    pub agents: Vec<Agent>,
+    /// Diagnostic codes to suppress (e.g. `["W001"]`).
+    pub disable: Vec<String>,
+    /// How `--fix` resolves name/directory mismatches (E003): `"name"`
+    /// rewrites the frontmatter name, `"directory"` renames the directory.
+    pub name_fix: NameFix,
     /// Rule-specific configuration.
     pub rules: RulesConfig,
 }
 
+impl LintConfig {
+    /// Warnings about unknown or deprecated codes in `disable`.
+    pub fn code_warnings(&self) -> Vec<String> {
+        self.disable
+            .iter()
+            .filter_map(|code| match DiagnosticCode::parse(code) {
+                None => Some(format!(
+                    "unknown diagnostic code '{}' in [lint] disable",
+                    code
+                )),
+                Some(parsed) => {
+                    let meta = parsed.metadata();
+                    if !meta.deprecated {
+                        return None;
+                    }
+                    Some(match meta.replaced_by {
+                        Some(new) => format!(
+                            "diagnostic code {} is deprecated; disable {} instead",
+                            parsed, new
+                        ),
+                        None => format!(
+                            "diagnostic code {} is deprecated and no longer reported",
+                            parsed
+                        ),
+                    })
+                }
+            })
+            .collect()
+    }
+}
+
 /// Configuration for individual lint rules.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 #[serde(default)]
 pub struct RulesConfig {
     /// Enable name format validation (E001).
@@ -87,6 +166,14 @@ pub struct RulesConfig {
     /// Maximum description length (E005).
     #[serde(deserialize_with = "deserialize_threshold")]
     pub description_length: Threshold,
+    /// Require the description to say when to use the skill (W016).
+    pub description_trigger: bool,
+    /// Case-insensitive regexes, one of which the description must match
+    /// (W016).
+    pub description_triggers: Vec<String>,
+    /// Maximum summary length (W012).
+    #[serde(deserialize_with = "deserialize_threshold")]
+    pub summary_length: Threshold,
     /// Maximum compatibility length (E006).
     #[serde(deserialize_with = "deserialize_threshold")]
     pub compatibility_length: Threshold,
@@ -95,10 +182,48 @@ pub struct RulesConfig {
     /// Maximum body length in lines (W001).
     #[serde(deserialize_with = "deserialize_threshold")]
     pub body_length: Threshold,
+    /// Maximum estimated body tokens (W008).
+    #[serde(deserialize_with = "deserialize_threshold")]
+    pub body_tokens: Threshold,
+    /// Count `references/` files towards the body token budget (W008).
+    pub body_tokens_include_references: bool,
+    /// Flag skills nested inside another skill (W009).
+    pub nested_skill: bool,
     /// Check scripts are executable (W002).
     pub script_executable: bool,
     /// Check scripts have shebang (W003).
     pub script_shebang: bool,
+    /// Check script interpreters are installed on this machine (W014).
+    pub script_interpreter: bool,
+    /// Check the Scripts section documents exactly the files in `scripts/` (W015).
+    pub script_docs: bool,
+    /// Validate deprecation metadata (E010, W005).
+    pub deprecation: bool,
+    /// Validate context, agent, model, and hooks fields (E011).
+    pub extended_fields: bool,
+    /// Validate per-agent overrides under `agents` (E013).
+    pub agent_overrides: bool,
+    /// Validate the `requires` runtime requirements (E015).
+    pub requirements: bool,
+    /// Check encodings and line endings of SKILL.md and scripts (E014, W013).
+    pub encoding: bool,
+    /// Flag credentials committed in skill files (E016).
+    pub secrets: bool,
+    /// Warn about features unsupported by `lint.agents` (W010).
+    pub agent_compatibility: bool,
+    /// Warn about names that collide with built-in agent commands (W011).
+    pub reserved_names: bool,
+    /// Check tag format (W006).
+    pub tag_format: bool,
+    /// Maximum number of tags (W007).
+    #[serde(deserialize_with = "deserialize_threshold")]
+    pub tag_count: Threshold,
+    /// Maximum number of examples (W017).
+    #[serde(deserialize_with = "deserialize_threshold")]
+    pub example_count: Threshold,
+    /// Maximum length of each example (W018).
+    #[serde(deserialize_with = "deserialize_threshold")]
+    pub example_length: Threshold,
 }
 
 impl Default for RulesConfig {
@@ -109,11 +234,31 @@ impl Default for RulesConfig {
             name_directory: true,
             description_required: true,
             description_length: Threshold::Default,
+            description_trigger: false,
+            description_triggers: DEFAULT_TRIGGERS.iter().map(|t| t.to_string()).collect(),
+            summary_length: Threshold::Default,
             compatibility_length: Threshold::Default,
             references_exist: true,
             body_length: Threshold::Default,
+            body_tokens: Threshold::Default,
+            body_tokens_include_references: false,
+            nested_skill: true,
             script_executable: true,
             script_shebang: true,
+            script_interpreter: false,
+            script_docs: true,
+            deprecation: true,
+            extended_fields: true,
+            agent_overrides: true,
+            requirements: true,
+            encoding: true,
+            secrets: false,
+            agent_compatibility: true,
+            reserved_names: true,
+            tag_format: true,
+            tag_count: Threshold::Default,
+            example_count: Threshold::Default,
+            example_length: Threshold::Default,
         }
     }
 }
@@ -128,6 +273,18 @@ pub struct FmtConfig {
     pub indent_size: usize,
     /// Format markdown tables.
     pub format_tables: bool,
+    /// Table layout: "padded" or "compact".
+    pub table_style: TableStyle,
+    /// Minimum table column width.
+    pub table_min_width: usize,
+    /// Pad table cells according to their column alignment.
+    pub align_table_cells: bool,
+    /// Rewrite CRLF line endings as LF and drop byte order marks, in
+    /// SKILL.md and in scripts.
+    pub normalize_line_endings: bool,
+    /// Layout of descriptions written over several lines: "folded" or
+    /// "single-line".
+    pub description_style: DescriptionStyle,
 }
 
 impl Default for FmtConfig {
@@ -136,6 +293,11 @@ impl Default for FmtConfig {
             sort_frontmatter: true,
             indent_size: 2,
             format_tables: true,
+            table_style: TableStyle::Padded,
+            table_min_width: 3,
+            align_table_cells: true,
+            normalize_line_endings: false,
+            description_style: DescriptionStyle::Folded,
         }
     }
 }
@@ -150,6 +312,11 @@ pub struct NewConfig {
     pub default_template: String,
     /// Default script language for new skills.
     pub default_lang: String,
+    /// Template variables, used as `{{key}}` in generated files.
+    ///
+    /// `header` and `footer` are added around the body of every new
+    /// SKILL.md.
+    pub vars: BTreeMap<String, String>,
 }
 
 impl Default for NewConfig {
@@ -158,6 +325,7 @@ impl Default for NewConfig {
             default_license: None,
             default_template: "hello-world".into(),
             default_lang: "python".into(),
+            vars: BTreeMap::new(),
         }
     }
 }
@@ -168,24 +336,102 @@ impl Default for NewConfig {
 pub struct AddConfig {
     /// Target agent for skill installation. If None, installs to ./skills/ in current directory.
     pub default_agent: Option<Agent>,
+    /// Target agents for skill installation. Takes precedence over `default_agent`.
+    pub default_agents: Vec<Agent>,
+    /// Scope used when `--global` is not given.
+    pub default_scope: Scope,
+    /// Glob patterns for git sources that may be installed from.
+    ///
+    /// Patterns are matched against `host/owner/repo` (e.g. `github.com/my-org/*`).
+    /// Patterns without a host (e.g. `my-org/*`) match `owner/repo` on any host.
+    /// An empty list allows any source. Local paths are always allowed.
+    /// Project config can narrow the global list but not widen it: a
+    /// source must match both.
+    pub allowed_sources: Vec<String>,
+    /// `allowed_sources` from the global config, set by [`Config::load`].
+    #[serde(skip)]
+    pub global_allowed_sources: Vec<String>,
+    /// Skill names or glob patterns (e.g. `legacy-*`) that are never installed.
+    pub exclude: Vec<String>,
     /// Prompt before installing (false for CI).
     pub confirm: bool,
     /// Validate skills before installing.
     pub validate: bool,
+    /// Write an `installed-by` comment at the top of installed SKILL.md bodies.
+    pub provenance_header: bool,
+    /// Per-skill install targets that replace the defaults, keyed by skill name.
+    pub overrides: BTreeMap<String, SkillOverride>,
+    /// Initialize and update git submodules of fetched repositories.
+    pub submodules: bool,
+    /// Download Git LFS objects of fetched repositories.
+    pub lfs: bool,
+    /// List the skills directory in the agent's config file, for agents
+    /// that only load skills from configured paths.
+    pub register: bool,
+    /// SSH private key tried after the SSH agent for git fetches.
+    ///
+    /// A leading `~` is expanded to the home directory.
+    pub ssh_key: Option<PathBuf>,
+    /// Keep skill files once in `~/.skilo/store/` and hard link them into
+    /// each skills directory, instead of copying them.
+    pub store: bool,
+}
+
+/// Install targets for a single skill, set under `[add.overrides.<name>]`.
+///
+/// Overrides replace `default_agents` and `default_scope` for that skill.
+/// Explicit `--agent`, `--global`, `--scope`, or `--output` flags still win.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct SkillOverride {
+    /// Agents to install the skill to.
+    pub agents: Vec<Agent>,
+    /// Scope to install the skill to.
+    pub scope: Option<Scope>,
 }
 
 impl Default for AddConfig {
     fn default() -> Self {
         Self {
             default_agent: None,
+            default_agents: Vec::new(),
+            default_scope: Scope::Project,
+            allowed_sources: Vec::new(),
+            global_allowed_sources: Vec::new(),
+            exclude: Vec::new(),
             confirm: true,
             validate: true,
+            provenance_header: false,
+            overrides: BTreeMap::new(),
+            submodules: true,
+            lfs: true,
+            register: false,
+            ssh_key: None,
+            store: true,
+        }
+    }
+}
+
+impl AddConfig {
+    /// Returns the configured default agents.
+    ///
+    /// `default_agents` wins over `default_agent` when both are set.
+    pub fn default_agent_list(&self) -> Vec<Agent> {
+        if self.default_agents.is_empty() {
+            self.default_agent.into_iter().collect()
+        } else {
+            self.default_agents.clone()
         }
     }
+
+    /// Returns the first configured default agent, if any.
+    pub fn primary_agent(&self) -> Option<Agent> {
+        self.default_agent_list().first().copied()
+    }
 }
 
 /// Configuration for skill discovery.
-#[derive(Debug, Default, Deserialize)]
+#[derive(Debug, Deserialize)]
 #[serde(default)]
 pub struct DiscoveryConfig {
     /// Glob patterns for directories to ignore during skill discovery.
@@ -197,24 +443,88 @@ pub struct DiscoveryConfig {
     /// - `foo/bar` - match path "foo/bar" relative to search root
     /// - `**/cache` - match "cache" directory at any depth
     pub ignore: Vec<String>,
+    /// Skill roots searched when no path is given, e.g. `packages/*/skills`.
+    ///
+    /// Paths are relative to the project directory and may be globs.
+    pub roots: Vec<PathBuf>,
+    /// Maximum directory depth to search below each root.
+    pub max_depth: usize,
+    /// Follow symbolic links while searching.
+    pub follow_symlinks: bool,
+}
+
+impl Default for DiscoveryConfig {
+    fn default() -> Self {
+        Self {
+            ignore: Vec::new(),
+            roots: Vec::new(),
+            max_depth: 10,
+            follow_symlinks: true,
+        }
+    }
 }
 
 impl Config {
+    /// Agents that lint checks target.
+    ///
+    /// `[lint] agents` wins; otherwise the `[add]` default agents are used.
+    pub fn lint_agents(&self) -> Vec<Agent> {
+        if self.lint.agents.is_empty() {
+            self.add.default_agent_list()
+        } else {
+            self.lint.agents.clone()
+        }
+    }
+
     /// Load configuration from a file or find it automatically.
+    ///
+    /// An explicit `path` is the only config read. Otherwise the global
+    /// config (`~/.skilo/config.toml`) is loaded first, then the project
+    /// config is layered on top of it, so projects only need to set the
+    /// keys they want to override. `[hooks]` in the project config is
+    /// ignored.
     pub fn load(path: Option<&PathBuf>) -> std::result::Result<Self, std::io::Error> {
-        let config_path = path.cloned().or_else(Self::find_config);
+        if let Some(path) = path {
+            if !path.exists() {
+                return Ok(Self::default());
+            }
+            return into_config(read_table(path)?);
+        }
 
-        let Some(config_path) = config_path else {
-            return Ok(Self::default());
-        };
+        let global_path = Self::global_config_path().filter(|p| p.exists());
+        let project_path = Self::find_config()
+            .filter(|p| !global_path.as_deref().is_some_and(|g| same_file(p, g)));
 
-        if !config_path.exists() {
-            return Ok(Self::default());
+        let mut merged = match &global_path {
+            Some(global_path) => read_table(global_path)?,
+            None => toml::Table::new(),
+        };
+        let global = into_config(merged.clone())?;
+        if let Some(project_path) = &project_path {
+            let project = read_table(project_path)?;
+            if project.contains_key("hooks") {
+                eprintln!(
+                    "{}: ignoring [hooks] in {}; hooks are only read from {}",
+                    "Warning".yellow(),
+                    project_path.display(),
+                    Self::global_config_path()
+                        .map(|p| p.display().to_string())
+                        .unwrap_or_else(|| "the global config".into())
+                );
+            }
+            merge_tables(&mut merged, project);
         }
 
-        let content = std::fs::read_to_string(&config_path)?;
-        toml::from_str(&content)
-            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string()))
+        let mut config = into_config(merged)?;
+        config.add.global_allowed_sources = global.add.allowed_sources;
+        // Hooks run shell commands, which a cloned repository must not set
+        config.hooks = global.hooks;
+        Ok(config)
+    }
+
+    /// Path to the global config file (`~/.skilo/config.toml`).
+    pub fn global_config_path() -> Option<PathBuf> {
+        crate::cache::skilo_home().map(|h| h.join("config.toml"))
     }
 
     fn find_config() -> Option<PathBuf> {
@@ -230,3 +540,88 @@ impl Config {
         None
     }
 }
+
+/// Read the config file at `path` as a TOML table.
+fn read_table(path: &Path) -> std::result::Result<toml::Table, std::io::Error> {
+    let content = std::fs::read_to_string(path)?;
+    toml::from_str(&content).map_err(|e| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("{}: {}", path.display(), e),
+        )
+    })
+}
+
+/// Deserialize a config from a (merged) TOML table.
+fn into_config(table: toml::Table) -> std::result::Result<Config, std::io::Error> {
+    toml::Value::Table(table)
+        .try_into()
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string()))
+}
+
+/// Recursively merge `overlay` into `base`, with `overlay` winning on conflicts.
+fn merge_tables(base: &mut toml::Table, overlay: toml::Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(existing)), toml::Value::Table(incoming)) => {
+                merge_tables(existing, incoming);
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Returns true if both paths refer to the same file.
+fn same_file(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_tables_overrides_nested_keys() {
+        let mut base: toml::Table =
+            toml::from_str("[add]\ndefault_agent = \"claude\"\nconfirm = false\n").unwrap();
+        let overlay: toml::Table = toml::from_str(
+            "[add]\ndefault_agents = [\"cursor\", \"codex\"]\ndefault_scope = \"global\"\n",
+        )
+        .unwrap();
+        merge_tables(&mut base, overlay);
+
+        let config: Config = toml::Value::Table(base).try_into().unwrap();
+        assert!(!config.add.confirm);
+        assert_eq!(config.add.default_scope, Scope::Global);
+        assert_eq!(
+            config.add.default_agent_list(),
+            vec![Agent::Cursor, Agent::Codex]
+        );
+        assert_eq!(config.add.primary_agent(), Some(Agent::Cursor));
+    }
+
+    #[test]
+    fn test_default_agent_list_falls_back_to_default_agent() {
+        let config: Config = toml::from_str("[add]\ndefault_agent = \"claude\"\n").unwrap();
+        assert_eq!(config.add.default_agent_list(), vec![Agent::Claude]);
+        assert_eq!(config.add.default_scope, Scope::Project);
+    }
+
+    #[test]
+    fn test_disabled_code_warnings() {
+        let config: Config =
+            toml::from_str("[lint]\ndisable = [\"w001\", \"W004\", \"X999\"]\n").unwrap();
+        assert_eq!(
+            config.lint.code_warnings(),
+            vec![
+                "diagnostic code W004 is deprecated and no longer reported",
+                "unknown diagnostic code 'X999' in [lint] disable",
+            ]
+        );
+    }
+}
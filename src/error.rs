@@ -61,6 +61,14 @@ pub enum SkiloError {
     #[diagnostic(code(skilo::invalid_source))]
     InvalidSource(String, String),
 
+    /// The source is not in the configured allowlist.
+    #[error("Source not allowed: {0}")]
+    #[diagnostic(
+        code(skilo::source_not_allowed),
+        help("Add a matching pattern to `add.allowed_sources` in your config")
+    )]
+    SourceNotAllowed(String),
+
     /// Git operation failed.
     #[error("Git error: {message}")]
     #[diagnostic(code(skilo::git))]
@@ -95,6 +103,27 @@ pub enum SkiloError {
         message: String,
     },
 
+    /// A hook that must succeed before an operation failed.
+    #[error("{hook} hook failed: {message}")]
+    #[diagnostic(code(skilo::hook_failed))]
+    HookFailed {
+        /// The hook name.
+        hook: String,
+        /// How the hook failed.
+        message: String,
+    },
+
+    /// Another running process is writing the same location.
+    #[error("Another operation is in progress on {path}")]
+    #[diagnostic(
+        code(skilo::operation_in_progress),
+        help("Wait for it to finish; if it was interrupted, run `skilo doctor --repair`")
+    )]
+    OperationInProgress {
+        /// The location being written.
+        path: String,
+    },
+
     /// User cancelled the operation.
     #[error("Operation cancelled by user")]
     #[diagnostic(code(skilo::cancelled))]
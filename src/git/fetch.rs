@@ -7,12 +7,23 @@
 use crate::cache::{
     checkout_name, checkouts_dir, db_dir, db_name, ensure_dir, is_offline, parse_owner_repo,
 };
-use crate::git::source::GitSource;
+use crate::git::auth::Credentials;
+use crate::git::lfs;
+use crate::git::source::{is_relative_subdir, GitSource};
+use crate::journal::{OperationKind, StagedDir};
 use crate::SkiloError;
-use git2::{build::RepoBuilder, Cred, FetchOptions, RemoteCallbacks, Repository};
+use git2::{
+    build::RepoBuilder, AutotagOption, FetchOptions, FetchPrune, RemoteCallbacks, Repository,
+    SubmoduleUpdateOptions,
+};
 use std::path::{Path, PathBuf};
 use tempfile::TempDir;
 
+/// Refspec mirroring upstream branches into a bare cache.
+const BRANCH_REFSPEC: &str = "+refs/heads/*:refs/heads/*";
+/// Refspec mirroring upstream tags, including moved ones.
+const TAG_REFSPEC: &str = "+refs/tags/*:refs/tags/*";
+
 /// Result of a successful fetch operation.
 pub struct FetchResult {
     /// The path to the root of the repository (or subdir if specified).
@@ -24,8 +35,35 @@ pub struct FetchResult {
     pub checkout_dir: Option<PathBuf>,
     /// Whether the result came from cache.
     pub from_cache: bool,
-    /// The commit hash of the checkout.
+    /// The full commit hash of the checkout.
     pub commit: Option<String>,
+    /// Git LFS pointer files under `root` whose content was not downloaded.
+    pub lfs_pointers: Vec<PathBuf>,
+}
+
+/// What to materialize in a checkout besides the tracked files.
+#[derive(Debug, Clone, Copy)]
+pub struct CheckoutOptions {
+    /// Initialize and update submodules, recursively.
+    pub submodules: bool,
+    /// Download Git LFS objects over their pointer files.
+    pub lfs: bool,
+}
+
+impl Default for CheckoutOptions {
+    fn default() -> Self {
+        Self {
+            submodules: true,
+            lfs: true,
+        }
+    }
+}
+
+impl FetchResult {
+    /// The abbreviated commit hash, for display.
+    pub fn short_commit(&self) -> Option<&str> {
+        self.commit.as_deref().map(|c| &c[..7.min(c.len())])
+    }
 }
 
 /// Fetch a git repository, using cache when possible.
@@ -34,18 +72,32 @@ pub struct FetchResult {
 /// 1. Clone/fetch bare repo to `~/.skilo/git/db/{owner}-{repo}/`
 /// 2. Checkout specific revision to `~/.skilo/git/checkouts/{owner}-{repo}-{rev}/`
 /// 3. Return the checkout path
-pub fn fetch(source: &GitSource) -> Result<FetchResult, SkiloError> {
+///
+/// `options` controls whether submodules and Git LFS objects are fetched,
+/// so skills can share references and keep large assets outside the repo.
+/// `credentials` authenticates against private remotes.
+pub fn fetch(
+    source: &GitSource,
+    options: CheckoutOptions,
+    credentials: &Credentials,
+) -> Result<FetchResult, SkiloError> {
     // Try to use cache if we can parse owner/repo
     if let Some((owner, repo)) = parse_owner_repo(&source.url) {
-        return fetch_cached(source, &owner, &repo);
+        return fetch_cached(source, &owner, &repo, options, credentials);
     }
 
     // Fall back to temporary directory for non-standard URLs
-    fetch_to_temp(source)
+    fetch_to_temp(source, options, credentials)
 }
 
 /// Fetch using the cache directory structure.
-fn fetch_cached(source: &GitSource, owner: &str, repo: &str) -> Result<FetchResult, SkiloError> {
+fn fetch_cached(
+    source: &GitSource,
+    owner: &str,
+    repo: &str,
+    options: CheckoutOptions,
+    credentials: &Credentials,
+) -> Result<FetchResult, SkiloError> {
     let db = db_dir()
         .ok_or_else(|| SkiloError::Config("Could not determine cache directory".to_string()))?;
     let checkouts = checkouts_dir()
@@ -64,11 +116,11 @@ fn fetch_cached(source: &GitSource, owner: &str, repo: &str) -> Result<FetchResu
         })?;
 
         if !is_offline() {
-            if let Err(e) = fetch_updates(&repo, &source.url) {
+            if let Err(e) = fetch_updates(&repo, &source.url, source.reference(), credentials) {
                 if matches!(&e, SkiloError::AuthenticationFailed) {
                     if let Some(ssh_url) = https_to_ssh_url(&source.url) {
                         eprintln!("HTTPS auth failed, retrying fetch with SSH: {}", ssh_url);
-                        fetch_updates(&repo, &ssh_url)?;
+                        fetch_updates(&repo, &ssh_url, source.reference(), credentials)?;
                     } else {
                         return Err(e);
                     }
@@ -86,63 +138,85 @@ fn fetch_cached(source: &GitSource, owner: &str, repo: &str) -> Result<FetchResu
             });
         }
 
-        // Clone as bare repository
-        match clone_bare(&source.url, &db_path) {
-            Ok(repo) => repo,
+        // Clone as bare repository, staged so an interrupted clone is never used
+        let staged = StagedDir::begin(OperationKind::Fetch, &db_path)?;
+        match clone_bare(&source.url, staged.path(), credentials) {
+            Ok(_) => {}
             Err(e) if matches!(&e, SkiloError::AuthenticationFailed) => {
                 if let Some(ssh_url) = https_to_ssh_url(&source.url) {
                     eprintln!("HTTPS auth failed, retrying clone with SSH: {}", ssh_url);
                     // Clean up partial clone directory before retrying
-                    let _ = std::fs::remove_dir_all(&db_path);
-                    clone_bare(&ssh_url, &db_path)?
+                    let _ = std::fs::remove_dir_all(staged.path());
+                    clone_bare(&ssh_url, staged.path(), credentials)?;
                 } else {
                     return Err(e);
                 }
             }
             Err(e) => return Err(e),
         }
+        staged.commit()?;
+
+        let repo = Repository::open_bare(&db_path).map_err(|e| SkiloError::Git {
+            message: format!("Failed to open cached repo: {}", e),
+        })?;
+
+        // A clone only fetches branches and tags
+        if source.reference().and_then(pull_request_refspec).is_some() {
+            fetch_updates(&repo, &source.url, source.reference(), credentials)?;
+        }
+
+        repo
     };
 
     // Resolve the reference to a commit
-    let commit_id = resolve_reference(&bare_repo, source.reference())?;
-    let short_commit = &commit_id[..7.min(commit_id.len())];
+    let commit_id = resolve_or_fetch(&bare_repo, source, credentials)?;
 
     // Check if we already have this checkout
     let checkout_path = checkouts.join(checkout_name(owner, repo, &commit_id));
 
     if !checkout_path.exists() {
         // Create the checkout from the bare repo
-        checkout_from_bare(&bare_repo, &commit_id, &checkout_path)?;
+        let staged = StagedDir::begin(OperationKind::Fetch, &checkout_path)?;
+        checkout_from_bare(&bare_repo, &commit_id, staged.path())?;
+        staged.commit()?;
     }
 
-    // Determine the root path (may be a subdirectory)
-    let root = if let Some(ref subdir) = source.subdir {
-        checkout_path.join(subdir)
-    } else {
-        checkout_path.clone()
-    };
-
-    if !root.exists() {
-        return Err(SkiloError::InvalidSource(
-            source.url.clone(),
-            format!(
-                "Subdirectory '{}' not found in repository",
-                source.subdir.as_deref().unwrap_or("")
-            ),
-        ));
+    if options.submodules || options.lfs {
+        let checkout_repo = Repository::open(&checkout_path).map_err(|e| SkiloError::Git {
+            message: format!("Failed to open checkout: {}", e),
+        })?;
+        // Relative submodule URLs and the LFS server resolve against origin,
+        // which is the bare repo
+        checkout_repo
+            .remote_set_url("origin", &source.url)
+            .map_err(|e| SkiloError::Git {
+                message: format!("Failed to set origin: {}", e),
+            })?;
+        if options.submodules {
+            update_submodules(&checkout_repo, credentials)?;
+        }
     }
 
+    let root = source_root(source, &checkout_path)?;
+
+    let lfs_pointers = lfs_pointers(options, &checkout_path, &root, &source.url);
+
     Ok(FetchResult {
         root,
         temp_dir: None,
         checkout_dir: Some(checkout_path),
         from_cache: true,
-        commit: Some(short_commit.to_string()),
+        commit: Some(commit_id),
+        lfs_pointers,
     })
 }
 
 /// Fall back to fetching to a temporary directory.
-fn fetch_to_temp(source: &GitSource) -> Result<FetchResult, SkiloError> {
+fn fetch_to_temp(
+    source: &GitSource,
+    options: CheckoutOptions,
+    credentials: &Credentials,
+) -> Result<FetchResult, SkiloError> {
     if is_offline() {
         return Err(SkiloError::Network {
             message: "Cannot fetch non-cached repository in offline mode".to_string(),
@@ -150,31 +224,36 @@ fn fetch_to_temp(source: &GitSource) -> Result<FetchResult, SkiloError> {
     }
 
     let temp_dir = TempDir::new().map_err(SkiloError::Io)?;
-    let repo = clone_repo(&source.url, source.reference(), temp_dir.path())?;
+    let branch_or_tag = source.branch.as_deref().or(source.tag.as_deref());
+    let shallow = source.reference().is_none();
+    let repo = clone_repo(
+        &source.url,
+        branch_or_tag,
+        shallow,
+        temp_dir.path(),
+        credentials,
+    )?;
+
+    // An @ref may be a commit, which cannot be cloned directly
+    if branch_or_tag.is_none() && source.rev.is_some() {
+        let commit_id = resolve_or_fetch(&repo, source, credentials)?;
+        checkout_rev(&repo, &commit_id)?;
+    }
+
+    if options.submodules {
+        update_submodules(&repo, credentials)?;
+    }
 
     // Get the HEAD commit
     let commit = repo
         .head()
         .ok()
         .and_then(|h| h.peel_to_commit().ok())
-        .map(|c| c.id().to_string()[..7].to_string());
+        .map(|c| c.id().to_string());
 
-    // Determine the root path (may be a subdirectory)
-    let root = if let Some(ref subdir) = source.subdir {
-        temp_dir.path().join(subdir)
-    } else {
-        temp_dir.path().to_path_buf()
-    };
+    let root = source_root(source, temp_dir.path())?;
 
-    if !root.exists() {
-        return Err(SkiloError::InvalidSource(
-            source.url.clone(),
-            format!(
-                "Subdirectory '{}' not found in repository",
-                source.subdir.as_deref().unwrap_or("")
-            ),
-        ));
-    }
+    let lfs_pointers = lfs_pointers(options, temp_dir.path(), &root, &source.url);
 
     Ok(FetchResult {
         root,
@@ -182,16 +261,19 @@ fn fetch_to_temp(source: &GitSource) -> Result<FetchResult, SkiloError> {
         checkout_dir: None,
         from_cache: false,
         commit,
+        lfs_pointers,
     })
 }
 
 /// Clone a bare repository.
-fn clone_bare(url: &str, dest: &Path) -> Result<Repository, SkiloError> {
+fn clone_bare(url: &str, dest: &Path, credentials: &Credentials) -> Result<Repository, SkiloError> {
     let mut builder = RepoBuilder::new();
     builder.bare(true);
+    // Keep branches under refs/heads, as `git clone --bare` does
+    builder.remote_create(|repo, name, url| repo.remote_with_fetch(name, url, BRANCH_REFSPEC));
 
     let mut callbacks = RemoteCallbacks::new();
-    setup_credentials(&mut callbacks);
+    credentials.apply(&mut callbacks);
 
     let mut fetch_opts = FetchOptions::new();
     fetch_opts.remote_callbacks(callbacks);
@@ -202,27 +284,125 @@ fn clone_bare(url: &str, dest: &Path) -> Result<Repository, SkiloError> {
 }
 
 /// Fetch updates to an existing bare repository.
-fn fetch_updates(repo: &Repository, url: &str) -> Result<(), SkiloError> {
-    let mut remote = repo
-        .find_remote("origin")
-        .or_else(|_| repo.remote_anonymous(url))
-        .map_err(|e| SkiloError::Git {
-            message: format!("Failed to get remote: {}", e),
-        })?;
+///
+/// Branches and tags are force-updated, so moved tags and rewritten branches
+/// are picked up, and refs deleted upstream are pruned. A pull request
+/// `reference` (`pull/<n>/head`) is fetched as well.
+fn fetch_updates(
+    repo: &Repository,
+    url: &str,
+    reference: Option<&str>,
+    credentials: &Credentials,
+) -> Result<(), SkiloError> {
+    // Fetch from `url` rather than the stored origin, which may be the HTTPS
+    // URL an SSH retry is working around
+    let mut remote = repo.remote_anonymous(url).map_err(|e| SkiloError::Git {
+        message: format!("Failed to get remote: {}", e),
+    })?;
 
     let mut callbacks = RemoteCallbacks::new();
-    setup_credentials(&mut callbacks);
+    credentials.apply(&mut callbacks);
 
     let mut fetch_opts = FetchOptions::new();
     fetch_opts.remote_callbacks(callbacks);
+    fetch_opts.prune(FetchPrune::On);
+    fetch_opts.download_tags(AutotagOption::All);
+
+    let mut refspecs = vec![BRANCH_REFSPEC.to_string(), TAG_REFSPEC.to_string()];
+    refspecs.extend(reference.and_then(pull_request_refspec));
 
     remote
-        .fetch(&["refs/heads/*:refs/heads/*"], Some(&mut fetch_opts), None)
+        .fetch(&refspecs, Some(&mut fetch_opts), None)
         .map_err(|e| map_git_error(e, url))?;
 
+    // Caches cloned by older versions track branches under refs/remotes,
+    // where pruning does not reach
+    if let Ok(stale) = repo.references_glob("refs/remotes/*") {
+        for mut reference in stale.flatten() {
+            let _ = reference.delete();
+        }
+    }
+
     Ok(())
 }
 
+/// Fetch a single commit by its full SHA.
+///
+/// Commits no longer reachable from a branch or tag (for example on a
+/// deleted or force-pushed branch) are not part of a regular fetch. Servers
+/// that allow it, including GitHub, serve them when asked by ID.
+fn fetch_commit(
+    repo: &Repository,
+    url: &str,
+    sha: &str,
+    credentials: &Credentials,
+) -> Result<(), SkiloError> {
+    let mut remote = repo.remote_anonymous(url).map_err(|e| SkiloError::Git {
+        message: format!("Failed to get remote: {}", e),
+    })?;
+
+    let mut callbacks = RemoteCallbacks::new();
+    credentials.apply(&mut callbacks);
+
+    let mut fetch_opts = FetchOptions::new();
+    fetch_opts.remote_callbacks(callbacks);
+    fetch_opts.download_tags(AutotagOption::None);
+
+    // Keep the commit referenced so it survives garbage collection
+    let refspec = format!("+{0}:refs/commits/{0}", sha);
+    remote
+        .fetch(&[refspec], Some(&mut fetch_opts), None)
+        .map_err(|e| map_git_error(e, url))
+}
+
+/// Returns true if `rev` is a full, unabbreviated commit SHA.
+fn is_full_sha(rev: &str) -> bool {
+    rev.len() == 40 && rev.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Resolve the source's reference, fetching a commit given by full SHA that
+/// the repository does not have yet.
+fn resolve_or_fetch(
+    repo: &Repository,
+    source: &GitSource,
+    credentials: &Credentials,
+) -> Result<String, SkiloError> {
+    let err = match resolve_reference(repo, source.reference()) {
+        Ok(commit_id) => return Ok(commit_id),
+        Err(e) => e,
+    };
+
+    match source.rev.as_deref() {
+        Some(sha) if is_full_sha(sha) && !is_offline() => {
+            fetch_commit(repo, &source.url, sha, credentials).map_err(|e| SkiloError::Git {
+                message: format!("Commit '{}' not found: {}", sha, e),
+            })?;
+            resolve_reference(repo, Some(sha))
+        }
+        Some(rev) if !is_full_sha(rev) => Err(SkiloError::Git {
+            message: format!(
+                "Reference '{}' not found; commits not on a branch or tag must be given by full SHA",
+                rev
+            ),
+        }),
+        _ => Err(err),
+    }
+}
+
+/// The refspec fetching a pull request reference such as `pull/12/head`.
+fn pull_request_refspec(reference: &str) -> Option<String> {
+    let name = reference.strip_prefix("refs/").unwrap_or(reference);
+    let mut parts = name.split('/');
+    match (parts.next(), parts.next(), parts.next(), parts.next()) {
+        (Some("pull"), Some(n), Some("head" | "merge"), None)
+            if !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()) =>
+        {
+            Some(format!("+refs/{0}:refs/{0}", name))
+        }
+        _ => None,
+    }
+}
+
 /// Resolve a reference (branch, tag, or HEAD) to a commit ID.
 fn resolve_reference(repo: &Repository, reference: Option<&str>) -> Result<String, SkiloError> {
     let commit = if let Some(ref_name) = reference {
@@ -237,18 +417,32 @@ fn resolve_reference(repo: &Repository, reference: Option<&str>) -> Result<Strin
                     .and_then(|r| r.target())
             })
             .or_else(|| {
-                // Try as tag
-                repo.find_reference(&format!("refs/tags/{}", ref_name))
+                // Try as remote branch (non-bare clones)
+                repo.find_reference(&format!("refs/remotes/origin/{}", ref_name))
                     .ok()
                     .and_then(|r| r.target())
             })
             .or_else(|| {
-                // Try as direct ref
-                repo.find_reference(ref_name).ok().and_then(|r| r.target())
+                // Try as tag, peeling annotated tags to their commit
+                repo.find_reference(&format!("refs/tags/{}", ref_name))
+                    .ok()
+                    .and_then(|r| r.peel_to_commit().ok())
+                    .map(|c| c.id())
+            })
+            .or_else(|| {
+                // Try as direct ref, with or without the `refs/` prefix
+                repo.find_reference(ref_name)
+                    .or_else(|_| repo.find_reference(&format!("refs/{}", ref_name)))
+                    .ok()
+                    .and_then(|r| r.peel_to_commit().ok())
+                    .map(|c| c.id())
             })
             .or_else(|| {
-                // Try as commit hash
-                git2::Oid::from_str(ref_name).ok()
+                // Try as (possibly abbreviated) commit hash
+                repo.revparse_single(ref_name)
+                    .ok()
+                    .and_then(|o| o.peel_to_commit().ok())
+                    .map(|c| c.id())
             })
             .ok_or_else(|| SkiloError::Git {
                 message: format!("Reference '{}' not found", ref_name),
@@ -332,17 +526,23 @@ fn checkout_from_bare(
 }
 
 /// Clone a repository to a destination (for non-cached fetches).
-fn clone_repo(url: &str, reference: Option<&str>, dest: &Path) -> Result<Repository, SkiloError> {
+fn clone_repo(
+    url: &str,
+    reference: Option<&str>,
+    shallow: bool,
+    dest: &Path,
+    credentials: &Credentials,
+) -> Result<Repository, SkiloError> {
     let mut builder = RepoBuilder::new();
     let mut callbacks = RemoteCallbacks::new();
 
-    setup_credentials(&mut callbacks);
+    credentials.apply(&mut callbacks);
 
     let mut fetch_opts = FetchOptions::new();
     fetch_opts.remote_callbacks(callbacks);
 
-    // Only use shallow clone when not specifying a branch/tag
-    if reference.is_none() {
+    // Only use shallow clone when not specifying a branch, tag, or commit
+    if shallow {
         fetch_opts.depth(1);
     }
 
@@ -355,32 +555,74 @@ fn clone_repo(url: &str, reference: Option<&str>, dest: &Path) -> Result<Reposit
     builder.clone(url, dest).map_err(|e| map_git_error(e, url))
 }
 
-/// Set up credential callbacks.
-fn setup_credentials(callbacks: &mut RemoteCallbacks) {
-    callbacks.credentials(|_url, username_from_url, allowed_types| {
-        // Try SSH agent first for SSH URLs
-        if allowed_types.contains(git2::CredentialType::SSH_KEY) {
-            if let Some(username) = username_from_url {
-                return Cred::ssh_key_from_agent(username);
+/// Check out a branch, tag, or commit in a full clone, detaching HEAD.
+fn checkout_rev(repo: &Repository, rev: &str) -> Result<(), SkiloError> {
+    let commit_id = resolve_reference(repo, Some(rev))?;
+    let oid = git2::Oid::from_str(&commit_id).map_err(|e| SkiloError::Git {
+        message: format!("Invalid commit ID: {}", e),
+    })?;
+    let commit = repo.find_commit(oid).map_err(|e| SkiloError::Git {
+        message: format!("Commit not found: {}", e),
+    })?;
+
+    repo.checkout_tree(commit.as_object(), None)
+        .map_err(|e| SkiloError::Git {
+            message: format!("Failed to checkout tree: {}", e),
+        })?;
+    repo.set_head_detached(oid).map_err(|e| SkiloError::Git {
+        message: format!("Failed to set HEAD: {}", e),
+    })?;
+
+    Ok(())
+}
+
+/// LFS pointer files left under `root`, after downloading their content
+/// when `options.lfs` is set.
+fn lfs_pointers(options: CheckoutOptions, checkout: &Path, root: &Path, url: &str) -> Vec<PathBuf> {
+    let pointers = if options.lfs {
+        lfs::materialize(checkout, root, url)
+    } else {
+        lfs::find_pointers(root)
+    };
+    pointers.into_iter().map(|p| p.path).collect()
+}
+
+/// Initialize and update the submodules of a checkout, recursively.
+///
+/// Submodules that are already checked out are left as they are, so cached
+/// checkouts are only fetched into once.
+fn update_submodules(repo: &Repository, credentials: &Credentials) -> Result<(), SkiloError> {
+    let submodules = repo.submodules().map_err(|e| SkiloError::Git {
+        message: format!("Failed to read submodules: {}", e),
+    })?;
+
+    for mut submodule in submodules {
+        if submodule.workdir_id().is_none() {
+            let url = submodule.url().unwrap_or_default().to_string();
+            if is_offline() {
+                return Err(SkiloError::Network {
+                    message: format!("Cannot fetch submodule {} in offline mode", url),
+                });
             }
-        }
 
-        // Try default credentials (git credential helper)
-        if allowed_types.contains(git2::CredentialType::USER_PASS_PLAINTEXT) {
-            return Cred::credential_helper(
-                &git2::Config::open_default()?,
-                _url,
-                username_from_url,
-            );
+            let mut callbacks = RemoteCallbacks::new();
+            credentials.apply(&mut callbacks);
+            let mut fetch_opts = FetchOptions::new();
+            fetch_opts.remote_callbacks(callbacks);
+            let mut update_opts = SubmoduleUpdateOptions::new();
+            update_opts.fetch(fetch_opts);
+
+            submodule
+                .update(true, Some(&mut update_opts))
+                .map_err(|e| map_git_error(e, &url))?;
         }
 
-        // Fall back to default for public repos
-        if allowed_types.contains(git2::CredentialType::DEFAULT) {
-            return Cred::default();
+        if let Ok(submodule_repo) = submodule.open() {
+            update_submodules(&submodule_repo, credentials)?;
         }
+    }
 
-        Err(git2::Error::from_str("no valid credentials available"))
-    });
+    Ok(())
 }
 
 /// Map git2 errors to SkiloError.
@@ -422,6 +664,29 @@ fn https_to_ssh_url(url: &str) -> Option<String> {
     None
 }
 
+/// The root path of `source` in the checkout at `dir`: `dir` itself, or
+/// the source's subdirectory, which must exist and stay inside `dir`.
+fn source_root(source: &GitSource, dir: &Path) -> Result<PathBuf, SkiloError> {
+    let Some(subdir) = &source.subdir else {
+        return Ok(dir.to_path_buf());
+    };
+
+    let root = dir.join(subdir);
+    // A symlink in the repository could still point outside of it
+    let inside = is_relative_subdir(subdir)
+        && matches!(
+            (root.canonicalize(), dir.canonicalize()),
+            (Ok(root), Ok(dir)) if root.starts_with(&dir)
+        );
+    if !root.exists() || !inside {
+        return Err(SkiloError::InvalidSource(
+            source.url.clone(),
+            format!("Subdirectory '{}' not found in repository", subdir),
+        ));
+    }
+    Ok(root)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -433,13 +698,218 @@ mod tests {
             url: "https://github.com/nonexistent-owner-xyz/nonexistent-repo-xyz.git".to_string(),
             branch: None,
             tag: None,
+            rev: None,
             subdir: None,
         };
 
-        let result = fetch(&source);
+        let result = fetch(&source, CheckoutOptions::default(), &Credentials::default());
         assert!(result.is_err());
     }
 
+    /// Commit every file in the work tree of `repo`.
+    fn commit_all(repo: &Repository) -> git2::Oid {
+        let mut index = repo.index().unwrap();
+        index
+            .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+            .unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = git2::Signature::now("test", "test@example.com").unwrap();
+        let parent = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+        repo.commit(
+            Some("HEAD"),
+            &sig,
+            &sig,
+            "commit",
+            &tree,
+            parent.as_ref().into_iter().collect::<Vec<_>>().as_slice(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_fetch_initializes_submodules() {
+        let temp = TempDir::new().unwrap();
+        let shared_path = temp.path().join("shared");
+        let shared = Repository::init(&shared_path).unwrap();
+        std::fs::write(shared_path.join("guide.md"), "# Guide\n").unwrap();
+        commit_all(&shared);
+
+        let main_path = temp.path().join("main");
+        let main = Repository::init(&main_path).unwrap();
+        std::fs::create_dir_all(main_path.join("my-skill")).unwrap();
+        std::fs::write(
+            main_path.join("my-skill/SKILL.md"),
+            "---\nname: my-skill\n---\n",
+        )
+        .unwrap();
+        let shared_url = format!("file://{}", shared_path.display());
+        let mut submodule = main
+            .submodule(&shared_url, Path::new("my-skill/references"), true)
+            .unwrap();
+        submodule.clone(None).unwrap();
+        submodule.add_finalize().unwrap();
+        commit_all(&main);
+
+        let source = GitSource {
+            url: format!("file://{}", main_path.display()),
+            branch: None,
+            tag: None,
+            rev: Some("HEAD".to_string()),
+            subdir: None,
+        };
+
+        let result = fetch_to_temp(
+            &source,
+            CheckoutOptions {
+                submodules: false,
+                lfs: false,
+            },
+            &Credentials::default(),
+        )
+        .unwrap();
+        assert!(!result.root.join("my-skill/references/guide.md").exists());
+
+        let result =
+            fetch_to_temp(&source, CheckoutOptions::default(), &Credentials::default()).unwrap();
+        assert!(result.root.join("my-skill/references/guide.md").exists());
+    }
+
+    #[test]
+    fn test_resolve_fetches_unreferenced_commit() {
+        let temp = TempDir::new().unwrap();
+        let upstream_path = temp.path().join("upstream");
+        let upstream = Repository::init(&upstream_path).unwrap();
+        std::fs::write(upstream_path.join("SKILL.md"), "v1\n").unwrap();
+        let first = commit_all(&upstream);
+
+        let url = format!("file://{}", upstream_path.display());
+        let bare = clone_bare(&url, &temp.path().join("db"), &Credentials::default()).unwrap();
+
+        // A commit on no branch or tag, such as one only a review ref keeps
+        let tree = upstream.find_commit(first).unwrap().tree().unwrap();
+        let sig = git2::Signature::now("test", "test@example.com").unwrap();
+        let orphan = upstream
+            .commit(Some("refs/keep/orphan"), &sig, &sig, "orphan", &tree, &[])
+            .unwrap();
+        fetch_updates(&bare, &url, None, &Credentials::default()).unwrap();
+        assert!(resolve_reference(&bare, Some(&orphan.to_string())).is_err());
+
+        let mut source = GitSource {
+            url,
+            branch: None,
+            tag: None,
+            rev: Some(orphan.to_string()[..7].to_string()),
+            subdir: None,
+        };
+        assert!(resolve_or_fetch(&bare, &source, &Credentials::default()).is_err());
+
+        source.rev = Some(orphan.to_string());
+        assert_eq!(
+            resolve_or_fetch(&bare, &source, &Credentials::default()).unwrap(),
+            orphan.to_string()
+        );
+    }
+
+    #[test]
+    fn test_fetch_updates_tags_and_pull_requests() {
+        let temp = TempDir::new().unwrap();
+        let upstream_path = temp.path().join("upstream");
+        let upstream = Repository::init(&upstream_path).unwrap();
+        std::fs::write(upstream_path.join("SKILL.md"), "v1\n").unwrap();
+        let first = commit_all(&upstream);
+        upstream
+            .tag_lightweight("v1", &upstream.find_object(first, None).unwrap(), false)
+            .unwrap();
+        upstream
+            .branch("feature", &upstream.find_commit(first).unwrap(), false)
+            .unwrap();
+
+        // Cache the repository, then change it upstream
+        let url = format!("file://{}", upstream_path.display());
+        let bare = clone_bare(&url, &temp.path().join("db"), &Credentials::default()).unwrap();
+        assert_eq!(
+            resolve_reference(&bare, Some("v1")).unwrap(),
+            first.to_string()
+        );
+        assert!(bare.find_branch("feature", git2::BranchType::Local).is_ok());
+
+        std::fs::write(upstream_path.join("SKILL.md"), "v2\n").unwrap();
+        let second = commit_all(&upstream);
+        let sig = git2::Signature::now("test", "test@example.com").unwrap();
+        let second_obj = upstream.find_object(second, None).unwrap();
+        upstream.tag("v2", &second_obj, &sig, "v2", false).unwrap();
+        upstream.tag_lightweight("v1", &second_obj, true).unwrap();
+        upstream
+            .find_branch("feature", git2::BranchType::Local)
+            .unwrap()
+            .delete()
+            .unwrap();
+        upstream
+            .reference("refs/pull/7/head", first, false, "pr")
+            .unwrap();
+
+        assert!(resolve_reference(&bare, Some("v2")).is_err());
+        fetch_updates(&bare, &url, Some("pull/7/head"), &Credentials::default()).unwrap();
+
+        assert_eq!(
+            resolve_reference(&bare, Some("v2")).unwrap(),
+            second.to_string()
+        );
+        assert_eq!(
+            resolve_reference(&bare, Some("v1")).unwrap(),
+            second.to_string()
+        );
+        assert!(bare
+            .find_branch("feature", git2::BranchType::Local)
+            .is_err());
+        assert_eq!(
+            resolve_reference(&bare, Some("pull/7/head")).unwrap(),
+            first.to_string()
+        );
+    }
+
+    #[test]
+    fn test_source_root_stays_in_checkout() {
+        let temp = TempDir::new().unwrap();
+        let checkout = temp.path().join("checkout");
+        std::fs::create_dir_all(checkout.join("skills")).unwrap();
+        let source = |subdir: &str| GitSource {
+            url: "https://github.com/owner/repo.git".to_string(),
+            branch: None,
+            tag: None,
+            rev: None,
+            subdir: Some(subdir.to_string()),
+        };
+
+        assert_eq!(
+            source_root(&source("skills"), &checkout).unwrap(),
+            checkout.join("skills")
+        );
+        assert!(source_root(&source("missing"), &checkout).is_err());
+        assert!(source_root(&source(".."), &checkout).is_err());
+
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(temp.path(), checkout.join("out")).unwrap();
+            assert!(source_root(&source("out"), &checkout).is_err());
+        }
+    }
+
+    #[test]
+    fn test_pull_request_refspec() {
+        assert_eq!(
+            pull_request_refspec("pull/12/head").as_deref(),
+            Some("+refs/pull/12/head:refs/pull/12/head")
+        );
+        assert_eq!(
+            pull_request_refspec("refs/pull/3/merge").as_deref(),
+            Some("+refs/pull/3/merge:refs/pull/3/merge")
+        );
+        assert_eq!(pull_request_refspec("main"), None);
+        assert_eq!(pull_request_refspec("pull/x/head"), None);
+    }
+
     #[test]
     fn test_https_to_ssh_url_github() {
         assert_eq!(
@@ -0,0 +1,315 @@
+//! Git LFS pointer detection and download.
+//!
+//! Repositories that keep assets in Git LFS check out small pointer files
+//! in place of the assets. Pointers are replaced with their content by
+//! `git lfs` when it is installed, and otherwise through the LFS batch API.
+
+use crate::cache::is_offline;
+use crate::SkiloError;
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use walkdir::WalkDir;
+
+/// First line of every LFS pointer file.
+const POINTER_VERSION: &str = "version https://git-lfs.github.com/spec/v1";
+/// Pointer files are well under this size.
+const MAX_POINTER_SIZE: u64 = 1024;
+const USER_AGENT: &str = concat!("skilo/", env!("CARGO_PKG_VERSION"));
+const LFS_MEDIA_TYPE: &str = "application/vnd.git-lfs+json";
+
+/// An LFS pointer file in a checkout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pointer {
+    /// Path of the pointer file.
+    pub path: PathBuf,
+    /// SHA-256 of the object content.
+    pub oid: String,
+    /// Size of the object content in bytes.
+    pub size: u64,
+}
+
+/// Parse the object ID and size out of LFS pointer file content.
+pub fn parse_pointer(content: &str) -> Option<(String, u64)> {
+    let mut lines = content.lines();
+    if lines.next()? != POINTER_VERSION {
+        return None;
+    }
+
+    let mut oid = None;
+    let mut size = None;
+    for line in lines {
+        if let Some(hash) = line.strip_prefix("oid sha256:") {
+            oid = Some(hash.to_string());
+        } else if let Some(n) = line.strip_prefix("size ") {
+            size = n.parse().ok();
+        }
+    }
+    Some((oid?, size?))
+}
+
+/// Find LFS pointer files under `dir`, skipping `.git` directories.
+pub fn find_pointers(dir: &Path) -> Vec<Pointer> {
+    WalkDir::new(dir)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|e| e.file_name() != ".git")
+        .filter_map(Result::ok)
+        .filter(|e| e.file_type().is_file())
+        .filter(|e| e.metadata().is_ok_and(|m| m.len() < MAX_POINTER_SIZE))
+        .filter_map(|e| {
+            let content = std::fs::read_to_string(e.path()).ok()?;
+            let (oid, size) = parse_pointer(&content)?;
+            Some(Pointer {
+                path: e.into_path(),
+                oid,
+                size,
+            })
+        })
+        .collect()
+}
+
+/// Replace the pointer files under `root` with their content.
+///
+/// `checkout` is the git work tree containing `root` and `url` the
+/// repository it was fetched from. Returns the pointers that could not be
+/// downloaded.
+pub fn materialize(checkout: &Path, root: &Path, url: &str) -> Vec<Pointer> {
+    let mut pointers = find_pointers(root);
+    if pointers.is_empty() || is_offline() {
+        return pointers;
+    }
+
+    if git_lfs_available() && git_lfs_pull(checkout, &pointers) {
+        pointers = find_pointers(root);
+        if pointers.is_empty() {
+            return pointers;
+        }
+    }
+
+    match download(url, &pointers) {
+        Ok(()) => find_pointers(root),
+        Err(e) => {
+            eprintln!("{}: Git LFS download failed: {}", "Warning".yellow(), e);
+            pointers
+        }
+    }
+}
+
+/// Returns true if the `git lfs` extension is installed.
+fn git_lfs_available() -> bool {
+    Command::new("git")
+        .args(["lfs", "version"])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|s| s.success())
+}
+
+/// Run `git lfs pull` for the pointer files in `checkout`.
+fn git_lfs_pull(checkout: &Path, pointers: &[Pointer]) -> bool {
+    let include: Vec<String> = pointers
+        .iter()
+        .filter_map(|p| p.path.strip_prefix(checkout).ok())
+        .map(|p| p.to_string_lossy().replace('\\', "/"))
+        .collect();
+
+    Command::new("git")
+        .args(["lfs", "pull", "--include", &include.join(",")])
+        .current_dir(checkout)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|s| s.success())
+}
+
+/// The LFS server URL of a repository, following the Git LFS convention of
+/// `<https url>.git/info/lfs`.
+pub fn lfs_endpoint(url: &str) -> Option<String> {
+    let https = if let Some(rest) = url.strip_prefix("https://") {
+        rest.to_string()
+    } else if let Some(rest) = url.strip_prefix("ssh://") {
+        let rest = rest.split_once('@').map_or(rest, |(_, r)| r);
+        rest.to_string()
+    } else if let Some((user_host, path)) = url.split_once(':') {
+        // scp-style: git@host:owner/repo.git
+        let host = user_host.split_once('@').map_or(user_host, |(_, h)| h);
+        if host.contains('/') || path.starts_with("//") {
+            return None;
+        }
+        format!("{}/{}", host, path)
+    } else {
+        return None;
+    };
+
+    let https = https.trim_end_matches('/');
+    let suffix = if https.ends_with(".git") { "" } else { ".git" };
+    Some(format!("https://{}{}/info/lfs", https, suffix))
+}
+
+#[derive(Serialize)]
+struct BatchRequest<'a> {
+    operation: &'static str,
+    transfers: [&'static str; 1],
+    objects: Vec<BatchObject<'a>>,
+}
+
+#[derive(Serialize)]
+struct BatchObject<'a> {
+    oid: &'a str,
+    size: u64,
+}
+
+#[derive(Deserialize)]
+struct BatchResponse {
+    objects: Vec<ObjectResponse>,
+}
+
+#[derive(Deserialize)]
+struct ObjectResponse {
+    oid: String,
+    actions: Option<Actions>,
+    error: Option<ObjectError>,
+}
+
+#[derive(Deserialize)]
+struct Actions {
+    download: Option<DownloadAction>,
+}
+
+#[derive(Deserialize)]
+struct DownloadAction {
+    href: String,
+    #[serde(default)]
+    header: HashMap<String, String>,
+}
+
+#[derive(Deserialize)]
+struct ObjectError {
+    message: String,
+}
+
+/// Download the objects behind `pointers` through the LFS batch API and
+/// write them over the pointer files.
+fn download(url: &str, pointers: &[Pointer]) -> Result<(), SkiloError> {
+    let network = |message: String| SkiloError::Network { message };
+    let endpoint =
+        lfs_endpoint(url).ok_or_else(|| network(format!("No Git LFS server known for {}", url)))?;
+
+    let client = reqwest::blocking::Client::builder()
+        .user_agent(USER_AGENT)
+        .build()
+        .map_err(|e| network(format!("Failed to create HTTP client: {}", e)))?;
+
+    let request = BatchRequest {
+        operation: "download",
+        transfers: ["basic"],
+        objects: pointers
+            .iter()
+            .map(|p| BatchObject {
+                oid: &p.oid,
+                size: p.size,
+            })
+            .collect(),
+    };
+    let response = client
+        .post(format!("{}/objects/batch", endpoint))
+        .header("Accept", LFS_MEDIA_TYPE)
+        .header("Content-Type", LFS_MEDIA_TYPE)
+        .json(&request)
+        .send()
+        .map_err(|e| network(format!("LFS batch request failed: {}", e)))?;
+    if !response.status().is_success() {
+        return Err(network(format!(
+            "LFS server returned status {}",
+            response.status()
+        )));
+    }
+    let batch: BatchResponse = response
+        .json()
+        .map_err(|e| network(format!("Invalid LFS batch response: {}", e)))?;
+
+    for object in batch.objects {
+        if let Some(error) = object.error {
+            return Err(network(format!(
+                "LFS object {}: {}",
+                object.oid, error.message
+            )));
+        }
+        let Some(action) = object.actions.and_then(|a| a.download) else {
+            continue;
+        };
+
+        let mut request = client.get(&action.href);
+        for (name, value) in &action.header {
+            request = request.header(name, value);
+        }
+        let bytes = request
+            .send()
+            .and_then(|r| r.error_for_status())
+            .and_then(|r| r.bytes())
+            .map_err(|e| network(format!("Failed to download LFS object: {}", e)))?;
+
+        let hash = format!("{:x}", Sha256::digest(&bytes));
+        if hash != object.oid {
+            return Err(network(format!(
+                "LFS object {} has checksum {}",
+                object.oid, hash
+            )));
+        }
+        for pointer in pointers.iter().filter(|p| p.oid == object.oid) {
+            std::fs::write(&pointer.path, &bytes)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const POINTER: &str = "version https://git-lfs.github.com/spec/v1\n\
+        oid sha256:4d7a214614ab2935c943f9e0ff69d22eadbb8f32b1258daaa5e2ca24d17e2393\n\
+        size 12345\n";
+
+    #[test]
+    fn test_find_pointers() {
+        let temp = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp.path().join("assets")).unwrap();
+        std::fs::write(temp.path().join("assets/logo.png"), POINTER).unwrap();
+        std::fs::write(temp.path().join("SKILL.md"), "---\nname: x\n---\n").unwrap();
+
+        let pointers = find_pointers(temp.path());
+        assert_eq!(pointers.len(), 1);
+        assert_eq!(pointers[0].path, temp.path().join("assets/logo.png"));
+        assert_eq!(pointers[0].size, 12345);
+        assert!(pointers[0].oid.starts_with("4d7a2146"));
+
+        assert_eq!(
+            parse_pointer("version https://git-lfs.github.com/spec/v1\n"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_lfs_endpoint() {
+        assert_eq!(
+            lfs_endpoint("https://github.com/owner/repo").as_deref(),
+            Some("https://github.com/owner/repo.git/info/lfs")
+        );
+        assert_eq!(
+            lfs_endpoint("git@github.com:owner/repo.git").as_deref(),
+            Some("https://github.com/owner/repo.git/info/lfs")
+        );
+        assert_eq!(
+            lfs_endpoint("ssh://git@gitlab.com/group/repo.git").as_deref(),
+            Some("https://gitlab.com/group/repo.git/info/lfs")
+        );
+        assert_eq!(lfs_endpoint("file:///tmp/repo"), None);
+    }
+}
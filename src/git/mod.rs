@@ -1,7 +1,12 @@
 //! Git operations for fetching skills from remote repositories.
 
+pub mod auth;
 pub mod fetch;
+pub mod lfs;
+pub mod release;
 pub mod source;
 
-pub use fetch::{fetch, FetchResult};
-pub use source::{GitSource, Source};
+pub use auth::Credentials;
+pub use fetch::{fetch, CheckoutOptions, FetchResult};
+pub use release::fetch_release_asset;
+pub use source::{ArchiveFormat, GitSource, ReleaseAsset, Source};
@@ -0,0 +1,135 @@
+//! Downloading skills attached to GitHub releases as archives.
+
+use crate::cache::is_offline;
+use crate::git::fetch::FetchResult;
+use crate::git::source::{is_relative_subdir, ArchiveFormat, ReleaseAsset};
+use crate::SkiloError;
+use std::io::Cursor;
+use std::path::Path;
+use tempfile::TempDir;
+
+/// User agent sent with downloads.
+const USER_AGENT: &str = concat!("skilo/", env!("CARGO_PKG_VERSION"));
+
+/// Download a release asset and unpack it into a temporary directory.
+///
+/// The result's `root` is the unpacked archive, or the asset's subdirectory
+/// within it. Release assets are not cached.
+pub fn fetch_release_asset(asset: &ReleaseAsset) -> Result<FetchResult, SkiloError> {
+    if is_offline() {
+        return Err(SkiloError::Network {
+            message: "Cannot download release assets in offline mode".to_string(),
+        });
+    }
+
+    let data = download(&asset.url)?;
+    let temp_dir = TempDir::new().map_err(SkiloError::Io)?;
+    unpack(asset.format, &data, temp_dir.path()).map_err(|e| {
+        SkiloError::InvalidSource(
+            asset.url.clone(),
+            format!("Failed to unpack {}: {}", asset.file_name(), e),
+        )
+    })?;
+
+    let root = match &asset.subdir {
+        Some(subdir) => temp_dir.path().join(subdir),
+        None => temp_dir.path().to_path_buf(),
+    };
+    let inside = asset.subdir.as_deref().is_none_or(is_relative_subdir)
+        && matches!(
+            (root.canonicalize(), temp_dir.path().canonicalize()),
+            (Ok(root), Ok(dir)) if root.starts_with(&dir)
+        );
+    if !root.exists() || !inside {
+        return Err(SkiloError::InvalidSource(
+            asset.url.clone(),
+            format!(
+                "Subdirectory '{}' not found in {}",
+                asset.subdir.as_deref().unwrap_or(""),
+                asset.file_name()
+            ),
+        ));
+    }
+
+    Ok(FetchResult {
+        root,
+        temp_dir: Some(temp_dir),
+        checkout_dir: None,
+        from_cache: false,
+        commit: None,
+        lfs_pointers: Vec::new(),
+    })
+}
+
+/// Download the file at `url`.
+fn download(url: &str) -> Result<Vec<u8>, SkiloError> {
+    let network = |message: String| SkiloError::Network { message };
+    let client = reqwest::blocking::Client::builder()
+        .user_agent(USER_AGENT)
+        .build()
+        .map_err(|e| network(format!("Failed to create HTTP client: {}", e)))?;
+
+    let response = client
+        .get(url)
+        .send()
+        .map_err(|e| network(format!("Failed to download {}: {}", url, e)))?;
+    if !response.status().is_success() {
+        return Err(network(format!(
+            "Download of {} failed with status {}",
+            url,
+            response.status()
+        )));
+    }
+
+    response
+        .bytes()
+        .map(|b| b.to_vec())
+        .map_err(|e| network(format!("Failed to read download: {}", e)))
+}
+
+/// Unpack an archive into `dest`.
+///
+/// Nothing is written outside `dest`: tarballs skip entries with absolute
+/// or `..` paths, and zip archives holding them are rejected.
+fn unpack(format: ArchiveFormat, data: &[u8], dest: &Path) -> std::io::Result<()> {
+    match format {
+        ArchiveFormat::TarGz => {
+            tar::Archive::new(flate2::read::GzDecoder::new(Cursor::new(data))).unpack(dest)
+        }
+        ArchiveFormat::Tar => tar::Archive::new(Cursor::new(data)).unpack(dest),
+        ArchiveFormat::Zip => zip::ZipArchive::new(Cursor::new(data))
+            .and_then(|mut archive| archive.extract(dest))
+            .map_err(std::io::Error::other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_unpack_tar_gz() {
+        let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+            Vec::new(),
+            flate2::Compression::default(),
+        ));
+        let content = b"---\nname: my-skill\ndescription: Test\n---\n";
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o644);
+        builder
+            .append_data(&mut header, "my-skill/SKILL.md", &content[..])
+            .unwrap();
+        let data = builder.into_inner().unwrap().finish().unwrap();
+
+        let temp = TempDir::new().unwrap();
+        unpack(ArchiveFormat::TarGz, &data, temp.path()).unwrap();
+        assert_eq!(
+            fs::read(temp.path().join("my-skill/SKILL.md")).unwrap(),
+            content
+        );
+
+        assert!(unpack(ArchiveFormat::Zip, &data, temp.path()).is_err());
+    }
+}
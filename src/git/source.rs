@@ -1,14 +1,17 @@
 //! Source URL parsing and normalization.
 
 use crate::SkiloError;
-use std::path::PathBuf;
+use std::path::{Component, Path, PathBuf};
 use url::Url;
 
-/// A parsed source for skills - either a git repository or a local path.
+/// A parsed source for skills - a git repository, a release asset, or a
+/// local path.
 #[derive(Debug, Clone)]
 pub enum Source {
     /// A git repository URL.
     Git(GitSource),
+    /// An archive attached to a GitHub release.
+    Release(ReleaseAsset),
     /// A local filesystem path.
     Local(PathBuf),
 }
@@ -22,10 +25,54 @@ pub struct GitSource {
     pub branch: Option<String>,
     /// The optional tag to checkout.
     pub tag: Option<String>,
+    /// The optional `@ref` suffix: a branch, tag, or commit.
+    pub rev: Option<String>,
     /// The optional subdirectory within the repository.
     pub subdir: Option<String>,
 }
 
+/// An archive attached to a GitHub release.
+#[derive(Debug, Clone)]
+pub struct ReleaseAsset {
+    /// The asset's download URL.
+    pub url: String,
+    /// The `owner/repo` the release belongs to.
+    pub repo: String,
+    /// The release tag.
+    pub tag: String,
+    /// The asset's archive format.
+    pub format: ArchiveFormat,
+    /// The optional subdirectory within the archive.
+    pub subdir: Option<String>,
+}
+
+/// Archive formats accepted as release assets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    /// A gzip-compressed tarball (`.tar.gz`, `.tgz`).
+    TarGz,
+    /// An uncompressed tarball (`.tar`).
+    Tar,
+    /// A zip archive (`.zip`).
+    Zip,
+}
+
+impl ArchiveFormat {
+    /// The format of an archive named `file_name`, by its extension.
+    pub fn from_file_name(file_name: &str) -> Option<Self> {
+        let lower = file_name.to_ascii_lowercase();
+        if lower.ends_with(".tar.gz") || lower.ends_with(".tgz") {
+            Some(Self::TarGz)
+        } else if lower.ends_with(".tar") {
+            Some(Self::Tar)
+        } else if lower.ends_with(".zip") {
+            Some(Self::Zip)
+        } else {
+            None
+        }
+    }
+}
+
 impl Source {
     /// Parse a source string into a Source enum.
     ///
@@ -35,17 +82,104 @@ impl Source {
     /// - GitLab URL: `https://gitlab.com/owner/repo`
     /// - SSH URL: `git@github.com:owner/repo.git`
     /// - Direct skill path: `https://github.com/owner/repo/tree/main/skills/my-skill`
-    /// - Local path: `./path/to/skills` or `/absolute/path`
+    /// - Link to a skill file: `https://github.com/owner/repo/blob/main/skills/my-skill/SKILL.md`,
+    ///   GitLab `-/blob/` and `-/raw/` links, or `raw.githubusercontent.com` URLs
+    /// - Gist: `gist:<id>`, `gist:user/<id>`, or `https://gist.github.com/user/<id>`
+    /// - Release asset: `https://github.com/owner/repo/releases/download/v1.0/skills.tar.gz`
+    ///   (`.tar.gz`, `.tgz`, `.tar`, or `.zip`)
+    /// - Local path: `./path/to/skills`, `/absolute/path`, `file:///absolute/path`,
+    ///   or any path that exists on disk, such as `skills-repo`
+    ///
+    /// Git sources may end in `@ref` to pin a branch, tag, or commit:
+    /// `owner/repo@v1.2.0`, `owner/repo@a1b2c3d`. On any host, a `?ref=`
+    /// query and a `#subdir` fragment work the same way as a `tree/` URL:
+    /// `https://gitlab.com/owner/repo?ref=v2#skills/foo`.
+    ///
+    /// An existing directory wins over the GitHub shorthand it resembles;
+    /// use the full URL to install from GitHub instead.
     pub fn parse(source: &str) -> Result<Self, SkiloError> {
         // Check for local path first
         if source.starts_with('/')
             || source.starts_with("./")
             || source.starts_with("../")
             || source.starts_with('~')
+            || Path::new(source).exists()
         {
             return Ok(Source::Local(PathBuf::from(source)));
         }
 
+        if source.starts_with("file://") {
+            return Url::parse(source)
+                .ok()
+                .and_then(|url| url.to_file_path().ok())
+                .map(Source::Local)
+                .ok_or_else(|| {
+                    SkiloError::InvalidSource(
+                        source.to_string(),
+                        "file:// URLs must hold an absolute local path".to_string(),
+                    )
+                });
+        }
+
+        let original = source;
+        let (source, fragment) = match source.split_once('#') {
+            Some((source, fragment)) => (source, Some(fragment.trim_matches('/'))),
+            None => (source, None),
+        };
+        let (source, query_ref) = split_query_ref(source)
+            .map_err(|reason| SkiloError::InvalidSource(original.to_string(), reason))?;
+        let (source, rev) = split_rev(source);
+        if rev.is_some() && query_ref.is_some() {
+            return Err(SkiloError::InvalidSource(
+                original.to_string(),
+                "Use either an @ref suffix or a ?ref= query, not both".to_string(),
+            ));
+        }
+
+        let mut result = Self::parse_git(source)?;
+        match &mut result {
+            Source::Git(git) => {
+                if let Some(rev) = rev.or(query_ref) {
+                    git.rev = Some(rev.to_string());
+                }
+                if let Some(fragment) = fragment.filter(|f| !f.is_empty()) {
+                    git.subdir = Some(match &git.subdir {
+                        Some(existing) => format!("{}/{}", existing, fragment),
+                        None => fragment.to_string(),
+                    });
+                }
+            }
+            Source::Release(asset) => {
+                if rev.is_some() || query_ref.is_some() {
+                    return Err(SkiloError::InvalidSource(
+                        original.to_string(),
+                        "Release assets are pinned by their tag; drop the @ref or ?ref="
+                            .to_string(),
+                    ));
+                }
+                asset.subdir = fragment.filter(|f| !f.is_empty()).map(str::to_string);
+            }
+            Source::Local(_) => {}
+        }
+
+        let subdir = match &result {
+            Source::Git(git) => git.subdir.as_deref(),
+            Source::Release(asset) => asset.subdir.as_deref(),
+            Source::Local(_) => None,
+        };
+        if let Some(subdir) = subdir.filter(|s| !is_relative_subdir(s)) {
+            return Err(SkiloError::InvalidSource(
+                original.to_string(),
+                format!(
+                    "Subdirectory '{}' must be a relative path inside the source",
+                    subdir
+                ),
+            ));
+        }
+        Ok(result)
+    }
+
+    fn parse_git(source: &str) -> Result<Self, SkiloError> {
         // Check for SSH URL: git@host:owner/repo.git
         if source.starts_with("git@") {
             return Self::parse_ssh_url(source);
@@ -56,37 +190,69 @@ impl Source {
             return Self::parse_https_url(source);
         }
 
+        // Check for gist shorthand: gist:<id> or gist:user/<id>
+        if let Some(gist) = source.strip_prefix("gist:") {
+            return Self::parse_gist(source, gist);
+        }
+
         // Check for GitHub shorthand: owner/repo
         if Self::is_github_shorthand(source) {
             return Ok(Source::Git(GitSource {
                 url: format!("https://github.com/{}.git", source),
                 branch: None,
                 tag: None,
+                rev: None,
                 subdir: None,
             }));
         }
 
         Err(SkiloError::InvalidSource(
             source.to_string(),
-            "Expected: owner/repo, https://github.com/owner/repo, git@github.com:owner/repo.git, or local path".to_string(),
+            "Expected: owner/repo, https://github.com/owner/repo, git@github.com:owner/repo.git, gist:<id>, file:///path, or an existing local path".to_string(),
         ))
     }
 
-    /// Parse a source string with optional branch/tag overrides.
+    /// Parse a source string with optional branch/tag/commit overrides.
     pub fn parse_with_options(
         source: &str,
         branch: Option<String>,
         tag: Option<String>,
+        rev: Option<String>,
     ) -> Result<Self, SkiloError> {
         let mut result = Self::parse(source)?;
 
-        if let Source::Git(ref mut git) = result {
-            if branch.is_some() {
-                git.branch = branch;
+        match result {
+            Source::Git(ref mut git) => {
+                if git.rev.is_some() && (branch.is_some() || tag.is_some() || rev.is_some()) {
+                    return Err(SkiloError::InvalidSource(
+                        source.to_string(),
+                        "Use either an @ref suffix or --branch/--tag/--rev, not both".to_string(),
+                    ));
+                }
+                if rev.is_some() {
+                    git.rev = rev;
+                }
+                if branch.is_some() {
+                    git.branch = branch;
+                }
+                if tag.is_some() {
+                    git.tag = tag;
+                }
+            }
+            Source::Release(_) if branch.is_some() || tag.is_some() || rev.is_some() => {
+                return Err(SkiloError::InvalidSource(
+                    source.to_string(),
+                    "--branch, --tag, and --rev only apply to git sources".to_string(),
+                ));
             }
-            if tag.is_some() {
-                git.tag = tag;
+            Source::Release(_) => {}
+            Source::Local(_) if rev.is_some() => {
+                return Err(SkiloError::InvalidSource(
+                    source.to_string(),
+                    "--rev only applies to git sources".to_string(),
+                ));
             }
+            Source::Local(_) => {}
         }
 
         Ok(result)
@@ -129,6 +295,7 @@ impl Source {
             url: format!("git@{}:{}.git", host, path),
             branch: None,
             tag: None,
+            rev: None,
             subdir: None,
         }))
     }
@@ -144,31 +311,57 @@ impl Source {
 
         let path = url.path().trim_start_matches('/').trim_end_matches(".git");
 
-        // Check for tree/branch/path format: owner/repo/tree/branch/path
-        if let Some(tree_idx) = path.find("/tree/") {
-            let repo_path = &path[..tree_idx];
-            let rest = &path[tree_idx + 6..]; // skip "/tree/"
+        if host == "gist.github.com" {
+            return Self::parse_gist(source, path);
+        }
+
+        // Release assets: owner/repo/releases/download/tag/file
+        if host == "github.com" {
+            if let Some((repo, rest)) = path.split_once("/releases/download/") {
+                return Self::parse_release_asset(source, repo, rest);
+            }
+        }
+
+        // Raw file URLs put the ref right after owner/repo
+        if host == "raw.githubusercontent.com" {
+            let mut parts = path.splitn(4, '/');
+            if let (Some(owner), Some(repo), Some(branch), Some(file)) =
+                (parts.next(), parts.next(), parts.next(), parts.next())
+            {
+                return Ok(Source::Git(GitSource {
+                    url: format!("https://github.com/{}/{}.git", owner, repo),
+                    branch: Some(branch.to_string()),
+                    tag: None,
+                    rev: None,
+                    subdir: file_dir(file),
+                }));
+            }
+        }
+
+        // Web UI paths: owner/repo/tree/branch/path, or blob/ and raw/ links
+        // to a file, with a `/-` before the marker on GitLab
+        for (marker, is_file) in [("/tree/", false), ("/blob/", true), ("/raw/", true)] {
+            let Some(idx) = path.find(marker) else {
+                continue;
+            };
+            let repo_path = path[..idx].trim_end_matches("/-");
+            let rest = &path[idx + marker.len()..];
 
             // Split into branch and optional subdir
-            let (branch, subdir) = if let Some(slash_idx) = rest.find('/') {
-                let branch = &rest[..slash_idx];
-                let subdir = &rest[slash_idx + 1..];
-                (
-                    Some(branch.to_string()),
-                    if subdir.is_empty() {
-                        None
-                    } else {
-                        Some(subdir.to_string())
-                    },
-                )
+            let (branch, subpath) = rest.split_once('/').unwrap_or((rest, ""));
+            let subdir = if is_file {
+                file_dir(subpath)
             } else {
-                (Some(rest.to_string()), None)
+                Some(subpath.trim_end_matches('/'))
+                    .filter(|s| !s.is_empty())
+                    .map(str::to_string)
             };
 
             return Ok(Source::Git(GitSource {
                 url: format!("https://{}/{}.git", host, repo_path),
-                branch,
+                branch: Some(branch.to_string()),
                 tag: None,
+                rev: None,
                 subdir,
             }));
         }
@@ -178,21 +371,160 @@ impl Source {
             url: format!("https://{}/{}.git", host, path),
             branch: None,
             tag: None,
+            rev: None,
             subdir: None,
         }))
     }
+
+    /// Parse the `<id>` or `user/<id>` path of a gist.
+    fn parse_gist(source: &str, path: &str) -> Result<Self, SkiloError> {
+        let id = path
+            .trim_matches('/')
+            .rsplit('/')
+            .next()
+            .unwrap_or_default();
+        if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(SkiloError::InvalidSource(
+                source.to_string(),
+                "Expected a gist ID: gist:<id> or https://gist.github.com/user/<id>".to_string(),
+            ));
+        }
+
+        Ok(Source::Git(GitSource {
+            url: format!("https://gist.github.com/{}.git", id),
+            branch: None,
+            tag: None,
+            rev: None,
+            subdir: None,
+        }))
+    }
+
+    /// Parse the `tag/file` part of a release asset URL of `repo`.
+    fn parse_release_asset(source: &str, repo: &str, rest: &str) -> Result<Self, SkiloError> {
+        let invalid = |reason: &str| SkiloError::InvalidSource(source.to_string(), reason.into());
+        let (tag, file) = rest
+            .split_once('/')
+            .filter(|(tag, file)| !tag.is_empty() && !file.is_empty() && !file.contains('/'))
+            .ok_or_else(|| invalid("Expected releases/download/<tag>/<file>"))?;
+        let format = ArchiveFormat::from_file_name(file).ok_or_else(|| {
+            invalid("Release assets must be .tar.gz, .tgz, .tar, or .zip archives")
+        })?;
+
+        Ok(Source::Release(ReleaseAsset {
+            url: source.to_string(),
+            repo: repo.to_string(),
+            tag: tag.to_string(),
+            format,
+            subdir: None,
+        }))
+    }
+}
+
+impl ReleaseAsset {
+    /// The asset's file name, such as `skills.tar.gz`.
+    pub fn file_name(&self) -> &str {
+        self.url.rsplit('/').next().unwrap_or(&self.url)
+    }
+
+    /// Get a display-friendly name, such as `owner/repo@v1.0 (skills.tar.gz)`.
+    pub fn display_name(&self) -> String {
+        format!("{}@{} ({})", self.repo, self.tag, self.file_name())
+    }
+
+    /// The repository the release belongs to, at the release tag.
+    ///
+    /// `add.allowed_sources` patterns are matched against it.
+    pub fn repository(&self) -> GitSource {
+        GitSource {
+            url: format!("https://github.com/{}.git", self.repo),
+            branch: None,
+            tag: Some(self.tag.clone()),
+            rev: None,
+            subdir: None,
+        }
+    }
+}
+
+/// Split a trailing `@ref` off a git source string.
+///
+/// Only an `@` after the last path separator counts, so the `git@` of SSH
+/// URLs and userinfo in HTTPS URLs are left alone.
+fn split_rev(source: &str) -> (&str, Option<&str>) {
+    let last_sep = source.rfind(['/', ':']).unwrap_or(0);
+    match source.rfind('@') {
+        Some(at) if at > last_sep && at + 1 < source.len() => {
+            (&source[..at], Some(&source[at + 1..]))
+        }
+        _ => (source, None),
+    }
+}
+
+/// The directory holding a file linked from a web UI, such as the skill
+/// directory of a `SKILL.md` blob link.
+fn file_dir(file: &str) -> Option<String> {
+    file.rsplit_once('/')
+        .map(|(dir, _)| dir.to_string())
+        .filter(|dir| !dir.is_empty())
+}
+
+/// Whether `subdir` is a relative path that cannot leave the directory it
+/// is joined to: no root, drive prefix, or `..` component.
+pub fn is_relative_subdir(subdir: &str) -> bool {
+    Path::new(subdir)
+        .components()
+        .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+}
+
+/// Split a `?ref=<ref>` query off a git source string.
+///
+/// Other query parameters are rejected rather than silently dropped.
+fn split_query_ref(source: &str) -> Result<(&str, Option<&str>), String> {
+    let Some((source, query)) = source.split_once('?') else {
+        return Ok((source, None));
+    };
+
+    let mut reference = None;
+    for param in query.split('&').filter(|p| !p.is_empty()) {
+        match param.split_once('=') {
+            Some(("ref", value)) if !value.is_empty() => reference = Some(value),
+            _ => {
+                return Err(format!(
+                    "Unsupported query parameter '{}'; only ?ref=<ref> is recognized",
+                    param
+                ))
+            }
+        }
+    }
+    Ok((source, reference))
 }
 
 impl GitSource {
-    /// Get the reference to checkout (branch, tag, or HEAD).
+    /// Get the reference to checkout (branch, tag, `@ref`, or HEAD).
     pub fn reference(&self) -> Option<&str> {
-        self.branch.as_deref().or(self.tag.as_deref())
+        self.branch
+            .as_deref()
+            .or(self.tag.as_deref())
+            .or(self.rev.as_deref())
+    }
+
+    /// Get the display name followed by the requested reference, if any.
+    ///
+    /// For example `owner/repo@v1.2.0`.
+    pub fn display_name_with_ref(&self) -> String {
+        match self.reference() {
+            Some(reference) => format!("{}@{}", self.display_name(), reference),
+            None => self.display_name(),
+        }
     }
 
     /// Get a display-friendly name for the source.
     pub fn display_name(&self) -> String {
-        // Extract owner/repo from URL
         let url = self.url.trim_end_matches(".git");
+        if let Some(id) = url.strip_prefix("https://gist.github.com/") {
+            return format!("gist:{}", id);
+        }
+
+        // Extract owner/repo from URL
         if let Some(idx) = url.rfind("://") {
             let path = &url[idx + 3..];
             if let Some(slash_idx) = path.find('/') {
@@ -206,6 +538,61 @@ impl GitSource {
         }
         url.to_string()
     }
+
+    /// Get the `host/owner/repo` identifier for this source.
+    pub fn host_path(&self) -> String {
+        let url = self.url.trim_end_matches(".git");
+        if let Some(idx) = url.find("://") {
+            let rest = &url[idx + 3..];
+            let (authority, path) = rest.split_once('/').unwrap_or((rest, ""));
+            // Drop any userinfo (e.g. `git@` in `ssh://git@host/...`), but
+            // only from the authority: `@` may also appear in the path
+            let host = authority
+                .rsplit_once('@')
+                .map(|(_, h)| h)
+                .unwrap_or(authority);
+            return if path.is_empty() {
+                host.to_string()
+            } else {
+                format!("{}/{}", host, path)
+            };
+        }
+        if let Some(rest) = url.strip_prefix("git@") {
+            return rest.replacen(':', "/", 1);
+        }
+        url.to_string()
+    }
+
+    /// Check whether this source matches any of the given glob patterns.
+    ///
+    /// Patterns with a host (`github.com/org/*`) match against [`host_path`],
+    /// patterns without one (`org/*`) match against [`display_name`].
+    ///
+    /// [`host_path`]: GitSource::host_path
+    /// [`display_name`]: GitSource::display_name
+    pub fn matches_any(&self, patterns: &[String]) -> bool {
+        let host_path = self.host_path();
+        let owner_repo = self.display_name();
+
+        patterns.iter().any(|pattern| {
+            let Ok(glob) = globset::GlobBuilder::new(pattern)
+                .literal_separator(true)
+                .build()
+            else {
+                return false;
+            };
+            let matcher = glob.compile_matcher();
+            let has_host = pattern
+                .split('/')
+                .next()
+                .is_some_and(|first| first.contains('.'));
+            if has_host {
+                matcher.is_match(&host_path)
+            } else {
+                matcher.is_match(&owner_repo)
+            }
+        })
+    }
 }
 
 #[cfg(test)]
@@ -277,14 +664,222 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_file_url() {
+        let source = Source::parse("file:///srv/my%20skills").unwrap();
+        assert!(matches!(source, Source::Local(path) if path == Path::new("/srv/my skills")));
+        assert!(Source::parse("file://relative").is_err());
+    }
+
+    #[test]
+    fn test_parse_gist() {
+        for gist in [
+            "gist:aa5a315d61ae9438b18d",
+            "gist:octocat/aa5a315d61ae9438b18d",
+            "https://gist.github.com/octocat/aa5a315d61ae9438b18d",
+        ] {
+            let Source::Git(git) = Source::parse(gist).unwrap() else {
+                panic!("Expected Git source");
+            };
+            assert_eq!(git.url, "https://gist.github.com/aa5a315d61ae9438b18d.git");
+            assert_eq!(git.display_name(), "gist:aa5a315d61ae9438b18d");
+        }
+        assert!(Source::parse("gist:").is_err());
+    }
+
+    #[test]
+    fn test_parse_release_asset() {
+        let url = "https://github.com/owner/repo/releases/download/v1.0/skills.tar.gz";
+        let Source::Release(asset) = Source::parse(&format!("{}#skills/pdf", url)).unwrap() else {
+            panic!("Expected Release source");
+        };
+        assert_eq!(asset.url, url);
+        assert_eq!(asset.repo, "owner/repo");
+        assert_eq!(asset.tag, "v1.0");
+        assert_eq!(asset.format, ArchiveFormat::TarGz);
+        assert_eq!(asset.subdir.as_deref(), Some("skills/pdf"));
+        assert_eq!(asset.display_name(), "owner/repo@v1.0 (skills.tar.gz)");
+        assert!(asset.repository().matches_any(&["owner/*".to_string()]));
+
+        let zip = "https://github.com/owner/repo/releases/download/v1.0/skills.zip";
+        assert!(matches!(
+            Source::parse(zip).unwrap(),
+            Source::Release(ReleaseAsset {
+                format: ArchiveFormat::Zip,
+                ..
+            })
+        ));
+        assert!(
+            Source::parse("https://github.com/owner/repo/releases/download/v1.0/skills.exe")
+                .is_err()
+        );
+        assert!(Source::parse_with_options(url, None, None, Some("main".into())).is_err());
+    }
+
+    #[test]
+    fn test_parse_existing_path_without_prefix() {
+        // Tests run from the crate root, where `src` exists
+        let source = Source::parse("src").unwrap();
+        assert!(matches!(source, Source::Local(path) if path == Path::new("src")));
+
+        assert!(Source::parse("no-such-skills-repo").is_err());
+    }
+
+    #[test]
+    fn test_matches_any() {
+        let Source::Git(git) = Source::parse("git@github.com:my-org/tools.git").unwrap() else {
+            panic!("Expected Git source");
+        };
+        assert_eq!(git.host_path(), "github.com/my-org/tools");
+        assert!(git.matches_any(&["github.com/my-org/*".to_string()]));
+        assert!(git.matches_any(&["my-org/*".to_string()]));
+        assert!(!git.matches_any(&["gitlab.com/my-org/*".to_string()]));
+        assert!(!git.matches_any(&["other/*".to_string()]));
+
+        // An `@` in the path is not userinfo
+        let Source::Git(git) =
+            Source::parse("https://evil.com/x@github.com/my-org/skills").unwrap()
+        else {
+            panic!("Expected Git source");
+        };
+        assert_eq!(git.host_path(), "evil.com/x@github.com/my-org/skills");
+        assert!(!git.matches_any(&["github.com/my-org/*".to_string()]));
+    }
+
     #[test]
     fn test_display_name() {
         let git = GitSource {
             url: "https://github.com/owner/repo.git".to_string(),
             branch: None,
             tag: None,
+            rev: None,
             subdir: None,
         };
         assert_eq!(git.display_name(), "owner/repo");
     }
+
+    #[test]
+    fn test_parse_file_links() {
+        for input in [
+            "https://github.com/owner/repo/blob/main/skills/foo/SKILL.md",
+            "https://github.com/owner/repo/raw/main/skills/foo/SKILL.md",
+            "https://raw.githubusercontent.com/owner/repo/main/skills/foo/SKILL.md",
+        ] {
+            let Source::Git(git) = Source::parse(input).unwrap() else {
+                panic!("Expected Git source");
+            };
+            assert_eq!(git.url, "https://github.com/owner/repo.git", "{}", input);
+            assert_eq!(git.branch.as_deref(), Some("main"));
+            assert_eq!(git.subdir.as_deref(), Some("skills/foo"));
+        }
+
+        let Source::Git(git) =
+            Source::parse("https://gitlab.com/group/repo/-/blob/v2/foo/SKILL.md").unwrap()
+        else {
+            panic!("Expected Git source");
+        };
+        assert_eq!(git.url, "https://gitlab.com/group/repo.git");
+        assert_eq!(git.branch.as_deref(), Some("v2"));
+        assert_eq!(git.subdir.as_deref(), Some("foo"));
+
+        let Source::Git(git) =
+            Source::parse("https://gitlab.com/group/repo/-/tree/main/skills/").unwrap()
+        else {
+            panic!("Expected Git source");
+        };
+        assert_eq!(git.url, "https://gitlab.com/group/repo.git");
+        assert_eq!(git.subdir.as_deref(), Some("skills"));
+
+        // A SKILL.md at the repository root
+        let Source::Git(git) =
+            Source::parse("https://github.com/owner/repo/blob/main/SKILL.md").unwrap()
+        else {
+            panic!("Expected Git source");
+        };
+        assert!(git.subdir.is_none());
+    }
+
+    #[test]
+    fn test_parse_rejects_escaping_subdir() {
+        assert!(Source::parse("owner/repo#../../etc").is_err());
+        assert!(Source::parse("https://github.com/owner/repo#skills/../../x").is_err());
+        assert!(is_relative_subdir("skills/./foo"));
+        assert!(!is_relative_subdir("/etc"));
+    }
+
+    #[test]
+    fn test_parse_fragment_and_query_ref() {
+        let Source::Git(git) = Source::parse("https://gitlab.com/owner/repo#skills/foo").unwrap()
+        else {
+            panic!("Expected Git source");
+        };
+        assert_eq!(git.url, "https://gitlab.com/owner/repo.git");
+        assert_eq!(git.subdir.as_deref(), Some("skills/foo"));
+        assert!(git.rev.is_none());
+
+        let Source::Git(git) = Source::parse("owner/repo?ref=v2#skills").unwrap() else {
+            panic!("Expected Git source");
+        };
+        assert_eq!(git.url, "https://github.com/owner/repo.git");
+        assert_eq!(git.rev.as_deref(), Some("v2"));
+        assert_eq!(git.subdir.as_deref(), Some("skills"));
+
+        let Source::Git(git) = Source::parse("git@gitlab.com:group/repo.git@v1#skills/").unwrap()
+        else {
+            panic!("Expected Git source");
+        };
+        assert_eq!(git.url, "git@gitlab.com:group/repo.git");
+        assert_eq!(git.rev.as_deref(), Some("v1"));
+        assert_eq!(git.subdir.as_deref(), Some("skills"));
+
+        let Source::Git(git) =
+            Source::parse("https://github.com/owner/repo/tree/main/skills#foo").unwrap()
+        else {
+            panic!("Expected Git source");
+        };
+        assert_eq!(git.branch.as_deref(), Some("main"));
+        assert_eq!(git.subdir.as_deref(), Some("skills/foo"));
+
+        assert!(Source::parse("owner/repo@v1?ref=v2").is_err());
+        assert!(Source::parse("owner/repo?path=skills").is_err());
+    }
+
+    #[test]
+    fn test_parse_rev_suffix() {
+        for input in [
+            "owner/repo@v1.2.0",
+            "https://github.com/owner/repo@v1.2.0",
+            "git@github.com:owner/repo.git@v1.2.0",
+        ] {
+            let Source::Git(git) = Source::parse(input).unwrap() else {
+                panic!("Expected Git source");
+            };
+            assert_eq!(git.display_name(), "owner/repo");
+            assert_eq!(git.rev.as_deref(), Some("v1.2.0"));
+            assert_eq!(git.reference(), Some("v1.2.0"));
+            assert_eq!(git.display_name_with_ref(), "owner/repo@v1.2.0");
+        }
+
+        let Source::Git(git) = Source::parse("git@github.com:owner/repo.git").unwrap() else {
+            panic!("Expected Git source");
+        };
+        assert!(git.rev.is_none());
+
+        assert!(
+            Source::parse_with_options("owner/repo@a1b2c3d", Some("main".into()), None, None)
+                .is_err()
+        );
+        assert!(
+            Source::parse_with_options("owner/repo@v1", None, None, Some("a1b2c3d".into()))
+                .is_err()
+        );
+
+        let Source::Git(git) =
+            Source::parse_with_options("owner/repo", None, None, Some("a1b2c3d".into())).unwrap()
+        else {
+            panic!("Expected Git source");
+        };
+        assert_eq!(git.reference(), Some("a1b2c3d"));
+        assert!(Source::parse_with_options(".", None, None, Some("a1b2c3d".into())).is_err());
+    }
 }
@@ -0,0 +1,573 @@
+//! Pull request reviews on GitHub (`lint --github-pr-review`).
+//!
+//! Lint diagnostics on lines a pull request changes are posted as review
+//! comments, in one review per run. Diagnostics elsewhere in changed files
+//! are listed in the review body; files the pull request does not touch
+//! are left alone.
+//!
+//! The pull request is taken from the GitHub Actions environment:
+//! `GITHUB_TOKEN`, `GITHUB_REPOSITORY`, the event payload at
+//! `GITHUB_EVENT_PATH` (or a `refs/pull/<n>/merge` `GITHUB_REF`), and
+//! `GITHUB_API_URL` for GitHub Enterprise.
+//!
+//! Comments and the summary review carry hidden markers, so a re-run
+//! updates the earlier summary, keeps comments that still apply, deletes
+//! the ones that no longer do, and only posts new ones.
+
+use crate::error::SkiloError;
+use crate::skill::{Diagnostic, ValidationResult};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Display;
+
+const USER_AGENT: &str = concat!("skilo/", env!("CARGO_PKG_VERSION"));
+const DEFAULT_API_URL: &str = "https://api.github.com";
+
+/// Marker in the body of the summary review.
+const REVIEW_MARKER: &str = "<!-- skilo-review -->";
+/// Marker in the body of every review comment.
+const COMMENT_MARKER: &str = "<!-- skilo -->";
+/// Comments posted per review; larger sets are split over several reviews.
+const COMMENTS_PER_REVIEW: usize = 50;
+/// Items requested per page of list endpoints.
+const PAGE_SIZE: usize = 100;
+
+/// The pull request to review.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PullRequest {
+    /// API base URL, without a trailing slash.
+    pub api_url: String,
+    /// Repository as `owner/name`.
+    pub repository: String,
+    /// Pull request number.
+    pub number: u64,
+    /// Head commit the review is attached to, if known.
+    pub head_sha: Option<String>,
+    /// API token.
+    pub token: String,
+}
+
+#[derive(Deserialize)]
+struct Event {
+    pull_request: Option<EventPullRequest>,
+}
+
+#[derive(Deserialize)]
+struct EventPullRequest {
+    number: u64,
+    head: EventHead,
+}
+
+#[derive(Deserialize)]
+struct EventHead {
+    sha: String,
+}
+
+impl PullRequest {
+    /// Read the pull request from the GitHub Actions environment.
+    pub fn from_env() -> Result<Self, SkiloError> {
+        Self::from_vars(|name| std::env::var(name).ok().filter(|v| !v.is_empty()))
+    }
+
+    /// Read the pull request from variables looked up with `var`.
+    fn from_vars(var: impl Fn(&str) -> Option<String>) -> Result<Self, SkiloError> {
+        let required = |name: &str| {
+            var(name).ok_or_else(|| {
+                SkiloError::Config(format!("--github-pr-review needs {} to be set", name))
+            })
+        };
+        let token = required("GITHUB_TOKEN")?;
+        let repository = required("GITHUB_REPOSITORY")?;
+
+        let event = var("GITHUB_EVENT_PATH")
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str::<Event>(&content).ok())
+            .and_then(|event| event.pull_request);
+        let (number, head_sha) = match event {
+            Some(pr) => (pr.number, Some(pr.head.sha)),
+            None => {
+                let number = var("GITHUB_REF")
+                    .and_then(|r| {
+                        r.strip_prefix("refs/pull/")?
+                            .split('/')
+                            .next()?
+                            .parse()
+                            .ok()
+                    })
+                    .ok_or_else(|| {
+                        SkiloError::Config(
+                            "--github-pr-review must run on a pull request event".to_string(),
+                        )
+                    })?;
+                (number, None)
+            }
+        };
+
+        Ok(Self {
+            api_url: var("GITHUB_API_URL")
+                .unwrap_or_else(|| DEFAULT_API_URL.to_string())
+                .trim_end_matches('/')
+                .to_string(),
+            repository,
+            number,
+            head_sha,
+            token,
+        })
+    }
+}
+
+/// A comment on a line of the new version of a file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReviewComment {
+    /// Path relative to the repository root.
+    pub path: String,
+    /// Line number in the new version of the file.
+    pub line: usize,
+    /// Comment text, including the marker.
+    pub body: String,
+}
+
+/// What a review run changed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReviewOutcome {
+    /// Comments posted by this run.
+    pub posted: usize,
+    /// Comments from earlier runs that still apply.
+    pub kept: usize,
+    /// Comments from earlier runs that were deleted.
+    pub removed: usize,
+    /// Diagnostics in changed files listed only in the review body.
+    pub in_body: usize,
+}
+
+/// Lines of the new file that `patch` lets reviews comment on: added lines
+/// and the context lines around them.
+pub fn commentable_lines(patch: &str) -> BTreeSet<usize> {
+    let mut lines = BTreeSet::new();
+    let mut next: Option<usize> = None;
+    for line in patch.lines() {
+        if let Some(header) = line.strip_prefix("@@ ") {
+            // @@ -a,b +c,d @@ section
+            next = header
+                .split_whitespace()
+                .find_map(|range| range.strip_prefix('+'))
+                .and_then(|range| range.split(',').next()?.parse().ok());
+            continue;
+        }
+        let Some(current) = next else {
+            continue;
+        };
+        match line.chars().next() {
+            Some('+') | Some(' ') | None => {
+                lines.insert(current);
+                next = Some(current + 1);
+            }
+            _ => {}
+        }
+    }
+    lines
+}
+
+/// Split diagnostics into comments on commentable lines and the rest of
+/// those in changed files.
+///
+/// Paths of `results` must be relative to the repository root.
+fn plan<'a>(
+    results: &'a [(String, ValidationResult)],
+    changed: &HashMap<String, BTreeSet<usize>>,
+) -> (Vec<ReviewComment>, Vec<&'a Diagnostic>) {
+    let mut comments = Vec::new();
+    let mut in_body = Vec::new();
+    for diag in results
+        .iter()
+        .flat_map(|(_, result)| result.errors.iter().chain(&result.warnings))
+    {
+        let Some(lines) = changed.get(&diag.path) else {
+            continue;
+        };
+        match diag.line.filter(|line| lines.contains(line)) {
+            Some(line) => comments.push(ReviewComment {
+                path: diag.path.clone(),
+                line,
+                body: comment_body(diag),
+            }),
+            None => in_body.push(diag),
+        }
+    }
+    comments.dedup();
+    (comments, in_body)
+}
+
+/// Body of the review comment for `diag`.
+fn comment_body(diag: &Diagnostic) -> String {
+    let severity = if diag.code.is_error() {
+        "error"
+    } else {
+        "warning"
+    };
+    let mut body = format!(
+        "{}\n**{}** ({}): {}",
+        COMMENT_MARKER, diag.code, severity, diag.message
+    );
+    if let Some(hint) = &diag.fix_hint {
+        body.push_str(&format!("\n\n{}", hint));
+    }
+    body
+}
+
+/// Body of the summary review.
+fn summary_body(
+    comments: usize,
+    in_body: &[&Diagnostic],
+    errors: usize,
+    warnings: usize,
+) -> String {
+    let mut body = format!("{}\n### skilo lint\n\n", REVIEW_MARKER);
+    if errors + warnings == 0 {
+        body.push_str("No issues found in the changed files.\n");
+        return body;
+    }
+    body.push_str(&format!(
+        "{} error(s) and {} warning(s) in the changed files",
+        errors, warnings
+    ));
+    body.push_str(&format!("; {} on changed lines.\n", comments));
+    if !in_body.is_empty() {
+        body.push_str("\nOutside the changed lines:\n\n");
+        for diag in in_body {
+            let location = match diag.line {
+                Some(line) => format!("{}:{}", diag.path, line),
+                None => diag.path.clone(),
+            };
+            body.push_str(&format!(
+                "- `{}` **{}**: {}\n",
+                location, diag.code, diag.message
+            ));
+        }
+    }
+    body
+}
+
+/// Post `results` as a review of `pr`, replacing the comments of earlier
+/// runs.
+///
+/// Paths of `results` must be relative to the repository root.
+pub fn review(
+    pr: &PullRequest,
+    results: &[(String, ValidationResult)],
+) -> Result<ReviewOutcome, SkiloError> {
+    let api = Api::new(pr)?;
+    let base = format!("/repos/{}/pulls/{}", pr.repository, pr.number);
+
+    let changed: HashMap<String, BTreeSet<usize>> = api
+        .get_all::<PullFile>(&format!("{}/files", base))?
+        .into_iter()
+        .filter(|file| file.status != "removed")
+        .map(|file| {
+            let lines = file
+                .patch
+                .as_deref()
+                .map(commentable_lines)
+                .unwrap_or_default();
+            (file.filename, lines)
+        })
+        .collect();
+
+    let (mut comments, in_body) = plan(results, &changed);
+    let (errors, warnings) = results
+        .iter()
+        .flat_map(|(_, result)| result.errors.iter().chain(&result.warnings))
+        .filter(|diag| changed.contains_key(&diag.path))
+        .fold((0, 0), |(e, w), diag| {
+            if diag.code.is_error() {
+                (e + 1, w)
+            } else {
+                (e, w + 1)
+            }
+        });
+    let summary = summary_body(comments.len(), &in_body, errors, warnings);
+
+    // Keep earlier comments that still apply and delete the rest
+    let mut outcome = ReviewOutcome {
+        in_body: in_body.len(),
+        ..ReviewOutcome::default()
+    };
+    for existing in api
+        .get_all::<ExistingComment>(&format!("{}/comments", base))?
+        .into_iter()
+        .filter(|c| c.body.starts_with(COMMENT_MARKER))
+    {
+        let position = comments.iter().position(|c| {
+            Some(c.line) == existing.line && c.path == existing.path && c.body == existing.body
+        });
+        match position {
+            Some(i) => {
+                comments.remove(i);
+                outcome.kept += 1;
+            }
+            None => {
+                api.delete(&format!(
+                    "/repos/{}/pulls/comments/{}",
+                    pr.repository, existing.id
+                ))?;
+                outcome.removed += 1;
+            }
+        }
+    }
+    outcome.posted = comments.len();
+
+    let previous = api
+        .get_all::<ExistingReview>(&format!("{}/reviews", base))?
+        .into_iter()
+        .rev()
+        .find(|r| r.body.as_deref().is_some_and(|b| b.contains(REVIEW_MARKER)));
+
+    let mut batches: Vec<&[ReviewComment]> = comments.chunks(COMMENTS_PER_REVIEW).collect();
+    match previous {
+        Some(previous) => {
+            api.send(
+                reqwest::Method::PUT,
+                &format!("{}/reviews/{}", base, previous.id),
+                &serde_json::json!({ "body": summary }),
+            )?;
+        }
+        None => {
+            let first = if batches.is_empty() {
+                &[][..]
+            } else {
+                batches.remove(0)
+            };
+            api.post_review(&base, &summary, first)?;
+        }
+    }
+    for batch in batches {
+        let body = format!("skilo lint: {} new comment(s)", batch.len());
+        api.post_review(&base, &body, batch)?;
+    }
+
+    Ok(outcome)
+}
+
+#[derive(Deserialize)]
+struct PullFile {
+    filename: String,
+    status: String,
+    patch: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ExistingComment {
+    id: u64,
+    path: String,
+    line: Option<usize>,
+    body: String,
+}
+
+#[derive(Deserialize)]
+struct ExistingReview {
+    id: u64,
+    body: Option<String>,
+}
+
+/// A review comment as sent to the API.
+#[derive(Serialize)]
+struct NewComment<'a> {
+    path: &'a str,
+    line: usize,
+    side: &'static str,
+    body: &'a str,
+}
+
+/// Minimal GitHub REST client.
+struct Api<'a> {
+    client: reqwest::blocking::Client,
+    pr: &'a PullRequest,
+}
+
+impl<'a> Api<'a> {
+    fn new(pr: &'a PullRequest) -> Result<Self, SkiloError> {
+        let client = reqwest::blocking::Client::builder()
+            .user_agent(USER_AGENT)
+            .build()
+            .map_err(network)?;
+        Ok(Self { client, pr })
+    }
+
+    fn request(&self, method: reqwest::Method, path: &str) -> reqwest::blocking::RequestBuilder {
+        self.client
+            .request(method, format!("{}{}", self.pr.api_url, path))
+            .bearer_auth(&self.pr.token)
+            .header("Accept", "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+    }
+
+    /// Send a request and fail on an unsuccessful status.
+    fn execute(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        request: reqwest::blocking::RequestBuilder,
+    ) -> Result<reqwest::blocking::Response, SkiloError> {
+        let response = request.send().map_err(network)?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+        let detail = response.text().unwrap_or_default();
+        Err(SkiloError::Network {
+            message: format!("{} {} returned {}: {}", method, path, status, detail.trim()),
+        })
+    }
+
+    /// Fetch every page of a list endpoint.
+    fn get_all<T: DeserializeOwned>(&self, path: &str) -> Result<Vec<T>, SkiloError> {
+        let mut items = Vec::new();
+        for page in 1.. {
+            let request = self.request(
+                reqwest::Method::GET,
+                &format!("{}?per_page={}&page={}", path, PAGE_SIZE, page),
+            );
+            let batch: Vec<T> = self
+                .execute(reqwest::Method::GET, path, request)?
+                .json()
+                .map_err(network)?;
+            let done = batch.len() < PAGE_SIZE;
+            items.extend(batch);
+            if done {
+                break;
+            }
+        }
+        Ok(items)
+    }
+
+    fn send(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        body: &serde_json::Value,
+    ) -> Result<(), SkiloError> {
+        let request = self.request(method.clone(), path).json(body);
+        self.execute(method, path, request).map(|_| ())
+    }
+
+    fn delete(&self, path: &str) -> Result<(), SkiloError> {
+        let request = self.request(reqwest::Method::DELETE, path);
+        self.execute(reqwest::Method::DELETE, path, request)
+            .map(|_| ())
+    }
+
+    fn post_review(
+        &self,
+        base: &str,
+        body: &str,
+        comments: &[ReviewComment],
+    ) -> Result<(), SkiloError> {
+        let comments: Vec<NewComment> = comments
+            .iter()
+            .map(|c| NewComment {
+                path: &c.path,
+                line: c.line,
+                side: "RIGHT",
+                body: &c.body,
+            })
+            .collect();
+        let mut review = serde_json::json!({
+            "body": body,
+            "event": "COMMENT",
+            "comments": comments,
+        });
+        if let Some(sha) = &self.pr.head_sha {
+            review["commit_id"] = serde_json::Value::from(sha.as_str());
+        }
+        self.send(reqwest::Method::POST, &format!("{}/reviews", base), &review)
+    }
+}
+
+fn network(e: impl Display) -> SkiloError {
+    SkiloError::Network {
+        message: e.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::skill::DiagnosticCode;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_commentable_lines() {
+        let patch = "@@ -1,4 +1,5 @@\n ---\n-name: a\n+name: b\n+description: B\n ---\n\\ No newline at end of file\n@@ -20,2 +21,2 @@ Usage\n-old\n+new\n context";
+        let lines: Vec<usize> = commentable_lines(patch).into_iter().collect();
+        assert_eq!(lines, vec![1, 2, 3, 4, 21, 22]);
+    }
+
+    #[test]
+    fn test_plan() {
+        let diag = |path: &str, line| Diagnostic {
+            path: path.to_string(),
+            line,
+            column: None,
+            message: "Description too long".to_string(),
+            code: DiagnosticCode::E004,
+            fix_hint: None,
+        };
+        let results = vec![(
+            "a/SKILL.md".to_string(),
+            ValidationResult {
+                errors: vec![
+                    diag("a/SKILL.md", Some(3)),
+                    diag("a/SKILL.md", Some(40)),
+                    diag("b/SKILL.md", Some(3)),
+                ],
+                warnings: Vec::new(),
+            },
+        )];
+        let changed = HashMap::from([("a/SKILL.md".to_string(), BTreeSet::from([2, 3, 4]))]);
+
+        let (comments, in_body) = plan(&results, &changed);
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].line, 3);
+        assert!(comments[0].body.starts_with(COMMENT_MARKER));
+        assert_eq!(in_body.len(), 1);
+        assert_eq!(in_body[0].line, Some(40));
+    }
+
+    #[test]
+    fn test_pull_request_from_vars() {
+        let temp = TempDir::new().unwrap();
+        let event = temp.path().join("event.json");
+        std::fs::write(
+            &event,
+            r#"{"pull_request": {"number": 7, "head": {"sha": "abc123"}}}"#,
+        )
+        .unwrap();
+
+        let vars = |extra: &[(&str, String)]| {
+            let mut vars: HashMap<String, String> = HashMap::from([
+                ("GITHUB_TOKEN".to_string(), "t".to_string()),
+                ("GITHUB_REPOSITORY".to_string(), "o/r".to_string()),
+            ]);
+            for (k, v) in extra {
+                vars.insert(k.to_string(), v.clone());
+            }
+            PullRequest::from_vars(|name| vars.get(name).cloned())
+        };
+
+        let pr = vars(&[("GITHUB_EVENT_PATH", event.display().to_string())]).unwrap();
+        assert_eq!(pr.number, 7);
+        assert_eq!(pr.head_sha.as_deref(), Some("abc123"));
+        assert_eq!(pr.api_url, DEFAULT_API_URL);
+
+        let pr = vars(&[
+            ("GITHUB_REF", "refs/pull/12/merge".to_string()),
+            ("GITHUB_API_URL", "https://ghe.example/api/v3/".to_string()),
+        ])
+        .unwrap();
+        assert_eq!(pr.number, 12);
+        assert_eq!(pr.api_url, "https://ghe.example/api/v3");
+
+        assert!(vars(&[("GITHUB_REF", "refs/heads/main".to_string())]).is_err());
+    }
+}
@@ -0,0 +1,266 @@
+//! Local history of install, update, and remove operations.
+//!
+//! Entries are appended as JSON lines to `~/.skilo/history.jsonl` (or
+//! `$SKILO_HOME/history.jsonl`). Nothing is ever sent anywhere; the file only
+//! backs `skilo activity`. Recording can be turned off with
+//! `[history] enabled = false`.
+
+use crate::agent::Agent;
+use crate::config::HistoryConfig;
+use crate::hooks::HookSkill;
+use crate::lockfile::now_secs;
+use crate::scope::Scope;
+use crate::skill::provenance::format_date;
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::fs::OpenOptions;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+/// Kind of operation recorded in the history.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Action {
+    /// A new skill was installed.
+    Install,
+    /// An installed skill was replaced.
+    Update,
+    /// A skill was removed.
+    Remove,
+}
+
+impl Action {
+    /// Lowercase name of the action.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Action::Install => "install",
+            Action::Update => "update",
+            Action::Remove => "remove",
+        }
+    }
+}
+
+/// One recorded operation on one skill.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+    /// What happened.
+    pub action: Action,
+    /// The skill name.
+    pub skill: String,
+    /// Path to the skill directory.
+    pub path: PathBuf,
+    /// Installation scope.
+    pub scope: Scope,
+    /// Agent the skill was installed for, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent: Option<Agent>,
+    /// Source the skill was installed from, for installs and updates.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
+
+/// Path to the history file.
+pub fn history_path() -> Option<PathBuf> {
+    crate::cache::skilo_home().map(|h| h.join("history.jsonl"))
+}
+
+/// Append entries for `skills` to the history, if recording is enabled.
+///
+/// Failing to write the history never fails the operation; a warning is
+/// printed instead.
+pub fn record(config: &HistoryConfig, action: Action, skills: &[HookSkill], source: Option<&str>) {
+    if !config.enabled || skills.is_empty() {
+        return;
+    }
+    let Some(path) = history_path() else {
+        return;
+    };
+
+    let timestamp = now_secs();
+    let entries: Vec<HistoryEntry> = skills
+        .iter()
+        .map(|skill| HistoryEntry {
+            timestamp,
+            action,
+            skill: skill.name.clone(),
+            path: skill.path.clone(),
+            scope: skill.scope,
+            agent: skill.agent,
+            source: source.map(str::to_string),
+        })
+        .collect();
+
+    if let Err(e) = append(&path, &entries) {
+        eprintln!(
+            "{}: Failed to record history in {}: {}",
+            "Warning".yellow(),
+            path.display(),
+            e
+        );
+    }
+}
+
+fn append(path: &Path, entries: &[HistoryEntry]) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let mut lines = String::new();
+    for entry in entries {
+        lines.push_str(&serde_json::to_string(entry).map_err(std::io::Error::other)?);
+        lines.push('\n');
+    }
+
+    // One write per batch keeps concurrent appends from interleaving lines
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(lines.as_bytes())
+}
+
+/// Read all entries from the history file, oldest first.
+///
+/// Lines that do not parse are skipped so a damaged line doesn't hide the
+/// rest of the history.
+pub fn load(path: &Path) -> std::io::Result<Vec<HistoryEntry>> {
+    let file = match std::fs::File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+
+    let mut entries = Vec::new();
+    for line in BufReader::new(file).lines() {
+        if let Ok(entry) = serde_json::from_str(&line?) {
+            entries.push(entry);
+        }
+    }
+    Ok(entries)
+}
+
+/// Remove entries older than `cutoff`, returning how many were dropped.
+pub fn prune(path: &Path, cutoff: u64) -> std::io::Result<usize> {
+    let entries = load(path)?;
+    let kept: Vec<HistoryEntry> = entries
+        .iter()
+        .filter(|e| e.timestamp >= cutoff)
+        .cloned()
+        .collect();
+    let dropped = entries.len() - kept.len();
+    if dropped == 0 {
+        return Ok(0);
+    }
+
+    let temp = path.with_extension("jsonl.tmp");
+    let _ = std::fs::remove_file(&temp);
+    append(&temp, &kept)?;
+    std::fs::rename(&temp, path)?;
+    Ok(dropped)
+}
+
+/// Parse a point in time relative to `now`.
+///
+/// Accepts an age such as `30m`, `12h`, `7d`, or `2w`, or a UTC date in
+/// `YYYY-MM-DD` form.
+pub fn parse_since(value: &str, now: u64) -> Option<u64> {
+    let value = value.trim();
+
+    if let Some((year, rest)) = value.split_once('-') {
+        let (month, day) = rest.split_once('-')?;
+        let days = days_from_civil(year.parse().ok()?, month.parse().ok()?, day.parse().ok()?)?;
+        return u64::try_from(days).ok().map(|d| d * 86_400);
+    }
+
+    let split = value.find(|c: char| !c.is_ascii_digit())?;
+    let (amount, unit) = value.split_at(split);
+    let amount: u64 = amount.parse().ok()?;
+    let seconds = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 3_600,
+        "d" => 86_400,
+        "w" => 7 * 86_400,
+        _ => return None,
+    };
+    Some(now.saturating_sub(amount.saturating_mul(seconds)))
+}
+
+/// Days since the Unix epoch for a UTC civil date.
+fn days_from_civil(year: i64, month: u32, day: u32) -> Option<i64> {
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    // Inverse of the conversion in `format_date` (Howard Hinnant's algorithm)
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let mp = (i64::from(month) + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    Some(era * 146_097 + doe - 719_468)
+}
+
+/// Format a timestamp as a UTC `YYYY-MM-DD HH:MM` string.
+pub fn format_timestamp(secs: u64) -> String {
+    format!(
+        "{} {:02}:{:02}",
+        format_date(secs),
+        secs % 86_400 / 3_600,
+        secs % 3_600 / 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_since() {
+        let now = 1_738_281_600; // 2025-01-31
+        assert_eq!(parse_since("2d", now), Some(now - 2 * 86_400));
+        assert_eq!(parse_since("1w", now), Some(now - 7 * 86_400));
+        assert_eq!(parse_since("2025-01-31", now), Some(now));
+        assert_eq!(parse_since("2000-02-29", now), Some(951_782_400));
+        assert_eq!(parse_since("2025-13-01", now), None);
+        assert_eq!(parse_since("soon", now), None);
+        assert_eq!(format_timestamp(now + 3_600 + 120), "2025-01-31 01:02");
+    }
+
+    #[test]
+    fn test_append_load_and_prune() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("history.jsonl");
+        let entry = |timestamp, action| HistoryEntry {
+            timestamp,
+            action,
+            skill: "my-skill".into(),
+            path: PathBuf::from("skills/my-skill"),
+            scope: Scope::Project,
+            agent: None,
+            source: None,
+        };
+
+        append(
+            &path,
+            &[entry(100, Action::Install), entry(200, Action::Update)],
+        )
+        .unwrap();
+        append(&path, &[entry(300, Action::Remove)]).unwrap();
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(b"not json\n")
+            .unwrap();
+
+        let loaded = load(&path).unwrap();
+        assert_eq!(loaded.len(), 3);
+        assert_eq!(loaded[1].action, Action::Update);
+
+        assert_eq!(prune(&path, 200).unwrap(), 1);
+        let loaded = load(&path).unwrap();
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded[0].timestamp, 200);
+    }
+}
@@ -0,0 +1,211 @@
+//! User-defined commands run around install and remove operations.
+//!
+//! Hooks are configured in the `[hooks]` section of the global config
+//! (`~/.skilo/config.toml`) or a file passed with `--config`, never a
+//! project's `.skilorc.toml`, and run through the shell from the current
+//! directory. The affected skills are
+//! described in environment variables:
+//!
+//! | Variable            | Value                                          |
+//! | ------------------- | ---------------------------------------------- |
+//! | `SKILO_HOOK`        | Hook name, e.g. `post_add`                     |
+//! | `SKILO_SKILLS`      | Skill names, separated by spaces               |
+//! | `SKILO_SKILL_PATHS` | Skill directories, one per line                |
+//! | `SKILO_SKILLS_JSON` | JSON array of `{name, path, scope, agent}`     |
+//! | `SKILO_SOURCE`      | Source the skills were installed from, if any  |
+//!
+//! Hooks get no stdin, and their stdout goes to skilo's stderr, so they
+//! can't consume `skilo api --stdio` requests or corrupt `--format json`
+//! output.
+//!
+//! A failing `pre_*` hook aborts the operation; a failing `post_*` hook only
+//! prints a warning, since the operation has already happened.
+
+use crate::agent::Agent;
+use crate::config::HooksConfig;
+use crate::error::SkiloError;
+use crate::scope::Scope;
+use colored::Colorize;
+use serde::Serialize;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+/// A point in an operation where a hook can run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookEvent {
+    /// After new skills are installed.
+    PostAdd,
+    /// After already installed skills are replaced by `add`.
+    PostUpdate,
+    /// Before skills are removed.
+    PreRemove,
+}
+
+impl HookEvent {
+    /// Config key of this hook.
+    pub fn name(self) -> &'static str {
+        match self {
+            HookEvent::PostAdd => "post_add",
+            HookEvent::PostUpdate => "post_update",
+            HookEvent::PreRemove => "pre_remove",
+        }
+    }
+
+    /// The command configured for this hook, if any.
+    fn command(self, config: &HooksConfig) -> Option<&str> {
+        match self {
+            HookEvent::PostAdd => config.post_add.as_deref(),
+            HookEvent::PostUpdate => config.post_update.as_deref(),
+            HookEvent::PreRemove => config.pre_remove.as_deref(),
+        }
+        .filter(|c| !c.trim().is_empty())
+    }
+
+    fn is_pre(self) -> bool {
+        matches!(self, HookEvent::PreRemove)
+    }
+}
+
+/// A skill affected by an operation.
+#[derive(Debug, Clone, Serialize)]
+pub struct HookSkill {
+    /// The skill name.
+    pub name: String,
+    /// Path to the skill directory.
+    pub path: PathBuf,
+    /// Installation scope.
+    pub scope: Scope,
+    /// Agent the skill is installed for, if any.
+    pub agent: Option<Agent>,
+}
+
+/// Run the hook for `event`, if one is configured and `skills` is not empty.
+///
+/// Returns an error only when a `pre_*` hook fails.
+pub fn run(
+    config: &HooksConfig,
+    event: HookEvent,
+    skills: &[HookSkill],
+    source: Option<&str>,
+) -> Result<(), SkiloError> {
+    let Some(command) = event.command(config) else {
+        return Ok(());
+    };
+    if skills.is_empty() {
+        return Ok(());
+    }
+
+    let mut cmd = shell(command);
+    cmd.stdin(Stdio::null())
+        .stdout(std::io::stderr())
+        .env("SKILO_HOOK", event.name())
+        .env(
+            "SKILO_SKILLS",
+            skills
+                .iter()
+                .map(|s| s.name.as_str())
+                .collect::<Vec<_>>()
+                .join(" "),
+        )
+        .env(
+            "SKILO_SKILL_PATHS",
+            skills
+                .iter()
+                .map(|s| s.path.display().to_string())
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )
+        .env(
+            "SKILO_SKILLS_JSON",
+            serde_json::to_string(skills).unwrap_or_else(|_| "[]".into()),
+        );
+    if let Some(source) = source {
+        cmd.env("SKILO_SOURCE", source);
+    }
+
+    let failure = match cmd.status() {
+        Ok(status) if status.success() => return Ok(()),
+        Ok(status) => status.to_string(),
+        Err(e) => e.to_string(),
+    };
+
+    if event.is_pre() {
+        return Err(SkiloError::HookFailed {
+            hook: event.name().to_string(),
+            message: failure,
+        });
+    }
+
+    eprintln!(
+        "{}: {} hook failed: {}",
+        "Warning".yellow(),
+        event.name(),
+        failure
+    );
+    Ok(())
+}
+
+#[cfg(unix)]
+fn shell(command: &str) -> Command {
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c").arg(command);
+    cmd
+}
+
+#[cfg(windows)]
+fn shell(command: &str) -> Command {
+    let mut cmd = Command::new("cmd");
+    cmd.arg("/C").arg(command);
+    cmd
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_run_hooks() {
+        let temp = TempDir::new().unwrap();
+        let out = temp.path().join("out");
+        let skills = vec![HookSkill {
+            name: "my-skill".into(),
+            path: temp.path().join("my-skill"),
+            scope: Scope::Project,
+            agent: Some(Agent::Claude),
+        }];
+
+        let config = HooksConfig {
+            post_add: Some(format!(
+                "echo \"$SKILO_HOOK $SKILO_SKILLS $SKILO_SOURCE\" > {}",
+                out.display()
+            )),
+            pre_remove: Some("exit 3".into()),
+            post_update: Some("exit 3".into()),
+        };
+
+        run(&config, HookEvent::PostAdd, &skills, Some("owner/repo")).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&out).unwrap(),
+            "post_add my-skill owner/repo\n"
+        );
+
+        // Failing post hooks only warn; failing pre hooks abort
+        assert!(run(&config, HookEvent::PostUpdate, &skills, None).is_ok());
+        assert!(matches!(
+            run(&config, HookEvent::PreRemove, &skills, None),
+            Err(SkiloError::HookFailed { .. })
+        ));
+
+        // Nothing runs without affected skills
+        assert!(run(&config, HookEvent::PreRemove, &[], None).is_ok());
+
+        // Hooks read nothing from skilo's stdin
+        let config = HooksConfig {
+            post_add: Some(format!("cat > {}", out.display())),
+            ..HooksConfig::default()
+        };
+        run(&config, HookEvent::PostAdd, &skills, None).unwrap();
+        assert_eq!(std::fs::read_to_string(&out).unwrap(), "");
+    }
+}
@@ -0,0 +1,537 @@
+//! Crash-safe directory writes and recovery of interrupted operations.
+//!
+//! Fetches and installs never write into their final location directly.
+//! Output is staged in a hidden sibling directory and swapped into place
+//! once complete, so an interruption leaves either the old or the new
+//! content, never a mix:
+//!
+//! ```text
+//! skills/
+//! ├── my-skill/                # final location
+//! ├── .my-skill.skilo-tmp/     # staged new content
+//! └── .my-skill.skilo-old/     # previous content during the swap
+//! ```
+//!
+//! Single files, such as SKILL.md rewritten by `fmt`, are replaced
+//! atomically with [`write_atomic`].
+//!
+//! Each in-flight write is recorded in `~/.skilo/journal/`. On error the
+//! write is rolled back immediately, on Ctrl-C the interrupt handler rolls
+//! back every active write before exiting, and anything left behind (for
+//! example after a crash) is recovered by `skilo doctor --repair` or the
+//! next write of the same target. Writes still owned by a running process
+//! are never recovered.
+
+use crate::error::SkiloError;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Suffix of directories holding staged content.
+pub const STAGING_SUFFIX: &str = ".skilo-tmp";
+
+/// Suffix of directories holding the previous content during a swap.
+pub const BACKUP_SUFFIX: &str = ".skilo-old";
+
+/// Staged writes in progress in this process, for the interrupt handler.
+static ACTIVE: Mutex<Vec<Operation>> = Mutex::new(Vec::new());
+
+/// What an operation was doing when it was recorded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OperationKind {
+    /// Cloning a repository or creating a checkout in the cache.
+    Fetch,
+    /// Installing a skill.
+    Install,
+}
+
+impl OperationKind {
+    /// Stable identifier used in output.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Fetch => "fetch",
+            Self::Install => "install",
+        }
+    }
+}
+
+/// A staged directory write recorded in the journal.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Operation {
+    /// What the operation was doing.
+    pub kind: OperationKind,
+    /// The final location being written.
+    pub target: PathBuf,
+    /// Where new content is staged.
+    pub staging: PathBuf,
+    /// Where the previous content is kept during the swap.
+    pub backup: PathBuf,
+    /// Process that started the operation.
+    pub pid: u32,
+    /// When the operation started, in seconds since the Unix epoch.
+    pub started_at: u64,
+    /// The journal file recording this operation.
+    #[serde(skip)]
+    pub journal: Option<PathBuf>,
+}
+
+/// How an interrupted operation was resolved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Recovery {
+    /// The new content was already in place; leftovers were removed.
+    Completed,
+    /// The previous content was restored.
+    RolledBack,
+    /// The swap had not started; staged content was discarded.
+    Discarded,
+}
+
+impl Recovery {
+    /// Stable identifier used in output.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Completed => "completed",
+            Self::RolledBack => "rolled-back",
+            Self::Discarded => "discarded",
+        }
+    }
+}
+
+impl Operation {
+    /// Finish or roll back this operation, whichever leaves a complete target.
+    ///
+    /// The new content only ever reaches the target by a single rename, so
+    /// if the target exists it is complete and the backup can be dropped;
+    /// otherwise the backup (if any) is restored.
+    pub fn recover(&self) -> std::io::Result<Recovery> {
+        let staged = self.staging.exists();
+        if staged {
+            fs::remove_dir_all(&self.staging)?;
+        }
+
+        let recovery = if self.backup.exists() {
+            if self.target.exists() {
+                fs::remove_dir_all(&self.backup)?;
+                Recovery::Completed
+            } else {
+                fs::rename(&self.backup, &self.target)?;
+                Recovery::RolledBack
+            }
+        } else if !staged && self.target.exists() {
+            Recovery::Completed
+        } else {
+            Recovery::Discarded
+        };
+
+        if let Some(journal) = &self.journal {
+            let _ = fs::remove_file(journal);
+        }
+
+        Ok(recovery)
+    }
+
+    /// Whether the process that started this operation is still running.
+    pub fn is_running(&self) -> bool {
+        self.pid == std::process::id() || process_alive(self.pid)
+    }
+}
+
+/// A directory write that is staged and then swapped into place.
+///
+/// Write the new content into [`StagedDir::path`], then call
+/// [`StagedDir::commit`]. Dropping a `StagedDir` without committing rolls
+/// the write back.
+pub struct StagedDir {
+    op: Operation,
+    committed: bool,
+}
+
+impl StagedDir {
+    /// Start a staged write of `target`.
+    ///
+    /// Fails with [`SkiloError::OperationInProgress`] if a running process,
+    /// this one included, is already writing `target`. Leftovers of an
+    /// interrupted write are recovered first.
+    pub fn begin(kind: OperationKind, target: &Path) -> Result<Self, SkiloError> {
+        let parent = target.parent().unwrap_or(Path::new("."));
+        let name = target
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        let mut op = Operation {
+            kind,
+            target: target.to_path_buf(),
+            staging: parent.join(format!(".{}{}", name, STAGING_SUFFIX)),
+            backup: parent.join(format!(".{}{}", name, BACKUP_SUFFIX)),
+            pid: std::process::id(),
+            started_at: now_secs(),
+            journal: None,
+        };
+
+        let active = ACTIVE
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .any(|a| a.staging == op.staging);
+        let recorded: Vec<Operation> = load_journal()
+            .into_iter()
+            .filter(|r| r.staging == op.staging)
+            .collect();
+        // Recorded writes of this process that are not active were left by
+        // an earlier process with the same pid. Where processes can't be
+        // checked, another process's write may still be running.
+        let running = |r: &Operation| cfg!(not(unix)) || r.is_running();
+        if active || recorded.iter().any(|r| r.pid != op.pid && running(r)) {
+            return Err(SkiloError::OperationInProgress {
+                path: target.display().to_string(),
+            });
+        }
+
+        // Leftovers from an earlier interrupted run of the same target
+        for interrupted in &recorded {
+            interrupted.recover()?;
+        }
+        if op.staging.exists() || op.backup.exists() {
+            op.recover()?;
+        }
+
+        op.journal = record(&op);
+        fs::create_dir_all(parent)?;
+
+        ACTIVE
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(op.clone());
+
+        Ok(Self {
+            op,
+            committed: false,
+        })
+    }
+
+    /// The directory to write the new content into.
+    pub fn path(&self) -> &Path {
+        &self.op.staging
+    }
+
+    /// Move the staged content into place, replacing any existing target.
+    pub fn commit(mut self) -> Result<(), SkiloError> {
+        if self.op.target.exists() {
+            fs::rename(&self.op.target, &self.op.backup)?;
+        }
+        fs::rename(&self.op.staging, &self.op.target)?;
+        self.committed = true;
+
+        if self.op.backup.exists() {
+            fs::remove_dir_all(&self.op.backup)?;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for StagedDir {
+    fn drop(&mut self) {
+        if !self.committed {
+            let _ = self.op.recover();
+        } else if let Some(journal) = &self.op.journal {
+            let _ = fs::remove_file(journal);
+        }
+
+        ACTIVE
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .retain(|op| op.staging != self.op.staging);
+    }
+}
+
+/// Write `contents` to `path` atomically.
+///
+/// The content goes to a temporary file in the same directory, which is
+/// flushed to disk and then renamed over `path`, so a crash leaves either
+/// the old or the new file. An existing file keeps its permissions (new
+/// files get mode 0644), and a symlink is followed rather than replaced.
+/// Hard links to the old file, such as those from the store, keep the old
+/// content.
+pub fn write_atomic(path: &Path, contents: impl AsRef<[u8]>) -> std::io::Result<()> {
+    let path = if path.is_symlink() {
+        fs::canonicalize(path)?
+    } else {
+        path.to_path_buf()
+    };
+    let parent = path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+
+    let mut temp = tempfile::Builder::new()
+        .prefix(".skilo-write-")
+        .tempfile_in(parent)?;
+    temp.write_all(contents.as_ref())?;
+    match fs::metadata(&path) {
+        Ok(metadata) => temp.as_file().set_permissions(metadata.permissions())?,
+        // Temporary files are private; new files get the usual mode
+        #[cfg(unix)]
+        Err(_) => {
+            use std::os::unix::fs::PermissionsExt;
+            temp.as_file()
+                .set_permissions(fs::Permissions::from_mode(0o644))?;
+        }
+        #[cfg(not(unix))]
+        Err(_) => {}
+    }
+    temp.as_file().sync_all()?;
+    temp.persist(&path).map_err(|e| e.error)?;
+    Ok(())
+}
+
+/// Get the journal directory (`~/.skilo/journal/`).
+pub fn journal_dir() -> Option<PathBuf> {
+    crate::cache::skilo_home().map(|h| h.join("journal"))
+}
+
+/// Load every operation recorded in the journal, oldest first.
+pub fn load_journal() -> Vec<Operation> {
+    let Some(dir) = journal_dir() else {
+        return Vec::new();
+    };
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return Vec::new();
+    };
+
+    let mut ops: Vec<Operation> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|path| {
+            let content = fs::read_to_string(&path).ok()?;
+            let mut op: Operation = serde_json::from_str(&content).ok()?;
+            op.journal = Some(path);
+            Some(op)
+        })
+        .collect();
+
+    ops.sort_by_key(|op| op.started_at);
+    ops
+}
+
+/// Roll back every staged write in progress in this process.
+///
+/// Called by the interrupt handler before exiting.
+pub fn recover_active() {
+    let ops = std::mem::take(&mut *ACTIVE.lock().unwrap_or_else(|e| e.into_inner()));
+    for op in ops.iter().rev() {
+        let _ = op.recover();
+    }
+}
+
+/// Write a journal entry for an operation. Journaling is best effort.
+fn record(op: &Operation) -> Option<PathBuf> {
+    let dir = journal_dir()?;
+    fs::create_dir_all(&dir).ok()?;
+
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or(0);
+    let path = dir.join(format!("{}-{}-{}.json", op.pid, op.started_at, nanos));
+
+    let json = serde_json::to_string_pretty(op).ok()?;
+    fs::write(&path, json).ok()?;
+    Some(path)
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(unix)]
+fn process_alive(pid: u32) -> bool {
+    // Signal 0 only checks that the process exists; EPERM means it exists
+    // but belongs to another user
+    unsafe { libc::kill(pid as libc::pid_t, 0) == 0 }
+    || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+/// Processes cannot be checked on this platform, so other processes are
+/// reported as stopped. [`StagedDir::begin`] therefore never recovers their
+/// writes itself; `skilo doctor --repair` does when asked.
+#[cfg(not(unix))]
+fn process_alive(_pid: u32) -> bool {
+    false
+}
+
+/// Install a Ctrl-C/SIGTERM handler that rolls back in-progress writes.
+///
+/// The signal handler only writes to a pipe; a watcher thread does the
+/// cleanup and exits with status 130. On other platforms interrupted
+/// writes are left for `skilo doctor --repair`.
+pub fn install_interrupt_handler() {
+    #[cfg(unix)]
+    unix::install();
+}
+
+#[cfg(unix)]
+mod unix {
+    use std::sync::atomic::{AtomicI32, Ordering};
+
+    static PIPE_WRITE: AtomicI32 = AtomicI32::new(-1);
+
+    extern "C" fn on_signal(_: libc::c_int) {
+        let fd = PIPE_WRITE.load(Ordering::Relaxed);
+        if fd >= 0 {
+            let byte = 1u8;
+            // Only async-signal-safe calls are allowed here
+            unsafe { libc::write(fd, &byte as *const u8 as *const libc::c_void, 1) };
+        }
+    }
+
+    pub(super) fn install() {
+        let mut fds = [0 as libc::c_int; 2];
+        if unsafe { libc::pipe(fds.as_mut_ptr()) } != 0 {
+            return;
+        }
+        let [read_fd, write_fd] = fds;
+        PIPE_WRITE.store(write_fd, Ordering::Relaxed);
+
+        let spawned = std::thread::Builder::new()
+            .name("skilo-interrupt".into())
+            .spawn(move || loop {
+                let mut byte = 0u8;
+                let n =
+                    unsafe { libc::read(read_fd, &mut byte as *mut u8 as *mut libc::c_void, 1) };
+                if n == 1 {
+                    super::recover_active();
+                    std::process::exit(130);
+                }
+                if n == 0 {
+                    return;
+                }
+            });
+        if spawned.is_err() {
+            return;
+        }
+
+        let handler = on_signal as extern "C" fn(libc::c_int) as libc::sighandler_t;
+        unsafe {
+            libc::signal(libc::SIGINT, handler);
+            libc::signal(libc::SIGTERM, handler);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_staged_dir_commit_and_rollback() {
+        let temp = TempDir::new().unwrap();
+        let target = temp.path().join("my-skill");
+        fs::create_dir(&target).unwrap();
+        fs::write(target.join("SKILL.md"), "old").unwrap();
+
+        // Dropped without commit: the old content stays
+        {
+            let staged = StagedDir::begin(OperationKind::Install, &target).unwrap();
+            fs::create_dir_all(staged.path()).unwrap();
+            fs::write(staged.path().join("SKILL.md"), "partial").unwrap();
+        }
+        assert_eq!(fs::read_to_string(target.join("SKILL.md")).unwrap(), "old");
+        assert!(!temp.path().join(".my-skill.skilo-tmp").exists());
+
+        let staged = StagedDir::begin(OperationKind::Install, &target).unwrap();
+        fs::create_dir_all(staged.path()).unwrap();
+        fs::write(staged.path().join("SKILL.md"), "new").unwrap();
+        staged.commit().unwrap();
+        assert_eq!(fs::read_to_string(target.join("SKILL.md")).unwrap(), "new");
+        assert_eq!(fs::read_dir(temp.path()).unwrap().count(), 1);
+        assert!(load_journal().is_empty());
+    }
+
+    #[test]
+    fn test_staged_dir_in_progress() {
+        let temp = TempDir::new().unwrap();
+        let target = temp.path().join("my-skill");
+
+        let staged = StagedDir::begin(OperationKind::Install, &target).unwrap();
+        fs::create_dir_all(staged.path()).unwrap();
+        assert!(matches!(
+            StagedDir::begin(OperationKind::Install, &target),
+            Err(SkiloError::OperationInProgress { .. })
+        ));
+        // The staged content of the running write is left alone
+        assert!(staged.path().exists());
+        drop(staged);
+
+        // A write left by a process that is gone is recovered, where
+        // processes can be checked
+        #[cfg(unix)]
+        {
+            let op = Operation {
+                kind: OperationKind::Install,
+                target: target.clone(),
+                staging: temp.path().join(".my-skill.skilo-tmp"),
+                backup: temp.path().join(".my-skill.skilo-old"),
+                pid: i32::MAX as u32,
+                started_at: now_secs(),
+                journal: None,
+            };
+            fs::create_dir(&op.staging).unwrap();
+            record(&op).unwrap();
+            let staged = StagedDir::begin(OperationKind::Install, &target).unwrap();
+            assert!(!staged.path().exists());
+            assert_eq!(load_journal().len(), 1);
+        }
+    }
+
+    #[test]
+    fn test_write_atomic() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("SKILL.md");
+        write_atomic(&path, "one").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "one");
+
+        // Other links to the file keep the old content
+        let link = temp.path().join("linked.md");
+        fs::hard_link(&path, &link).unwrap();
+        write_atomic(&path, "two").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "two");
+        assert_eq!(fs::read_to_string(&link).unwrap(), "one");
+        assert_eq!(fs::read_dir(temp.path()).unwrap().count(), 2);
+    }
+
+    #[test]
+    fn test_recover_interrupted_swap() {
+        let temp = TempDir::new().unwrap();
+        let op = Operation {
+            kind: OperationKind::Install,
+            target: temp.path().join("my-skill"),
+            staging: temp.path().join(".my-skill.skilo-tmp"),
+            backup: temp.path().join(".my-skill.skilo-old"),
+            pid: 0,
+            started_at: now_secs(),
+            journal: None,
+        };
+
+        // Interrupted after the old content was moved aside
+        fs::create_dir(&op.staging).unwrap();
+        fs::create_dir(&op.backup).unwrap();
+        assert_eq!(op.recover().unwrap(), Recovery::RolledBack);
+        assert!(op.target.exists() && !op.staging.exists() && !op.backup.exists());
+
+        // Interrupted after the new content was moved into place
+        fs::create_dir(&op.backup).unwrap();
+        assert_eq!(op.recover().unwrap(), Recovery::Completed);
+        assert!(op.target.exists() && !op.backup.exists());
+    }
+}
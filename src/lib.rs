@@ -4,16 +4,25 @@
 //! [Agent Skills](https://agentskills.io/specification).
 
 pub mod agent;
+pub mod agent_config;
+pub mod bundle;
 pub mod cache;
 pub mod cli;
 pub mod commands;
 pub mod config;
 pub mod error;
 pub mod git;
+pub mod github;
+pub mod history;
+pub mod hooks;
+pub mod journal;
 pub mod lang;
+pub mod lockfile;
 pub mod output;
 pub mod scope;
 pub mod skill;
+pub mod store;
 pub mod templates;
 
 pub use error::{Result, SkiloError};
+pub use skill::{Diagnostic, DiagnosticCode, Manifest, ValidationResult, Validator};
@@ -0,0 +1,482 @@
+//! Install receipts recorded in `skills.lock`.
+//!
+//! Every skill installed by `skilo add` is recorded in the lockfile of its
+//! scope, so later commands know where it came from and can detect drift:
+//!
+//! ```text
+//! <project>/skills.lock     # Project-scope installs
+//! ~/.skilo/skills.lock      # Global-scope installs
+//! ```
+//!
+//! Project paths are stored relative to the project root so the lockfile can
+//! be committed alongside the installed skills.
+
+use crate::agent::Agent;
+use crate::journal::write_atomic;
+use crate::scope::{list_skills, list_skills_from_path, Scope};
+use crate::SkiloError;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// File name of the lockfile.
+pub const LOCKFILE_NAME: &str = "skills.lock";
+
+/// Current lockfile format version.
+pub const LOCKFILE_VERSION: u32 = 1;
+
+/// The contents of a `skills.lock` file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Lockfile {
+    /// Format version.
+    pub version: u32,
+    /// Installed skills, sorted by path.
+    #[serde(default, rename = "skill")]
+    pub skills: Vec<LockedSkill>,
+}
+
+impl Default for Lockfile {
+    fn default() -> Self {
+        Self {
+            version: LOCKFILE_VERSION,
+            skills: Vec::new(),
+        }
+    }
+}
+
+/// A single installed skill.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockedSkill {
+    /// The skill name.
+    pub name: String,
+    /// The agent the skill was installed for (None for `./skills/` or `--output`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent: Option<Agent>,
+    /// Installed directory (relative to the project root for project scope).
+    pub path: PathBuf,
+    /// The source the skill was installed from (git URL or local path).
+    pub source: String,
+    /// Subdirectory within the source that was selected with `--path` or a tree URL.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subdir: Option<String>,
+    /// The requested branch, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    /// The requested tag, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    /// The requested `@ref` (branch, tag, or commit), if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rev: Option<String>,
+    /// The resolved commit, for git sources.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    /// Checksum of the installed directory contents (`sha256:<hex>`).
+    pub checksum: String,
+    /// Installation time as seconds since the Unix epoch.
+    pub installed_at: u64,
+}
+
+impl Lockfile {
+    /// Path to the lockfile for the given scope.
+    pub fn path_for(scope: Scope, project_root: &Path) -> Option<PathBuf> {
+        match scope {
+            Scope::Project => Some(project_root.join(LOCKFILE_NAME)),
+            Scope::Global => crate::cache::skilo_home().map(|h| h.join(LOCKFILE_NAME)),
+        }
+    }
+
+    /// Load a lockfile, returning an empty one if it does not exist.
+    pub fn load(path: &Path) -> Result<Self, SkiloError> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = std::fs::read_to_string(path)?;
+        toml::from_str(&content)
+            .map_err(|e| SkiloError::Config(format!("Invalid {}: {}", path.display(), e)))
+    }
+
+    /// Load the lockfile for a scope.
+    pub fn load_scope(scope: Scope, project_root: &Path) -> Result<Self, SkiloError> {
+        match Self::path_for(scope, project_root) {
+            Some(path) => Self::load(&path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Write the lockfile, removing it if it has no entries.
+    pub fn save(&self, path: &Path) -> Result<(), SkiloError> {
+        if self.skills.is_empty() {
+            if path.exists() {
+                std::fs::remove_file(path)?;
+            }
+            return Ok(());
+        }
+
+        let content = toml::to_string_pretty(self)
+            .map_err(|e| SkiloError::Config(format!("Failed to serialize lockfile: {}", e)))?;
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        write_atomic(
+            path,
+            format!("# Generated by skilo. Do not edit.\n{}", content),
+        )?;
+        Ok(())
+    }
+
+    /// Insert or replace the entry installed at the same path.
+    pub fn upsert(&mut self, entry: LockedSkill) {
+        self.skills.retain(|s| s.path != entry.path);
+        self.skills.push(entry);
+        self.skills.sort_by(|a, b| a.path.cmp(&b.path));
+    }
+
+    /// Remove the entry installed at `path`. Returns true if one was removed.
+    pub fn remove_path(&mut self, path: &Path) -> bool {
+        let before = self.skills.len();
+        self.skills.retain(|s| s.path != path);
+        self.skills.len() != before
+    }
+
+    /// Find the entry installed at `path`.
+    pub fn find_path(&self, path: &Path) -> Option<&LockedSkill> {
+        self.skills.iter().find(|s| s.path == path)
+    }
+}
+
+/// Convert an install path into the form stored in the lockfile.
+///
+/// Project paths are made relative to the project root; global paths stay absolute.
+pub fn lock_path(scope: Scope, path: &Path, project_root: &Path) -> PathBuf {
+    match scope {
+        Scope::Project => path
+            .strip_prefix(project_root)
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|_| path.to_path_buf()),
+        Scope::Global => path.to_path_buf(),
+    }
+}
+
+/// Resolve a path stored in the lockfile back to an absolute install path.
+pub fn resolve_lock_path(scope: Scope, path: &Path, project_root: &Path) -> PathBuf {
+    match scope {
+        Scope::Project => project_root.join(path),
+        Scope::Global => path.to_path_buf(),
+    }
+}
+
+/// Point the receipts of the skill installed at `from` to its new directory
+/// `to` and name `name`, in the lockfiles of both scopes.
+///
+/// Used after `lint --fix` renames an installed skill. Returns the number
+/// of receipts updated.
+pub fn rename_receipts(
+    from: &Path,
+    to: &Path,
+    name: &str,
+    project_root: &Path,
+) -> Result<usize, SkiloError> {
+    let from = std::path::absolute(from)?;
+    let to = std::path::absolute(to)?;
+    let mut updated = 0;
+
+    for scope in [Scope::Project, Scope::Global] {
+        let Some(lock_file) = Lockfile::path_for(scope, project_root) else {
+            continue;
+        };
+        let mut lock = Lockfile::load(&lock_file)?;
+        let before = updated;
+        for entry in &mut lock.skills {
+            let installed = resolve_lock_path(scope, &entry.path, project_root);
+            if std::path::absolute(installed).is_ok_and(|p| p == from) {
+                entry.path = lock_path(scope, &to, project_root);
+                entry.name = name.to_string();
+                updated += 1;
+            }
+        }
+        if updated > before {
+            lock.skills.sort_by(|a, b| a.path.cmp(&b.path));
+            lock.save(&lock_file)?;
+        }
+    }
+    Ok(updated)
+}
+
+/// Find installed skills in a scope that are missing from its lockfile.
+///
+/// Returns absolute install paths, sorted.
+pub fn untracked_skills(lock: &Lockfile, scope: Scope, project_root: &Path) -> Vec<PathBuf> {
+    let mut installed = Vec::new();
+    for agent in Agent::all() {
+        installed.extend(list_skills(*agent, scope, project_root));
+    }
+    if scope.is_project() {
+        installed.extend(list_skills_from_path(
+            &project_root.join("skills"),
+            None,
+            scope,
+        ));
+    }
+
+    let mut untracked: Vec<PathBuf> = installed
+        .into_iter()
+        .map(|skill| skill.path)
+        .filter(|path| {
+            lock.find_path(&lock_path(scope, path, project_root))
+                .is_none()
+        })
+        .collect();
+    untracked.sort();
+    untracked.dedup();
+    untracked
+}
+
+/// The kind of difference between the lockfile and installed skills.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DriftKind {
+    /// Recorded in the lockfile but not installed.
+    Missing,
+    /// Installed, but the files differ from the recorded checksum.
+    Modified,
+    /// Installed but not recorded in the lockfile.
+    Extra,
+}
+
+impl DriftKind {
+    /// Returns the lowercase name of this kind.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DriftKind::Missing => "missing",
+            DriftKind::Modified => "modified",
+            DriftKind::Extra => "extra",
+        }
+    }
+}
+
+/// A single difference between the lockfile and installed skills.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Drift {
+    /// What kind of drift this is.
+    pub kind: DriftKind,
+    /// The scope of the affected skill.
+    pub scope: Scope,
+    /// The skill name.
+    pub name: String,
+    /// Absolute install path.
+    pub path: PathBuf,
+    /// Checksum recorded in the lockfile, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected: Option<String>,
+    /// Checksum of the installed files, if installed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actual: Option<String>,
+}
+
+/// Compare installed skills in a scope against its lockfile.
+pub fn check_drift(scope: Scope, project_root: &Path) -> Result<Vec<Drift>, SkiloError> {
+    let lock = Lockfile::load_scope(scope, project_root)?;
+    let mut drift = Vec::new();
+
+    for entry in &lock.skills {
+        let path = resolve_lock_path(scope, &entry.path, project_root);
+        if !path.join("SKILL.md").exists() {
+            drift.push(Drift {
+                kind: DriftKind::Missing,
+                scope,
+                name: entry.name.clone(),
+                path,
+                expected: Some(entry.checksum.clone()),
+                actual: None,
+            });
+            continue;
+        }
+
+        let actual = checksum_dir(&path)?;
+        if actual != entry.checksum {
+            drift.push(Drift {
+                kind: DriftKind::Modified,
+                scope,
+                name: entry.name.clone(),
+                path,
+                expected: Some(entry.checksum.clone()),
+                actual: Some(actual),
+            });
+        }
+    }
+
+    for path in untracked_skills(&lock, scope, project_root) {
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let actual = checksum_dir(&path).ok();
+        drift.push(Drift {
+            kind: DriftKind::Extra,
+            scope,
+            name,
+            path,
+            expected: None,
+            actual,
+        });
+    }
+
+    Ok(drift)
+}
+
+/// Compute a checksum over all files in a directory.
+///
+/// Files are hashed in sorted path order, together with their relative paths,
+/// so renames and additions change the checksum as well as edits. `.git`
+/// files and directories are left out, as installs never copy them.
+pub fn checksum_dir(dir: &Path) -> std::io::Result<String> {
+    checksum_dir_with(dir, |_, bytes| bytes)
+}
+
+/// Compute a checksum like [`checksum_dir`], passing each file's relative
+/// path and contents through `normalize` before hashing.
+pub fn checksum_dir_with<F>(dir: &Path, normalize: F) -> std::io::Result<String>
+where
+    F: Fn(&Path, Vec<u8>) -> Vec<u8>,
+{
+    let mut files: Vec<PathBuf> = WalkDir::new(dir)
+        .follow_links(false)
+        .into_iter()
+        .filter_entry(|e| e.depth() == 0 || e.file_name() != ".git")
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .map(|e| e.into_path())
+        .collect();
+    files.sort();
+
+    let mut hasher = Sha256::new();
+    for file in files {
+        let relative = file.strip_prefix(dir).unwrap_or(&file);
+        hasher.update(relative.to_string_lossy().replace('\\', "/").as_bytes());
+        hasher.update([0]);
+        hasher.update(normalize(relative, std::fs::read(&file)?));
+        hasher.update([0]);
+    }
+
+    let digest = hasher.finalize();
+    let hex: String = digest.iter().map(|b| format!("{:02x}", b)).collect();
+    Ok(format!("sha256:{}", hex))
+}
+
+/// Current time as seconds since the Unix epoch.
+pub fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn entry(name: &str) -> LockedSkill {
+        LockedSkill {
+            name: name.to_string(),
+            agent: Some(Agent::Claude),
+            path: PathBuf::from(format!(".claude/skills/{}", name)),
+            source: "https://github.com/owner/repo.git".to_string(),
+            subdir: None,
+            branch: None,
+            tag: Some("v1".to_string()),
+            rev: None,
+            commit: Some("abc123".to_string()),
+            checksum: "sha256:00".to_string(),
+            installed_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join(LOCKFILE_NAME);
+
+        let mut lock = Lockfile::default();
+        lock.upsert(entry("b"));
+        lock.upsert(entry("a"));
+        lock.upsert(entry("a"));
+        lock.save(&path).unwrap();
+
+        let loaded = Lockfile::load(&path).unwrap();
+        assert_eq!(loaded, lock);
+        assert_eq!(loaded.skills.len(), 2);
+        assert_eq!(loaded.skills[0].name, "a");
+    }
+
+    #[test]
+    fn test_save_empty_removes_file() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join(LOCKFILE_NAME);
+
+        let mut lock = Lockfile::default();
+        lock.upsert(entry("a"));
+        lock.save(&path).unwrap();
+        assert!(path.exists());
+
+        assert!(lock.remove_path(Path::new(".claude/skills/a")));
+        lock.save(&path).unwrap();
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_check_drift() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        let skills = root.join("skills");
+        for name in ["kept", "edited", "extra"] {
+            std::fs::create_dir_all(skills.join(name)).unwrap();
+            std::fs::write(
+                skills.join(name).join("SKILL.md"),
+                format!("---\nname: {}\ndescription: Test\n---\n", name),
+            )
+            .unwrap();
+        }
+
+        let mut lock = Lockfile::default();
+        for name in ["kept", "edited", "gone"] {
+            let path = skills.join(name);
+            lock.upsert(LockedSkill {
+                agent: None,
+                path: lock_path(Scope::Project, &path, root),
+                checksum: checksum_dir(&path).unwrap(),
+                ..entry(name)
+            });
+        }
+        lock.save(&root.join(LOCKFILE_NAME)).unwrap();
+        std::fs::write(skills.join("edited").join("SKILL.md"), "changed").unwrap();
+
+        let drift = check_drift(Scope::Project, root).unwrap();
+        let kinds: Vec<(DriftKind, &str)> =
+            drift.iter().map(|d| (d.kind, d.name.as_str())).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (DriftKind::Modified, "edited"),
+                (DriftKind::Missing, "gone"),
+                (DriftKind::Extra, "extra"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_checksum_dir_detects_changes() {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(temp.path().join("SKILL.md"), "one").unwrap();
+        let first = checksum_dir(temp.path()).unwrap();
+        assert!(first.starts_with("sha256:"));
+        assert_eq!(first, checksum_dir(temp.path()).unwrap());
+
+        std::fs::write(temp.path().join("SKILL.md"), "two").unwrap();
+        assert_ne!(first, checksum_dir(temp.path()).unwrap());
+    }
+}
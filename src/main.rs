@@ -9,12 +9,17 @@ use skilo::config::Config;
 fn main() -> Result<()> {
     let cli = Cli::parse();
 
+    skilo::output::init_color(&cli);
+    skilo::journal::install_interrupt_handler();
+
     let config = Config::load(cli.config.as_ref())
         .map_err(|e| miette::miette!("Failed to load config: {}", e))?;
 
     let exit_code = match &cli.command {
         Command::Add(args) => commands::add::run(args.clone(), &config, &cli)?,
         Command::New(args) => commands::new::run(args.clone(), &config, &cli)?,
+        Command::Init(args) => commands::init::run(args.clone(), &config, &cli)?,
+        Command::Merge(args) => commands::merge::run(args.clone(), &config, &cli)?,
         Command::Lint(args) => commands::lint::run(args.clone(), &config, &cli)?,
         Command::Fmt(args) => commands::fmt::run(args.clone(), &config, &cli)?,
         Command::Check(args) => commands::check::run(args.clone(), &config, &cli)?,
@@ -28,9 +33,32 @@ fn main() -> Result<()> {
         }
         Command::ToPrompt(args) => commands::to_prompt::run(args.clone(), &config, &cli)?,
         Command::List(args) => commands::list::run(args.clone(), &config, &cli)?,
+        Command::Grep(args) => commands::grep::run(args.clone(), &config, &cli)?,
+        Command::Permissions(args) => commands::permissions::run(args.clone(), &config, &cli)?,
+        Command::Which(args) => commands::which::run(args.clone(), &config, &cli)?,
         Command::Remove(args) => commands::remove::run(args.clone(), &config, &cli)?,
         Command::Agents(args) => commands::agents::run(args.clone(), &config, &cli)?,
+        Command::Ci(args) => commands::ci::run(args.clone(), &config, &cli)?,
+        Command::CiCheck(args) => commands::ci_check::run(args.clone(), &config, &cli)?,
+        Command::Export(args) => commands::export::run(args.clone(), &config, &cli)?,
+        Command::Import(args) => commands::import::run(args.clone(), &config, &cli)?,
+        Command::Bundle(args) => commands::bundle::run(args.clone(), &config, &cli)?,
+        Command::Plugin(args) => commands::plugin::run(args.clone(), &config, &cli)?,
+        Command::Tree(args) => commands::tree::run(args.clone(), &config, &cli)?,
+        Command::Preview(args) => commands::preview::run(args.clone(), &config, &cli)?,
+        Command::EnvCheck(args) => commands::env_check::run(args.clone(), &config, &cli)?,
+        Command::Vendor(args) => commands::vendor::run(args.clone(), &config, &cli)?,
+        Command::Clean(args) => commands::clean::run(args.clone(), &config, &cli)?,
+        Command::Dedupe(args) => commands::dedupe::run(args.clone(), &config, &cli)?,
+        Command::Doctor(args) => commands::doctor::run(args.clone(), &config, &cli)?,
         Command::Cache(args) => commands::cache::run(args.clone(), &config, &cli)?,
+        Command::Store(args) => commands::store::run(args.clone(), &config, &cli)?,
+        Command::Activity(args) => commands::activity::run(args.clone(), &config, &cli)?,
+        Command::Api(args) => commands::api::run(args.clone(), &config, &cli)?,
+        #[cfg(feature = "serve")]
+        Command::Serve(args) => commands::serve::run(args.clone(), &config, &cli)?,
+        Command::Bench(args) => commands::bench::run(args.clone(), &config, &cli)?,
+        Command::Selftest(args) => commands::selftest::run(args.clone(), &config, &cli)?,
         Command::SelfCmd(args) => match &args.command {
             SelfCommand::Update(update_args) => {
                 commands::self_update::run(update_args.clone(), &config, &cli)?
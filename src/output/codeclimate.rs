@@ -0,0 +1,241 @@
+//! Code Climate issue output, as read by GitLab code quality reports and
+//! reviewdog (`--format code-climate`).
+//!
+//! The output is a JSON array of issues. Each issue's `fingerprint` hashes
+//! the code, path, message, and the text of the flagged line rather than
+//! its number, so an issue keeps its fingerprint when unrelated edits move
+//! it and pipelines can tell new issues from old ones.
+
+use super::{OutputFormatter, ReportOptions};
+use crate::skill::{Diagnostic, DiagnosticCode, ValidationResult};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+
+/// Formatter that outputs Code Climate issues.
+pub struct CodeClimateFormatter {
+    quiet: bool,
+}
+
+impl CodeClimateFormatter {
+    /// Create a new Code Climate formatter.
+    pub fn new(quiet: bool) -> Self {
+        Self { quiet }
+    }
+}
+
+#[derive(Serialize)]
+struct Issue<'a> {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    check_name: String,
+    description: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content: Option<Content<'a>>,
+    categories: [&'static str; 1],
+    location: Location<'a>,
+    severity: &'static str,
+    fingerprint: String,
+}
+
+#[derive(Serialize)]
+struct Content<'a> {
+    body: &'a str,
+}
+
+#[derive(Serialize)]
+struct Location<'a> {
+    path: &'a str,
+    lines: Lines,
+}
+
+#[derive(Serialize)]
+struct Lines {
+    begin: usize,
+}
+
+impl OutputFormatter for CodeClimateFormatter {
+    fn format_validation(&self, results: &[(String, ValidationResult)]) -> String {
+        self.format_report(results, &ReportOptions::default())
+    }
+
+    /// One issue per diagnostic; grouping by rule is not supported.
+    fn format_report(
+        &self,
+        results: &[(String, ValidationResult)],
+        options: &ReportOptions,
+    ) -> String {
+        let mut occurrences: HashMap<String, usize> = HashMap::new();
+        let issues: Vec<Issue> = results
+            .iter()
+            .flat_map(|(_, result)| result.errors.iter().chain(&result.warnings))
+            .take(options.max_diagnostics.unwrap_or(usize::MAX))
+            .map(|diag| {
+                // Identical issues in one file still need distinct fingerprints
+                let base = fingerprint(diag);
+                let seen = occurrences.entry(base.clone()).or_default();
+                *seen += 1;
+                let fingerprint = if *seen == 1 {
+                    base
+                } else {
+                    hex(&Sha256::digest(format!("{}:{}", base, seen)))
+                };
+
+                Issue {
+                    kind: "issue",
+                    check_name: diag.code.to_string(),
+                    description: &diag.message,
+                    content: diag.fix_hint.as_deref().map(|body| Content { body }),
+                    categories: [category(diag.code)],
+                    location: Location {
+                        path: &diag.path,
+                        lines: Lines {
+                            begin: diag.line.unwrap_or(1),
+                        },
+                    },
+                    severity: if diag.code.is_error() {
+                        "major"
+                    } else {
+                        "minor"
+                    },
+                    fingerprint,
+                }
+            })
+            .collect();
+
+        serde_json::to_string_pretty(&issues).unwrap_or_else(|_| "[]".to_string()) + "\n"
+    }
+
+    fn format_message(&self, message: &str) {
+        if !self.quiet {
+            eprintln!("{}", message);
+        }
+    }
+
+    fn format_error(&self, message: &str) {
+        eprintln!("error: {}", message);
+    }
+
+    fn format_success(&self, message: &str) {
+        if !self.quiet {
+            eprintln!("{}", message);
+        }
+    }
+}
+
+/// Hash of the diagnostic's code, path, message, and flagged line text.
+fn fingerprint(diag: &Diagnostic) -> String {
+    let line_text = diag
+        .line
+        .and_then(|line| {
+            let content = std::fs::read_to_string(&diag.path).ok()?;
+            content
+                .lines()
+                .nth(line.checked_sub(1)?)
+                .map(|l| l.trim().to_string())
+        })
+        .unwrap_or_default();
+
+    let mut hasher = Sha256::new();
+    for part in [
+        diag.code.to_string().as_str(),
+        &diag.path,
+        &diag.message,
+        &line_text,
+    ] {
+        hasher.update(part.as_bytes());
+        hasher.update([0]);
+    }
+    hex(&hasher.finalize())
+}
+
+/// Lowercase hex encoding of `bytes`.
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// The Code Climate category of a diagnostic code.
+fn category(code: DiagnosticCode) -> &'static str {
+    match code {
+        DiagnosticCode::E001
+        | DiagnosticCode::E002
+        | DiagnosticCode::W006
+        | DiagnosticCode::W019 => "Style",
+        DiagnosticCode::E004
+        | DiagnosticCode::E005
+        | DiagnosticCode::E006
+        | DiagnosticCode::W007
+        | DiagnosticCode::W012
+        | DiagnosticCode::W015
+        | DiagnosticCode::W016
+        | DiagnosticCode::W017
+        | DiagnosticCode::W018 => "Clarity",
+        DiagnosticCode::W001 | DiagnosticCode::W008 | DiagnosticCode::W009 => "Complexity",
+        DiagnosticCode::E010
+        | DiagnosticCode::E011
+        | DiagnosticCode::E013
+        | DiagnosticCode::E014
+        | DiagnosticCode::E015
+        | DiagnosticCode::W005
+        | DiagnosticCode::W010
+        | DiagnosticCode::W011
+        | DiagnosticCode::W013
+        | DiagnosticCode::W014 => "Compatibility",
+        DiagnosticCode::E012 => "Performance",
+        DiagnosticCode::E016 => "Security",
+        DiagnosticCode::E003
+        | DiagnosticCode::E017
+        | DiagnosticCode::E007
+        | DiagnosticCode::E008
+        | DiagnosticCode::E009
+        | DiagnosticCode::W002
+        | DiagnosticCode::W003
+        | DiagnosticCode::W004 => "Bug Risk",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_code_climate_issues() {
+        let diag = |line| Diagnostic {
+            path: "a/SKILL.md".to_string(),
+            line,
+            column: None,
+            message: "Duplicate tag 'x'".to_string(),
+            code: DiagnosticCode::W006,
+            fix_hint: None,
+        };
+        let results = vec![(
+            "a/SKILL.md".to_string(),
+            ValidationResult {
+                errors: Vec::new(),
+                warnings: vec![diag(Some(4)), diag(Some(4)), diag(None)],
+            },
+        )];
+
+        let output = CodeClimateFormatter::new(false).format_validation(&results);
+        let issues: serde_json::Value = serde_json::from_str(&output).unwrap();
+        let issues = issues.as_array().unwrap();
+        assert_eq!(issues.len(), 3);
+        assert_eq!(issues[0]["check_name"], "W006");
+        assert_eq!(issues[0]["severity"], "minor");
+        assert_eq!(issues[0]["categories"][0], "Style");
+        assert_eq!(issues[0]["location"]["lines"]["begin"], 4);
+        assert_eq!(issues[2]["location"]["lines"]["begin"], 1);
+
+        // Unreadable files hash the same with or without a line number, but
+        // every issue still gets its own fingerprint
+        let fingerprints: std::collections::HashSet<&str> = issues
+            .iter()
+            .map(|i| i["fingerprint"].as_str().unwrap())
+            .collect();
+        assert_eq!(fingerprints.len(), 3);
+
+        // Fingerprints are stable between runs
+        let again = CodeClimateFormatter::new(false).format_validation(&results);
+        assert_eq!(output, again);
+    }
+}
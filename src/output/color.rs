@@ -0,0 +1,81 @@
+//! Terminal color handling.
+//!
+//! All colored output goes through the `colored` crate, whose global override
+//! is set once here at startup. Commands never decide on color themselves.
+
+use crate::cli::{Cli, ColorChoice, OutputFormat};
+use std::io::IsTerminal;
+
+/// Decide whether output should be colorized.
+///
+/// An explicit `always`/`never` wins. In `auto` mode, `NO_COLOR` (any
+/// non-empty value) disables color, `CLICOLOR_FORCE` (any value other than
+/// `0`) forces it, and otherwise color is used only when writing to a terminal.
+pub fn should_colorize(
+    choice: ColorChoice,
+    no_color: Option<&str>,
+    clicolor_force: Option<&str>,
+    is_terminal: bool,
+) -> bool {
+    match choice {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto => {
+            if no_color.is_some_and(|v| !v.is_empty()) {
+                false
+            } else if clicolor_force.is_some_and(|v| !v.is_empty() && v != "0") {
+                true
+            } else {
+                is_terminal
+            }
+        }
+    }
+}
+
+/// Configure color output for the whole process.
+///
+/// Machine-readable formats and porcelain output are never colorized,
+/// regardless of `--color`.
+pub fn init_color(cli: &Cli) {
+    let enabled = matches!(cli.format, OutputFormat::Text)
+        && !cli.porcelain
+        && should_colorize(
+            cli.color,
+            std::env::var("NO_COLOR").ok().as_deref(),
+            std::env::var("CLICOLOR_FORCE").ok().as_deref(),
+            std::io::stdout().is_terminal(),
+        );
+
+    colored::control::set_override(enabled);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_explicit_choice_wins() {
+        assert!(should_colorize(ColorChoice::Always, Some("1"), None, false));
+        assert!(!should_colorize(ColorChoice::Never, None, Some("1"), true));
+    }
+
+    #[test]
+    fn test_auto_follows_terminal() {
+        assert!(should_colorize(ColorChoice::Auto, None, None, true));
+        assert!(!should_colorize(ColorChoice::Auto, None, None, false));
+    }
+
+    #[test]
+    fn test_auto_respects_env() {
+        assert!(!should_colorize(ColorChoice::Auto, Some("1"), None, true));
+        assert!(should_colorize(ColorChoice::Auto, Some(""), None, true));
+        assert!(should_colorize(ColorChoice::Auto, None, Some("1"), false));
+        assert!(!should_colorize(ColorChoice::Auto, None, Some("0"), false));
+        assert!(!should_colorize(
+            ColorChoice::Auto,
+            Some("1"),
+            Some("1"),
+            true
+        ));
+    }
+}
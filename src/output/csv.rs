@@ -0,0 +1,145 @@
+//! CSV output for spreadsheets (`--format csv`).
+//!
+//! Every record is one row with a fixed set of columns and a header row
+//! first. Fields are quoted when they contain a comma, quote, or line
+//! break, as RFC 4180 describes. Messages go to stderr so that stdout
+//! holds only the CSV.
+//!
+//! Row layouts:
+//! - `lint`, `check`: `skill,path,code,severity,line,column,message`
+//! - `list`:          `name,agent,scope,source,version,path`
+
+use super::{OutputFormatter, ReportOptions};
+use crate::skill::ValidationResult;
+
+/// Header row of lint results.
+pub const DIAGNOSTIC_HEADER: &[&str] = &[
+    "skill", "path", "code", "severity", "line", "column", "message",
+];
+
+/// Quote a CSV field if it contains a separator, quote, or line break.
+pub fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Join fields into a CSV row, with a trailing newline.
+pub fn csv_line<S: AsRef<str>>(fields: &[S]) -> String {
+    let fields: Vec<String> = fields.iter().map(|f| csv_field(f.as_ref())).collect();
+    format!("{}\n", fields.join(","))
+}
+
+/// Formatter that outputs CSV.
+pub struct CsvFormatter {
+    quiet: bool,
+}
+
+impl CsvFormatter {
+    /// Create a new CSV formatter.
+    pub fn new(quiet: bool) -> Self {
+        Self { quiet }
+    }
+}
+
+impl OutputFormatter for CsvFormatter {
+    fn format_validation(&self, results: &[(String, ValidationResult)]) -> String {
+        self.format_report(results, &ReportOptions::default())
+    }
+
+    /// One row per diagnostic; grouping by rule is not supported.
+    fn format_report(
+        &self,
+        results: &[(String, ValidationResult)],
+        options: &ReportOptions,
+    ) -> String {
+        let mut output = csv_line(DIAGNOSTIC_HEADER);
+        let diagnostics = results.iter().flat_map(|(skill, result)| {
+            result
+                .errors
+                .iter()
+                .chain(&result.warnings)
+                .map(move |diag| (skill, diag))
+        });
+
+        for (skill, diag) in diagnostics.take(options.max_diagnostics.unwrap_or(usize::MAX)) {
+            let severity = if diag.code.is_error() {
+                "error"
+            } else {
+                "warning"
+            };
+            output.push_str(&csv_line(&[
+                skill.as_str(),
+                &diag.path,
+                &diag.code.to_string(),
+                severity,
+                &diag.line.map(|l| l.to_string()).unwrap_or_default(),
+                &diag.column.map(|c| c.to_string()).unwrap_or_default(),
+                &diag.message,
+            ]));
+        }
+        output
+    }
+
+    fn format_message(&self, message: &str) {
+        if !self.quiet {
+            eprintln!("{}", message);
+        }
+    }
+
+    fn format_error(&self, message: &str) {
+        eprintln!("error: {}", message);
+    }
+
+    fn format_success(&self, message: &str) {
+        if !self.quiet {
+            eprintln!("{}", message);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::skill::{Diagnostic, DiagnosticCode};
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_csv_report() {
+        let diag = |code, line, message: &str| Diagnostic {
+            path: "a/scripts/run.sh".to_string(),
+            line,
+            column: None,
+            message: message.to_string(),
+            code,
+            fix_hint: None,
+        };
+        let results = vec![(
+            "a/SKILL.md".to_string(),
+            ValidationResult {
+                errors: vec![diag(DiagnosticCode::E001, Some(2), "Name \"A\", invalid")],
+                warnings: vec![diag(DiagnosticCode::W002, None, "Script is not executable")],
+            },
+        )];
+
+        let csv = CsvFormatter::new(false).format_validation(&results);
+        assert_eq!(
+            csv,
+            "skill,path,code,severity,line,column,message\n\
+             a/SKILL.md,a/scripts/run.sh,E001,error,2,,\"Name \"\"A\"\", invalid\"\n\
+             a/SKILL.md,a/scripts/run.sh,W002,warning,,,Script is not executable\n"
+        );
+
+        let capped = CsvFormatter::new(false).format_report(
+            &results,
+            &ReportOptions {
+                group_by_rule: false,
+                max_diagnostics: Some(1),
+                sources: BTreeMap::new(),
+            },
+        );
+        assert_eq!(capped.lines().count(), 2);
+    }
+}
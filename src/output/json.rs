@@ -1,7 +1,7 @@
 //! JSON output formatter.
 
-use super::OutputFormatter;
-use crate::skill::{Diagnostic, ValidationResult};
+use super::{get_rule_description, group_by_rule, OutputFormatter, ReportOptions};
+use crate::skill::{Diagnostic, DiagnosticCode, ValidationResult};
 use serde::Serialize;
 
 /// Formatter that outputs JSON.
@@ -17,28 +17,31 @@ impl JsonFormatter {
 }
 
 #[derive(Serialize)]
-struct JsonOutput {
-    skills: Vec<SkillResult>,
+struct JsonOutput<'a> {
+    skills: Vec<SkillResult<'a>>,
     summary: Summary,
 }
 
 #[derive(Serialize)]
-struct SkillResult {
-    path: String,
-    errors: Vec<JsonDiagnostic>,
-    warnings: Vec<JsonDiagnostic>,
+struct SkillResult<'a> {
+    path: &'a str,
+    errors: &'a [Diagnostic],
+    warnings: &'a [Diagnostic],
 }
 
 #[derive(Serialize)]
-struct JsonDiagnostic {
-    code: String,
-    message: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    line: Option<usize>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    column: Option<usize>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    fix_hint: Option<String>,
+struct GroupedOutput<'a> {
+    rules: Vec<RuleResult<'a>>,
+    summary: Summary,
+}
+
+#[derive(Serialize)]
+struct RuleResult<'a> {
+    code: DiagnosticCode,
+    description: &'static str,
+    count: usize,
+    skills: usize,
+    examples: Vec<&'a Diagnostic>,
 }
 
 #[derive(Serialize)]
@@ -47,42 +50,82 @@ struct Summary {
     total_errors: usize,
     total_warnings: usize,
     success: bool,
+    /// Diagnostics left out by `--max-diagnostics`.
+    #[serde(skip_serializing_if = "is_zero")]
+    omitted: usize,
 }
 
-impl From<&Diagnostic> for JsonDiagnostic {
-    fn from(diag: &Diagnostic) -> Self {
+fn is_zero(n: &usize) -> bool {
+    *n == 0
+}
+
+impl Summary {
+    fn new(results: &[(String, ValidationResult)], shown: usize) -> Self {
+        let total_errors: usize = results.iter().map(|(_, r)| r.errors.len()).sum();
+        let total_warnings: usize = results.iter().map(|(_, r)| r.warnings.len()).sum();
         Self {
-            code: diag.code.to_string(),
-            message: diag.message.clone(),
-            line: diag.line,
-            column: diag.column,
-            fix_hint: diag.fix_hint.clone(),
+            skills_checked: results.len(),
+            total_errors,
+            total_warnings,
+            success: total_errors == 0,
+            omitted: total_errors + total_warnings - shown,
         }
     }
 }
 
+/// The leading diagnostics that fit in `budget`, which is reduced by as many.
+fn take<'a>(diagnostics: &'a [Diagnostic], budget: &mut usize) -> &'a [Diagnostic] {
+    let shown = diagnostics.len().min(*budget);
+    *budget -= shown;
+    &diagnostics[..shown]
+}
+
 impl OutputFormatter for JsonFormatter {
     fn format_validation(&self, results: &[(String, ValidationResult)]) -> String {
+        self.format_report(results, &ReportOptions::default())
+    }
+
+    fn format_report(
+        &self,
+        results: &[(String, ValidationResult)],
+        options: &ReportOptions,
+    ) -> String {
+        if options.group_by_rule {
+            let rules: Vec<RuleResult> = group_by_rule(results, options.max_diagnostics)
+                .into_iter()
+                .map(|group| RuleResult {
+                    code: group.code,
+                    description: get_rule_description(group.code),
+                    count: group.count,
+                    skills: group.skills,
+                    examples: group.examples,
+                })
+                .collect();
+            let shown = rules.iter().map(|r| r.count).sum();
+            let output = GroupedOutput {
+                rules,
+                summary: Summary::new(results, shown),
+            };
+            return serde_json::to_string_pretty(&output).unwrap_or_else(|_| "{}".to_string());
+        }
+
+        let mut budget = options.max_diagnostics.unwrap_or(usize::MAX);
         let skills: Vec<SkillResult> = results
             .iter()
             .map(|(path, result)| SkillResult {
-                path: path.clone(),
-                errors: result.errors.iter().map(Into::into).collect(),
-                warnings: result.warnings.iter().map(Into::into).collect(),
+                path,
+                errors: take(&result.errors, &mut budget),
+                warnings: take(&result.warnings, &mut budget),
             })
             .collect();
 
-        let total_errors: usize = results.iter().map(|(_, r)| r.errors.len()).sum();
-        let total_warnings: usize = results.iter().map(|(_, r)| r.warnings.len()).sum();
-
+        let shown = skills
+            .iter()
+            .map(|s| s.errors.len() + s.warnings.len())
+            .sum();
         let output = JsonOutput {
             skills,
-            summary: Summary {
-                skills_checked: results.len(),
-                total_errors,
-                total_warnings,
-                success: total_errors == 0,
-            },
+            summary: Summary::new(results, shown),
         };
 
         serde_json::to_string_pretty(&output).unwrap_or_else(|_| "{}".to_string())
@@ -1,20 +1,114 @@
 //! Output formatting for command results.
 
+mod codeclimate;
+mod color;
+mod csv;
 mod json;
+mod paths;
+mod porcelain;
+mod progress;
 mod sarif;
+mod table;
 mod text;
 
 use crate::cli::OutputFormat;
-use crate::skill::ValidationResult;
+use crate::skill::{Diagnostic, DiagnosticCode, ValidationResult};
+use std::collections::BTreeMap;
 
+pub use codeclimate::CodeClimateFormatter;
+pub use color::{init_color, should_colorize};
+pub use csv::{csv_field, csv_line, CsvFormatter};
 pub use json::JsonFormatter;
+pub use paths::PathNormalizer;
+pub use porcelain::{porcelain_line, print_porcelain, EMPTY_FIELD};
+pub use progress::{Progress, ProgressEvent};
+pub(crate) use sarif::get_rule_description;
 pub use sarif::SarifFormatter;
+pub use table::{Align, Table};
 pub use text::TextFormatter;
 
+/// Number of example diagnostics shown per rule when grouping by rule.
+pub const EXAMPLES_PER_RULE: usize = 3;
+
+/// Options controlling how validation results are reported.
+#[derive(Debug, Clone, Default)]
+pub struct ReportOptions {
+    /// Group diagnostics by rule code, with counts and a few examples each,
+    /// instead of listing them per skill.
+    pub group_by_rule: bool,
+    /// Show at most this many diagnostics; summaries still count all of them.
+    pub max_diagnostics: Option<usize>,
+    /// Contents of the files diagnostics point into, keyed by the path the
+    /// diagnostics show. Text output quotes the offending line of files
+    /// listed here under each diagnostic.
+    pub sources: BTreeMap<String, String>,
+}
+
+/// Diagnostics for one rule, collected across skills.
+pub struct RuleGroup<'a> {
+    /// The rule's diagnostic code.
+    pub code: DiagnosticCode,
+    /// Number of diagnostics for the rule.
+    pub count: usize,
+    /// Number of skills with at least one diagnostic for the rule.
+    pub skills: usize,
+    /// The first few diagnostics for the rule.
+    pub examples: Vec<&'a Diagnostic>,
+}
+
+/// Group diagnostics by rule code, errors before warnings.
+///
+/// Each group keeps up to [`EXAMPLES_PER_RULE`] examples, and at most
+/// `max_examples` examples are kept across all groups.
+pub fn group_by_rule(
+    results: &[(String, ValidationResult)],
+    max_examples: Option<usize>,
+) -> Vec<RuleGroup<'_>> {
+    let mut groups: BTreeMap<DiagnosticCode, RuleGroup<'_>> = BTreeMap::new();
+    for (_, result) in results {
+        let mut seen = Vec::new();
+        for diag in result.errors.iter().chain(&result.warnings) {
+            let group = groups.entry(diag.code).or_insert_with(|| RuleGroup {
+                code: diag.code,
+                count: 0,
+                skills: 0,
+                examples: Vec::new(),
+            });
+            group.count += 1;
+            if !seen.contains(&diag.code) {
+                seen.push(diag.code);
+                group.skills += 1;
+            }
+            if group.examples.len() < EXAMPLES_PER_RULE {
+                group.examples.push(diag);
+            }
+        }
+    }
+
+    let mut budget = max_examples.unwrap_or(usize::MAX);
+    let mut groups: Vec<RuleGroup<'_>> = groups.into_values().collect();
+    for group in &mut groups {
+        group.examples.truncate(budget);
+        budget -= group.examples.len();
+    }
+    groups
+}
+
 /// Trait for formatting command output.
 pub trait OutputFormatter {
     /// Format validation results.
     fn format_validation(&self, results: &[(String, ValidationResult)]) -> String;
+    /// Format validation results as described by `options`.
+    ///
+    /// Formatters that don't support grouping or capping ignore `options`.
+    fn format_report(
+        &self,
+        results: &[(String, ValidationResult)],
+        options: &ReportOptions,
+    ) -> String {
+        let _ = options;
+        self.format_validation(results)
+    }
     /// Format an informational message.
     fn format_message(&self, message: &str);
     /// Format an error message.
@@ -26,8 +120,93 @@ pub trait OutputFormatter {
 /// Get a formatter for the given output format.
 pub fn get_formatter(format: OutputFormat, quiet: bool) -> Box<dyn OutputFormatter> {
     match format {
-        OutputFormat::Text => Box::new(TextFormatter::new(quiet)),
+        OutputFormat::Text | OutputFormat::Table => Box::new(TextFormatter::new(quiet)),
         OutputFormat::Json => Box::new(JsonFormatter::new(quiet)),
         OutputFormat::Sarif => Box::new(SarifFormatter::new(quiet)),
+        OutputFormat::Csv => Box::new(CsvFormatter::new(quiet)),
+        OutputFormat::CodeClimate => Box::new(CodeClimateFormatter::new(quiet)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn results() -> Vec<(String, ValidationResult)> {
+        let diag = |path: &str, code| Diagnostic {
+            path: path.to_string(),
+            line: Some(2),
+            column: None,
+            message: format!("{} in {}", code, path),
+            code,
+            fix_hint: None,
+        };
+        (0..5)
+            .map(|i| {
+                let path = format!("s{}/SKILL.md", i);
+                let result = ValidationResult {
+                    errors: vec![
+                        diag(&path, DiagnosticCode::E009),
+                        diag(&path, DiagnosticCode::E009),
+                    ],
+                    warnings: if i == 0 {
+                        vec![diag(&path, DiagnosticCode::W001)]
+                    } else {
+                        Vec::new()
+                    },
+                };
+                (path, result)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_group_by_rule() {
+        let results = results();
+        let groups = group_by_rule(&results, None);
+        let summary: Vec<_> = groups
+            .iter()
+            .map(|g| (g.code, g.count, g.skills, g.examples.len()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (DiagnosticCode::E009, 10, 5, EXAMPLES_PER_RULE),
+                (DiagnosticCode::W001, 1, 1, 1),
+            ]
+        );
+
+        // The example budget is shared across rules
+        let groups = group_by_rule(&results, Some(3));
+        assert_eq!(groups[0].examples.len(), 3);
+        assert!(groups[1].examples.is_empty());
+    }
+
+    #[test]
+    fn test_max_diagnostics() {
+        let output = TextFormatter::new(false).format_report(
+            &results(),
+            &ReportOptions {
+                group_by_rule: false,
+                max_diagnostics: Some(3),
+                sources: BTreeMap::new(),
+            },
+        );
+        assert_eq!(output.matches(" in s").count(), 3);
+        assert!(output.contains("8 more diagnostic(s) not shown"));
+        assert!(output.contains("5 skill(s) checked: 10 error(s), 1 warning(s)"));
+
+        let json = JsonFormatter::new(false).format_report(
+            &results(),
+            &ReportOptions {
+                group_by_rule: true,
+                max_diagnostics: None,
+                sources: BTreeMap::new(),
+            },
+        );
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["rules"][0]["code"], "E009");
+        assert_eq!(value["rules"][0]["count"], 10);
+        assert_eq!(value["summary"]["total_errors"], 10);
     }
 }
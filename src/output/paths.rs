@@ -0,0 +1,166 @@
+//! Normalization of file paths shown in diagnostics.
+//!
+//! Discovery hands out paths in whatever form they were found (`./a`,
+//! `a`, `/abs/a`, or `\\?\C:\a` on Windows). [`PathNormalizer`] turns
+//! them into one consistent form chosen with `--path-style`.
+
+use crate::cli::PathStyle;
+use crate::skill::ValidationResult;
+use std::path::{Component, Path, PathBuf};
+
+/// Rewrites diagnostic paths according to a [`PathStyle`].
+#[derive(Debug, Clone)]
+pub struct PathNormalizer {
+    style: PathStyle,
+    /// Directory relative paths are resolved against.
+    cwd: PathBuf,
+    /// Directory paths are shown relative to, for relative styles.
+    base: PathBuf,
+}
+
+impl PathNormalizer {
+    /// Create a normalizer for `style`, relative to the current directory.
+    pub fn new(style: PathStyle) -> Self {
+        let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+        Self::with_cwd(style, &cwd)
+    }
+
+    /// Create a normalizer for `style` as if run from `cwd`.
+    pub fn with_cwd(style: PathStyle, cwd: &Path) -> Self {
+        let cwd = absolute(cwd, Path::new("/"));
+        let base = match style {
+            PathStyle::Root => cwd
+                .ancestors()
+                .find(|dir| dir.join(".git").exists())
+                .unwrap_or(&cwd)
+                .to_path_buf(),
+            _ => cwd.clone(),
+        };
+        Self { style, cwd, base }
+    }
+
+    /// Normalize one path.
+    ///
+    /// Relative styles use `/` separators; paths outside the base directory
+    /// are shown absolute.
+    pub fn normalize(&self, path: &str) -> String {
+        if self.style == PathStyle::AsGiven {
+            return path.to_string();
+        }
+
+        let absolute = absolute(Path::new(path), &self.cwd);
+        if self.style == PathStyle::Absolute {
+            return absolute.display().to_string();
+        }
+
+        match absolute.strip_prefix(&self.base) {
+            Ok(relative) if relative.as_os_str().is_empty() => ".".to_string(),
+            Ok(relative) => relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/"),
+            Err(_) => absolute.display().to_string(),
+        }
+    }
+
+    /// Normalize the paths of lint results and their diagnostics.
+    pub fn normalize_results(&self, results: &mut [(String, ValidationResult)]) {
+        for (path, result) in results {
+            *path = self.normalize(path);
+            for diag in result.errors.iter_mut().chain(result.warnings.iter_mut()) {
+                diag.path = self.normalize(&diag.path);
+            }
+        }
+    }
+}
+
+/// Make `path` absolute against `cwd`, resolving symlinks where the path
+/// exists and removing `.` and `..` components otherwise.
+fn absolute(path: &Path, cwd: &Path) -> PathBuf {
+    if let Ok(canonical) = path.canonicalize() {
+        return strip_verbatim(canonical);
+    }
+
+    let joined = cwd.join(path);
+    let mut out = PathBuf::new();
+    for component in joined.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                out.pop();
+            }
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+/// Remove the `\\?\` prefix Windows adds to canonical paths.
+///
+/// `\\?\C:\dir` becomes `C:\dir` and `\\?\UNC\server\share` becomes
+/// `\\server\share`. Other paths are returned unchanged.
+fn strip_verbatim(path: PathBuf) -> PathBuf {
+    let text = path.to_string_lossy();
+    if let Some(rest) = text.strip_prefix(r"\\?\UNC\") {
+        return PathBuf::from(format!(r"\\{}", rest));
+    }
+    match text.strip_prefix(r"\\?\") {
+        Some(rest) if rest.as_bytes().get(1) == Some(&b':') => PathBuf::from(rest),
+        _ => path,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_normalize_styles() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().canonicalize().unwrap();
+        let cwd = root.join("pkg");
+        std::fs::create_dir_all(root.join(".git")).unwrap();
+        std::fs::create_dir_all(cwd.join("demo")).unwrap();
+        std::fs::write(cwd.join("demo/SKILL.md"), "").unwrap();
+
+        let relative = PathNormalizer::with_cwd(PathStyle::Relative, &cwd);
+        let as_given = cwd.join("./demo/SKILL.md").display().to_string();
+        assert_eq!(relative.normalize(&as_given), "demo/SKILL.md");
+        assert_eq!(
+            relative.normalize(&cwd.join("gone/../demo/x.md").display().to_string()),
+            "demo/x.md"
+        );
+        let outside = root.join("other.md").display().to_string();
+        assert_eq!(relative.normalize(&outside), outside);
+
+        let from_root = PathNormalizer::with_cwd(PathStyle::Root, &cwd);
+        assert_eq!(from_root.normalize(&as_given), "pkg/demo/SKILL.md");
+
+        let absolute = PathNormalizer::with_cwd(PathStyle::Absolute, &cwd);
+        assert_eq!(
+            absolute.normalize(&as_given),
+            cwd.join("demo/SKILL.md").display().to_string()
+        );
+
+        let verbatim = PathNormalizer::with_cwd(PathStyle::AsGiven, &cwd);
+        assert_eq!(verbatim.normalize("./demo/SKILL.md"), "./demo/SKILL.md");
+    }
+
+    #[test]
+    fn test_strip_verbatim() {
+        assert_eq!(
+            strip_verbatim(PathBuf::from(r"\\?\C:\skills\a")),
+            PathBuf::from(r"C:\skills\a")
+        );
+        assert_eq!(
+            strip_verbatim(PathBuf::from(r"\\?\UNC\server\share\a")),
+            PathBuf::from(r"\\server\share\a")
+        );
+        assert_eq!(
+            strip_verbatim(PathBuf::from("/skills/a")),
+            PathBuf::from("/skills/a")
+        );
+    }
+}
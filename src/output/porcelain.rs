@@ -0,0 +1,63 @@
+//! Stable, line-oriented output for scripts (`--porcelain`).
+//!
+//! Each record is printed on its own line as tab-separated fields, without
+//! color or headers. Tabs and newlines inside fields are replaced by spaces
+//! so that every line always has the same number of columns. Missing values
+//! are written as `-`.
+//!
+//! Record layouts:
+//! - `list`:   `scope  agent  name  path  description`
+//! - `agents`: `scope  agent  skill_count  path`
+//! - `add`:    `status  name  agent  scope  path` (status: installed, skipped, invalid)
+
+use std::io::Write;
+
+/// Placeholder for a missing field value.
+pub const EMPTY_FIELD: &str = "-";
+
+/// Join fields into a single porcelain line.
+pub fn porcelain_line(fields: &[&str]) -> String {
+    fields
+        .iter()
+        .map(|f| {
+            if f.is_empty() {
+                EMPTY_FIELD.to_string()
+            } else {
+                f.replace(['\t', '\n', '\r'], " ")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\t")
+}
+
+/// Print a porcelain record to stdout.
+///
+/// Exits quietly if the reading end of the pipe was closed (e.g. `| head`).
+pub fn print_porcelain(fields: &[&str]) {
+    let mut stdout = std::io::stdout().lock();
+    if let Err(e) = writeln!(stdout, "{}", porcelain_line(fields)) {
+        if e.kind() == std::io::ErrorKind::BrokenPipe {
+            std::process::exit(0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_porcelain_line_joins_with_tabs() {
+        assert_eq!(porcelain_line(&["a", "b", "c"]), "a\tb\tc");
+    }
+
+    #[test]
+    fn test_porcelain_line_escapes_separators() {
+        assert_eq!(porcelain_line(&["a\tb", "c\nd"]), "a b\tc d");
+    }
+
+    #[test]
+    fn test_porcelain_line_marks_empty_fields() {
+        assert_eq!(porcelain_line(&["a", "", "c"]), "a\t-\tc");
+    }
+}
@@ -0,0 +1,126 @@
+//! Progress events for long operations under `--format json`.
+//!
+//! Instead of human progress text, each step is reported as one line of
+//! JSON on stdout, tagged with its `event`:
+//!
+//! ```text
+//! {"event":"fetch-started","source":"owner/repo"}
+//! {"event":"fetch-done","source":"owner/repo","commit":"3b893f8...","from_cache":true}
+//! {"event":"validation-finished","skills":2,"valid":2}
+//! {"event":"skill-installed","skill":"code-review","agent":"claude","scope":"project",...}
+//! {"event":"install-finished","installed":2,"targets":1}
+//! ```
+
+use crate::agent::Agent;
+use crate::cli::OutputFormat;
+use crate::scope::Scope;
+use serde::Serialize;
+use std::path::Path;
+
+/// A step of a long operation.
+#[derive(Debug, Serialize)]
+#[serde(tag = "event", rename_all = "kebab-case")]
+pub enum ProgressEvent<'a> {
+    /// A git source started fetching.
+    FetchStarted {
+        /// The source, as `owner/repo[@ref]`.
+        source: &'a str,
+    },
+    /// A git source finished fetching.
+    FetchDone {
+        /// The source, as `owner/repo[@ref]`.
+        source: &'a str,
+        /// The commit checked out.
+        commit: Option<&'a str>,
+        /// Whether the repository was already cached.
+        from_cache: bool,
+    },
+    /// The skills found in a source were validated.
+    ValidationFinished {
+        /// Number of skills found.
+        skills: usize,
+        /// Number of skills that passed validation.
+        valid: usize,
+    },
+    /// A skill was installed to a target.
+    SkillInstalled {
+        /// Name of the skill.
+        skill: &'a str,
+        /// Agent of the target, if any.
+        agent: Option<Agent>,
+        /// Scope of the target.
+        scope: Scope,
+        /// Where the skill was installed.
+        path: &'a Path,
+        /// Whether an installed copy was replaced.
+        replaced: bool,
+    },
+    /// A skill was not installed to a target.
+    SkillSkipped {
+        /// Name of the skill.
+        skill: &'a str,
+        /// Agent of the target, if any.
+        agent: Option<Agent>,
+        /// Scope of the target.
+        scope: Scope,
+        /// `skipped`, `invalid`, or `excluded`.
+        reason: &'a str,
+    },
+    /// Installation to every target finished.
+    InstallFinished {
+        /// Number of skills installed, counting each target.
+        installed: usize,
+        /// Number of targets installed to.
+        targets: usize,
+    },
+}
+
+/// Emits progress events when the output format is JSON.
+#[derive(Debug, Clone, Copy)]
+pub struct Progress {
+    enabled: bool,
+}
+
+impl Progress {
+    /// Progress reporting for `format`, silent with `quiet`.
+    pub fn new(format: OutputFormat, quiet: bool) -> Self {
+        Self {
+            enabled: matches!(format, OutputFormat::Json) && !quiet,
+        }
+    }
+
+    /// Returns true if events are emitted, in which case human progress
+    /// text should be left out.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Emit `event` as a line of JSON on stdout.
+    pub fn emit(&self, event: ProgressEvent<'_>) {
+        if self.enabled {
+            if let Ok(line) = serde_json::to_string(&event) {
+                println!("{}", line);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_json() {
+        let event = ProgressEvent::FetchDone {
+            source: "owner/repo",
+            commit: Some("abc1234"),
+            from_cache: true,
+        };
+        assert_eq!(
+            serde_json::to_string(&event).unwrap(),
+            r#"{"event":"fetch-done","source":"owner/repo","commit":"abc1234","from_cache":true}"#
+        );
+        assert!(!Progress::new(OutputFormat::Text, false).is_enabled());
+        assert!(!Progress::new(OutputFormat::Json, true).is_enabled());
+    }
+}
@@ -51,6 +51,16 @@ struct SarifRule {
     short_description: SarifMessage,
     #[serde(rename = "defaultConfiguration")]
     default_configuration: SarifConfiguration,
+    /// Deprecated codes whose check this rule took over.
+    #[serde(rename = "deprecatedIds", skip_serializing_if = "Vec::is_empty")]
+    deprecated_ids: Vec<String>,
+    properties: SarifRuleProperties,
+}
+
+#[derive(Serialize)]
+struct SarifRuleProperties {
+    #[serde(rename = "introducedIn")]
+    introduced_in: &'static str,
 }
 
 #[derive(Serialize)]
@@ -99,7 +109,26 @@ struct SarifRegion {
     start_column: Option<usize>,
 }
 
-fn get_rule_description(code: DiagnosticCode) -> &'static str {
+/// Turn a file path into a SARIF artifact URI.
+///
+/// Relative paths become relative references with `/` separators; absolute
+/// paths become `file://` URIs (`file://server/share/...` for UNC paths).
+fn to_uri(path: &str) -> String {
+    let slashed = path.replace('\\', "/");
+    let encoded = slashed.replace('%', "%25").replace(' ', "%20");
+    if let Some(unc) = encoded.strip_prefix("//") {
+        format!("file://{}", unc)
+    } else if encoded.starts_with('/') {
+        format!("file://{}", encoded)
+    } else if encoded.as_bytes().get(1) == Some(&b':') {
+        format!("file:///{}", encoded)
+    } else {
+        encoded
+    }
+}
+
+/// Short description of the rule behind `code`.
+pub(crate) fn get_rule_description(code: DiagnosticCode) -> &'static str {
     match code {
         DiagnosticCode::E001 => "Invalid skill name format",
         DiagnosticCode::E002 => "Skill name exceeds maximum length",
@@ -110,10 +139,33 @@ fn get_rule_description(code: DiagnosticCode) -> &'static str {
         DiagnosticCode::E007 => "Invalid YAML in frontmatter",
         DiagnosticCode::E008 => "Missing SKILL.md file",
         DiagnosticCode::E009 => "Referenced file not found",
+        DiagnosticCode::E010 => "Invalid deprecation metadata",
+        DiagnosticCode::E011 => "Invalid extended frontmatter field",
+        DiagnosticCode::E012 => "SKILL.md file too large",
+        DiagnosticCode::E013 => "Invalid per-agent overrides",
+        DiagnosticCode::E014 => "File is not valid UTF-8",
+        DiagnosticCode::E015 => "Invalid runtime requirements",
+        DiagnosticCode::E016 => "Credential committed in a skill file",
+        DiagnosticCode::E017 => "Skill name used by more than one skill",
         DiagnosticCode::W001 => "Skill body exceeds recommended length",
         DiagnosticCode::W002 => "Script is not executable",
         DiagnosticCode::W003 => "Script missing shebang line",
         DiagnosticCode::W004 => "Empty optional directory",
+        DiagnosticCode::W005 => "superseded-by set on a skill that is not deprecated",
+        DiagnosticCode::W006 => "Invalid tag format",
+        DiagnosticCode::W007 => "Too many tags",
+        DiagnosticCode::W008 => "Skill body exceeds recommended token budget",
+        DiagnosticCode::W009 => "Skill is nested inside another skill",
+        DiagnosticCode::W010 => "Feature not supported by a target agent",
+        DiagnosticCode::W011 => "Name collides with a built-in agent command",
+        DiagnosticCode::W012 => "Summary exceeds maximum length",
+        DiagnosticCode::W013 => "Byte order mark or CRLF line endings",
+        DiagnosticCode::W014 => "Script interpreter not found",
+        DiagnosticCode::W015 => "Scripts section out of sync with scripts/",
+        DiagnosticCode::W016 => "Description does not say when to use the skill",
+        DiagnosticCode::W017 => "Too many examples",
+        DiagnosticCode::W018 => "Empty or too long example",
+        DiagnosticCode::W019 => "SKILL.md is not formatted",
     }
 }
 
@@ -138,6 +190,14 @@ impl OutputFormatter for SarifFormatter {
                                 "warning"
                             },
                         },
+                        deprecated_ids: DiagnosticCode::ALL
+                            .into_iter()
+                            .filter(|c| c.metadata().replaced_by == Some(diag.code))
+                            .map(|c| c.to_string())
+                            .collect(),
+                        properties: SarifRuleProperties {
+                            introduced_in: diag.code.metadata().introduced_in,
+                        },
                     });
                 }
             }
@@ -146,7 +206,7 @@ impl OutputFormatter for SarifFormatter {
         // Collect all results
         let mut sarif_results: Vec<SarifResult> = Vec::new();
 
-        for (path, result) in results {
+        for (_, result) in results {
             for diag in result.errors.iter().chain(result.warnings.iter()) {
                 sarif_results.push(SarifResult {
                     rule_id: diag.code.to_string(),
@@ -160,7 +220,9 @@ impl OutputFormatter for SarifFormatter {
                     },
                     locations: vec![SarifLocation {
                         physical_location: SarifPhysicalLocation {
-                            artifact_location: SarifArtifactLocation { uri: path.clone() },
+                            artifact_location: SarifArtifactLocation {
+                                uri: to_uri(&diag.path),
+                            },
                             region: diag.line.map(|line| SarifRegion {
                                 start_line: line,
                                 start_column: diag.column,
@@ -206,3 +268,20 @@ impl OutputFormatter for SarifFormatter {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_uri() {
+        assert_eq!(
+            to_uri("skills/my skill/SKILL.md"),
+            "skills/my%20skill/SKILL.md"
+        );
+        assert_eq!(to_uri(r"skills\a\SKILL.md"), "skills/a/SKILL.md");
+        assert_eq!(to_uri("/repo/a/SKILL.md"), "file:///repo/a/SKILL.md");
+        assert_eq!(to_uri(r"C:\repo\a\SKILL.md"), "file:///C:/repo/a/SKILL.md");
+        assert_eq!(to_uri(r"\\server\share\a"), "file://server/share/a");
+    }
+}
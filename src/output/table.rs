@@ -0,0 +1,128 @@
+//! Aligned table rendering for terminal output.
+
+use colored::Colorize;
+
+/// Horizontal alignment of a table column.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Align {
+    /// Pad on the right.
+    #[default]
+    Left,
+    /// Pad on the left.
+    Right,
+    /// Pad on both sides.
+    Center,
+}
+
+/// A table of plain-text cells rendered with aligned columns.
+#[derive(Debug, Default)]
+pub struct Table {
+    headers: Vec<String>,
+    align: Vec<Align>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    /// Create a table with the given column headers.
+    pub fn new<S: Into<String>>(headers: impl IntoIterator<Item = S>) -> Self {
+        let headers: Vec<String> = headers.into_iter().map(Into::into).collect();
+        Self {
+            align: vec![Align::Left; headers.len()],
+            headers,
+            rows: Vec::new(),
+        }
+    }
+
+    /// Set the alignment of a column.
+    pub fn align(mut self, column: usize, align: Align) -> Self {
+        if let Some(a) = self.align.get_mut(column) {
+            *a = align;
+        }
+        self
+    }
+
+    /// Append a row. Missing cells are left blank.
+    pub fn row<S: Into<String>>(&mut self, cells: impl IntoIterator<Item = S>) {
+        self.rows.push(cells.into_iter().map(Into::into).collect());
+    }
+
+    /// Returns true if the table has no rows.
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Render the table, prefixing every line with `indent`.
+    ///
+    /// The header is bold and followed by a dashed rule.
+    pub fn render(&self, indent: &str) -> String {
+        let widths: Vec<usize> = (0..self.headers.len())
+            .map(|col| {
+                self.rows
+                    .iter()
+                    .filter_map(|row| row.get(col))
+                    .chain(std::iter::once(&self.headers[col]))
+                    .map(|cell| cell.chars().count())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        let total = widths.iter().sum::<usize>() + 2 * widths.len().saturating_sub(1);
+
+        let mut out = String::new();
+        out.push_str(&self.line(indent, &self.headers, &widths, true));
+        out.push_str(&format!("{}{}\n", indent, "-".repeat(total)));
+        for row in &self.rows {
+            out.push_str(&self.line(indent, row, &widths, false));
+        }
+        out
+    }
+
+    /// Print the table to stdout with a two-space indent.
+    pub fn print(&self) {
+        print!("{}", self.render("  "));
+    }
+
+    fn line(&self, indent: &str, cells: &[String], widths: &[usize], bold: bool) -> String {
+        let padded: Vec<String> = widths
+            .iter()
+            .enumerate()
+            .map(|(col, &width)| {
+                let cell = cells.get(col).map(String::as_str).unwrap_or("");
+                let pad = width - cell.chars().count().min(width);
+                let (left, right) = match self.align[col] {
+                    Align::Left => (0, pad),
+                    Align::Right => (pad, 0),
+                    Align::Center => (pad / 2, pad - pad / 2),
+                };
+                let text = if bold {
+                    cell.bold().to_string()
+                } else {
+                    cell.to_string()
+                };
+                format!("{}{}{}", " ".repeat(left), text, " ".repeat(right))
+            })
+            .collect();
+
+        format!("{}{}\n", indent, padded.join("  ").trim_end())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_aligns_columns() {
+        let mut table = Table::new(["Name", "Size"]).align(1, Align::Right);
+        table.row(["code-review", "1.2 KB"]);
+        table.row(["a", "12 B"]);
+
+        let rendered = table.render("");
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[1], "-".repeat(19));
+        assert_eq!(lines[2], "code-review  1.2 KB");
+        assert_eq!(lines[3], "a              12 B");
+    }
+}
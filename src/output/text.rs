@@ -1,8 +1,9 @@
 //! Human-readable text output formatter.
 
-use super::OutputFormatter;
-use crate::skill::ValidationResult;
-use colored::Colorize;
+use super::{get_rule_description, group_by_rule, OutputFormatter, ReportOptions};
+use crate::skill::{Diagnostic, ValidationResult};
+use colored::{ColoredString, Colorize};
+use std::collections::BTreeMap;
 
 /// Formatter that outputs human-readable text.
 pub struct TextFormatter {
@@ -18,53 +19,19 @@ impl TextFormatter {
 
 impl OutputFormatter for TextFormatter {
     fn format_validation(&self, results: &[(String, ValidationResult)]) -> String {
-        let mut output = String::new();
-
-        for (skill_path, result) in results {
-            if !result.errors.is_empty() || !result.warnings.is_empty() {
-                output.push_str(&format!("\n{}\n", skill_path.bold()));
-
-                for diag in &result.errors {
-                    let location = match (diag.line, diag.column) {
-                        (Some(line), Some(col)) => format!("{}:{}", line, col),
-                        (Some(line), None) => format!("{}:", line),
-                        _ => String::new(),
-                    };
-
-                    output.push_str(&format!(
-                        "  {} {} {}: {}\n",
-                        "error".red().bold(),
-                        format!("[{}]", diag.code).dimmed(),
-                        location.dimmed(),
-                        diag.message
-                    ));
-
-                    if let Some(hint) = &diag.fix_hint {
-                        output.push_str(&format!("    {} {}\n", "hint:".cyan(), hint));
-                    }
-                }
-
-                for diag in &result.warnings {
-                    let location = match (diag.line, diag.column) {
-                        (Some(line), Some(col)) => format!("{}:{}", line, col),
-                        (Some(line), None) => format!("{}:", line),
-                        _ => String::new(),
-                    };
-
-                    output.push_str(&format!(
-                        "  {} {} {}: {}\n",
-                        "warning".yellow().bold(),
-                        format!("[{}]", diag.code).dimmed(),
-                        location.dimmed(),
-                        diag.message
-                    ));
-
-                    if let Some(hint) = &diag.fix_hint {
-                        output.push_str(&format!("    {} {}\n", "hint:".cyan(), hint));
-                    }
-                }
-            }
-        }
+        self.format_report(results, &ReportOptions::default())
+    }
+
+    fn format_report(
+        &self,
+        results: &[(String, ValidationResult)],
+        options: &ReportOptions,
+    ) -> String {
+        let mut output = if options.group_by_rule {
+            format_by_rule(results, options.max_diagnostics, &options.sources)
+        } else {
+            format_by_skill(results, options.max_diagnostics, &options.sources)
+        };
 
         // Summary
         let total_errors: usize = results.iter().map(|(_, r)| r.errors.len()).sum();
@@ -111,3 +78,202 @@ impl OutputFormatter for TextFormatter {
         }
     }
 }
+
+/// List diagnostics under each skill, stopping after `max` diagnostics.
+fn format_by_skill(
+    results: &[(String, ValidationResult)],
+    max: Option<usize>,
+    sources: &BTreeMap<String, String>,
+) -> String {
+    let mut output = String::new();
+    let mut budget = max.unwrap_or(usize::MAX);
+    let mut hidden = 0;
+
+    for (skill_path, result) in results {
+        if result.errors.is_empty() && result.warnings.is_empty() {
+            continue;
+        }
+
+        let diagnostics = result.errors.len() + result.warnings.len();
+        if budget == 0 {
+            hidden += diagnostics;
+            continue;
+        }
+
+        output.push_str(&format!("\n{}\n", skill_path.bold()));
+        for diag in result.errors.iter().chain(&result.warnings) {
+            if budget == 0 {
+                hidden += 1;
+                continue;
+            }
+            budget -= 1;
+            output.push_str(&format_diagnostic(diag, false, sources));
+        }
+    }
+
+    if hidden > 0 {
+        output.push_str(&format!(
+            "\n{} more diagnostic(s) not shown; raise --max-diagnostics to see them\n",
+            hidden
+        ));
+    }
+
+    output
+}
+
+/// List each rule with its counts and a few example diagnostics.
+fn format_by_rule(
+    results: &[(String, ValidationResult)],
+    max: Option<usize>,
+    sources: &BTreeMap<String, String>,
+) -> String {
+    let mut output = String::new();
+
+    for group in group_by_rule(results, max) {
+        output.push_str(&format!(
+            "\n{} {} {}\n",
+            format!("[{}]", group.code).bold(),
+            get_rule_description(group.code),
+            format!("({} in {} skill(s))", group.count, group.skills).dimmed()
+        ));
+        for diag in &group.examples {
+            output.push_str(&format_diagnostic(diag, true, sources));
+        }
+        let more = group.count - group.examples.len();
+        if more > 0 {
+            output.push_str(&format!(
+                "  {}\n",
+                format!("... and {} more", more).dimmed()
+            ));
+        }
+    }
+
+    output
+}
+
+/// Format one diagnostic with its location and fix hint.
+///
+/// Diagnostics listed outside their skill's heading include the file path.
+/// When `sources` holds the diagnostic's file, the offending line is quoted
+/// below the message.
+fn format_diagnostic(
+    diag: &Diagnostic,
+    with_path: bool,
+    sources: &BTreeMap<String, String>,
+) -> String {
+    let mut location = match (diag.line, diag.column) {
+        (Some(line), Some(col)) => format!("{}:{}", line, col),
+        (Some(line), None) => format!("{}:", line),
+        _ => String::new(),
+    };
+    if with_path {
+        location = if location.is_empty() {
+            diag.path.clone()
+        } else {
+            format!("{}:{}", diag.path, location)
+        };
+    }
+    let severity = if diag.code.is_error() {
+        "error".red().bold()
+    } else {
+        "warning".yellow().bold()
+    };
+
+    let mut output = format!(
+        "  {} {} {}: {}\n",
+        severity,
+        format!("[{}]", diag.code).dimmed(),
+        location.dimmed(),
+        diag.message
+    );
+    let snippet = sources
+        .get(&diag.path)
+        .and_then(|source| snippet(source, diag));
+    if let Some(snippet) = snippet {
+        output.push_str(&snippet);
+    }
+    if let Some(hint) = &diag.fix_hint {
+        output.push_str(&format!("    {} {}\n", "hint:".cyan(), hint));
+    }
+    output
+}
+
+/// Quote the line `diag` points at, marking the offending text with carets
+/// as rustc does.
+///
+/// Carets run from the diagnostic's column, or the line's first non-blank
+/// character, to the end of the line. Returns `None` for diagnostics
+/// without a line, or with one past the end of `source`.
+fn snippet(source: &str, diag: &Diagnostic) -> Option<String> {
+    let number = diag.line?;
+    // Tabs would shift the carets; show them as single spaces
+    let line: Vec<char> = source
+        .lines()
+        .nth(number.checked_sub(1)?)?
+        .trim_end()
+        .chars()
+        .map(|c| if c == '\t' { ' ' } else { c })
+        .collect();
+
+    let indent = line.iter().take_while(|c| **c == ' ').count();
+    let start = match diag.column {
+        Some(column) => column.saturating_sub(1).min(line.len()),
+        None => indent,
+    };
+    let carets = line.len().saturating_sub(start).max(1);
+
+    let width = number.to_string().len();
+    let gutter = |label: &str| format!("{:>w$} |", label, w = width).blue().bold();
+    let marks: ColoredString = if diag.code.is_error() {
+        "^".repeat(carets).red().bold()
+    } else {
+        "^".repeat(carets).yellow().bold()
+    };
+
+    Some(format!(
+        "    {}\n    {} {}\n    {} {}{}\n",
+        gutter(""),
+        gutter(&number.to_string()),
+        line.iter().collect::<String>(),
+        gutter(""),
+        " ".repeat(start),
+        marks
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::skill::DiagnosticCode;
+
+    /// The snippet for `diag` without ANSI escape sequences.
+    fn plain_snippet(source: &str, diag: &Diagnostic) -> Option<String> {
+        let ansi = regex::Regex::new("\x1b\\[[0-9;]*m").unwrap();
+        snippet(source, diag).map(|s| ansi.replace_all(&s, "").to_string())
+    }
+
+    #[test]
+    fn test_snippet() {
+        let source = "---\nname: My_Skill\ndescription: Test\n---\n";
+        let mut diag = Diagnostic {
+            path: "a/SKILL.md".into(),
+            line: Some(2),
+            column: Some(7),
+            message: "Invalid name".into(),
+            code: DiagnosticCode::E001,
+            fix_hint: None,
+        };
+        assert_eq!(
+            plain_snippet(source, &diag).unwrap(),
+            "      |\n    2 | name: My_Skill\n      |       ^^^^^^^^\n"
+        );
+
+        diag.column = None;
+        assert!(plain_snippet(source, &diag)
+            .unwrap()
+            .ends_with("|\n      | ^^^^^^^^^^^^^^\n"));
+
+        diag.line = Some(9);
+        assert_eq!(snippet(source, &diag), None);
+    }
+}
@@ -1,10 +1,13 @@
 //! Installation scope handling (project vs global).
 
 use crate::agent::Agent;
+use crate::skill::manifest::Manifest;
+use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 
 /// Installation scope for skills.
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Scope {
     /// Project-level installation (relative to project root).
     #[default]
@@ -60,6 +63,25 @@ pub struct InstalledSkill {
     pub agent: Option<Agent>,
     /// Installation scope.
     pub scope: Scope,
+    /// Whether the skill is marked `deprecated: true`.
+    pub deprecated: bool,
+    /// The skill that replaces this one, from `superseded-by`.
+    pub superseded_by: Option<String>,
+    /// Tags from the skill's frontmatter.
+    pub tags: Vec<String>,
+    /// Version from the skill's `metadata.version`, if any.
+    pub version: Option<String>,
+}
+
+/// Fields read from an installed skill's frontmatter.
+#[derive(Default)]
+struct SkillSummary {
+    name: String,
+    description: String,
+    deprecated: bool,
+    superseded_by: Option<String>,
+    tags: Vec<String>,
+    version: Option<String>,
 }
 
 /// List installed skills at a given scope.
@@ -91,11 +113,15 @@ pub fn list_skills_from_path(
                 if skill_md.exists() {
                     if let Some(info) = read_skill_info(&path) {
                         skills.push(InstalledSkill {
-                            name: info.0,
-                            description: info.1,
+                            name: info.name,
+                            description: info.description,
                             path,
                             agent,
                             scope,
+                            deprecated: info.deprecated,
+                            superseded_by: info.superseded_by,
+                            tags: info.tags,
+                            version: info.version,
                         });
                     }
                 }
@@ -143,22 +169,31 @@ pub fn skill_exists_other_scope(
     }
 }
 
-/// Read basic skill info (name, description) from a skill directory.
-fn read_skill_info(skill_dir: &Path) -> Option<(String, String)> {
+/// Read name, description, and metadata from a skill's SKILL.md.
+///
+/// Falls back to a line-based scan when the frontmatter does not parse,
+/// so malformed skills are still listed.
+fn read_skill_info(skill_dir: &Path) -> Option<SkillSummary> {
     let skill_md = skill_dir.join("SKILL.md");
-    let content = std::fs::read_to_string(&skill_md).ok()?;
 
-    // Parse frontmatter
-    if !content.starts_with("---") {
-        return None;
+    if let Ok(manifest) = Manifest::parse(skill_md.clone()) {
+        let frontmatter = manifest.frontmatter;
+        return Some(SkillSummary {
+            deprecated: frontmatter.is_deprecated(),
+            tags: frontmatter.tags().to_vec(),
+            version: frontmatter
+                .metadata
+                .as_ref()
+                .and_then(|m| m.get("version").cloned()),
+            name: frontmatter.name,
+            description: frontmatter.description,
+            superseded_by: frontmatter.superseded_by,
+        });
     }
 
-    let parts: Vec<&str> = content.splitn(3, "---").collect();
-    if parts.len() < 3 {
-        return None;
-    }
+    let content = std::fs::read_to_string(&skill_md).ok()?;
 
-    let frontmatter = parts[1];
+    let (frontmatter, _) = content.strip_prefix("---")?.split_once("---")?;
 
     let mut name = None;
     let mut description = None;
@@ -166,33 +201,32 @@ fn read_skill_info(skill_dir: &Path) -> Option<(String, String)> {
     for line in frontmatter.lines() {
         let line = line.trim();
         if let Some(value) = line.strip_prefix("name:") {
-            name = Some(
-                value
-                    .trim()
-                    .trim_matches('"')
-                    .trim_matches('\'')
-                    .to_string(),
-            );
+            name = Some(unquote(value));
         } else if let Some(value) = line.strip_prefix("description:") {
-            description = Some(
-                value
-                    .trim()
-                    .trim_matches('"')
-                    .trim_matches('\'')
-                    .to_string(),
-            );
+            description = Some(unquote(value));
         }
     }
 
-    match (name, description) {
-        (Some(n), Some(d)) => Some((n, d)),
-        (Some(n), None) => Some((n, String::new())),
-        _ => {
-            // Fall back to directory name
-            let dir_name = skill_dir.file_name()?.to_str()?.to_string();
-            Some((dir_name, String::new()))
-        }
-    }
+    let name = match name {
+        Some(n) => n,
+        // Fall back to directory name
+        None => skill_dir.file_name()?.to_str()?.to_string(),
+    };
+
+    Some(SkillSummary {
+        name,
+        description: description.unwrap_or_default(),
+        ..Default::default()
+    })
+}
+
+/// Strip surrounding whitespace and quotes from a YAML scalar.
+fn unquote(value: &str) -> String {
+    value
+        .trim()
+        .trim_matches('"')
+        .trim_matches('\'')
+        .to_string()
 }
 
 /// Get the global skills directory, creating it if necessary.
@@ -1,7 +1,10 @@
 //! Skill discovery utilities.
 
+use crate::config::DiscoveryConfig;
 use crate::skill::manifest::{Manifest, ManifestError};
-use globset::{Glob, GlobSetBuilder};
+use colored::Colorize;
+use globset::{Glob, GlobBuilder, GlobSetBuilder};
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 
@@ -11,13 +14,18 @@ pub struct Discovery;
 impl Discovery {
     /// Find all SKILL.md files in a directory tree.
     ///
-    /// The `ignore_patterns` parameter specifies glob patterns for directories to skip during traversal.
+    /// `config.ignore` specifies glob patterns for directories to skip during traversal.
     /// Patterns follow `.gitignore` style glob syntax and can match directory names or paths:
     /// - `target` - matches any directory named "target"
     /// - `build-*` - matches directories starting with "build-"
     /// - `foo/bar` - matches the path "foo/bar" relative to search root
     /// - `**/cache` - matches "cache" at any depth
-    pub fn find_skills(root: &Path, ignore_patterns: &[String]) -> Vec<PathBuf> {
+    ///
+    /// Traversal stops at `config.max_depth` and only follows symlinks when
+    /// `config.follow_symlinks` is set. Directories reached more than once
+    /// (through symlinks) are visited only the first time, and a warning is
+    /// printed when symlink loops or the depth limit cut the search short.
+    pub fn find_skills(root: &Path, config: &DiscoveryConfig) -> Vec<PathBuf> {
         // If root is a SKILL.md file, return it directly
         if root.is_file() && root.file_name().map(|n| n == "SKILL.md").unwrap_or(false) {
             return vec![root.to_path_buf()];
@@ -31,7 +39,7 @@ impl Discovery {
 
         // Build a GlobSet from ignore patterns
         let mut builder = GlobSetBuilder::new();
-        for pattern in ignore_patterns {
+        for pattern in &config.ignore {
             if let Ok(glob) = Glob::new(pattern) {
                 builder.add(glob);
             }
@@ -40,9 +48,14 @@ impl Discovery {
             .build()
             .unwrap_or_else(|_| GlobSetBuilder::new().build().unwrap());
 
+        let mut visited: HashSet<PathBuf> = root.canonicalize().into_iter().collect();
+        let mut truncated = false;
+        let mut skills = Vec::new();
+
         // Otherwise, search recursively, skipping ignored directories
-        WalkDir::new(root)
-            .follow_links(true)
+        let walker = WalkDir::new(root)
+            .follow_links(config.follow_symlinks)
+            .max_depth(config.max_depth)
             .into_iter()
             .filter_entry(|e| {
                 // Allow the root directory itself
@@ -50,31 +63,161 @@ impl Discovery {
                     return true;
                 }
 
+                if !e.file_type().is_dir() {
+                    return true;
+                }
+
                 // Skip ignored directories using glob matching against relative path
-                if e.file_type().is_dir() {
-                    // Get relative path from root for matching
-                    if let Ok(rel_path) = e.path().strip_prefix(root) {
-                        // Match against both the relative path and just the directory name
-                        // This supports both "target" and "foo/bar" style patterns
-                        let path_str = rel_path.to_string_lossy();
-                        if globset.is_match(path_str.as_ref()) {
+                // Get relative path from root for matching
+                if let Ok(rel_path) = e.path().strip_prefix(root) {
+                    // Match against both the relative path and just the directory name
+                    // This supports both "target" and "foo/bar" style patterns
+                    let path_str = rel_path.to_string_lossy();
+                    if globset.is_match(path_str.as_ref()) {
+                        return false;
+                    }
+
+                    // Also check just the directory name for simple patterns
+                    if let Some(name) = e.file_name().to_str() {
+                        if globset.is_match(name) {
                             return false;
                         }
+                    }
+                }
 
-                        // Also check just the directory name for simple patterns
-                        if let Some(name) = e.file_name().to_str() {
-                            if globset.is_match(name) {
-                                return false;
-                            }
-                        }
+                // Skip directories already reached through another symlink
+                if let Ok(canonical) = e.path().canonicalize() {
+                    if !visited.insert(canonical) {
+                        return false;
                     }
                 }
 
+                if e.depth() == config.max_depth && has_entries(e.path()) {
+                    truncated = true;
+                }
+
                 true
+            });
+
+        for entry in walker {
+            match entry {
+                Ok(e) if e.file_name() == "SKILL.md" => skills.push(e.into_path()),
+                Ok(_) => {}
+                Err(e) if e.loop_ancestor().is_some() => {
+                    let path = e.path().unwrap_or(root);
+                    eprintln!(
+                        "{}: skipping symlink loop at {}",
+                        "Warning".yellow(),
+                        path.display()
+                    );
+                }
+                Err(_) => {}
+            }
+        }
+
+        if truncated {
+            eprintln!(
+                "{}: skill discovery in {} stopped at max depth {}; set discovery.max_depth to search deeper",
+                "Warning".yellow(),
+                root.display(),
+                config.max_depth
+            );
+        }
+
+        skills
+    }
+
+    /// Find all SKILL.md files across several paths.
+    ///
+    /// Each path may be a skill directory, a directory containing skills, a
+    /// SKILL.md file, any other file inside a skill (resolved to the nearest
+    /// enclosing SKILL.md), or a glob pattern. Results are merged in argument
+    /// order with duplicates removed.
+    pub fn find_skills_in(paths: &[PathBuf], config: &DiscoveryConfig) -> Vec<PathBuf> {
+        let mut found = Vec::new();
+        let mut seen = HashSet::new();
+
+        for path in paths {
+            let expanded = if !path.exists() && is_glob(path) {
+                expand_glob(path)
+            } else {
+                vec![path.clone()]
+            };
+
+            for path in expanded {
+                let skills = if path.is_file() && path.file_name() != Some("SKILL.md".as_ref()) {
+                    enclosing_skill(&path).into_iter().collect()
+                } else {
+                    Self::find_skills(&path, config)
+                };
+
+                for skill in skills {
+                    let key = skill.canonicalize().unwrap_or_else(|_| skill.clone());
+                    if seen.insert(key) {
+                        found.push(skill);
+                    }
+                }
+            }
+        }
+
+        found
+    }
+
+    /// The roots to search for `paths`.
+    ///
+    /// When `paths` is only the default `.` and `discovery.roots` is set, the
+    /// configured roots are used instead; otherwise `paths` is returned as is.
+    pub fn roots(paths: &[PathBuf], config: &DiscoveryConfig) -> Vec<PathBuf> {
+        if config.roots.is_empty() || paths != [PathBuf::from(".")] {
+            return paths.to_vec();
+        }
+        Self::configured_roots(Path::new("."), config)
+    }
+
+    /// Expand `discovery.roots` relative to `base` into existing directories.
+    ///
+    /// Roots may be glob patterns such as `packages/*/skills`.
+    pub fn configured_roots(base: &Path, config: &DiscoveryConfig) -> Vec<PathBuf> {
+        let mut roots = Vec::new();
+        for root in &config.roots {
+            let path = if base == Path::new(".") {
+                root.clone()
+            } else {
+                base.join(root)
+            };
+            let expanded = if is_glob(&path) {
+                expand_glob(&path)
+            } else {
+                vec![path]
+            };
+            for dir in expanded.into_iter().filter(|p| p.is_dir()) {
+                if !roots.contains(&dir) {
+                    roots.push(dir);
+                }
+            }
+        }
+        roots
+    }
+
+    /// Find skills under each root, grouped by root.
+    ///
+    /// A skill reachable from several roots is listed under the first one.
+    pub fn find_skills_by_root(
+        roots: &[PathBuf],
+        config: &DiscoveryConfig,
+    ) -> Vec<(PathBuf, Vec<PathBuf>)> {
+        let mut seen = HashSet::new();
+        roots
+            .iter()
+            .map(|root| {
+                let skills = Self::find_skills_in(std::slice::from_ref(root), config)
+                    .into_iter()
+                    .filter(|skill| {
+                        seen.insert(skill.canonicalize().unwrap_or_else(|_| skill.clone()))
+                    })
+                    .collect();
+                (root.clone(), skills)
             })
-            .filter_map(|e| e.ok())
-            .filter(|e| e.file_name() == "SKILL.md")
-            .map(|e| e.into_path())
             .collect()
     }
 
@@ -89,19 +232,95 @@ impl Discovery {
     /// Find and load all skills in a directory.
     pub fn discover(
         root: &Path,
-        ignore_patterns: &[String],
+        config: &DiscoveryConfig,
     ) -> Vec<Result<Manifest, (PathBuf, ManifestError)>> {
-        let paths = Self::find_skills(root, ignore_patterns);
+        let paths = Self::find_skills(root, config);
         Self::load_skills(&paths)
     }
 }
 
+/// Check whether a path contains glob metacharacters.
+pub(crate) fn is_glob(path: &Path) -> bool {
+    path.to_string_lossy().contains(['*', '?', '[', '{'])
+}
+
+/// Expand a glob pattern into matching paths, sorted for stable output.
+fn expand_glob(pattern: &Path) -> Vec<PathBuf> {
+    // Walk from the longest literal prefix of the pattern
+    let base: PathBuf = pattern
+        .components()
+        .take_while(|c| !is_glob(Path::new(c.as_os_str())))
+        .collect();
+    let rest = pattern.strip_prefix(&base).unwrap_or(pattern);
+
+    if base.as_os_str().is_empty() {
+        glob_paths(Path::new("."), &rest.to_string_lossy())
+            .into_iter()
+            .map(|p| p.strip_prefix(".").map(Path::to_path_buf).unwrap_or(p))
+            .collect()
+    } else {
+        glob_paths(&base, &rest.to_string_lossy())
+    }
+}
+
+/// Find paths under `root` whose path relative to `root` matches `pattern`.
+///
+/// `*` does not cross `/`; use `**` to match at any depth. Results are
+/// sorted for stable output.
+pub(crate) fn glob_paths(root: &Path, pattern: &str) -> Vec<PathBuf> {
+    let Ok(glob) = GlobBuilder::new(pattern).literal_separator(true).build() else {
+        return Vec::new();
+    };
+    let matcher = glob.compile_matcher();
+    let depth = if pattern.contains("**") {
+        usize::MAX
+    } else {
+        Path::new(pattern).components().count()
+    };
+
+    let mut matches: Vec<PathBuf> = WalkDir::new(root)
+        .min_depth(1)
+        .max_depth(depth)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| {
+            e.path()
+                .strip_prefix(root)
+                .is_ok_and(|rel| matcher.is_match(rel))
+        })
+        .map(|e| e.into_path())
+        .collect();
+
+    matches.sort();
+    matches
+}
+
+/// Check whether a directory has any entries.
+fn has_entries(dir: &Path) -> bool {
+    std::fs::read_dir(dir).is_ok_and(|mut entries| entries.next().is_some())
+}
+
+/// Find the SKILL.md of the skill directory that contains `file`, if any.
+fn enclosing_skill(file: &Path) -> Option<PathBuf> {
+    file.ancestors()
+        .skip(1)
+        .map(|dir| dir.join("SKILL.md"))
+        .find(|skill_md| skill_md.is_file())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::fs;
     use tempfile::TempDir;
 
+    fn ignore(patterns: &[&str]) -> DiscoveryConfig {
+        DiscoveryConfig {
+            ignore: patterns.iter().map(|p| p.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
     #[test]
     fn test_find_single_skill() {
         let temp = TempDir::new().unwrap();
@@ -113,7 +332,7 @@ mod tests {
         )
         .unwrap();
 
-        let skills = Discovery::find_skills(&skill_dir, &[]);
+        let skills = Discovery::find_skills(&skill_dir, &ignore(&[]));
         assert_eq!(skills.len(), 1);
         assert!(skills[0].ends_with("SKILL.md"));
     }
@@ -132,7 +351,7 @@ mod tests {
             .unwrap();
         }
 
-        let skills = Discovery::find_skills(temp.path(), &[]);
+        let skills = Discovery::find_skills(temp.path(), &ignore(&[]));
         assert_eq!(skills.len(), 3);
     }
 
@@ -162,7 +381,7 @@ mod tests {
         )
         .unwrap();
 
-        let skills = Discovery::find_skills(temp.path(), &["target".to_string()]);
+        let skills = Discovery::find_skills(temp.path(), &ignore(&["target"]));
         assert_eq!(skills.len(), 2);
         assert!(skills
             .iter()
@@ -196,7 +415,7 @@ mod tests {
         }
 
         // Use glob pattern to ignore all build-* directories
-        let skills = Discovery::find_skills(temp.path(), &["build-*".to_string()]);
+        let skills = Discovery::find_skills(temp.path(), &ignore(&["build-*"]));
         assert_eq!(skills.len(), 1);
         assert!(skills[0].to_string_lossy().contains("skill-a"));
         assert!(!skills
@@ -233,12 +452,7 @@ mod tests {
         // Ignore multiple patterns
         let skills = Discovery::find_skills(
             temp.path(),
-            &[
-                "target".to_string(),
-                "node_modules".to_string(),
-                "dist".to_string(),
-                "build".to_string(),
-            ],
+            &ignore(&["target", "node_modules", "dist", "build"]),
         );
         assert_eq!(skills.len(), 1);
         assert!(skills[0].to_string_lossy().contains("my-skill"));
@@ -282,7 +496,7 @@ mod tests {
         .unwrap();
 
         // Ignore target/debug specifically (should still find target/release)
-        let skills = Discovery::find_skills(temp.path(), &["target/debug".to_string()]);
+        let skills = Discovery::find_skills(temp.path(), &ignore(&["target/debug"]));
         assert_eq!(skills.len(), 2);
         assert!(skills
             .iter()
@@ -293,7 +507,7 @@ mod tests {
         assert!(!skills.iter().any(|p| p.to_string_lossy().contains("debug")));
 
         // Ignore entire target directory with any subdirectory
-        let skills = Discovery::find_skills(temp.path(), &["target/*".to_string()]);
+        let skills = Discovery::find_skills(temp.path(), &ignore(&["target/*"]));
         assert_eq!(skills.len(), 1);
         assert!(skills[0].to_string_lossy().contains("skill-root"));
     }
@@ -327,13 +541,134 @@ mod tests {
         .unwrap();
 
         // Ignore foo/bar/baz specifically
-        let skills = Discovery::find_skills(temp.path(), &["foo/bar/baz".to_string()]);
+        let skills = Discovery::find_skills(temp.path(), &ignore(&["foo/bar/baz"]));
         assert_eq!(skills.len(), 1);
         assert!(skills[0].to_string_lossy().contains("root-skill"));
 
         // Use ** pattern to match baz at any depth
-        let skills = Discovery::find_skills(temp.path(), &["**/baz".to_string()]);
+        let skills = Discovery::find_skills(temp.path(), &ignore(&["**/baz"]));
         assert_eq!(skills.len(), 1);
         assert!(skills[0].to_string_lossy().contains("root-skill"));
     }
+
+    #[test]
+    fn test_find_skills_in_multiple_paths() {
+        let temp = TempDir::new().unwrap();
+
+        for name in ["skill-a", "skill-b", "other"] {
+            let skill_dir = temp.path().join(name);
+            fs::create_dir_all(skill_dir.join("scripts")).unwrap();
+            fs::write(
+                skill_dir.join("SKILL.md"),
+                format!("---\nname: {}\ndescription: test\n---\n", name),
+            )
+            .unwrap();
+            fs::write(skill_dir.join("scripts/run.sh"), "#!/bin/sh\n").unwrap();
+        }
+
+        let skills = Discovery::find_skills_in(
+            &[
+                temp.path().join("skill-*"),
+                temp.path().join("skill-a/SKILL.md"),
+                temp.path().join("other/scripts/run.sh"),
+            ],
+            &ignore(&[]),
+        );
+        assert_eq!(skills.len(), 3);
+        assert!(skills[0].ends_with("skill-a/SKILL.md"));
+        assert!(skills[1].ends_with("skill-b/SKILL.md"));
+        assert!(skills[2].ends_with("other/SKILL.md"));
+    }
+
+    #[test]
+    fn test_find_skills_max_depth() {
+        let temp = TempDir::new().unwrap();
+        let deep = temp.path().join("a/b/c/deep-skill");
+        fs::create_dir_all(&deep).unwrap();
+        fs::write(
+            deep.join("SKILL.md"),
+            "---\nname: deep-skill\ndescription: test\n---\n",
+        )
+        .unwrap();
+
+        let mut config = ignore(&[]);
+        assert_eq!(Discovery::find_skills(temp.path(), &config).len(), 1);
+
+        config.max_depth = 3;
+        assert!(Discovery::find_skills(temp.path(), &config).is_empty());
+    }
+
+    #[test]
+    fn test_configured_roots() {
+        let temp = TempDir::new().unwrap();
+        for dir in [
+            "packages/a/skills/one",
+            "packages/b/skills/two",
+            "tools/skills/three",
+        ] {
+            let dir = temp.path().join(dir);
+            fs::create_dir_all(&dir).unwrap();
+            let name = dir.file_name().unwrap().to_str().unwrap().to_string();
+            fs::write(
+                dir.join("SKILL.md"),
+                format!("---\nname: {}\ndescription: test\n---\n", name),
+            )
+            .unwrap();
+        }
+
+        let config = DiscoveryConfig {
+            roots: vec![
+                "packages/*/skills".into(),
+                "tools/skills".into(),
+                "missing".into(),
+            ],
+            ..Default::default()
+        };
+        let roots = Discovery::configured_roots(temp.path(), &config);
+        assert_eq!(
+            roots,
+            vec![
+                temp.path().join("packages/a/skills"),
+                temp.path().join("packages/b/skills"),
+                temp.path().join("tools/skills"),
+            ]
+        );
+
+        // Explicit paths win over configured roots
+        let explicit = vec![PathBuf::from("tools")];
+        assert_eq!(Discovery::roots(&explicit, &config), explicit);
+
+        // Overlapping roots list each skill once
+        let mut overlapping = roots.clone();
+        overlapping.push(temp.path().join("packages"));
+        let by_root = Discovery::find_skills_by_root(&overlapping, &config);
+        let counts: Vec<usize> = by_root.iter().map(|(_, skills)| skills.len()).collect();
+        assert_eq!(counts, vec![1, 1, 1, 0]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_find_skills_symlinks() {
+        let temp = TempDir::new().unwrap();
+        let skills = temp.path().join("skills");
+        let skill_dir = skills.join("my-skill");
+        fs::create_dir_all(&skill_dir).unwrap();
+        fs::write(
+            skill_dir.join("SKILL.md"),
+            "---\nname: my-skill\ndescription: test\n---\n",
+        )
+        .unwrap();
+
+        // A loop back to the root and a second link to the same skill
+        std::os::unix::fs::symlink(temp.path(), skills.join("loop")).unwrap();
+        std::os::unix::fs::symlink(&skill_dir, temp.path().join("alias")).unwrap();
+
+        let mut config = ignore(&[]);
+        assert_eq!(Discovery::find_skills(temp.path(), &config).len(), 1);
+
+        config.follow_symlinks = false;
+        let found = Discovery::find_skills(temp.path(), &config);
+        assert_eq!(found.len(), 1);
+        assert!(found[0].ends_with("skills/my-skill/SKILL.md"));
+    }
 }
@@ -0,0 +1,389 @@
+//! Structured fixes for lint diagnostics.
+//!
+//! Rules describe a fix as a list of [`Edit`]s against the files on disk
+//! (see [`Rule::fix`](crate::skill::rules::Rule::fix)). [`plan`] resolves
+//! the edits of several fixes into the resulting file [`Change`]s, which
+//! can be previewed with [`Change::diff`] or written with [`apply`].
+//!
+//! Renames move the index entries of tracked files along with them, like
+//! `git mv`, so a fixed skill stays staged under its new path.
+
+use crate::journal::write_atomic;
+use crate::skill::formatter::minimal_diff;
+use crate::skill::validator::DiagnosticCode;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+/// A fix for the diagnostics a rule reported on one skill.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Fix {
+    /// The diagnostic code being fixed.
+    pub code: DiagnosticCode,
+    /// What the fix does (e.g. "Make 2 script(s) executable").
+    pub message: String,
+    /// Edits that make up the fix.
+    pub edits: Vec<Edit>,
+}
+
+/// A single change to the filesystem.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum Edit {
+    /// Replace a byte range of a file's current content.
+    Replace {
+        /// The file to edit.
+        path: PathBuf,
+        /// Byte range to replace; an empty range inserts.
+        range: Range<usize>,
+        /// The replacement text.
+        replacement: String,
+    },
+    /// Create a file that does not exist yet.
+    Create {
+        /// The file to create; parent directories are created as needed.
+        path: PathBuf,
+        /// The file content.
+        content: String,
+    },
+    /// Set the executable bits of a file.
+    SetExecutable {
+        /// The file to change.
+        path: PathBuf,
+    },
+    /// Rename a file or directory, after all other edits are applied.
+    Rename {
+        /// The file or directory to rename.
+        path: PathBuf,
+        /// The new path; the rename is dropped if it already exists.
+        to: PathBuf,
+    },
+}
+
+impl Edit {
+    /// The file the edit applies to.
+    pub fn path(&self) -> &Path {
+        match self {
+            Edit::Replace { path, .. }
+            | Edit::Create { path, .. }
+            | Edit::SetExecutable { path }
+            | Edit::Rename { path, .. } => path,
+        }
+    }
+}
+
+/// The combined effect of all edits to one file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    /// The file.
+    pub path: PathBuf,
+    /// Content before the edits, or `None` if the file is created.
+    pub before: Option<String>,
+    /// Content after the edits, or `None` if the content is unchanged.
+    pub after: Option<String>,
+    /// Whether the file is made executable.
+    pub executable: bool,
+    /// The path the file is renamed to, if any.
+    pub renamed_to: Option<PathBuf>,
+}
+
+impl Change {
+    /// Describe the change as a diff for previewing.
+    pub fn diff(&self) -> String {
+        let mut out = match (&self.before, &self.after) {
+            (None, Some(after)) => format!("new file ({} lines)\n", after.lines().count()),
+            (Some(before), Some(after)) => minimal_diff(before, after).unwrap_or_default(),
+            _ => String::new(),
+        };
+        if self.executable {
+            out.push_str("chmod +x\n");
+        }
+        if let Some(to) = &self.renamed_to {
+            out.push_str(&format!("rename to {}\n", to.display()));
+        }
+        out
+    }
+}
+
+/// Resolve the edits of `fixes` into one change per file.
+///
+/// Edits are applied against the current content of each file. A replace
+/// that overlaps an earlier one (in fix order) is dropped, as are creating
+/// a file and renaming onto a path that already exist.
+pub fn plan(fixes: &[Fix]) -> std::io::Result<Vec<Change>> {
+    let mut replaces: BTreeMap<&Path, Vec<(&Range<usize>, &str)>> = BTreeMap::new();
+    let mut changes: BTreeMap<&Path, Change> = BTreeMap::new();
+
+    for edit in fixes.iter().flat_map(|f| &f.edits) {
+        match edit {
+            Edit::Replace {
+                path,
+                range,
+                replacement,
+            } => replaces.entry(path).or_default().push((range, replacement)),
+            Edit::Create { path, content } => {
+                if path.exists()
+                    || changes
+                        .get(path.as_path())
+                        .is_some_and(|c| c.after.is_some())
+                {
+                    continue;
+                }
+                let change = changes.entry(path).or_insert_with(|| unchanged(path));
+                change.after = Some(content.clone());
+            }
+            Edit::SetExecutable { path } => {
+                changes
+                    .entry(path)
+                    .or_insert_with(|| unchanged(path))
+                    .executable = true;
+            }
+            Edit::Rename { path, to } => {
+                if to.exists() {
+                    continue;
+                }
+                changes
+                    .entry(path)
+                    .or_insert_with(|| unchanged(path))
+                    .renamed_to
+                    .get_or_insert_with(|| to.clone());
+            }
+        }
+    }
+
+    for (path, mut edits) in replaces {
+        let before = fs::read_to_string(path)?;
+
+        // Keep the first of any overlapping edits, then apply back to front
+        let mut kept: Vec<(&Range<usize>, &str)> = Vec::new();
+        for (range, replacement) in edits.drain(..) {
+            let overlaps = kept
+                .iter()
+                .any(|(r, _)| range.start < r.end && r.start < range.end);
+            let valid = range.start <= range.end
+                && range.end <= before.len()
+                && before.is_char_boundary(range.start)
+                && before.is_char_boundary(range.end);
+            if !overlaps && valid {
+                kept.push((range, replacement));
+            }
+        }
+        kept.sort_by_key(|(r, _)| std::cmp::Reverse(r.start));
+
+        let mut after = before.clone();
+        for (range, replacement) in kept {
+            after.replace_range(range.clone(), replacement);
+        }
+
+        let change = changes.entry(path).or_insert_with(|| unchanged(path));
+        if after != before {
+            change.before = Some(before);
+            change.after = Some(after);
+        }
+    }
+
+    Ok(changes
+        .into_values()
+        .filter(|c| c.after.is_some() || c.executable || c.renamed_to.is_some())
+        .collect())
+}
+
+/// A change to `path` that does nothing yet.
+fn unchanged(path: &Path) -> Change {
+    Change {
+        path: path.to_path_buf(),
+        before: None,
+        after: None,
+        executable: false,
+        renamed_to: None,
+    }
+}
+
+/// Write planned changes to disk.
+///
+/// Renames happen last, so the other changes can refer to the old paths.
+pub fn apply(changes: &[Change]) -> std::io::Result<()> {
+    for change in changes {
+        if let Some(after) = &change.after {
+            if let Some(parent) = change.path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            write_atomic(&change.path, after)?;
+        }
+
+        #[cfg(unix)]
+        if change.executable {
+            use std::os::unix::fs::PermissionsExt;
+            let mut permissions = fs::metadata(&change.path)?.permissions();
+            permissions.set_mode(permissions.mode() | 0o111);
+            fs::set_permissions(&change.path, permissions)?;
+        }
+    }
+
+    for change in changes {
+        if let Some(to) = &change.renamed_to {
+            rename(&change.path, to)?;
+        }
+    }
+    Ok(())
+}
+
+/// Rename `from` to `to`, moving the index entries of any tracked files
+/// below it like `git mv`.
+fn rename(from: &Path, to: &Path) -> std::io::Result<()> {
+    let repo = git2::Repository::discover(from).ok();
+    let workdir = repo
+        .as_ref()
+        .and_then(|repo| repo.workdir())
+        .and_then(|workdir| workdir.canonicalize().ok());
+    let tracked = workdir.as_ref().and_then(|workdir| {
+        let from = from.canonicalize().ok()?;
+        from.strip_prefix(workdir).ok().map(Path::to_path_buf)
+    });
+
+    fs::rename(from, to)?;
+
+    if let (Some(repo), Some(workdir), Some(from)) = (repo, workdir, tracked) {
+        if let Ok(to) = to.canonicalize()?.strip_prefix(&workdir) {
+            move_index_entries(&repo, &from, to).map_err(std::io::Error::other)?;
+        }
+    }
+    Ok(())
+}
+
+/// Move the index entries at or below `from` to `to`, keeping their staged
+/// content. Both paths are relative to the work tree.
+fn move_index_entries(repo: &git2::Repository, from: &Path, to: &Path) -> Result<(), git2::Error> {
+    let from = git_path(from);
+    let to = git_path(to);
+    let mut index = repo.index()?;
+
+    let moved: Vec<git2::IndexEntry> = index
+        .iter()
+        .filter(|entry| {
+            entry.path == from.as_bytes()
+                || (entry.path.starts_with(from.as_bytes())
+                    && entry.path.get(from.len()) == Some(&b'/'))
+        })
+        .collect();
+    if moved.is_empty() {
+        return Ok(());
+    }
+
+    for mut entry in moved {
+        index.remove_path(Path::new(&*String::from_utf8_lossy(&entry.path)))?;
+        let mut path = to.clone().into_bytes();
+        path.extend_from_slice(&entry.path[from.len()..]);
+        // The low bits of the flags hold the path length
+        entry.flags = (entry.flags & !0xfff) | path.len().min(0xfff) as u16;
+        entry.path = path;
+        index.add(&entry)?;
+    }
+    index.write()
+}
+
+/// A relative path in the `/`-separated form git uses.
+fn git_path(path: &Path) -> String {
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_plan_and_apply() {
+        let temp = TempDir::new().unwrap();
+        let file = temp.path().join("a.txt");
+        let created = temp.path().join("new/b.txt");
+        fs::write(&file, "one two three\n").unwrap();
+
+        let replace = |range: Range<usize>, replacement: &str| Edit::Replace {
+            path: file.clone(),
+            range,
+            replacement: replacement.to_string(),
+        };
+        let fixes = vec![
+            Fix {
+                code: DiagnosticCode::W003,
+                message: "first".into(),
+                edits: vec![replace(0..3, "1"), replace(8..13, "3")],
+            },
+            Fix {
+                code: DiagnosticCode::W002,
+                message: "second".into(),
+                edits: vec![
+                    // Overlaps the first edit
+                    replace(2..6, "x"),
+                    Edit::Create {
+                        path: created.clone(),
+                        content: "b\n".into(),
+                    },
+                    Edit::SetExecutable {
+                        path: created.clone(),
+                    },
+                ],
+            },
+        ];
+
+        let changes = plan(&fixes).unwrap();
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].path, file);
+        assert_eq!(changes[0].after.as_deref(), Some("1 two 3\n"));
+        assert_eq!(changes[1].diff(), "new file (1 lines)\nchmod +x\n");
+
+        // Planning does not touch the disk
+        assert_eq!(fs::read_to_string(&file).unwrap(), "one two three\n");
+        assert!(!created.exists());
+
+        apply(&changes).unwrap();
+        assert_eq!(fs::read_to_string(&file).unwrap(), "1 two 3\n");
+        assert_eq!(fs::read_to_string(&created).unwrap(), "b\n");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&created).unwrap().permissions().mode();
+            assert_eq!(mode & 0o111, 0o111);
+        }
+    }
+
+    #[test]
+    fn test_rename_moves_index_entries() {
+        let temp = TempDir::new().unwrap();
+        let repo = git2::Repository::init(temp.path()).unwrap();
+        fs::create_dir_all(temp.path().join("skills/old")).unwrap();
+        fs::write(temp.path().join("skills/old/SKILL.md"), "staged\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("skills/old/SKILL.md")).unwrap();
+        index.write().unwrap();
+        // Unstaged edits stay unstaged, as with `git mv`
+        fs::write(temp.path().join("skills/old/SKILL.md"), "edited\n").unwrap();
+
+        let changes = plan(&[Fix {
+            code: DiagnosticCode::E003,
+            message: "rename".into(),
+            edits: vec![Edit::Rename {
+                path: temp.path().join("skills/old"),
+                to: temp.path().join("skills/new"),
+            }],
+        }])
+        .unwrap();
+        assert_eq!(changes.len(), 1);
+        apply(&changes).unwrap();
+
+        assert!(temp.path().join("skills/new/SKILL.md").exists());
+        let index = repo.index().unwrap();
+        assert!(index
+            .get_path(Path::new("skills/old/SKILL.md"), 0)
+            .is_none());
+        let entry = index.get_path(Path::new("skills/new/SKILL.md"), 0).unwrap();
+        let blob = repo.find_blob(entry.id).unwrap();
+        assert_eq!(blob.content(), b"staged\n");
+    }
+}
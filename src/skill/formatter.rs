@@ -2,22 +2,70 @@
 //!
 //! Provides consistent formatting for SKILL.md files including
 //! YAML frontmatter normalization and markdown table alignment.
+//!
+//! Frontmatter is reformatted from the raw YAML rather than re-serialized,
+//! so unknown keys, comments, and quoting are kept exactly as written.
 
-use crate::skill::Manifest;
+use crate::skill::frontmatter::fold;
+use crate::skill::rules::BOM;
+use crate::skill::{Frontmatter, Manifest};
 use comrak::nodes::NodeValue;
 use comrak::{parse_document, Arena, Options};
+use serde::Deserialize;
+
+/// Layout used when rewriting markdown tables.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TableStyle {
+    /// Pad cells so every column lines up.
+    #[default]
+    Padded,
+    /// A single space around each cell, without padding.
+    Compact,
+}
+
+/// Layout of descriptions written over several lines.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DescriptionStyle {
+    /// A `>-` folded block scalar, wrapped at [`FOLD_WIDTH`] columns.
+    #[default]
+    Folded,
+    /// A single line.
+    SingleLine,
+}
+
+/// Line width folded descriptions are wrapped at.
+pub const FOLD_WIDTH: usize = 80;
 
 /// Configuration for skill formatting.
 #[derive(Debug, Clone)]
 pub struct FormatterConfig {
     /// Whether to format markdown tables with aligned columns.
     pub format_tables: bool,
+    /// Layout of formatted tables.
+    pub table_style: TableStyle,
+    /// Minimum width of a table column, in characters.
+    pub table_min_width: usize,
+    /// Pad cells according to the column alignment in the separator row.
+    ///
+    /// When false, every cell is left-aligned regardless of the markers.
+    pub align_table_cells: bool,
+    /// Write LF line endings.
+    pub normalize_line_endings: bool,
+    /// Layout of multi-line descriptions.
+    pub description_style: DescriptionStyle,
 }
 
 impl Default for FormatterConfig {
     fn default() -> Self {
         Self {
             format_tables: true,
+            table_style: TableStyle::Padded,
+            table_min_width: 3,
+            align_table_cells: true,
+            normalize_line_endings: false,
+            description_style: DescriptionStyle::Folded,
         }
     }
 }
@@ -35,32 +83,218 @@ impl Formatter {
 
     /// Format a manifest, returning the formatted content.
     pub fn format(&self, manifest: &Manifest) -> Result<String, serde_yaml::Error> {
-        let yaml = manifest.frontmatter.to_yaml()?;
+        let raw = format_description(&manifest.frontmatter_raw, self.config.description_style);
+        let yaml = format_frontmatter(&raw);
 
         let body = if self.config.format_tables {
-            format_tables(&manifest.body)
+            format_tables(&manifest.body, &self.config)
         } else {
             manifest.body.clone()
         };
 
-        Ok(format!("---\n{}---\n\n{}", yaml, body))
+        let formatted = format!("---\n{}---\n\n{}", yaml, body);
+        if self.config.normalize_line_endings {
+            Ok(normalize_line_endings(&formatted))
+        } else {
+            Ok(formatted)
+        }
     }
 }
 
+/// Drop a leading byte order mark and rewrite CRLF line endings as LF.
+pub fn normalize_line_endings(text: &str) -> String {
+    text.trim_start_matches(BOM).replace("\r\n", "\n")
+}
+
 impl From<&crate::config::FmtConfig> for FormatterConfig {
     fn from(config: &crate::config::FmtConfig) -> Self {
         Self {
             format_tables: config.format_tables,
+            table_style: config.table_style,
+            table_min_width: config.table_min_width,
+            align_table_cells: config.align_table_cells,
+            normalize_line_endings: config.normalize_line_endings,
+            description_style: config.description_style,
         }
     }
 }
 
+/// Describe where two formatter outputs differ, or `None` if they match.
+///
+/// Lines shared at the start and end are dropped, leaving only the changed
+/// region with one line of context on each side.
+pub fn minimal_diff(first: &str, second: &str) -> Option<String> {
+    if first == second {
+        return None;
+    }
+
+    let a: Vec<&str> = first.lines().collect();
+    let b: Vec<&str> = second.lines().collect();
+    let prefix = a.iter().zip(&b).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..]
+        .iter()
+        .rev()
+        .zip(b[prefix..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+
+    let mut out = format!("@@ line {} @@\n", prefix + 1);
+    if prefix > 0 {
+        out.push_str(&format!(" {}\n", a[prefix - 1]));
+    }
+    for line in &a[prefix..a.len() - suffix] {
+        out.push_str(&format!("-{}\n", line));
+    }
+    for line in &b[prefix..b.len() - suffix] {
+        out.push_str(&format!("+{}\n", line));
+    }
+    if suffix > 0 {
+        out.push_str(&format!(" {}\n", a[a.len() - suffix]));
+    } else if a.len() == b.len() && prefix == a.len() {
+        // Same lines, so the difference is in trailing newlines
+        out.push_str("(trailing newline differs)\n");
+    }
+    Some(out)
+}
+
+/// Rewrite a `description` written over several lines in `style`.
+///
+/// Single-line descriptions are left as written. The text is folded the
+/// way [`Frontmatter::folded_description`] counts it, so block scalars of
+/// any kind (`>`, `|`, with chomping indicators) and multi-line quoted or
+/// plain scalars all format the same.
+fn format_description(raw: &str, style: DescriptionStyle) -> String {
+    let lines: Vec<&str> = raw.lines().collect();
+    let Some(start) = lines.iter().position(|line| {
+        !line.starts_with([' ', '\t'])
+            && line
+                .split_once(':')
+                .is_some_and(|(key, _)| key.trim_matches(['"', '\'']) == "description")
+    }) else {
+        return raw.to_string();
+    };
+
+    // Continuation lines are indented; trailing blank lines stay outside
+    let mut end = start + 1;
+    for (i, line) in lines.iter().enumerate().skip(start + 1) {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if !line.starts_with([' ', '\t']) {
+            break;
+        }
+        end = i + 1;
+    }
+    if end == start + 1 {
+        return raw.to_string();
+    }
+
+    let block = lines[start..end].join("\n");
+    let Some(text) = serde_yaml::from_str::<serde_yaml::Mapping>(&block)
+        .ok()
+        .and_then(|map| map.get("description")?.as_str().map(fold))
+    else {
+        return raw.to_string();
+    };
+
+    let replacement = match style {
+        DescriptionStyle::SingleLine => {
+            let scalar = serde_yaml::to_string(&text).unwrap_or_else(|_| text.clone());
+            format!("description: {}", scalar.trim_end())
+        }
+        DescriptionStyle::Folded => {
+            let mut out = String::from("description: >-");
+            let mut line = String::new();
+            for word in text.split(' ') {
+                if !line.is_empty() && 2 + line.len() + 1 + word.len() > FOLD_WIDTH {
+                    out.push_str(&format!("\n  {}", line));
+                    line.clear();
+                }
+                if !line.is_empty() {
+                    line.push(' ');
+                }
+                line.push_str(word);
+            }
+            if !line.is_empty() {
+                out.push_str(&format!("\n  {}", line));
+            }
+            out
+        }
+    };
+
+    let mut out: Vec<&str> = lines[..start].to_vec();
+    out.push(&replacement);
+    out.extend(&lines[end..]);
+    out.join("\n")
+}
+
+/// Reorder top-level frontmatter keys into canonical order.
+///
+/// Each top-level key is moved together with its nested lines and the
+/// comments directly above it. Known keys follow [`Frontmatter::KEY_ORDER`];
+/// other keys come after them in their original order. Trailing whitespace
+/// and blank lines between keys are removed; everything else is unchanged.
+fn format_frontmatter(raw: &str) -> String {
+    // (key, lines) for each top-level entry
+    let mut blocks: Vec<(Option<String>, Vec<&str>)> = Vec::new();
+    let mut comments: Vec<&str> = Vec::new();
+    let mut blanks = 0;
+
+    for line in raw.lines().map(str::trim_end) {
+        if line.is_empty() {
+            blanks += 1;
+            continue;
+        }
+
+        let continues = line.starts_with([' ', '\t', '-']) && comments.is_empty();
+        if continues {
+            if let Some((_, lines)) = blocks.last_mut() {
+                lines.extend(std::iter::repeat_n("", blanks));
+                lines.push(line);
+                blanks = 0;
+                continue;
+            }
+        }
+        blanks = 0;
+
+        if line.starts_with('#') {
+            comments.push(line);
+            continue;
+        }
+
+        let key = line
+            .split_once(':')
+            .map(|(key, _)| key.trim().trim_matches(['"', '\'']).to_string());
+        let mut lines = std::mem::take(&mut comments);
+        lines.push(line);
+        blocks.push((key, lines));
+    }
+
+    let rank = |key: &Option<String>| {
+        key.as_deref()
+            .and_then(|k| Frontmatter::KEY_ORDER.iter().position(|o| *o == k))
+            .unwrap_or(Frontmatter::KEY_ORDER.len())
+    };
+    blocks.sort_by_key(|(key, _)| rank(key));
+
+    let mut out = String::new();
+    for line in blocks.iter().flat_map(|(_, lines)| lines).chain(&comments) {
+        out.push_str(line);
+        out.push('\n');
+    }
+    out
+}
+
 /// Format all tables in a markdown string with aligned columns.
-fn format_tables(markdown: &str) -> String {
+fn format_tables(markdown: &str, config: &FormatterConfig) -> String {
     let arena = Arena::new();
 
+    // Footnotes, task lists, and HTML blocks must be recognized so they end
+    // a table instead of being read as extra rows
     let mut options = Options::default();
     options.extension.table = true;
+    options.extension.footnotes = true;
+    options.extension.tasklist = true;
 
     let root = parse_document(&arena, markdown, &options);
 
@@ -70,14 +304,16 @@ fn format_tables(markdown: &str) -> String {
     // Collect table line ranges and their formatted replacements
     let mut replacements: Vec<(usize, usize, String)> = Vec::new();
 
-    for node in root.descendants() {
+    // Only top-level tables: nested ones (in lists, quotes, or footnotes)
+    // have line prefixes that replacing whole lines would drop
+    for node in root.children() {
         let node_data = node.data.borrow();
         if let NodeValue::Table(node_table) = &node_data.value {
             let start_line = node_data.sourcepos.start.line;
             let end_line = node_data.sourcepos.end.line;
 
-            let table = extract_table(node, &node_table.alignments);
-            let formatted = table.format();
+            let table = extract_table(node, &node_table.alignments, &lines);
+            let formatted = table.format(config);
 
             // Lines are 1-indexed in sourcepos
             replacements.push((start_line, end_line, formatted));
@@ -131,13 +367,14 @@ fn format_tables(markdown: &str) -> String {
 fn extract_table<'a>(
     table_node: &'a comrak::arena_tree::Node<'a, std::cell::RefCell<comrak::nodes::Ast>>,
     alignments: &[comrak::nodes::TableAlignment],
+    lines: &[&str],
 ) -> Table {
     let mut rows: Vec<Vec<String>> = Vec::new();
 
     for child in table_node.children() {
         let child_data = child.data.borrow();
         if let NodeValue::TableRow(_) = &child_data.value {
-            let row = extract_row(child);
+            let row = extract_row(child, lines);
             rows.push(row);
         }
     }
@@ -150,13 +387,14 @@ fn extract_table<'a>(
 
 fn extract_row<'a>(
     row_node: &'a comrak::arena_tree::Node<'a, std::cell::RefCell<comrak::nodes::Ast>>,
+    lines: &[&str],
 ) -> Vec<String> {
     let mut cells: Vec<String> = Vec::new();
 
     for cell in row_node.children() {
         let cell_data = cell.data.borrow();
         if let NodeValue::TableCell = &cell_data.value {
-            let content = extract_cell_content(cell);
+            let content = cell_source(cell, lines).unwrap_or_else(|| extract_cell_content(cell));
             cells.push(content);
         }
     }
@@ -164,6 +402,24 @@ fn extract_row<'a>(
     cells
 }
 
+/// The cell's markdown exactly as written, so inline HTML, links, emphasis,
+/// footnote references, and escapes survive formatting.
+fn cell_source<'a>(
+    cell_node: &'a comrak::arena_tree::Node<'a, std::cell::RefCell<comrak::nodes::Ast>>,
+    lines: &[&str],
+) -> Option<String> {
+    // Cells padded in by the parser for short rows have no content
+    cell_node.first_child()?;
+
+    let pos = cell_node.data.borrow().sourcepos;
+    if pos.start.line != pos.end.line {
+        return None;
+    }
+    let line = lines.get(pos.start.line.checked_sub(1)?)?;
+    let text = line.get(pos.start.column.checked_sub(1)?..pos.end.column)?;
+    Some(text.trim().to_string())
+}
+
 fn extract_cell_content<'a>(
     cell_node: &'a comrak::arena_tree::Node<'a, std::cell::RefCell<comrak::nodes::Ast>>,
 ) -> String {
@@ -172,12 +428,14 @@ fn extract_cell_content<'a>(
     for child in cell_node.descendants() {
         let child_data = child.data.borrow();
         match &child_data.value {
+            // Pipes were unescaped by the parser; escape them again so
+            // they don't split the cell
             NodeValue::Text(text) => {
-                content.push_str(text);
+                content.push_str(&text.replace('|', "\\|"));
             }
             NodeValue::Code(code) => {
                 content.push('`');
-                content.push_str(&code.literal);
+                content.push_str(&code.literal.replace('|', "\\|"));
                 content.push('`');
             }
             NodeValue::SoftBreak => {
@@ -197,7 +455,7 @@ struct Table {
 }
 
 impl Table {
-    fn format(&self) -> String {
+    fn format(&self, config: &FormatterConfig) -> String {
         if self.rows.is_empty() {
             return String::new();
         }
@@ -207,111 +465,97 @@ impl Table {
             return String::new();
         }
 
-        // Calculate column widths
-        let mut widths: Vec<usize> = vec![3; col_count]; // minimum width of 3 for separator
-        for row in &self.rows {
-            for (i, cell) in row.iter().enumerate() {
-                if i < widths.len() {
-                    widths[i] = widths[i].max(cell.len());
+        // Separators need at least three dashes
+        let min_width = config.table_min_width.max(3);
+        let mut widths: Vec<usize> = vec![min_width; col_count];
+        if config.table_style == TableStyle::Padded {
+            for row in &self.rows {
+                for (i, cell) in row.iter().enumerate() {
+                    if i < widths.len() {
+                        widths[i] = widths[i].max(cell.chars().count());
+                    }
                 }
             }
         }
 
-        let mut result = String::new();
-
-        // Format header row
-        if let Some(header) = self.rows.first() {
-            result.push_str(&self.format_row(header, &widths));
-            result.push('\n');
-
-            // Format separator row
-            result.push('|');
-            for (i, width) in widths.iter().enumerate() {
-                let alignment = self
-                    .alignments
-                    .get(i)
-                    .copied()
-                    .unwrap_or(comrak::nodes::TableAlignment::None);
-                result.push_str(&self.format_separator(*width, alignment));
-                result.push('|');
+        let mut lines = Vec::with_capacity(self.rows.len() + 1);
+        for (i, row) in self.rows.iter().enumerate() {
+            lines.push(self.format_row(row, &widths, config));
+            if i == 0 {
+                lines.push(self.format_separator(&widths, config.table_style));
             }
-            result.push('\n');
         }
 
-        // Format data rows
-        for row in self.rows.iter().skip(1) {
-            result.push_str(&self.format_row(row, &widths));
-            result.push('\n');
-        }
-
-        // Remove trailing newline to match original behavior
-        if result.ends_with('\n') {
-            result.pop();
-        }
+        lines.join("\n")
+    }
 
-        result
+    fn alignment(&self, column: usize) -> comrak::nodes::TableAlignment {
+        self.alignments
+            .get(column)
+            .copied()
+            .unwrap_or(comrak::nodes::TableAlignment::None)
     }
 
-    fn format_row(&self, row: &[String], widths: &[usize]) -> String {
+    fn format_row(&self, row: &[String], widths: &[usize], config: &FormatterConfig) -> String {
         let mut result = String::from("|");
         for (i, width) in widths.iter().enumerate() {
             let cell = row.get(i).map(|s| s.as_str()).unwrap_or("");
-            let alignment = self
-                .alignments
-                .get(i)
-                .copied()
-                .unwrap_or(comrak::nodes::TableAlignment::None);
             result.push(' ');
-            result.push_str(&self.pad_cell(cell, *width, alignment));
+            match config.table_style {
+                TableStyle::Padded if config.align_table_cells => {
+                    result.push_str(&pad_cell(cell, *width, self.alignment(i)));
+                }
+                TableStyle::Padded => {
+                    result.push_str(&pad_cell(cell, *width, comrak::nodes::TableAlignment::None));
+                }
+                TableStyle::Compact => result.push_str(cell),
+            }
             result.push_str(" |");
         }
         result
     }
 
-    fn pad_cell(
-        &self,
-        content: &str,
-        width: usize,
-        alignment: comrak::nodes::TableAlignment,
-    ) -> String {
+    fn format_separator(&self, widths: &[usize], style: TableStyle) -> String {
         use comrak::nodes::TableAlignment;
 
-        let padding = width.saturating_sub(content.len());
-        match alignment {
-            TableAlignment::Right => format!("{:>width$}", content, width = width),
-            TableAlignment::Center => {
-                let left_pad = padding / 2;
-                let right_pad = padding - left_pad;
-                format!(
-                    "{}{}{}",
-                    " ".repeat(left_pad),
-                    content,
-                    " ".repeat(right_pad)
-                )
-            }
-            TableAlignment::Left | TableAlignment::None => {
-                format!("{:<width$}", content, width = width)
-            }
+        let mut result = String::from("|");
+        for (i, width) in widths.iter().enumerate() {
+            // Padded separators span the cell and its surrounding spaces
+            let (total, (open, close)) = match style {
+                TableStyle::Padded => (width + 2, ("", "")),
+                TableStyle::Compact => (*width, (" ", " ")),
+            };
+            let dashes = match self.alignment(i) {
+                TableAlignment::Left => format!(":{}", "-".repeat(total - 1)),
+                TableAlignment::Right => format!("{}:", "-".repeat(total - 1)),
+                TableAlignment::Center => format!(":{}:", "-".repeat(total.saturating_sub(2))),
+                TableAlignment::None => "-".repeat(total),
+            };
+            result.push_str(open);
+            result.push_str(&dashes);
+            result.push_str(close);
+            result.push('|');
         }
+        result
     }
+}
 
-    fn format_separator(&self, width: usize, alignment: comrak::nodes::TableAlignment) -> String {
-        use comrak::nodes::TableAlignment;
+fn pad_cell(content: &str, width: usize, alignment: comrak::nodes::TableAlignment) -> String {
+    use comrak::nodes::TableAlignment;
 
-        // Separator needs to match cell width + 2 spaces
-        let total_width = width + 2;
-        match alignment {
-            TableAlignment::Left => format!(":{}", "-".repeat(total_width - 1)),
-            TableAlignment::Right => format!("{}:", "-".repeat(total_width - 1)),
-            TableAlignment::Center => format!(":{}:", "-".repeat(total_width.saturating_sub(2))),
-            TableAlignment::None => "-".repeat(total_width),
-        }
-    }
+    let padding = width.saturating_sub(content.chars().count());
+    let (left, right) = match alignment {
+        TableAlignment::Right => (padding, 0),
+        TableAlignment::Center => (padding / 2, padding - padding / 2),
+        TableAlignment::Left | TableAlignment::None => (0, padding),
+    };
+    format!("{}{}{}", " ".repeat(left), content, " ".repeat(right))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::path::PathBuf;
 
     #[test]
     fn test_format_simple_table() {
@@ -325,7 +569,7 @@ mod tests {
 | foo    | A short one          |
 | barbaz | A longer description |"#;
 
-        assert_eq!(format_tables(input), expected);
+        assert_eq!(format_tables(input, &FormatterConfig::default()), expected);
     }
 
     #[test]
@@ -335,12 +579,50 @@ mod tests {
 | a | b | c |
 | longer | text | here |"#;
 
-        let output = format_tables(input);
+        let output = format_tables(input, &FormatterConfig::default());
         // Check that alignment markers are preserved
         assert!(output.contains(":---"));
         assert!(output.contains("---:"));
     }
 
+    #[test]
+    fn test_table_options() {
+        let input = "| Name | Pattern |\n|:---|---:|\n| pipe | `a \\| b` |\n| or | x \\| y |";
+
+        let padded = format_tables(input, &FormatterConfig::default());
+        assert_eq!(
+            padded,
+            "| Name |  Pattern |\n|:-----|---------:|\n| pipe | `a \\| b` |\n| or   |   x \\| y |"
+        );
+
+        let config = FormatterConfig {
+            table_min_width: 6,
+            align_table_cells: false,
+            ..FormatterConfig::default()
+        };
+        assert!(format_tables(input, &config).contains("| or     | x \\| y   |"));
+
+        let config = FormatterConfig {
+            table_style: TableStyle::Compact,
+            ..FormatterConfig::default()
+        };
+        assert_eq!(
+            format_tables(input, &config),
+            "| Name | Pattern |\n| :-- | --: |\n| pipe | `a \\| b` |\n| or | x \\| y |"
+        );
+    }
+
+    #[test]
+    fn test_table_preserves_inline_markdown_and_neighbors() {
+        let input = "| Item | Notes |\n|---|---|\n| **bold** [^1] | <br> [link](https://example.com) |\n[^1]: A footnote | with a pipe\n\n- [x] done\n- [ ] todo\n\n<div>\n| raw | html |\n</div>\n\n> | quoted | table |\n> |---|---|\n> | a | b |\n";
+
+        let output = format_tables(input, &FormatterConfig::default());
+        assert_eq!(
+            output,
+            "| Item          | Notes                            |\n|---------------|----------------------------------|\n| **bold** [^1] | <br> [link](https://example.com) |\n[^1]: A footnote | with a pipe\n\n- [x] done\n- [ ] todo\n\n<div>\n| raw | html |\n</div>\n\n> | quoted | table |\n> |---|---|\n> | a | b |\n"
+        );
+    }
+
     #[test]
     fn test_preserves_text_around_table() {
         let input = r#"# Header
@@ -353,16 +635,130 @@ Some text before.
 
 Some text after."#;
 
-        let output = format_tables(input);
+        let output = format_tables(input, &FormatterConfig::default());
         assert!(output.contains("# Header"));
         assert!(output.contains("Some text before."));
         assert!(output.contains("Some text after."));
     }
 
+    #[test]
+    fn test_preserves_provenance_header() {
+        let header = crate::skill::provenance::render_header("owner/repo", Some("1a2b3c4"), 0);
+        let content = format!(
+            "---\nname: my-skill\ndescription: Test\n---\n\n{}\n\n# My Skill\n\n| A | B |\n|---|---|\n| 1 | 2 |\n",
+            header
+        );
+        let manifest =
+            Manifest::parse_content(std::path::PathBuf::from("my-skill/SKILL.md"), &content)
+                .unwrap();
+
+        let formatted = Formatter::new(FormatterConfig::default())
+            .format(&manifest)
+            .unwrap();
+        assert!(formatted.contains(&format!("---\n\n{}\n\n# My Skill", header)));
+    }
+
+    #[test]
+    fn test_preserves_extended_fields() {
+        let content = "---\nname: my-skill\ndescription: Test\ncontext: fork\nagent: Explore\nmodel: sonnet\nhooks:\n  PreToolUse:\n  - matcher: Bash\n    hooks:\n    - type: command\n      command: ./scripts/check.sh\nuser-invocable: false\n---\n\n# My Skill\n";
+        let manifest =
+            Manifest::parse_content(std::path::PathBuf::from("my-skill/SKILL.md"), content)
+                .unwrap();
+
+        let formatted = Formatter::new(FormatterConfig::default())
+            .format(&manifest)
+            .unwrap();
+        assert_eq!(formatted, content);
+    }
+
+    #[test]
+    fn test_format_frontmatter_preserves_unknown_keys_and_comments() {
+        let raw = "# Owned by the platform team\ndescription: Test  \nx-team: platform\nname: my-skill\nmetadata:\n  # internal\n  owner: me\n\n  tier: \"1\"\n# trailing note";
+        let formatted = format_frontmatter(raw);
+        assert_eq!(
+            formatted,
+            "name: my-skill\n# Owned by the platform team\ndescription: Test\nmetadata:\n  # internal\n  owner: me\n\n  tier: \"1\"\nx-team: platform\n# trailing note\n"
+        );
+        assert_eq!(format_frontmatter(&formatted), formatted);
+    }
+
+    #[test]
+    fn test_format_description() {
+        let long = "Review pull requests for style, correctness, and missing tests. Use when asked to review a diff or a branch.";
+        let folded = "description: >-\n  Review pull requests for style, correctness, and missing tests. Use when asked\n  to review a diff or a branch.";
+        for raw in [
+            "name: a\ndescription: |\n  Review pull requests for style, correctness,\n  and missing tests.\n\n  Use when asked to review a diff or a branch.\nlicense: MIT".to_string(),
+            format!("name: a\ndescription: >\n  {}\nlicense: MIT", long),
+            "name: a\ndescription: \"Review pull requests for style, correctness,\n  and missing tests. Use when asked to review a diff or a branch.\"\nlicense: MIT".to_string(),
+        ] {
+            let formatted = format_description(&raw, DescriptionStyle::Folded);
+            assert_eq!(formatted, format!("name: a\n{}\nlicense: MIT", folded));
+            assert_eq!(format_description(&formatted, DescriptionStyle::Folded), formatted);
+
+            let single = format_description(&raw, DescriptionStyle::SingleLine);
+            assert_eq!(single, format!("name: a\ndescription: {}\nlicense: MIT", long));
+        }
+
+        // Single-line descriptions and nested keys are left alone
+        let raw = format!(
+            "name: a\ndescription: {}\nmetadata:\n  description: |\n    x\n    y",
+            long
+        );
+        assert_eq!(format_description(&raw, DescriptionStyle::Folded), raw);
+
+        let quoted = format_description(
+            "description: >\n  Use for: parsing\n  #tags",
+            DescriptionStyle::SingleLine,
+        );
+        let parsed: serde_yaml::Mapping = serde_yaml::from_str(&quoted).unwrap();
+        assert_eq!(
+            parsed["description"].as_str(),
+            Some("Use for: parsing #tags")
+        );
+    }
+
+    #[test]
+    fn test_normalize_line_endings() {
+        let content = "\u{feff}---\r\nname: my-skill\r\ndescription: Test\r\n---\r\n\r\n# My Skill\r\n\r\nText\n";
+        let manifest = Manifest::parse_content(PathBuf::from("SKILL.md"), content).unwrap();
+        let config = FormatterConfig {
+            normalize_line_endings: true,
+            ..FormatterConfig::default()
+        };
+
+        let formatted = Formatter::new(config).format(&manifest).unwrap();
+        assert_eq!(
+            formatted,
+            "---\nname: my-skill\ndescription: Test\n---\n\n# My Skill\n\nText\n"
+        );
+    }
+
+    #[test]
+    fn test_minimal_diff() {
+        assert_eq!(minimal_diff("a\nb\n", "a\nb\n"), None);
+        assert_eq!(
+            minimal_diff("a\nb\nc\nd\ne\n", "a\nb\nX\nd\ne\n").unwrap(),
+            "@@ line 3 @@\n b\n-c\n+X\n d\n"
+        );
+    }
+
+    #[test]
+    fn test_format_is_idempotent() {
+        let formatter = Formatter::new(FormatterConfig::default());
+        let content = "---\n# note\ndescription: Test\nx-extra: 1\nname: my-skill\n---\n\n| a | bb |\n|---|---|\n| ccc | d |\n";
+        let first = formatter
+            .format(&Manifest::parse_content(PathBuf::from("SKILL.md"), content).unwrap())
+            .unwrap();
+        let second = formatter
+            .format(&Manifest::parse_content(PathBuf::from("SKILL.md"), &first).unwrap())
+            .unwrap();
+        assert_eq!(minimal_diff(&first, &second), None);
+    }
+
     #[test]
     fn test_no_table() {
         let input = "Just some text without a table.";
-        assert_eq!(format_tables(input), input);
+        assert_eq!(format_tables(input, &FormatterConfig::default()), input);
     }
 
     #[test]
@@ -371,21 +767,21 @@ Some text after."#;
 |---|---|
 | `foo` | Run foo |"#;
 
-        let output = format_tables(input);
+        let output = format_tables(input, &FormatterConfig::default());
         assert!(output.contains("`foo`"));
     }
 
     #[test]
     fn test_preserves_trailing_newline() {
         let input = "| A | B |\n|---|---|\n| 1 | 2 |\n";
-        let output = format_tables(input);
+        let output = format_tables(input, &FormatterConfig::default());
         assert!(output.ends_with('\n'), "Should preserve trailing newline");
     }
 
     #[test]
     fn test_no_trailing_newline_when_absent() {
         let input = "| A | B |\n|---|---|\n| 1 | 2 |";
-        let output = format_tables(input);
+        let output = format_tables(input, &FormatterConfig::default());
         assert!(!output.ends_with('\n'), "Should not add trailing newline");
     }
 }
@@ -1,7 +1,12 @@
 //! Skill frontmatter types.
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::BTreeMap;
+
+/// Fold line breaks and runs of whitespace in `text` into single spaces.
+pub fn fold(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
 
 /// YAML frontmatter from a SKILL.md file.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -12,6 +17,10 @@ pub struct Frontmatter {
     /// Skill description (required, 1-1024 chars).
     pub description: String,
 
+    /// Short summary used instead of the body when embedding in prompts.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+
     /// License identifier or file reference.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub license: Option<String>,
@@ -20,13 +29,100 @@ pub struct Frontmatter {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub compatibility: Option<String>,
 
+    /// Programs and environment variables needed at runtime; see
+    /// [`Requirements`](crate::skill::requirements::Requirements).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requires: Option<serde_yaml::Value>,
+
     /// Additional metadata key-value pairs.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub metadata: Option<HashMap<String, String>>,
+    pub metadata: Option<BTreeMap<String, String>>,
 
     /// Pre-approved tools (space-delimited).
     #[serde(rename = "allowed-tools", skip_serializing_if = "Option::is_none")]
     pub allowed_tools: Option<String>,
+
+    /// Tags for grouping skills by capability (also accepted as `keywords`).
+    #[serde(alias = "keywords", skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+
+    /// Example user requests the skill handles, to help agents match intents.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub examples: Option<Vec<String>>,
+
+    /// Whether the skill is deprecated.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+
+    /// Name of the skill that replaces this one.
+    #[serde(rename = "superseded-by", skip_serializing_if = "Option::is_none")]
+    pub superseded_by: Option<String>,
+
+    /// Execution context; `fork` runs the skill in a sub-agent (Claude Code).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context: Option<String>,
+
+    /// Sub-agent type used with `context: fork` (Claude Code).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agent: Option<String>,
+
+    /// Model to use while the skill is active (Claude Code).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+
+    /// Hooks scoped to the skill, keyed by event name (Claude Code).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hooks: Option<BTreeMap<String, Vec<HookMatcher>>>,
+
+    /// Whether users can invoke the skill directly as a slash command.
+    #[serde(rename = "user-invocable", skip_serializing_if = "Option::is_none")]
+    pub user_invocable: Option<bool>,
+
+    /// Prevent the model from invoking the skill on its own.
+    #[serde(
+        rename = "disable-model-invocation",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub disable_model_invocation: Option<bool>,
+
+    /// Hint for the skill's arguments when invoked as a slash command.
+    #[serde(rename = "argument-hint", skip_serializing_if = "Option::is_none")]
+    pub argument_hint: Option<String>,
+
+    /// Per-agent overrides of the other fields, keyed by agent name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agents: Option<BTreeMap<String, serde_yaml::Value>>,
+}
+
+/// Hooks that run for tool calls matching a pattern.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HookMatcher {
+    /// Tool name pattern (e.g. `Bash` or `Edit|Write`); all tools if unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub matcher: Option<String>,
+
+    /// Hooks to run.
+    pub hooks: Vec<Hook>,
+}
+
+/// A single hook action.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Hook {
+    /// Hook type (`command` or `prompt`).
+    #[serde(rename = "type")]
+    pub kind: String,
+
+    /// Shell command to run, for `command` hooks.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+
+    /// Prompt to evaluate, for `prompt` hooks.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<String>,
+
+    /// Timeout in seconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u64>,
 }
 
 impl Frontmatter {
@@ -34,12 +130,89 @@ impl Frontmatter {
     pub const KEY_ORDER: &'static [&'static str] = &[
         "name",
         "description",
+        "summary",
         "license",
         "compatibility",
+        "requires",
         "metadata",
         "allowed-tools",
+        "tags",
+        "examples",
+        "deprecated",
+        "superseded-by",
+        "context",
+        "agent",
+        "model",
+        "hooks",
+        "user-invocable",
+        "disable-model-invocation",
+        "argument-hint",
+        "agents",
     ];
 
+    /// The description with line breaks and runs of whitespace folded into
+    /// single spaces, as agents show it.
+    ///
+    /// Length rules count this, so a description measures the same whether
+    /// it is written on one line or as a `>` or `|` block.
+    pub fn folded_description(&self) -> String {
+        fold(&self.description)
+    }
+
+    /// Returns the skill's tags, or an empty slice if none are set.
+    pub fn tags(&self) -> &[String] {
+        self.tags.as_deref().unwrap_or_default()
+    }
+
+    /// Returns the skill's example requests, or an empty slice if none are
+    /// set.
+    pub fn examples(&self) -> &[String] {
+        self.examples.as_deref().unwrap_or_default()
+    }
+
+    /// The entries of `allowed-tools`, such as `Read` or `Bash(git log:*)`.
+    ///
+    /// Entries are separated by whitespace or commas outside parentheses,
+    /// so tool arguments may contain spaces.
+    pub fn allowed_tool_list(&self) -> Vec<String> {
+        let mut tools = Vec::new();
+        let mut current = String::new();
+        let mut depth = 0usize;
+        for c in self.allowed_tools.as_deref().unwrap_or_default().chars() {
+            match c {
+                '(' => depth += 1,
+                ')' => depth = depth.saturating_sub(1),
+                ',' | ' ' | '\t' | '\n' if depth == 0 => {
+                    if !current.is_empty() {
+                        tools.push(std::mem::take(&mut current));
+                    }
+                    continue;
+                }
+                _ => {}
+            }
+            current.push(c);
+        }
+        if !current.is_empty() {
+            tools.push(current);
+        }
+        tools
+    }
+
+    /// Returns true if the skill is marked `deprecated: true`.
+    pub fn is_deprecated(&self) -> bool {
+        self.deprecated.unwrap_or(false)
+    }
+
+    /// Returns true if the skill sets `context: fork`.
+    pub fn is_forked(&self) -> bool {
+        self.context.as_deref() == Some("fork")
+    }
+
+    /// Returns true if the skill defines any hooks.
+    pub fn has_hooks(&self) -> bool {
+        self.hooks.as_ref().is_some_and(|h| !h.is_empty())
+    }
+
     /// Serialize to YAML with canonical key ordering.
     pub fn to_yaml(&self) -> Result<String, serde_yaml::Error> {
         serde_yaml::to_string(self)
@@ -0,0 +1,151 @@
+//! Golden tests for lint rules.
+//!
+//! A case is a directory of skills with an `expected.txt` listing the
+//! diagnostics the linter should report for them, one per line:
+//!
+//! ```text
+//! E003 bad-name/SKILL.md:2: Name 'other' does not match directory 'bad-name'
+//! W002 scripts-demo/scripts/run.sh: Script is not executable
+//! ```
+//!
+//! Paths are relative to the case directory and lines are sorted. An
+//! optional `config.toml` in the case configures the linter the same way
+//! a project's `.skilorc.toml` would.
+
+use crate::config::Config;
+use crate::error::SkiloError;
+use crate::skill::{Diagnostic, Discovery, Manifest, Validator};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// File listing a case's expected diagnostics.
+pub const EXPECTED_FILE: &str = "expected.txt";
+
+/// File holding a case's lint configuration.
+pub const CONFIG_FILE: &str = "config.toml";
+
+/// The outcome of running one case.
+#[derive(Debug)]
+pub struct CaseResult {
+    /// The case directory.
+    pub dir: PathBuf,
+    /// Contents of `expected.txt`, or `None` if the case has none yet.
+    pub expected: Option<String>,
+    /// Diagnostics the linter reported, in `expected.txt` format.
+    pub actual: String,
+}
+
+impl CaseResult {
+    /// Returns true if the reported diagnostics match the expected ones.
+    pub fn passed(&self) -> bool {
+        self.expected.as_deref() == Some(self.actual.as_str())
+    }
+
+    /// Overwrite `expected.txt` with the reported diagnostics.
+    pub fn bless(&self) -> std::io::Result<()> {
+        fs::write(self.dir.join(EXPECTED_FILE), &self.actual)
+    }
+}
+
+/// The case directories under `root`.
+///
+/// `root` is itself the only case if it has an `expected.txt`; otherwise
+/// each of its subdirectories is a case.
+pub fn find_cases(root: &Path) -> std::io::Result<Vec<PathBuf>> {
+    if root.join(EXPECTED_FILE).exists() {
+        return Ok(vec![root.to_path_buf()]);
+    }
+
+    let mut cases: Vec<PathBuf> = fs::read_dir(root)?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect();
+    cases.sort();
+    Ok(cases)
+}
+
+/// Lint the skills in a case directory and compare against `expected.txt`.
+pub fn run_case(dir: &Path) -> Result<CaseResult, SkiloError> {
+    let config_path = dir.join(CONFIG_FILE);
+    let config: Config = if config_path.exists() {
+        toml::from_str(&fs::read_to_string(&config_path)?).map_err(|e| {
+            SkiloError::Config(format!("{}: {}", config_path.display(), e.message()))
+        })?
+    } else {
+        Config::default()
+    };
+
+    let validator = Validator::new(&config.lint);
+    let mut lines = Vec::new();
+    for path in Discovery::find_skills(dir, &config.discovery) {
+        match Manifest::parse(path.clone()) {
+            Ok(manifest) => {
+                let result = validator.validate(&manifest);
+                for diag in result.errors.iter().chain(&result.warnings) {
+                    lines.push(render(diag, dir));
+                }
+            }
+            Err(e) => match Diagnostic::for_parse_error(&path, &e) {
+                Some(diag) => lines.push(render(&diag, dir)),
+                None => lines.push(format!("parse {}: {}", relative(&path, dir), e)),
+            },
+        }
+    }
+    lines.sort();
+
+    let actual = lines.iter().map(|l| format!("{}\n", l)).collect();
+    let expected = fs::read_to_string(dir.join(EXPECTED_FILE)).ok();
+
+    Ok(CaseResult {
+        dir: dir.to_path_buf(),
+        expected,
+        actual,
+    })
+}
+
+/// Render a diagnostic as an `expected.txt` line.
+fn render(diag: &Diagnostic, dir: &Path) -> String {
+    let mut location = relative(Path::new(&diag.path), dir);
+    if let Some(line) = diag.line {
+        location.push_str(&format!(":{}", line));
+        if let Some(column) = diag.column {
+            location.push_str(&format!(":{}", column));
+        }
+    }
+    format!("{} {}: {}", diag.code, location, diag.message)
+}
+
+/// `path` relative to `dir`, with `/` separators.
+fn relative(path: &Path, dir: &Path) -> String {
+    path.strip_prefix(dir)
+        .unwrap_or(path)
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::skill::minimal_diff;
+
+    #[test]
+    fn test_rule_fixtures() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/rules");
+        let cases = find_cases(&root).unwrap();
+        assert!(!cases.is_empty());
+
+        for case in cases {
+            let result = run_case(&case).unwrap();
+            assert!(
+                result.passed(),
+                "{} (run `skilo selftest --bless` to update):\n{}",
+                case.display(),
+                minimal_diff(result.expected.as_deref().unwrap_or(""), &result.actual)
+                    .unwrap_or_default()
+            );
+        }
+    }
+}
@@ -1,10 +1,20 @@
 //! SKILL.md manifest parsing.
 
 use crate::skill::frontmatter::Frontmatter;
+use crate::skill::outline::Outline;
+use crate::skill::rules::BOM;
+use crate::skill::summary::summarize;
 use std::fmt;
+use std::io::Read;
 use std::path::PathBuf;
 use thiserror::Error;
 
+/// Largest SKILL.md file that is parsed, in bytes.
+///
+/// Anything bigger is almost certainly generated content; reading it would
+/// only make every later step slow.
+pub const MAX_MANIFEST_SIZE: u64 = 4 * 1024 * 1024;
+
 /// A parsed SKILL.md file.
 #[derive(Debug)]
 pub struct Manifest {
@@ -22,6 +32,9 @@ pub struct Manifest {
 
     /// Line number where body starts.
     pub body_start_line: usize,
+
+    /// Byte offset of the body in the file content.
+    pub body_offset: usize,
 }
 
 /// Errors that can occur when parsing a manifest.
@@ -39,6 +52,15 @@ pub enum ManifestError {
     #[error("Invalid YAML in frontmatter: {0}")]
     InvalidYaml(#[from] serde_yaml::Error),
 
+    /// The file is larger than [`MAX_MANIFEST_SIZE`].
+    #[error("SKILL.md is {size} bytes, over the {limit}-byte limit")]
+    TooLarge {
+        /// Size of the file in bytes (at least `limit + 1` if it grew while reading).
+        size: u64,
+        /// The size limit in bytes.
+        limit: u64,
+    },
+
     /// An I/O error occurred while reading the file.
     #[error("IO error reading {path}: {source}")]
     Io {
@@ -52,11 +74,35 @@ pub enum ManifestError {
 
 impl Manifest {
     /// Parse a SKILL.md file.
+    ///
+    /// Files over [`MAX_MANIFEST_SIZE`] are rejected without being read.
     pub fn parse(path: PathBuf) -> Result<Self, ManifestError> {
-        let content = std::fs::read_to_string(&path).map_err(|e| ManifestError::Io {
+        let io_error = |source| ManifestError::Io {
             path: path.clone(),
-            source: e,
-        })?;
+            source,
+        };
+        let file = std::fs::File::open(&path).map_err(io_error)?;
+        let size = file.metadata().map_err(io_error)?.len();
+        if size > MAX_MANIFEST_SIZE {
+            return Err(ManifestError::TooLarge {
+                size,
+                limit: MAX_MANIFEST_SIZE,
+            });
+        }
+
+        // The file may grow after the size check, so never read past the limit
+        let mut content = String::new();
+        let read = file
+            .take(MAX_MANIFEST_SIZE + 1)
+            .read_to_string(&mut content)
+            .map_err(io_error)? as u64;
+        if read > MAX_MANIFEST_SIZE {
+            return Err(ManifestError::TooLarge {
+                size: read,
+                limit: MAX_MANIFEST_SIZE,
+            });
+        }
+
         Self::parse_content(path, &content)
     }
 
@@ -65,17 +111,43 @@ impl Manifest {
         let (frontmatter_raw, body, body_start_line) = Self::split_content(content)?;
         let frontmatter: Frontmatter = serde_yaml::from_str(&frontmatter_raw)?;
 
+        // The body runs to the end of the file
+        let body_offset = content.len() - body.len();
+
         Ok(Self {
             path,
             frontmatter,
             frontmatter_raw,
             body,
             body_start_line,
+            body_offset,
         })
     }
 
+    /// Number of lines in the body, counted the way [`str::lines`] does.
+    pub fn body_line_count(&self) -> usize {
+        count_lines(&self.body)
+    }
+
+    /// Extract the outline of the body: headings and mentioned scripts and references.
+    pub fn outline(&self) -> Outline {
+        Outline::from_markdown(&self.body)
+    }
+
+    /// Summary of the body for prompt embedding.
+    ///
+    /// The frontmatter `summary` wins; otherwise the body is summarized with
+    /// [`summarize`].
+    pub fn summary(&self, lines_per_section: Option<usize>) -> String {
+        match &self.frontmatter.summary {
+            Some(summary) if !summary.trim().is_empty() => summary.trim().to_string(),
+            _ => summarize(&self.body, lines_per_section),
+        }
+    }
+
     fn split_content(content: &str) -> Result<(String, String, usize), ManifestError> {
-        let content = content.trim_start();
+        // A byte order mark is tolerated; the encoding rule reports it
+        let content = content.trim_start_matches(BOM).trim_start();
 
         if !content.starts_with("---") {
             return Err(ManifestError::MissingFrontmatter);
@@ -101,6 +173,13 @@ impl Manifest {
     }
 }
 
+/// Count lines without decoding: every newline ends a line, and trailing
+/// text without one is a line of its own.
+pub fn count_lines(text: &str) -> usize {
+    let newlines = text.bytes().filter(|&b| b == b'\n').count();
+    newlines + usize::from(!text.is_empty() && !text.ends_with('\n'))
+}
+
 impl fmt::Display for Manifest {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -148,4 +227,26 @@ Some content here.
         let result = Manifest::parse_content(PathBuf::from("test/SKILL.md"), content);
         assert!(matches!(result, Err(ManifestError::UnclosedFrontmatter)));
     }
+
+    #[test]
+    fn test_parse_too_large() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("SKILL.md");
+        let file = std::fs::File::create(&path).unwrap();
+        file.set_len(MAX_MANIFEST_SIZE + 1).unwrap();
+
+        let result = Manifest::parse(path);
+        assert!(matches!(
+            result,
+            Err(ManifestError::TooLarge { size, limit: MAX_MANIFEST_SIZE })
+                if size == MAX_MANIFEST_SIZE + 1
+        ));
+    }
+
+    #[test]
+    fn test_count_lines() {
+        for text in ["", "a", "a\n", "a\nb", "a\n\nb\n", "\n", "a\r\nb\r\n"] {
+            assert_eq!(count_lines(text), text.lines().count(), "{:?}", text);
+        }
+    }
 }
@@ -7,14 +7,25 @@
 //! - [`Validator`] - Validate skills against the specification
 
 pub mod discovery;
+pub mod fix;
 pub mod formatter;
 pub mod frontmatter;
+pub mod golden;
 pub mod manifest;
+pub mod outline;
+pub mod overrides;
+pub mod provenance;
+pub mod requirements;
 pub mod rules;
+pub mod split;
+pub mod summary;
 pub mod validator;
 
 pub use discovery::Discovery;
-pub use formatter::{Formatter, FormatterConfig};
+pub use formatter::{
+    minimal_diff, normalize_line_endings, DescriptionStyle, Formatter, FormatterConfig, TableStyle,
+};
 pub use frontmatter::Frontmatter;
 pub use manifest::Manifest;
+pub use outline::{Outline, OutlineHeading};
 pub use validator::{Diagnostic, DiagnosticCode, ValidationResult, Validator};
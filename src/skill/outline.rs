@@ -0,0 +1,138 @@
+//! Structured outline of a skill body.
+//!
+//! An outline lists a body's headings and the `scripts/` and `references/`
+//! files it mentions, so a reader can judge what a skill covers without
+//! loading the whole body.
+
+use comrak::nodes::{AstNode, NodeValue};
+use comrak::{parse_document, Arena, Options};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+
+/// Pattern for paths into a skill's `scripts/` or `references/` directory.
+static MENTION_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\b(scripts|references)/[\w./-]*\w").unwrap());
+
+/// Headings and file mentions extracted from a skill body.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Outline {
+    /// Headings in document order.
+    pub headings: Vec<OutlineHeading>,
+    /// Files under `scripts/` mentioned in the body, in order of first mention.
+    pub scripts: Vec<String>,
+    /// Files under `references/` mentioned in the body, in order of first mention.
+    pub references: Vec<String>,
+}
+
+/// A heading in an [`Outline`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OutlineHeading {
+    /// Heading level, 1 to 6.
+    pub level: u8,
+    /// Heading text without markup.
+    pub text: String,
+}
+
+impl Outline {
+    /// Extract the outline of a markdown body.
+    ///
+    /// Mentions are found in text, inline code, code blocks, and link targets.
+    pub fn from_markdown(markdown: &str) -> Self {
+        let arena = Arena::new();
+        let mut options = Options::default();
+        options.extension.table = true;
+        options.extension.strikethrough = true;
+        options.extension.tasklist = true;
+        options.extension.footnotes = true;
+
+        let root = parse_document(&arena, markdown, &options);
+        let mut outline = Outline::default();
+
+        for node in root.descendants() {
+            let value = node.data.borrow().value.clone();
+            match value {
+                NodeValue::Heading(heading) => outline.headings.push(OutlineHeading {
+                    level: heading.level,
+                    text: plain_text(node).trim().to_string(),
+                }),
+                NodeValue::Text(text) => outline.add_mentions(&text),
+                NodeValue::Code(code) => outline.add_mentions(&code.literal),
+                NodeValue::CodeBlock(code) => outline.add_mentions(&code.literal),
+                NodeValue::Link(link) => outline.add_mentions(&link.url),
+                _ => {}
+            }
+        }
+
+        outline
+    }
+
+    /// Returns true if the outline has no headings and no mentions.
+    pub fn is_empty(&self) -> bool {
+        self.headings.is_empty() && self.scripts.is_empty() && self.references.is_empty()
+    }
+
+    fn add_mentions(&mut self, text: &str) {
+        for m in MENTION_REGEX.captures_iter(text) {
+            let list = if &m[1] == "scripts" {
+                &mut self.scripts
+            } else {
+                &mut self.references
+            };
+            let path = m[0].to_string();
+            if !list.contains(&path) {
+                list.push(path);
+            }
+        }
+    }
+}
+
+/// Flatten the inline content of `node` to plain text.
+fn plain_text<'a>(node: &'a AstNode<'a>) -> String {
+    let mut out = String::new();
+    for child in node.children() {
+        match &child.data.borrow().value {
+            NodeValue::Text(text) => out.push_str(text),
+            NodeValue::Code(code) => out.push_str(&code.literal),
+            NodeValue::SoftBreak | NodeValue::LineBreak => out.push(' '),
+            _ => out.push_str(&plain_text(child)),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_outline_from_markdown() {
+        let body = "# My Skill\n\n\
+            Run `scripts/build.sh --release` first.\n\n\
+            ## Using *the* `api`\n\n\
+            See [the API](references/api.md) and references/api.md.\n\n\
+            ```bash\npython ./scripts/check.py\n```\n\n\
+            Not a mention: myscripts/x\n";
+
+        let outline = Outline::from_markdown(body);
+        assert_eq!(
+            outline.headings,
+            vec![
+                OutlineHeading {
+                    level: 1,
+                    text: "My Skill".into()
+                },
+                OutlineHeading {
+                    level: 2,
+                    text: "Using the api".into()
+                },
+            ]
+        );
+        assert_eq!(
+            outline.scripts,
+            vec!["scripts/build.sh", "scripts/check.py"]
+        );
+        assert_eq!(outline.references, vec!["references/api.md"]);
+        assert!(Outline::from_markdown("Just text.").is_empty());
+    }
+}
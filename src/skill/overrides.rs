@@ -0,0 +1,155 @@
+//! Per-agent frontmatter overrides.
+//!
+//! A skill can adjust its frontmatter for individual agents under `agents`,
+//! keyed by agent name:
+//!
+//! ```yaml
+//! allowed-tools: Read Grep
+//! agents:
+//!   claude:
+//!     allowed-tools: Read Grep Bash(git:*)
+//!     context: fork
+//!   cursor:
+//!     model: null
+//! ```
+//!
+//! When `add` installs the skill for an agent, that agent's fields replace
+//! the top-level ones (`null` removes a field) and the `agents` map is
+//! dropped. Installs not tied to an agent keep the file unchanged.
+
+use crate::agent::Agent;
+use crate::skill::frontmatter::Frontmatter;
+use crate::skill::provenance::body_offset;
+use serde_yaml::{Mapping, Value};
+
+/// Frontmatter key holding the overrides.
+pub const AGENTS_KEY: &str = "agents";
+
+/// Fields that cannot be overridden per agent.
+pub const FIXED_FIELDS: &[&str] = &["name", AGENTS_KEY];
+
+/// The agent an `agents` key refers to.
+///
+/// Both the CLI name (`opencode`) and the config name (`open-code`) are
+/// accepted.
+pub fn agent_for_key(key: &str) -> Option<Agent> {
+    Agent::all().iter().copied().find(|agent| {
+        agent.cli_name() == key
+            || serde_yaml::to_value(agent)
+                .ok()
+                .is_some_and(|name| name.as_str() == Some(key))
+    })
+}
+
+/// Apply `agent`'s overrides to a parsed frontmatter mapping and drop the
+/// `agents` map.
+///
+/// Returns false if the mapping has no `agents` map.
+pub fn apply(frontmatter: &mut Mapping, agent: Agent) -> bool {
+    let Some(Value::Mapping(agents)) = frontmatter.remove(AGENTS_KEY) else {
+        return false;
+    };
+
+    let overrides = agents.into_iter().find_map(|(key, value)| {
+        let key = key.as_str()?;
+        (agent_for_key(key) == Some(agent)).then_some(value)
+    });
+    if let Some(Value::Mapping(fields)) = overrides {
+        for (key, value) in fields {
+            if key.as_str().is_some_and(|k| FIXED_FIELDS.contains(&k)) {
+                continue;
+            }
+            if value.is_null() {
+                frontmatter.remove(&key);
+            } else {
+                frontmatter.insert(key, value);
+            }
+        }
+    }
+    true
+}
+
+/// Frontmatter as it would be installed for `agent`.
+pub fn resolve(frontmatter_raw: &str, agent: Agent) -> Result<Frontmatter, serde_yaml::Error> {
+    let mut mapping: Mapping = serde_yaml::from_str(frontmatter_raw)?;
+    apply(&mut mapping, agent);
+    serde_yaml::from_value(Value::Mapping(mapping))
+}
+
+/// Rewrite SKILL.md `content` for `agent`.
+///
+/// Content without an `agents` map, or whose frontmatter does not parse,
+/// is returned unchanged.
+pub fn apply_to_content(content: &str, agent: Agent) -> String {
+    let Some(body_start) = body_offset(content) else {
+        return content.to_string();
+    };
+    let (head, body) = content.split_at(body_start);
+    let Some(yaml) = head
+        .trim()
+        .strip_prefix("---")
+        .and_then(|rest| rest.strip_suffix("---"))
+    else {
+        return content.to_string();
+    };
+
+    let Ok(mut mapping) = serde_yaml::from_str::<Mapping>(yaml) else {
+        return content.to_string();
+    };
+    if !apply(&mut mapping, agent) {
+        return content.to_string();
+    }
+    match serde_yaml::to_string(&mapping) {
+        Ok(yaml) => format!("---\n{}---\n{}", yaml, body),
+        Err(_) => content.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SKILL: &str = "---
+name: demo
+description: Demo skill
+allowed-tools: Read Grep
+model: sonnet
+agents:
+  claude:
+    allowed-tools: Read Grep Bash
+    context: fork
+  open-code:
+    model: null
+---
+
+# Demo
+";
+
+    #[test]
+    fn test_apply_to_content() {
+        let claude = apply_to_content(SKILL, Agent::Claude);
+        assert!(claude.contains("allowed-tools: Read Grep Bash\n"));
+        assert!(claude.contains("context: fork\n"));
+        assert!(!claude.contains("agents:"));
+        assert!(claude.ends_with("---\n\n# Demo\n"));
+        assert!(claude.find("name:").unwrap() < claude.find("description:").unwrap());
+
+        let opencode = apply_to_content(SKILL, Agent::OpenCode);
+        assert!(!opencode.contains("model:"));
+        assert!(opencode.contains("allowed-tools: Read Grep\n"));
+
+        // Agents without overrides still get the map removed
+        assert!(!apply_to_content(SKILL, Agent::Cursor).contains("agents:"));
+
+        let plain = "---\nname: demo\ndescription: Demo\n---\n\nBody\n";
+        assert_eq!(apply_to_content(plain, Agent::Claude), plain);
+    }
+
+    #[test]
+    fn test_agent_for_key() {
+        assert_eq!(agent_for_key("opencode"), Some(Agent::OpenCode));
+        assert_eq!(agent_for_key("open-code"), Some(Agent::OpenCode));
+        assert_eq!(agent_for_key("claude"), Some(Agent::Claude));
+        assert_eq!(agent_for_key("cursr"), None);
+    }
+}
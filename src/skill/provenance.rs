@@ -0,0 +1,116 @@
+//! Provenance header for installed skills.
+//!
+//! When enabled, `add` writes a one-line HTML comment at the top of the
+//! installed SKILL.md body so anyone browsing an agent's skills directory
+//! can see where a skill came from:
+//!
+//! ```text
+//! <!-- installed-by: skilo, source: https://github.com/owner/repo.git, commit: 1a2b3c4, date: 2025-01-31 -->
+//! ```
+//!
+//! HTML comments are invisible when the markdown is rendered and are left
+//! untouched by `fmt`.
+
+/// Prefix identifying a provenance header line.
+pub const HEADER_PREFIX: &str = "<!-- installed-by: skilo";
+
+/// Render a provenance header line.
+pub fn render_header(source: &str, commit: Option<&str>, installed_at: u64) -> String {
+    let mut header = format!("{}, source: {}", HEADER_PREFIX, source);
+    if let Some(commit) = commit {
+        header.push_str(&format!(", commit: {}", &commit[..7.min(commit.len())]));
+    }
+    header.push_str(&format!(", date: {} -->", format_date(installed_at)));
+    header
+}
+
+/// Insert `header` at the top of the body of SKILL.md `content`.
+///
+/// An existing provenance header is replaced. Content without frontmatter
+/// is returned unchanged.
+pub fn apply_header(content: &str, header: &str) -> String {
+    let Some(body_start) = body_offset(content) else {
+        return content.to_string();
+    };
+
+    let (frontmatter, body) = content.split_at(body_start);
+    let mut body = body.trim_start();
+
+    if body.starts_with(HEADER_PREFIX) {
+        body = body
+            .find("-->")
+            .map(|end| body[end + 3..].trim_start())
+            .unwrap_or(body);
+    }
+
+    if body.is_empty() {
+        format!("{}\n{}\n", frontmatter.trim_end(), header)
+    } else {
+        format!("{}\n\n{}\n\n{}", frontmatter.trim_end(), header, body)
+    }
+}
+
+/// Byte offset just past the closing `---` line of the frontmatter.
+pub(crate) fn body_offset(content: &str) -> Option<usize> {
+    let start = content.len() - content.trim_start().len();
+    let rest = content[start..].strip_prefix("---")?;
+    let close = rest.find("\n---")?;
+    let after_close = start + 3 + close + 4;
+    let line_end = content[after_close..]
+        .find('\n')
+        .map(|i| after_close + i + 1)
+        .unwrap_or(content.len());
+    Some(line_end)
+}
+
+/// Format seconds since the Unix epoch as a UTC `YYYY-MM-DD` date.
+pub fn format_date(secs: u64) -> String {
+    // Civil-from-days conversion (Howard Hinnant's algorithm)
+    let days = (secs / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let doe = days.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_date() {
+        assert_eq!(format_date(0), "1970-01-01");
+        assert_eq!(format_date(951_782_400), "2000-02-29");
+        assert_eq!(format_date(1_738_281_600), "2025-01-31");
+    }
+
+    #[test]
+    fn test_apply_header_inserts_and_replaces() {
+        let content = "---\nname: my-skill\ndescription: Test\n---\n\n# My Skill\n";
+        let header = render_header("owner/repo", Some("1a2b3c4d5e"), 1_738_281_600);
+        assert_eq!(
+            header,
+            "<!-- installed-by: skilo, source: owner/repo, commit: 1a2b3c4, date: 2025-01-31 -->"
+        );
+
+        let once = apply_header(content, &header);
+        assert_eq!(
+            once,
+            format!(
+                "---\nname: my-skill\ndescription: Test\n---\n\n{}\n\n# My Skill\n",
+                header
+            )
+        );
+
+        let newer = render_header("owner/repo", None, 0);
+        let twice = apply_header(&once, &newer);
+        assert_eq!(twice.matches(HEADER_PREFIX).count(), 1);
+        assert!(twice.contains(&newer));
+        assert!(twice.ends_with("\n\n# My Skill\n"));
+    }
+}
@@ -0,0 +1,330 @@
+//! Runtime requirements declared under the `requires` frontmatter key.
+//!
+//! A skill lists the programs and environment variables its scripts need,
+//! optionally with a minimum program version:
+//!
+//! ```yaml
+//! requires:
+//!   bins:
+//!     - jq
+//!     - python3 >= 3.10
+//!   env:
+//!     - GITHUB_TOKEN
+//! ```
+//!
+//! The `requirements` lint rule validates the section (E015), and
+//! `skilo env-check` verifies the current machine satisfies it.
+
+use crate::agent::find_executable;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+use serde_yaml::{Mapping, Value};
+use std::cmp::Ordering;
+use std::path::Path;
+use std::process::Command;
+
+/// Frontmatter key holding the requirements.
+pub const REQUIRES_KEY: &str = "requires";
+
+/// Keys accepted under `requires`.
+const KNOWN_KEYS: &[&str] = &["bins", "env"];
+
+/// A program entry: a name, optionally followed by `>= <version>`.
+static BIN_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^([A-Za-z0-9][A-Za-z0-9._+-]*)\s*(?:>=\s*([0-9]+(?:\.[0-9]+)*))?$").unwrap()
+});
+
+/// An environment variable name.
+static ENV_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^[A-Za-z_][A-Za-z0-9_]*$").unwrap());
+
+/// The first version number in a program's `--version` output.
+static VERSION_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"[0-9]+(?:\.[0-9]+)+").unwrap());
+
+/// The parsed `requires` section of a skill.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Requirements {
+    /// Programs that must be on `PATH`.
+    pub bins: Vec<BinRequirement>,
+    /// Environment variables that must be set.
+    pub env: Vec<String>,
+}
+
+/// A program a skill needs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BinRequirement {
+    /// The program name, looked up on `PATH`.
+    pub name: String,
+    /// Minimum version, compared against the program's `--version` output.
+    pub min_version: Option<String>,
+}
+
+impl std::fmt::Display for BinRequirement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.min_version {
+            Some(version) => write!(f, "{} >= {}", self.name, version),
+            None => write!(f, "{}", self.name),
+        }
+    }
+}
+
+/// The result of checking one requirement on the current machine.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RequirementCheck {
+    /// `bin` or `env`.
+    pub kind: &'static str,
+    /// The program or variable name.
+    pub name: String,
+    /// The minimum version required, for programs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_version: Option<String>,
+    /// The installed version, for programs that report one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Whether the requirement is met.
+    pub ok: bool,
+    /// Why the requirement is not met.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub problem: Option<String>,
+}
+
+impl Requirements {
+    /// Parse the `requires` value, returning every schema problem found.
+    pub fn parse(value: &Value) -> Result<Self, Vec<String>> {
+        let mut requirements = Self::default();
+        let mut problems = Vec::new();
+
+        let fields = match value {
+            Value::Mapping(fields) => fields,
+            Value::Null => return Ok(requirements),
+            _ => return Err(vec!["requires must be a mapping with bins and env".into()]),
+        };
+
+        for (key, entries) in fields {
+            let key = key.as_str().unwrap_or_default();
+            if !KNOWN_KEYS.contains(&key) {
+                problems.push(format!(
+                    "Unknown key '{}' in requires (expected {})",
+                    key,
+                    KNOWN_KEYS.join(", ")
+                ));
+                continue;
+            }
+
+            let entries = match entries {
+                Value::Sequence(entries) => entries,
+                Value::Null => continue,
+                _ => {
+                    problems.push(format!("requires.{} must be a list", key));
+                    continue;
+                }
+            };
+            for entry in entries {
+                let Some(entry) = entry.as_str().map(str::trim) else {
+                    problems.push(format!("requires.{} entries must be strings", key));
+                    continue;
+                };
+                if key == "env" {
+                    if ENV_REGEX.is_match(entry) {
+                        requirements.env.push(entry.to_string());
+                    } else {
+                        problems.push(format!("Invalid environment variable '{}'", entry));
+                    }
+                } else {
+                    match BIN_REGEX.captures(entry) {
+                        Some(caps) => requirements.bins.push(BinRequirement {
+                            name: caps[1].to_string(),
+                            min_version: caps.get(2).map(|v| v.as_str().to_string()),
+                        }),
+                        None => problems.push(format!(
+                            "Invalid program '{}': use a name, optionally with '>= <version>'",
+                            entry
+                        )),
+                    }
+                }
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(requirements)
+        } else {
+            Err(problems)
+        }
+    }
+
+    /// Returns true if nothing is required.
+    pub fn is_empty(&self) -> bool {
+        self.bins.is_empty() && self.env.is_empty()
+    }
+
+    /// Add the requirements of `other` that are not already listed.
+    ///
+    /// A program required by both keeps the higher minimum version.
+    pub fn union(&mut self, other: &Requirements) {
+        for bin in &other.bins {
+            match self.bins.iter_mut().find(|b| b.name == bin.name) {
+                Some(kept) => {
+                    let higher = match (&kept.min_version, &bin.min_version) {
+                        (Some(a), Some(b)) => compare_versions(b, a) == Ordering::Greater,
+                        (None, Some(_)) => true,
+                        _ => false,
+                    };
+                    if higher {
+                        kept.min_version = bin.min_version.clone();
+                    }
+                }
+                None => self.bins.push(bin.clone()),
+            }
+        }
+        for var in &other.env {
+            if !self.env.contains(var) {
+                self.env.push(var.clone());
+            }
+        }
+    }
+
+    /// The requirements in their frontmatter form.
+    pub fn to_value(&self) -> Value {
+        let mut fields = Mapping::new();
+        if !self.bins.is_empty() {
+            fields.insert(
+                "bins".into(),
+                Value::Sequence(self.bins.iter().map(|b| b.to_string().into()).collect()),
+            );
+        }
+        if !self.env.is_empty() {
+            fields.insert(
+                "env".into(),
+                Value::Sequence(self.env.iter().map(|v| v.as_str().into()).collect()),
+            );
+        }
+        Value::Mapping(fields)
+    }
+
+    /// Check every requirement against the current machine.
+    pub fn check(&self) -> Vec<RequirementCheck> {
+        let bins = self.bins.iter().map(|bin| {
+            let mut check = RequirementCheck {
+                kind: "bin",
+                name: bin.name.clone(),
+                min_version: bin.min_version.clone(),
+                version: None,
+                ok: false,
+                problem: None,
+            };
+            let Some(path) = find_executable(&bin.name) else {
+                check.problem = Some("not found on PATH".into());
+                return check;
+            };
+            check.version = installed_version(&path);
+            check.ok = match (&bin.min_version, &check.version) {
+                (None, _) => true,
+                (Some(min), Some(version)) => compare_versions(version, min) != Ordering::Less,
+                (Some(_), None) => false,
+            };
+            if !check.ok {
+                check.problem = Some(match &check.version {
+                    Some(version) => format!("version {} is older than required", version),
+                    None => "version could not be determined".into(),
+                });
+            }
+            check
+        });
+
+        let env = self.env.iter().map(|var| {
+            let ok = std::env::var_os(var).is_some_and(|v| !v.is_empty());
+            RequirementCheck {
+                kind: "env",
+                name: var.clone(),
+                min_version: None,
+                version: None,
+                ok,
+                problem: (!ok).then(|| "not set".into()),
+            }
+        });
+
+        bins.chain(env).collect()
+    }
+}
+
+/// The version a program reports with `--version`, if any.
+fn installed_version(program: &Path) -> Option<String> {
+    let output = Command::new(program).arg("--version").output().ok()?;
+    // Some programs (such as older Pythons) print their version to stderr
+    [output.stdout, output.stderr].iter().find_map(|stream| {
+        VERSION_REGEX
+            .find(&String::from_utf8_lossy(stream))
+            .map(|m| m.as_str().to_string())
+    })
+}
+
+/// Compare dotted version numbers component by component, treating
+/// missing components as zero.
+fn compare_versions(a: &str, b: &str) -> Ordering {
+    let parse = |v: &str| -> Vec<u64> { v.split('.').map(|c| c.parse().unwrap_or(0)).collect() };
+    let (a, b) = (parse(a), parse(b));
+    let len = a.len().max(b.len());
+    (0..len)
+        .map(|i| {
+            let x = a.get(i).copied().unwrap_or(0);
+            let y = b.get(i).copied().unwrap_or(0);
+            x.cmp(&y)
+        })
+        .find(|o| o.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_requirements() {
+        let value: Value = serde_yaml::from_str(
+            "bins: [jq, 'python3 >= 3.10', 'node>=18']\nenv: [GITHUB_TOKEN]\n",
+        )
+        .unwrap();
+        let requirements = Requirements::parse(&value).unwrap();
+        assert_eq!(
+            requirements
+                .bins
+                .iter()
+                .map(|b| b.to_string())
+                .collect::<Vec<_>>(),
+            vec!["jq", "python3 >= 3.10", "node >= 18"]
+        );
+        assert_eq!(requirements.env, vec!["GITHUB_TOKEN"]);
+        assert_eq!(
+            Requirements::parse(&requirements.to_value()),
+            Ok(requirements)
+        );
+
+        let value: Value =
+            serde_yaml::from_str("bins: [jq >= latest]\nenv: GITHUB_TOKEN\nos: linux\n").unwrap();
+        let problems = Requirements::parse(&value).unwrap_err();
+        assert_eq!(problems.len(), 3, "{:?}", problems);
+    }
+
+    #[test]
+    fn test_compare_versions() {
+        assert_eq!(compare_versions("3.10", "3.9"), Ordering::Greater);
+        assert_eq!(compare_versions("3.10.0", "3.10"), Ordering::Equal);
+        assert_eq!(compare_versions("1.2", "1.10"), Ordering::Less);
+    }
+
+    #[test]
+    fn test_check_missing() {
+        let requirements = Requirements {
+            bins: vec![BinRequirement {
+                name: "skilo-no-such-program".into(),
+                min_version: None,
+            }],
+            env: vec!["SKILO_NO_SUCH_VARIABLE".into()],
+        };
+        let checks = requirements.check();
+        assert_eq!(checks.len(), 2);
+        assert!(checks.iter().all(|c| !c.ok));
+        assert_eq!(checks[0].problem.as_deref(), Some("not found on PATH"));
+        assert_eq!(checks[1].problem.as_deref(), Some("not set"));
+    }
+}
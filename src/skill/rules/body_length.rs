@@ -1,7 +1,9 @@
 //! Warns when the skill body exceeds a recommended line count.
 
+use crate::skill::fix::{Edit, Fix};
 use crate::skill::manifest::Manifest;
 use crate::skill::rules::Rule;
+use crate::skill::split::split_body;
 use crate::skill::validator::{Diagnostic, DiagnosticCode};
 
 /// W001: Warns if body exceeds max_body_lines.
@@ -23,7 +25,7 @@ impl Rule for BodyLengthRule {
     }
 
     fn check(&self, manifest: &Manifest) -> Vec<Diagnostic> {
-        let line_count = manifest.body.lines().count();
+        let line_count = manifest.body_line_count();
 
         if line_count <= self.max_lines {
             return Vec::new();
@@ -41,4 +43,82 @@ impl Rule for BodyLengthRule {
             fix_hint: Some("Move detailed content to references/ directory".into()),
         }]
     }
+
+    /// Move trailing sections of the body to `references/`, replacing them
+    /// with links.
+    fn fix(&self, manifest: &Manifest) -> Option<Fix> {
+        let references_dir = manifest.path.parent()?.join("references");
+        let taken: Vec<String> = std::fs::read_dir(&references_dir)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .map(|e| e.file_name().to_string_lossy().into_owned())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let (body, references) = split_body(&manifest.body, self.max_lines, 0, &taken)?;
+
+        let mut edits = vec![Edit::Replace {
+            path: manifest.path.clone(),
+            range: manifest.body_offset..manifest.body_offset + manifest.body.len(),
+            replacement: body,
+        }];
+        let message = format!("moved {} section(s) to references/", references.len());
+        edits.extend(references.into_iter().map(|r| Edit::Create {
+            path: references_dir.join(r.file_name),
+            content: r.content,
+        }));
+
+        Some(Fix {
+            code: DiagnosticCode::W001,
+            message,
+            edits,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::skill::fix::{apply, plan};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_fix_splits_long_body() {
+        let temp = TempDir::new().unwrap();
+        let skill_md = temp.path().join("SKILL.md");
+        let mut content =
+            "---\nname: demo\ndescription: Demo.\n---\n\n# Demo\n\nIntro.\n".to_string();
+        for section in ["One", "Two"] {
+            content.push_str(&format!("\n## {}\n\n{}\n", section, "line\n".repeat(8)));
+        }
+        std::fs::write(&skill_md, &content).unwrap();
+        let manifest = Manifest::parse(skill_md.clone()).unwrap();
+
+        assert!(BodyLengthRule::new(100).fix(&manifest).is_none());
+
+        let fix = BodyLengthRule::new(20).fix(&manifest).unwrap();
+        let written: Vec<_> = fix
+            .edits
+            .iter()
+            .filter(|e| matches!(e, Edit::Create { .. }))
+            .map(|e| e.path().to_path_buf())
+            .collect();
+        assert!(!written.is_empty());
+        apply(&plan(&[fix]).unwrap()).unwrap();
+
+        let manifest = Manifest::parse(skill_md.clone()).unwrap();
+        assert!(manifest.body.lines().count() <= 20);
+        assert!(manifest.body.starts_with("# Demo\n\nIntro."));
+        assert!(manifest.body.contains("## References"));
+        assert_eq!(manifest.frontmatter.name, "demo");
+        for file in &written {
+            assert!(file.exists());
+            assert!(manifest.body.contains(&format!(
+                "references/{}",
+                file.file_name().unwrap().to_string_lossy()
+            )));
+        }
+    }
 }
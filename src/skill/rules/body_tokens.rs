@@ -0,0 +1,152 @@
+//! Warns when the skill body exceeds a token budget.
+
+use crate::skill::manifest::Manifest;
+use crate::skill::rules::Rule;
+use crate::skill::validator::{Diagnostic, DiagnosticCode};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use walkdir::WalkDir;
+
+/// Estimate the number of tokens in a piece of text.
+///
+/// Uses the common ~4 characters per token heuristic. This is not exact for
+/// any particular tokenizer but is stable and close enough for budgeting.
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
+/// Estimate the combined tokens of all readable files under `references/`.
+pub fn reference_tokens(skill_dir: &Path) -> usize {
+    let references = skill_dir.join("references");
+    if !references.is_dir() {
+        return 0;
+    }
+
+    WalkDir::new(references)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| file_tokens(e.path()))
+        .sum()
+}
+
+/// Estimate the tokens of a text file, reading it in chunks.
+///
+/// Returns `None` for unreadable files and files that are not UTF-8.
+fn file_tokens(path: &Path) -> Option<usize> {
+    let mut reader = BufReader::new(File::open(path).ok()?);
+    let mut chars = 0;
+    let mut pending: Vec<u8> = Vec::new();
+
+    loop {
+        let chunk = reader.fill_buf().ok()?;
+        if chunk.is_empty() {
+            break;
+        }
+        pending.extend_from_slice(chunk);
+        let read = chunk.len();
+        reader.consume(read);
+
+        // Keep a character split across chunks for the next round
+        let valid = match std::str::from_utf8(&pending) {
+            Ok(text) => text,
+            Err(e) if e.error_len().is_none() => {
+                std::str::from_utf8(&pending[..e.valid_up_to()]).ok()?
+            }
+            Err(_) => return None,
+        };
+        chars += valid.chars().count();
+        let consumed = valid.len();
+        pending.drain(..consumed);
+    }
+
+    pending.is_empty().then(|| chars.div_ceil(4))
+}
+
+/// W008: Warns if the estimated body tokens exceed max_tokens.
+pub struct BodyTokensRule {
+    /// Maximum recommended tokens.
+    max_tokens: usize,
+    /// Count `references/` files towards the budget.
+    include_references: bool,
+}
+
+impl BodyTokensRule {
+    /// Create a new body tokens rule with the specified budget.
+    pub fn new(max_tokens: usize, include_references: bool) -> Self {
+        Self {
+            max_tokens,
+            include_references,
+        }
+    }
+}
+
+impl Rule for BodyTokensRule {
+    fn name(&self) -> &'static str {
+        "body-tokens"
+    }
+
+    fn check(&self, manifest: &Manifest) -> Vec<Diagnostic> {
+        let mut tokens = estimate_tokens(&manifest.body);
+        if self.include_references {
+            if let Some(skill_dir) = manifest.path.parent() {
+                tokens += reference_tokens(skill_dir);
+            }
+        }
+
+        if tokens <= self.max_tokens {
+            return Vec::new();
+        }
+
+        let what = if self.include_references {
+            "Body and references exceed"
+        } else {
+            "Body exceeds"
+        };
+
+        vec![Diagnostic {
+            path: manifest.path.display().to_string(),
+            line: None,
+            column: None,
+            message: format!(
+                "{} recommended {} tokens (~{} tokens)",
+                what, self.max_tokens, tokens
+            ),
+            code: DiagnosticCode::W008,
+            fix_hint: Some("Trim the body or split content into separate skills".into()),
+        }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_estimate_tokens() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("abcd"), 1);
+        assert_eq!(estimate_tokens("abcde"), 2);
+    }
+
+    #[test]
+    fn test_body_tokens_with_references() {
+        let temp = TempDir::new().unwrap();
+        let skill_dir = temp.path().join("my-skill");
+        std::fs::create_dir_all(skill_dir.join("references")).unwrap();
+        std::fs::write(skill_dir.join("references/guide.md"), "x".repeat(400)).unwrap();
+
+        let content = format!(
+            "---\nname: my-skill\ndescription: Test\n---\n\n{}\n",
+            "y".repeat(200)
+        );
+        let manifest = Manifest::parse_content(skill_dir.join("SKILL.md"), &content).unwrap();
+
+        assert!(BodyTokensRule::new(100, false).check(&manifest).is_empty());
+        let diags = BodyTokensRule::new(100, true).check(&manifest);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].code, DiagnosticCode::W008);
+    }
+}
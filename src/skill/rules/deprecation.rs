@@ -0,0 +1,105 @@
+//! Validates deprecation metadata in frontmatter.
+
+use crate::skill::manifest::Manifest;
+use crate::skill::rules::name::NAME_REGEX;
+use crate::skill::rules::{frontmatter_key_line, Rule};
+use crate::skill::validator::{Diagnostic, DiagnosticCode};
+
+/// E010/W005: Validates `deprecated` and `superseded-by`.
+///
+/// `superseded-by` must name a valid skill other than this one (E010), and
+/// should only be set on skills marked `deprecated: true` (W005).
+pub struct DeprecationRule;
+
+impl Rule for DeprecationRule {
+    fn name(&self) -> &'static str {
+        "deprecation"
+    }
+
+    fn check(&self, manifest: &Manifest) -> Vec<Diagnostic> {
+        let Some(replacement) = &manifest.frontmatter.superseded_by else {
+            return Vec::new();
+        };
+
+        let path = manifest.path.display().to_string();
+        let line = frontmatter_key_line(&manifest.frontmatter_raw, "superseded-by");
+        let mut diagnostics = Vec::new();
+
+        if !NAME_REGEX.is_match(replacement) {
+            diagnostics.push(Diagnostic {
+                path: path.clone(),
+                line,
+                column: None,
+                message: format!(
+                    "Invalid superseded-by '{}': must be a valid skill name",
+                    replacement
+                ),
+                code: DiagnosticCode::E010,
+                fix_hint: Some("Use the name of the replacement skill".into()),
+            });
+        } else if *replacement == manifest.frontmatter.name {
+            diagnostics.push(Diagnostic {
+                path: path.clone(),
+                line,
+                column: None,
+                message: "Skill cannot be superseded by itself".into(),
+                code: DiagnosticCode::E010,
+                fix_hint: None,
+            });
+        }
+
+        if !manifest.frontmatter.is_deprecated() {
+            diagnostics.push(Diagnostic {
+                path,
+                line,
+                column: None,
+                message: "superseded-by is set but the skill is not deprecated".into(),
+                code: DiagnosticCode::W005,
+                fix_hint: Some("Add 'deprecated: true' to the frontmatter".into()),
+            });
+        }
+
+        diagnostics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn check(frontmatter: &str) -> Vec<DiagnosticCode> {
+        let content = format!("---\n{}\n---\n\n# Body\n", frontmatter);
+        let manifest =
+            Manifest::parse_content(PathBuf::from("old-skill/SKILL.md"), &content).unwrap();
+        DeprecationRule
+            .check(&manifest)
+            .into_iter()
+            .map(|d| d.code)
+            .collect()
+    }
+
+    #[test]
+    fn test_valid_deprecation() {
+        let codes =
+            check("name: old-skill\ndescription: Old\ndeprecated: true\nsuperseded-by: new-skill");
+        assert!(codes.is_empty());
+    }
+
+    #[test]
+    fn test_invalid_replacement() {
+        let codes =
+            check("name: old-skill\ndescription: Old\ndeprecated: true\nsuperseded-by: New Skill");
+        assert_eq!(codes, vec![DiagnosticCode::E010]);
+
+        let codes =
+            check("name: old-skill\ndescription: Old\ndeprecated: true\nsuperseded-by: old-skill");
+        assert_eq!(codes, vec![DiagnosticCode::E010]);
+    }
+
+    #[test]
+    fn test_superseded_without_deprecated() {
+        let codes = check("name: old-skill\ndescription: Old\nsuperseded-by: new-skill");
+        assert_eq!(codes, vec![DiagnosticCode::W005]);
+    }
+}
@@ -1,8 +1,9 @@
-//! Validates skill descriptions: presence and length.
+//! Validates skill descriptions: presence, length, and trigger phrasing.
 
 use crate::skill::manifest::Manifest;
-use crate::skill::rules::Rule;
+use crate::skill::rules::{frontmatter_key_line, Rule};
 use crate::skill::validator::{Diagnostic, DiagnosticCode};
+use regex::{Regex, RegexBuilder};
 
 /// E004: Validates description is not empty.
 pub struct DescriptionRequiredRule;
@@ -13,9 +14,7 @@ impl Rule for DescriptionRequiredRule {
     }
 
     fn check(&self, manifest: &Manifest) -> Vec<Diagnostic> {
-        let desc = &manifest.frontmatter.description;
-
-        if !desc.is_empty() {
+        if !manifest.frontmatter.folded_description().is_empty() {
             return Vec::new();
         }
 
@@ -49,7 +48,7 @@ impl Rule for DescriptionLengthRule {
     }
 
     fn check(&self, manifest: &Manifest) -> Vec<Diagnostic> {
-        let desc = &manifest.frontmatter.description;
+        let desc = manifest.frontmatter.folded_description();
 
         if desc.len() <= self.max_length {
             return Vec::new();
@@ -69,3 +68,144 @@ impl Rule for DescriptionLengthRule {
         }]
     }
 }
+
+/// W012: Validates the length of the optional `summary` field.
+pub struct SummaryLengthRule {
+    /// Maximum allowed summary length.
+    max_length: usize,
+}
+
+impl SummaryLengthRule {
+    /// Create a new summary length rule with the specified maximum.
+    pub fn new(max_length: usize) -> Self {
+        Self { max_length }
+    }
+}
+
+impl Rule for SummaryLengthRule {
+    fn name(&self) -> &'static str {
+        "summary-length"
+    }
+
+    fn check(&self, manifest: &Manifest) -> Vec<Diagnostic> {
+        let Some(summary) = &manifest.frontmatter.summary else {
+            return Vec::new();
+        };
+
+        if summary.len() <= self.max_length {
+            return Vec::new();
+        }
+
+        vec![Diagnostic {
+            path: manifest.path.display().to_string(),
+            line: frontmatter_key_line(&manifest.frontmatter_raw, "summary"),
+            column: None,
+            message: format!(
+                "Summary too long ({} chars, max {})",
+                summary.len(),
+                self.max_length
+            ),
+            code: DiagnosticCode::W012,
+            fix_hint: Some("Shorten the summary; it replaces the body in prompts".into()),
+        }]
+    }
+}
+
+/// Trigger phrases accepted by W016 when `description_triggers` is not set.
+pub const DEFAULT_TRIGGERS: &[&str] = &[
+    r"\buse (this|it )?(skill )?(when|whenever|for|to)\b",
+    r"\bhelps? (with|you|to)\b",
+    r"\bwhen (the )?(user|you)\b",
+    r"\bif (the )?(user|you)\b",
+    r"\btrigger(s|ed)? (on|when|by)\b",
+];
+
+/// W016: Warns when the description does not say when to use the skill.
+///
+/// Agents pick skills from their descriptions alone, so a description that
+/// only says what a skill does is easily overlooked.
+pub struct DescriptionTriggerRule {
+    /// Case-insensitive patterns, any of which satisfies the rule.
+    triggers: Vec<Regex>,
+}
+
+impl DescriptionTriggerRule {
+    /// Create a new trigger rule from regex `patterns`.
+    ///
+    /// Patterns that are not valid regexes match as literal text.
+    pub fn new(patterns: &[String]) -> Self {
+        let triggers = patterns
+            .iter()
+            .map(|p| {
+                RegexBuilder::new(p)
+                    .case_insensitive(true)
+                    .build()
+                    .or_else(|_| {
+                        RegexBuilder::new(&regex::escape(p))
+                            .case_insensitive(true)
+                            .build()
+                    })
+                    .expect("escaped pattern is a valid regex")
+            })
+            .collect();
+        Self { triggers }
+    }
+}
+
+impl Rule for DescriptionTriggerRule {
+    fn name(&self) -> &'static str {
+        "description-trigger"
+    }
+
+    fn check(&self, manifest: &Manifest) -> Vec<Diagnostic> {
+        let desc = manifest.frontmatter.folded_description();
+
+        // An empty description is reported by E004
+        if desc.is_empty() || self.triggers.iter().any(|t| t.is_match(&desc)) {
+            return Vec::new();
+        }
+
+        vec![Diagnostic {
+            path: manifest.path.display().to_string(),
+            line: frontmatter_key_line(&manifest.frontmatter_raw, "description"),
+            column: None,
+            message: "Description does not say when to use the skill".into(),
+            code: DiagnosticCode::W016,
+            fix_hint: Some("Add a trigger clause, e.g. 'Use when the user asks to ...'".into()),
+        }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn manifest(description: &str) -> Manifest {
+        let content = format!(
+            "---\nname: my-skill\ndescription: {}\n---\n\n# Body\n",
+            description
+        );
+        Manifest::parse_content(PathBuf::from("my-skill/SKILL.md"), &content).unwrap()
+    }
+
+    #[test]
+    fn test_description_trigger() {
+        let defaults: Vec<String> = DEFAULT_TRIGGERS.iter().map(|t| t.to_string()).collect();
+        let rule = DescriptionTriggerRule::new(&defaults);
+        assert!(rule
+            .check(&manifest("Formats SQL. Use when the user pastes a query."))
+            .is_empty());
+        assert!(rule.check(&manifest("Helps with PDF forms.")).is_empty());
+
+        let diags = rule.check(&manifest("Formats SQL queries."));
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].code, DiagnosticCode::W016);
+        assert_eq!(diags[0].line, Some(3));
+
+        // Invalid regexes match literally
+        let rule = DescriptionTriggerRule::new(&["invoke (for".to_string()]);
+        assert!(rule.check(&manifest("Invoke (for SQL.")).is_empty());
+        assert_eq!(rule.check(&manifest("Use when asked.")).len(), 1);
+    }
+}
@@ -0,0 +1,198 @@
+//! Validates file encodings and line endings of SKILL.md and scripts.
+
+use crate::skill::manifest::Manifest;
+use crate::skill::rules::{scripts, Rule};
+use crate::skill::validator::{Diagnostic, DiagnosticCode};
+use std::path::Path;
+
+/// UTF-8 byte order mark.
+pub const BOM: &str = "\u{feff}";
+
+/// Line terminators counted in a text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LineEndings {
+    /// `\r\n` terminators.
+    pub crlf: usize,
+    /// `\n` terminators without a preceding `\r`.
+    pub lf: usize,
+}
+
+impl LineEndings {
+    /// Count the line terminators of `text`.
+    pub fn count(text: &str) -> Self {
+        let mut endings = Self::default();
+        let mut previous = None;
+        for byte in text.bytes() {
+            if byte == b'\n' {
+                if previous == Some(b'\r') {
+                    endings.crlf += 1;
+                } else {
+                    endings.lf += 1;
+                }
+            }
+            previous = Some(byte);
+        }
+        endings
+    }
+}
+
+/// E014, W013: Validates encodings and line endings.
+///
+/// Scripts that are not UTF-8 text are errors (E014; files with NUL bytes
+/// count as binaries and are skipped). A byte order mark or CRLF line
+/// endings in SKILL.md or a script are warnings (W013): agents read them
+/// as literal characters, and a CRLF shebang names an interpreter ending
+/// in `\r` that does not exist. SKILL.md files that are not UTF-8 do not
+/// parse and are reported as E014 by the linter directly.
+pub struct EncodingRule;
+
+impl EncodingRule {
+    /// Diagnostics for one file's content.
+    fn check_text(path: &Path, content: &str, diagnostics: &mut Vec<Diagnostic>) {
+        let script = path.file_name().is_some_and(|name| name != "SKILL.md");
+        let mut warn = |line, message: String, fix_hint: &str| {
+            diagnostics.push(Diagnostic {
+                path: path.display().to_string(),
+                line,
+                column: None,
+                message,
+                code: DiagnosticCode::W013,
+                fix_hint: Some(fix_hint.into()),
+            });
+        };
+
+        if content.starts_with(BOM) {
+            warn(
+                Some(1),
+                "File starts with a UTF-8 byte order mark".into(),
+                "Save the file as UTF-8 without BOM",
+            );
+        }
+
+        let endings = LineEndings::count(content);
+        let hint = "Set normalize_line_endings = true under [fmt] and run skilo fmt";
+        if endings.crlf > 0 && endings.lf > 0 {
+            warn(
+                None,
+                format!(
+                    "Mixed line endings ({} CRLF, {} LF)",
+                    endings.crlf, endings.lf
+                ),
+                hint,
+            );
+        } else if endings.crlf > 0 && script {
+            warn(
+                Some(1),
+                "Script uses CRLF line endings, which break the shebang on Unix".into(),
+                hint,
+            );
+        } else if endings.crlf > 0 {
+            warn(None, "File uses CRLF line endings".into(), hint);
+        }
+    }
+}
+
+impl Rule for EncodingRule {
+    fn name(&self) -> &'static str {
+        "encoding"
+    }
+
+    fn check(&self, manifest: &Manifest) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        if let Ok(content) = std::fs::read_to_string(&manifest.path) {
+            Self::check_text(&manifest.path, &content, &mut diagnostics);
+        }
+
+        for path in scripts(manifest) {
+            let Ok(bytes) = std::fs::read(&path) else {
+                continue;
+            };
+            if bytes.contains(&0) {
+                continue;
+            }
+            match String::from_utf8(bytes) {
+                Ok(content) => Self::check_text(&path, &content, &mut diagnostics),
+                Err(e) => diagnostics.extend(not_utf8(&path, e.as_bytes())),
+            }
+        }
+
+        diagnostics
+    }
+}
+
+/// E014 for the file at `path` with content `bytes`, or `None` if the
+/// content is valid UTF-8.
+pub fn not_utf8(path: &Path, bytes: &[u8]) -> Option<Diagnostic> {
+    let valid_up_to = std::str::from_utf8(bytes).err()?.valid_up_to();
+    let line = bytes[..valid_up_to].iter().filter(|&&b| b == b'\n').count() + 1;
+    Some(Diagnostic {
+        path: path.display().to_string(),
+        line: Some(line),
+        column: None,
+        message: "File is not valid UTF-8".into(),
+        code: DiagnosticCode::E014,
+        fix_hint: Some("Convert the file to UTF-8, e.g. with iconv".into()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_line_endings() {
+        assert_eq!(
+            LineEndings::count("a\r\nb\nc\r\n"),
+            LineEndings { crlf: 2, lf: 1 }
+        );
+        assert_eq!(LineEndings::count("a\rb"), LineEndings::default());
+    }
+
+    #[test]
+    fn test_encoding_rule() {
+        let temp = TempDir::new().unwrap();
+        let skill = temp.path().join("demo");
+        std::fs::create_dir_all(skill.join("scripts")).unwrap();
+        let skill_md = "\u{feff}---\r\nname: demo\r\ndescription: Demo\r\n---\r\n\r\nBody\r\n";
+        std::fs::write(skill.join("SKILL.md"), skill_md).unwrap();
+        std::fs::write(skill.join("scripts/run.sh"), "#!/bin/sh\r\necho hi\r\n").unwrap();
+        std::fs::write(skill.join("scripts/latin1.py"), b"print('caf\xe9')\n").unwrap();
+        std::fs::write(skill.join("scripts/tool.bin"), b"\x7fELF\x00\r\n").unwrap();
+        std::fs::write(skill.join("scripts/ok.py"), "print('ok')\n").unwrap();
+
+        let manifest = Manifest::parse(skill.join("SKILL.md")).unwrap();
+        let diagnostics: Vec<(DiagnosticCode, String)> = EncodingRule
+            .check(&manifest)
+            .into_iter()
+            .map(|d| {
+                let file = Path::new(&d.path).file_name().unwrap().to_string_lossy();
+                (d.code, format!("{}: {}", file, d.message))
+            })
+            .collect();
+
+        assert_eq!(
+            diagnostics,
+            vec![
+                (
+                    DiagnosticCode::W013,
+                    "SKILL.md: File starts with a UTF-8 byte order mark".to_string()
+                ),
+                (
+                    DiagnosticCode::W013,
+                    "SKILL.md: File uses CRLF line endings".to_string()
+                ),
+                (
+                    DiagnosticCode::E014,
+                    "latin1.py: File is not valid UTF-8".to_string()
+                ),
+                (
+                    DiagnosticCode::W013,
+                    "run.sh: Script uses CRLF line endings, which break the shebang on Unix"
+                        .to_string()
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,127 @@
+//! Validates the optional `examples` list in frontmatter.
+
+use crate::skill::manifest::Manifest;
+use crate::skill::rules::{frontmatter_key_line, Rule};
+use crate::skill::validator::{Diagnostic, DiagnosticCode};
+
+/// W017: Validates the number of examples.
+pub struct ExampleCountRule {
+    /// Maximum allowed number of examples.
+    max_count: usize,
+}
+
+impl ExampleCountRule {
+    /// Create a new example count rule with the specified maximum.
+    pub fn new(max_count: usize) -> Self {
+        Self { max_count }
+    }
+}
+
+impl Rule for ExampleCountRule {
+    fn name(&self) -> &'static str {
+        "example-count"
+    }
+
+    fn check(&self, manifest: &Manifest) -> Vec<Diagnostic> {
+        let count = manifest.frontmatter.examples().len();
+
+        if count <= self.max_count {
+            return Vec::new();
+        }
+
+        vec![Diagnostic {
+            path: manifest.path.display().to_string(),
+            line: frontmatter_key_line(&manifest.frontmatter_raw, "examples"),
+            column: None,
+            message: format!("Too many examples ({}, max {})", count, self.max_count),
+            code: DiagnosticCode::W017,
+            fix_hint: Some("Keep a few examples covering distinct requests".into()),
+        }]
+    }
+}
+
+/// W018: Validates that each example is non-empty and not too long.
+pub struct ExampleLengthRule {
+    /// Maximum allowed length of an example.
+    max_length: usize,
+}
+
+impl ExampleLengthRule {
+    /// Create a new example length rule with the specified maximum.
+    pub fn new(max_length: usize) -> Self {
+        Self { max_length }
+    }
+}
+
+impl Rule for ExampleLengthRule {
+    fn name(&self) -> &'static str {
+        "example-length"
+    }
+
+    fn check(&self, manifest: &Manifest) -> Vec<Diagnostic> {
+        let line = frontmatter_key_line(&manifest.frontmatter_raw, "examples");
+        let mut diagnostics = Vec::new();
+
+        for (i, example) in manifest.frontmatter.examples().iter().enumerate() {
+            let length = example.trim().chars().count();
+            let (message, fix_hint) = if length == 0 {
+                (
+                    format!("Example {} is empty", i + 1),
+                    "Remove the empty entry",
+                )
+            } else if length > self.max_length {
+                (
+                    format!(
+                        "Example {} too long ({} chars, max {})",
+                        i + 1,
+                        length,
+                        self.max_length
+                    ),
+                    "Phrase examples as short user requests",
+                )
+            } else {
+                continue;
+            };
+            diagnostics.push(Diagnostic {
+                path: manifest.path.display().to_string(),
+                line,
+                column: None,
+                message,
+                code: DiagnosticCode::W018,
+                fix_hint: Some(fix_hint.into()),
+            });
+        }
+
+        diagnostics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn manifest(frontmatter: &str) -> Manifest {
+        let content = format!("---\n{}\n---\n\n# Body\n", frontmatter);
+        Manifest::parse_content(PathBuf::from("my-skill/SKILL.md"), &content).unwrap()
+    }
+
+    #[test]
+    fn test_examples() {
+        let m = manifest(
+            "name: my-skill\ndescription: Test\nexamples:\n  - Format this query\n  - \"\"\n  - Tidy up the SQL in report.sql",
+        );
+        assert_eq!(m.frontmatter.examples().len(), 3);
+        assert!(ExampleCountRule::new(3).check(&m).is_empty());
+        assert_eq!(
+            ExampleCountRule::new(2).check(&m)[0].code,
+            DiagnosticCode::W017
+        );
+
+        let diags = ExampleLengthRule::new(20).check(&m);
+        assert_eq!(diags.len(), 2);
+        assert_eq!(diags[0].message, "Example 2 is empty");
+        assert_eq!(diags[0].line, Some(4));
+        assert!(diags[1].message.starts_with("Example 3 too long"));
+    }
+}
@@ -0,0 +1,223 @@
+//! Validates agent-specific frontmatter fields such as `context` and `hooks`.
+
+use crate::agent::Agent;
+use crate::skill::manifest::Manifest;
+use crate::skill::rules::{frontmatter_key_line, Rule};
+use crate::skill::validator::{Diagnostic, DiagnosticCode};
+
+/// Hook events accepted in skill frontmatter.
+const HOOK_EVENTS: &[&str] = &[
+    "PreToolUse",
+    "PostToolUse",
+    "Notification",
+    "UserPromptSubmit",
+    "Stop",
+    "SubagentStop",
+    "PreCompact",
+    "SessionStart",
+    "SessionEnd",
+];
+
+/// E011: Validates the values of extended frontmatter fields.
+///
+/// Checks that `context` is `fork`, `model` is not empty, and that hooks use
+/// known events and types with the field their type requires.
+pub struct ExtendedFieldsRule;
+
+impl Rule for ExtendedFieldsRule {
+    fn name(&self) -> &'static str {
+        "extended-fields"
+    }
+
+    fn check(&self, manifest: &Manifest) -> Vec<Diagnostic> {
+        let frontmatter = &manifest.frontmatter;
+        let mut diagnostics = Vec::new();
+        let mut error = |key: &str, message: String, fix_hint: Option<&str>| {
+            diagnostics.push(Diagnostic {
+                path: manifest.path.display().to_string(),
+                line: frontmatter_key_line(&manifest.frontmatter_raw, key),
+                column: None,
+                message,
+                code: DiagnosticCode::E011,
+                fix_hint: fix_hint.map(str::to_string),
+            });
+        };
+
+        if let Some(context) = &frontmatter.context {
+            if context != "fork" {
+                error(
+                    "context",
+                    format!("Invalid context '{}': only 'fork' is supported", context),
+                    Some("Use 'context: fork' or remove the field"),
+                );
+            }
+        }
+
+        if frontmatter.agent.is_some() && !frontmatter.is_forked() {
+            error(
+                "agent",
+                "agent is only used together with 'context: fork'".into(),
+                Some("Add 'context: fork' or remove the field"),
+            );
+        }
+
+        if frontmatter
+            .model
+            .as_deref()
+            .is_some_and(|m| m.trim().is_empty())
+        {
+            error("model", "model must not be empty".into(), None);
+        }
+
+        for (event, matchers) in frontmatter.hooks.iter().flatten() {
+            if !HOOK_EVENTS.contains(&event.as_str()) {
+                error(
+                    "hooks",
+                    format!("Unknown hook event '{}'", event),
+                    Some("Use an event such as PreToolUse, PostToolUse, or Stop"),
+                );
+            }
+
+            for hook in matchers.iter().flat_map(|m| &m.hooks) {
+                let missing = match hook.kind.as_str() {
+                    "command" => hook.command.is_none().then_some("command"),
+                    "prompt" => hook.prompt.is_none().then_some("prompt"),
+                    other => {
+                        error(
+                            "hooks",
+                            format!("Unknown hook type '{}' for {}", other, event),
+                            Some("Use 'type: command' or 'type: prompt'"),
+                        );
+                        None
+                    }
+                };
+                if let Some(field) = missing {
+                    error(
+                        "hooks",
+                        format!("{} hook for {} is missing '{}'", hook.kind, event, field),
+                        None,
+                    );
+                }
+            }
+        }
+
+        diagnostics
+    }
+}
+
+/// W010: Warns when a skill uses features its target agents do not support.
+pub struct AgentCompatibilityRule {
+    /// Agents the skill is expected to run on.
+    agents: Vec<Agent>,
+}
+
+impl AgentCompatibilityRule {
+    /// Create a new compatibility rule for the given target agents.
+    pub fn new(agents: Vec<Agent>) -> Self {
+        Self { agents }
+    }
+}
+
+impl Rule for AgentCompatibilityRule {
+    fn name(&self) -> &'static str {
+        "agent-compatibility"
+    }
+
+    fn check(&self, manifest: &Manifest) -> Vec<Diagnostic> {
+        let frontmatter = &manifest.frontmatter;
+        let used = [
+            ("context", "context: fork", frontmatter.is_forked()),
+            ("hooks", "hooks", frontmatter.has_hooks()),
+            (
+                "allowed-tools",
+                "allowed-tools",
+                frontmatter.allowed_tools.is_some(),
+            ),
+        ];
+
+        let mut diagnostics = Vec::new();
+        for (key, feature, in_use) in used {
+            if !in_use {
+                continue;
+            }
+
+            let unsupported: Vec<&str> = self
+                .agents
+                .iter()
+                .filter(|agent| {
+                    let features = agent.features();
+                    match key {
+                        "context" => !features.context_fork,
+                        "hooks" => !features.hooks,
+                        _ => !features.allowed_tools,
+                    }
+                })
+                .map(|agent| agent.display_name())
+                .collect();
+
+            if unsupported.is_empty() {
+                continue;
+            }
+
+            diagnostics.push(Diagnostic {
+                path: manifest.path.display().to_string(),
+                line: frontmatter_key_line(&manifest.frontmatter_raw, key),
+                column: None,
+                message: format!("{} is not supported by {}", feature, unsupported.join(", ")),
+                code: DiagnosticCode::W010,
+                fix_hint: Some("Remove the field or drop these agents from [lint] agents".into()),
+            });
+        }
+
+        diagnostics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn manifest(frontmatter: &str) -> Manifest {
+        let content = format!(
+            "---\nname: my-skill\ndescription: Test\n{}\n---\n\n# Body\n",
+            frontmatter
+        );
+        Manifest::parse_content(PathBuf::from("my-skill/SKILL.md"), &content).unwrap()
+    }
+
+    #[test]
+    fn test_extended_fields() {
+        let valid = manifest(
+            "context: fork\nagent: Explore\nhooks:\n  PreToolUse:\n    - matcher: Bash\n      hooks:\n        - type: command\n          command: ./scripts/check.sh",
+        );
+        assert!(ExtendedFieldsRule.check(&valid).is_empty());
+
+        let invalid = manifest(
+            "context: thread\nhooks:\n  BeforeTool:\n    - hooks:\n        - type: command",
+        );
+        let messages: Vec<String> = ExtendedFieldsRule
+            .check(&invalid)
+            .into_iter()
+            .map(|d| d.message)
+            .collect();
+        assert_eq!(messages.len(), 3, "{:?}", messages);
+        assert!(messages[0].contains("Invalid context"));
+        assert!(messages[1].contains("Unknown hook event"));
+        assert!(messages[2].contains("missing 'command'"));
+    }
+
+    #[test]
+    fn test_agent_compatibility() {
+        let forked = manifest("context: fork");
+
+        let rule = AgentCompatibilityRule::new(vec![Agent::Claude]);
+        assert!(rule.check(&forked).is_empty());
+
+        let rule = AgentCompatibilityRule::new(vec![Agent::Claude, Agent::Cursor]);
+        let diags = rule.check(&forked);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].code, DiagnosticCode::W010);
+        assert!(diags[0].message.contains("Cursor"));
+    }
+}
@@ -4,22 +4,81 @@
 //! of skill manifests, from name format to script permissions.
 
 mod body_length;
+mod body_tokens;
 mod compatibility;
+mod deprecation;
 mod description;
+mod encoding;
+mod examples;
+mod extended;
 mod name;
+mod nested;
+mod overrides;
 mod references;
+mod requirements;
+mod script_docs;
 mod scripts;
+mod secrets;
+mod tags;
 
 pub use body_length::BodyLengthRule;
+pub use body_tokens::{estimate_tokens, reference_tokens, BodyTokensRule};
 pub use compatibility::CompatibilityLengthRule;
-pub use description::{DescriptionLengthRule, DescriptionRequiredRule};
-pub use name::{NameDirectoryRule, NameFormatRule, NameLengthRule};
+pub use deprecation::DeprecationRule;
+pub use description::{
+    DescriptionLengthRule, DescriptionRequiredRule, DescriptionTriggerRule, SummaryLengthRule,
+    DEFAULT_TRIGGERS,
+};
+pub use encoding::{not_utf8, EncodingRule, LineEndings, BOM};
+pub use examples::{ExampleCountRule, ExampleLengthRule};
+pub use extended::{AgentCompatibilityRule, ExtendedFieldsRule};
+pub use name::{NameDirectoryRule, NameFix, NameFormatRule, NameLengthRule, ReservedNameRule};
+pub use nested::NestedSkillRule;
+pub use overrides::AgentOverridesRule;
 pub use references::ReferencesExistRule;
-pub use scripts::{ScriptExecutableRule, ScriptShebangRule};
+pub use requirements::RequirementsRule;
+pub use script_docs::ScriptDocsRule;
+pub(crate) use scripts::scripts;
+pub use scripts::{ScriptExecutableRule, ScriptInterpreterRule, ScriptShebangRule};
+pub use secrets::SecretsRule;
+pub use tags::{TagCountRule, TagFormatRule};
 
+use crate::skill::fix::Fix;
 use crate::skill::manifest::Manifest;
 use crate::skill::validator::Diagnostic;
 
+/// Names of all rules, as accepted by `lint --only` and `--skip`.
+pub const RULE_NAMES: &[&str] = &[
+    "name-format",
+    "name-length",
+    "name-directory",
+    "description-required",
+    "description-length",
+    "description-trigger",
+    "summary-length",
+    "compatibility-length",
+    "references-exist",
+    "body-length",
+    "body-tokens",
+    "nested-skill",
+    "script-executable",
+    "script-shebang",
+    "script-interpreter",
+    "script-docs",
+    "deprecation",
+    "extended-fields",
+    "agent-overrides",
+    "requirements",
+    "encoding",
+    "agent-compatibility",
+    "reserved-name",
+    "tag-format",
+    "tag-count",
+    "example-count",
+    "example-length",
+    "secrets",
+];
+
 /// A lint rule that checks a manifest for issues.
 pub trait Rule: Send + Sync {
     /// Human-readable name for this rule (e.g., "name-format")
@@ -27,4 +86,18 @@ pub trait Rule: Send + Sync {
 
     /// Check the manifest and return any diagnostics found.
     fn check(&self, manifest: &Manifest) -> Vec<Diagnostic>;
+
+    /// A fix for the diagnostics this rule reports, if it can fix them.
+    fn fix(&self, _manifest: &Manifest) -> Option<Fix> {
+        None
+    }
+}
+
+/// Find the SKILL.md line number of a top-level frontmatter key.
+pub(crate) fn frontmatter_key_line(frontmatter_raw: &str, key: &str) -> Option<usize> {
+    frontmatter_raw
+        .lines()
+        .position(|l| l.starts_with(key) && l[key.len()..].starts_with(':'))
+        // Frontmatter starts on line 2, after the opening `---`
+        .map(|i| i + 2)
 }
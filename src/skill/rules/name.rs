@@ -1,13 +1,18 @@
 //! Validates skill names: format, length, and directory matching.
 
+use crate::agent::Agent;
+use crate::skill::fix::{Edit, Fix};
 use crate::skill::manifest::Manifest;
 use crate::skill::rules::Rule;
 use crate::skill::validator::{Diagnostic, DiagnosticCode};
 use once_cell::sync::Lazy;
 use regex::Regex;
+use serde::Deserialize;
+use std::path::Path;
 
 /// Pattern for valid skill names: lowercase alphanumeric with single hyphens.
-static NAME_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^[a-z0-9]+(-[a-z0-9]+)*$").unwrap());
+pub(crate) static NAME_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^[a-z0-9]+(-[a-z0-9]+)*$").unwrap());
 
 /// E001: Validates name format (lowercase alphanumeric + single hyphens)
 pub struct NameFormatRule;
@@ -78,8 +83,29 @@ impl Rule for NameLengthRule {
     }
 }
 
+/// How `lint --fix` resolves a name/directory mismatch (E003).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum NameFix {
+    /// Rewrite the frontmatter name to the directory name.
+    #[default]
+    Name,
+    /// Rename the directory to the frontmatter name.
+    Directory,
+}
+
 /// E003: Validates name matches parent directory
-pub struct NameDirectoryRule;
+pub struct NameDirectoryRule {
+    /// How mismatches are fixed.
+    fix: NameFix,
+}
+
+impl NameDirectoryRule {
+    /// Create a new name/directory rule that fixes mismatches with `fix`.
+    pub fn new(fix: NameFix) -> Self {
+        Self { fix }
+    }
+}
 
 impl Rule for NameDirectoryRule {
     fn name(&self) -> &'static str {
@@ -89,11 +115,7 @@ impl Rule for NameDirectoryRule {
     fn check(&self, manifest: &Manifest) -> Vec<Diagnostic> {
         let name = &manifest.frontmatter.name;
 
-        let Some(parent) = manifest.path.parent() else {
-            return Vec::new();
-        };
-
-        let Some(dir_name) = parent.file_name().and_then(|n| n.to_str()) else {
+        let Some(dir_name) = dir_name(manifest) else {
             return Vec::new();
         };
 
@@ -116,11 +138,139 @@ impl Rule for NameDirectoryRule {
             )),
         }]
     }
+
+    /// Rewrite the frontmatter name or rename the directory, whichever
+    /// [`NameFix`] asks for.
+    ///
+    /// The new name must be a valid skill name. A directory is not renamed
+    /// onto an existing path or while it contains the working directory.
+    fn fix(&self, manifest: &Manifest) -> Option<Fix> {
+        let name = &manifest.frontmatter.name;
+        let dir_name = dir_name(manifest)?;
+        if dir_name == name {
+            return None;
+        }
+
+        match self.fix {
+            NameFix::Name => {
+                if !NAME_REGEX.is_match(dir_name) {
+                    return None;
+                }
+                let content = std::fs::read_to_string(&manifest.path).ok()?;
+                let range = name_value_range(content.get(..manifest.body_offset)?)?;
+                Some(Fix {
+                    code: DiagnosticCode::E003,
+                    message: format!("renamed '{}' to '{}'", name, dir_name),
+                    edits: vec![Edit::Replace {
+                        path: manifest.path.clone(),
+                        range,
+                        replacement: format!(" {}", dir_name),
+                    }],
+                })
+            }
+            NameFix::Directory => {
+                if !NAME_REGEX.is_match(name) {
+                    return None;
+                }
+                let dir = manifest.path.parent()?;
+                let to = dir.with_file_name(name);
+                if to.exists() || contains_current_dir(dir) {
+                    return None;
+                }
+                Some(Fix {
+                    code: DiagnosticCode::E003,
+                    message: format!("moved '{}' to '{}'", dir_name, name),
+                    edits: vec![Edit::Rename {
+                        path: dir.to_path_buf(),
+                        to,
+                    }],
+                })
+            }
+        }
+    }
+}
+
+/// The name of the directory containing the SKILL.md.
+fn dir_name(manifest: &Manifest) -> Option<&str> {
+    manifest.path.parent()?.file_name()?.to_str()
+}
+
+/// Byte range of the `name:` value in the frontmatter, from just after
+/// the colon to the end of the line.
+fn name_value_range(frontmatter: &str) -> Option<std::ops::Range<usize>> {
+    let mut offset = 0;
+    for line in frontmatter.split_inclusive('\n') {
+        if let Some(value) = line.strip_prefix("name:") {
+            let start = offset + "name:".len();
+            let len = value.trim_end_matches(['\n', '\r']).len();
+            return Some(start..start + len);
+        }
+        offset += line.len();
+    }
+    None
+}
+
+/// Returns true if `dir` is the working directory or one of its ancestors.
+fn contains_current_dir(dir: &Path) -> bool {
+    let cwd = std::env::current_dir().and_then(|cwd| cwd.canonicalize());
+    match (dir.canonicalize(), cwd) {
+        (Ok(dir), Ok(cwd)) => cwd.starts_with(dir),
+        // Err on the side of leaving the directory alone
+        _ => true,
+    }
+}
+
+/// W011: Flags names that collide with a target agent's built-in commands.
+pub struct ReservedNameRule {
+    /// Agents the skill is expected to run on.
+    agents: Vec<Agent>,
+}
+
+impl ReservedNameRule {
+    /// Create a new reserved name rule for the given target agents.
+    pub fn new(agents: Vec<Agent>) -> Self {
+        Self { agents }
+    }
+}
+
+impl Rule for ReservedNameRule {
+    fn name(&self) -> &'static str {
+        "reserved-name"
+    }
+
+    fn check(&self, manifest: &Manifest) -> Vec<Diagnostic> {
+        let name = &manifest.frontmatter.name;
+
+        let conflicts: Vec<&str> = self
+            .agents
+            .iter()
+            .filter(|agent| agent.is_reserved_name(name))
+            .map(|agent| agent.display_name())
+            .collect();
+
+        if conflicts.is_empty() {
+            return Vec::new();
+        }
+
+        vec![Diagnostic {
+            path: manifest.path.display().to_string(),
+            line: Some(2),
+            column: Some(7),
+            message: format!(
+                "Name '{}' collides with a built-in command of {}",
+                name,
+                conflicts.join(", ")
+            ),
+            code: DiagnosticCode::W011,
+            fix_hint: Some("Choose a more specific name".into()),
+        }]
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::path::PathBuf;
 
     #[test]
     fn test_valid_names() {
@@ -139,4 +289,69 @@ mod tests {
         assert!(!NAME_REGEX.is_match("my_skill")); // underscore
         assert!(!NAME_REGEX.is_match("")); // empty
     }
+
+    #[test]
+    fn test_reserved_names() {
+        let manifest = |name: &str| {
+            let content = format!("---\nname: {}\ndescription: Test\n---\n\n# Body\n", name);
+            Manifest::parse_content(PathBuf::from(format!("{}/SKILL.md", name)), &content).unwrap()
+        };
+
+        let rule = ReservedNameRule::new(vec![Agent::Claude, Agent::Codex, Agent::Cursor]);
+        let diags = rule.check(&manifest("review"));
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].code, DiagnosticCode::W011);
+        assert!(diags[0].message.contains("Claude Code, Codex"));
+
+        assert!(rule.check(&manifest("code-review")).is_empty());
+        assert!(ReservedNameRule::new(vec![Agent::Cursor])
+            .check(&manifest("review"))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_name_directory_fix() {
+        use crate::skill::fix::{apply, plan};
+        use tempfile::TempDir;
+
+        let temp = TempDir::new().unwrap();
+        let write = |dir: &str, name: &str| {
+            let skill_md = temp.path().join(dir).join("SKILL.md");
+            std::fs::create_dir_all(skill_md.parent().unwrap()).unwrap();
+            let content = format!(
+                "---\nname: \"{}\"\ndescription: Test\n---\n\n# Body\n",
+                name
+            );
+            std::fs::write(&skill_md, content).unwrap();
+            Manifest::parse(skill_md).unwrap()
+        };
+
+        let manifest = write("right", "wrong");
+        let fix = NameDirectoryRule::new(NameFix::Name)
+            .fix(&manifest)
+            .unwrap();
+        apply(&plan(&[fix]).unwrap()).unwrap();
+        let manifest = Manifest::parse(manifest.path).unwrap();
+        assert_eq!(manifest.frontmatter.name, "right");
+        assert!(NameDirectoryRule::new(NameFix::Name)
+            .fix(&manifest)
+            .is_none());
+
+        let manifest = write("old", "new");
+        let fix = NameDirectoryRule::new(NameFix::Directory)
+            .fix(&manifest)
+            .unwrap();
+        apply(&plan(&[fix]).unwrap()).unwrap();
+        assert!(!temp.path().join("old").exists());
+        let manifest = Manifest::parse(temp.path().join("new/SKILL.md")).unwrap();
+        assert!(NameDirectoryRule::new(NameFix::Directory)
+            .check(&manifest)
+            .is_empty());
+
+        // Invalid target names are left for the user
+        let manifest = write("Upper_Case", "lower");
+        assert!(NameDirectoryRule::new(NameFix::Name)
+            .fix(&manifest)
+            .is_none());
+    }
 }
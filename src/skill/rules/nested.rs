@@ -0,0 +1,79 @@
+//! Detects skills nested inside another skill's directory.
+
+use crate::skill::manifest::Manifest;
+use crate::skill::rules::Rule;
+use crate::skill::validator::{Diagnostic, DiagnosticCode};
+use std::path::{Path, PathBuf};
+
+/// W009: Warns if a SKILL.md lives inside another skill's directory tree.
+///
+/// Nested skills are discovered and installed as separate skills, which is
+/// rarely what the author intended. The search stops at the repository root.
+pub struct NestedSkillRule;
+
+impl Rule for NestedSkillRule {
+    fn name(&self) -> &'static str {
+        "nested-skill"
+    }
+
+    fn check(&self, manifest: &Manifest) -> Vec<Diagnostic> {
+        let Some(parent) = enclosing_skill_dir(&manifest.path) else {
+            return Vec::new();
+        };
+
+        vec![Diagnostic {
+            path: manifest.path.display().to_string(),
+            line: None,
+            column: None,
+            message: format!(
+                "Skill is nested inside another skill at {}",
+                parent.display()
+            ),
+            code: DiagnosticCode::W009,
+            fix_hint: Some("Move the skill out of the other skill's directory".into()),
+        }]
+    }
+}
+
+/// Find the nearest ancestor skill directory of the skill at `skill_md`.
+fn enclosing_skill_dir(skill_md: &Path) -> Option<PathBuf> {
+    let skill_dir = std::path::absolute(skill_md.parent()?).ok()?;
+
+    for dir in skill_dir.ancestors().skip(1) {
+        if dir.join("SKILL.md").is_file() {
+            return Some(dir.to_path_buf());
+        }
+        if dir.join(".git").exists() {
+            break;
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_nested_skill() {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir(temp.path().join(".git")).unwrap();
+        let outer = temp.path().join("my-skill");
+        let inner = outer.join("references/other");
+        fs::create_dir_all(&inner).unwrap();
+
+        let content = "---\nname: my-skill\ndescription: Test\n---\n";
+        fs::write(outer.join("SKILL.md"), content).unwrap();
+
+        let outer_manifest = Manifest::parse_content(outer.join("SKILL.md"), content).unwrap();
+        assert!(NestedSkillRule.check(&outer_manifest).is_empty());
+
+        let inner_manifest = Manifest::parse_content(inner.join("SKILL.md"), content).unwrap();
+        let diags = NestedSkillRule.check(&inner_manifest);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].code, DiagnosticCode::W009);
+    }
+}
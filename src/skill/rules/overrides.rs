@@ -0,0 +1,128 @@
+//! Validates per-agent overrides under the `agents` frontmatter key.
+
+use crate::agent::Agent;
+use crate::skill::frontmatter::Frontmatter;
+use crate::skill::manifest::Manifest;
+use crate::skill::overrides::{agent_for_key, resolve, AGENTS_KEY, FIXED_FIELDS};
+use crate::skill::rules::{frontmatter_key_line, Rule};
+use crate::skill::validator::{Diagnostic, DiagnosticCode};
+use serde_yaml::Value;
+
+/// E013: Validates the `agents` overrides map.
+///
+/// Checks that every key names a known agent, that overrides only set
+/// known fields other than `name`, and that the frontmatter is still valid
+/// once an agent's overrides are applied.
+pub struct AgentOverridesRule;
+
+impl Rule for AgentOverridesRule {
+    fn name(&self) -> &'static str {
+        "agent-overrides"
+    }
+
+    fn check(&self, manifest: &Manifest) -> Vec<Diagnostic> {
+        let Some(agents) = &manifest.frontmatter.agents else {
+            return Vec::new();
+        };
+
+        let mut diagnostics = Vec::new();
+        let mut error = |message: String, fix_hint: Option<String>| {
+            diagnostics.push(Diagnostic {
+                path: manifest.path.display().to_string(),
+                line: frontmatter_key_line(&manifest.frontmatter_raw, AGENTS_KEY),
+                column: None,
+                message,
+                code: DiagnosticCode::E013,
+                fix_hint,
+            });
+        };
+
+        for (key, overrides) in agents {
+            let Some(agent) = agent_for_key(key) else {
+                let known: Vec<&str> = Agent::all().iter().map(|a| a.cli_name()).collect();
+                error(
+                    format!("Unknown agent '{}' in agents overrides", key),
+                    Some(format!("Use one of: {}", known.join(", "))),
+                );
+                continue;
+            };
+
+            let fields = match overrides {
+                Value::Mapping(fields) => fields,
+                Value::Null => continue,
+                _ => {
+                    error(
+                        format!("Overrides for '{}' must be a mapping of fields", key),
+                        None,
+                    );
+                    continue;
+                }
+            };
+
+            let mut fields_ok = true;
+            for field in fields.keys() {
+                let field = field.as_str().unwrap_or_default();
+                if FIXED_FIELDS.contains(&field) {
+                    fields_ok = false;
+                    error(
+                        format!("'{}' cannot be overridden per agent ('{}')", field, key),
+                        None,
+                    );
+                } else if !Frontmatter::KEY_ORDER.contains(&field) && field != "keywords" {
+                    fields_ok = false;
+                    error(
+                        format!("Unknown field '{}' in overrides for '{}'", field, key),
+                        None,
+                    );
+                }
+            }
+
+            if fields_ok {
+                if let Err(e) = resolve(&manifest.frontmatter_raw, agent) {
+                    error(
+                        format!("Overrides for '{}' produce invalid frontmatter: {}", key, e),
+                        None,
+                    );
+                }
+            }
+        }
+
+        diagnostics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn check(frontmatter: &str) -> Vec<String> {
+        let content = format!(
+            "---\nname: demo\ndescription: Demo\n{}---\n\nBody\n",
+            frontmatter
+        );
+        let manifest = Manifest::parse_content(PathBuf::from("demo/SKILL.md"), &content).unwrap();
+        AgentOverridesRule
+            .check(&manifest)
+            .into_iter()
+            .map(|d| d.message)
+            .collect()
+    }
+
+    #[test]
+    fn test_agent_overrides() {
+        assert!(check("agents:\n  claude:\n    context: fork\n  open-code: {}\n").is_empty());
+        assert_eq!(
+            check("agents:\n  cursr:\n    model: x\n"),
+            vec!["Unknown agent 'cursr' in agents overrides"]
+        );
+        assert_eq!(
+            check("agents:\n  claude:\n    name: other\n    colour: red\n"),
+            vec![
+                "'name' cannot be overridden per agent ('claude')",
+                "Unknown field 'colour' in overrides for 'claude'"
+            ]
+        );
+        assert_eq!(check("agents:\n  claude:\n    tags: oops\n").len(), 1);
+    }
+}
@@ -0,0 +1,65 @@
+//! Validates the `requires` runtime requirements section.
+
+use crate::skill::manifest::Manifest;
+use crate::skill::requirements::{Requirements, REQUIRES_KEY};
+use crate::skill::rules::{frontmatter_key_line, Rule};
+use crate::skill::validator::{Diagnostic, DiagnosticCode};
+
+/// E015: Validates the schema of the `requires` section.
+///
+/// Checks that it only has `bins` and `env` lists, that programs are names
+/// with an optional `>= <version>`, and that variables are valid names.
+pub struct RequirementsRule;
+
+impl Rule for RequirementsRule {
+    fn name(&self) -> &'static str {
+        "requirements"
+    }
+
+    fn check(&self, manifest: &Manifest) -> Vec<Diagnostic> {
+        let Some(requires) = &manifest.frontmatter.requires else {
+            return Vec::new();
+        };
+        let Err(problems) = Requirements::parse(requires) else {
+            return Vec::new();
+        };
+
+        let line = frontmatter_key_line(&manifest.frontmatter_raw, REQUIRES_KEY);
+        problems
+            .into_iter()
+            .map(|message| Diagnostic {
+                path: manifest.path.display().to_string(),
+                line,
+                column: None,
+                message,
+                code: DiagnosticCode::E015,
+                fix_hint: Some("Use 'bins: [jq, python3 >= 3.10]' and 'env: [TOKEN]'".into()),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_requirements_schema() {
+        let manifest = |requires: &str| {
+            let content = format!(
+                "---\nname: tool\ndescription: Test\nrequires:\n{}\n---\n\n# Body\n",
+                requires
+            );
+            Manifest::parse_content(PathBuf::from("tool/SKILL.md"), &content).unwrap()
+        };
+
+        let valid = manifest("  bins: [jq, python3 >= 3.10]\n  env: [GITHUB_TOKEN]");
+        assert!(RequirementsRule.check(&valid).is_empty());
+
+        let diags = RequirementsRule.check(&manifest("  bins: jq\n  env: [1TOKEN]"));
+        assert_eq!(diags.len(), 2);
+        assert_eq!(diags[0].code, DiagnosticCode::E015);
+        assert_eq!(diags[0].line, Some(4));
+    }
+}
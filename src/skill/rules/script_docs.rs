@@ -0,0 +1,176 @@
+//! Validates that a skill's `## Scripts` section matches its `scripts/`.
+
+use crate::skill::manifest::Manifest;
+use crate::skill::rules::{scripts, Rule};
+use crate::skill::validator::{Diagnostic, DiagnosticCode};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::BTreeSet;
+
+/// A markdown heading: its level and text.
+static HEADING_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(#{1,6})\s+(.*?)\s*#*\s*$").unwrap());
+
+/// Pattern for paths into the skill's `scripts/` directory.
+static SCRIPT_PATH_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\bscripts/([\w.-]*\w)").unwrap());
+
+/// Pattern for file names with a script extension, as used in examples.
+static SCRIPT_NAME_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?:^|[\s`'(])(?:\./)?([\w-][\w.-]*\.(?:sh|bash|py|js|mjs|ts|rb|pl))\b").unwrap()
+});
+
+/// W015: Warns when the `## Scripts` section and `scripts/` disagree.
+///
+/// Only skills whose body has a Scripts section are checked. Every file in
+/// `scripts/` must be mentioned there, and every script the section
+/// mentions, as a `scripts/` path or by file name in an example, must exist.
+pub struct ScriptDocsRule;
+
+impl Rule for ScriptDocsRule {
+    fn name(&self) -> &'static str {
+        "script-docs"
+    }
+
+    fn check(&self, manifest: &Manifest) -> Vec<Diagnostic> {
+        let Some(section) = scripts_section(&manifest.body) else {
+            return Vec::new();
+        };
+
+        let files: BTreeSet<String> = scripts(manifest)
+            .iter()
+            .filter_map(|path| Some(path.file_name()?.to_str()?.to_string()))
+            .collect();
+
+        let mut diagnostics = Vec::new();
+        let mut warn = |line: usize, message: String, fix_hint: String| {
+            diagnostics.push(Diagnostic {
+                path: manifest.path.display().to_string(),
+                line: Some(manifest.body_start_line + line),
+                column: None,
+                message,
+                code: DiagnosticCode::W015,
+                fix_hint: Some(fix_hint),
+            });
+        };
+
+        let mut documented = BTreeSet::new();
+        let mut missing = BTreeSet::new();
+        for (line, text) in &section.lines {
+            let mentions = SCRIPT_PATH_REGEX
+                .captures_iter(text)
+                .chain(SCRIPT_NAME_REGEX.captures_iter(text));
+            for cap in mentions {
+                // Paths may point into subdirectories of scripts/
+                let name = cap[1].split('/').next().unwrap_or_default().to_string();
+                if files.contains(&name) {
+                    documented.insert(name);
+                } else if missing.insert(name.clone()) {
+                    warn(
+                        *line,
+                        format!(
+                            "Scripts section mentions '{}', which is not in scripts/",
+                            name
+                        ),
+                        format!("Add scripts/{} or fix the name", name),
+                    );
+                }
+            }
+        }
+
+        for file in files.difference(&documented) {
+            warn(
+                section.heading_line,
+                format!(
+                    "Script scripts/{} is not documented in the Scripts section",
+                    file
+                ),
+                format!("Describe scripts/{} and its arguments", file),
+            );
+        }
+
+        diagnostics
+    }
+}
+
+/// The lines of a body's Scripts section.
+struct Section<'a> {
+    /// Body line of the heading (0-based).
+    heading_line: usize,
+    /// Body lines of the section content (0-based) with their text.
+    lines: Vec<(usize, &'a str)>,
+}
+
+/// Find the first heading titled "Scripts" and the lines up to the next
+/// heading of the same or a higher level.
+fn scripts_section(body: &str) -> Option<Section<'_>> {
+    let mut section: Option<(Section, usize)> = None;
+    let mut in_fence = false;
+
+    for (i, line) in body.lines().enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+        }
+        let heading = (!in_fence)
+            .then(|| HEADING_REGEX.captures(line))
+            .flatten()
+            .map(|cap| (cap[1].len(), cap[2].to_string()));
+
+        if let Some((current, level)) = &mut section {
+            if heading.as_ref().is_some_and(|(next, _)| *next <= *level) {
+                break;
+            }
+            current.lines.push((i, line));
+        } else if let Some((level, text)) = heading {
+            if text.eq_ignore_ascii_case("scripts") {
+                let current = Section {
+                    heading_line: i,
+                    lines: Vec::new(),
+                };
+                section = Some((current, level));
+            }
+        }
+    }
+
+    section.map(|(section, _)| section)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_script_docs() {
+        let temp = TempDir::new().unwrap();
+        let skill = temp.path().join("tool");
+        std::fs::create_dir_all(skill.join("scripts")).unwrap();
+        for script in ["run.sh", "lint.py", "helper.sh"] {
+            std::fs::write(skill.join("scripts").join(script), "#!/bin/sh\n").unwrap();
+        }
+        let body = "# Tool\n\n## Scripts\n\n- `scripts/run.sh <file>`: run it\n\n\
+                    ```bash\n# Usage\npython lint.py --fix\n./build.sh\n```\n\n\
+                    ## Other\n\nhelper.sh is not documented here.\n";
+        std::fs::write(
+            skill.join("SKILL.md"),
+            format!("---\nname: tool\ndescription: Test\n---\n\n{}", body),
+        )
+        .unwrap();
+        let manifest = Manifest::parse(skill.join("SKILL.md")).unwrap();
+
+        let diags = ScriptDocsRule.check(&manifest);
+        let messages: Vec<&str> = diags.iter().map(|d| d.message.as_str()).collect();
+        assert_eq!(diags.len(), 2, "{:?}", messages);
+        assert!(messages[0].contains("'build.sh'"));
+        assert_eq!(diags[0].line, Some(manifest.body_start_line + 9));
+        assert!(messages[1].contains("scripts/helper.sh"));
+        assert_eq!(diags[1].line, Some(manifest.body_start_line + 2));
+
+        let manifest = Manifest::parse_content(
+            skill.join("SKILL.md"),
+            "---\nname: tool\ndescription: Test\n---\n\n# Tool\n",
+        )
+        .unwrap();
+        assert!(ScriptDocsRule.check(&manifest).is_empty());
+    }
+}
@@ -1,105 +1,316 @@
-//! Validates script files: executable permissions and shebang lines.
+//! Validates script files: executable permissions, shebang lines, and
+//! interpreter availability.
 
+use crate::agent::{find_executable, Agent};
+use crate::skill::fix::{Edit, Fix};
 use crate::skill::manifest::Manifest;
 use crate::skill::rules::Rule;
 use crate::skill::validator::{Diagnostic, DiagnosticCode};
+use std::io::Read;
+use std::path::{Path, PathBuf};
 
 /// W002: Warns if scripts are not executable.
 pub struct ScriptExecutableRule;
 
+impl ScriptExecutableRule {
+    /// Scripts without any executable bit set.
+    fn offenders(manifest: &Manifest) -> Vec<PathBuf> {
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            scripts(manifest)
+                .into_iter()
+                .filter(|path| {
+                    path.metadata()
+                        .is_ok_and(|meta| meta.permissions().mode() & 0o111 == 0)
+                })
+                .collect()
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = manifest;
+            Vec::new()
+        }
+    }
+}
+
 impl Rule for ScriptExecutableRule {
     fn name(&self) -> &'static str {
         "script-executable"
     }
 
     fn check(&self, manifest: &Manifest) -> Vec<Diagnostic> {
-        let Some(skill_dir) = manifest.path.parent() else {
-            return Vec::new();
-        };
-
-        let scripts_dir = skill_dir.join("scripts");
-        if !scripts_dir.exists() {
-            return Vec::new();
-        }
+        Self::offenders(manifest)
+            .into_iter()
+            .map(|path| Diagnostic {
+                path: path.display().to_string(),
+                line: None,
+                column: None,
+                message: "Script is not executable".into(),
+                code: DiagnosticCode::W002,
+                fix_hint: Some(format!("Run: chmod +x {}", path.display())),
+            })
+            .collect()
+    }
 
-        let Ok(entries) = std::fs::read_dir(&scripts_dir) else {
-            return Vec::new();
-        };
-
-        let mut diagnostics = Vec::new();
-
-        for entry in entries.flatten() {
-            let path = entry.path();
-            if !path.is_file() {
-                continue;
-            }
-
-            #[cfg(unix)]
-            {
-                use std::os::unix::fs::PermissionsExt;
-                if let Ok(meta) = path.metadata() {
-                    if meta.permissions().mode() & 0o111 == 0 {
-                        diagnostics.push(Diagnostic {
-                            path: path.display().to_string(),
-                            line: None,
-                            column: None,
-                            message: "Script is not executable".into(),
-                            code: DiagnosticCode::W002,
-                            fix_hint: Some(format!("Run: chmod +x {}", path.display())),
-                        });
-                    }
-                }
-            }
+    fn fix(&self, manifest: &Manifest) -> Option<Fix> {
+        let offenders = Self::offenders(manifest);
+        if offenders.is_empty() {
+            return None;
         }
 
-        diagnostics
+        Some(Fix {
+            code: DiagnosticCode::W002,
+            message: format!("made {} script(s) executable", offenders.len()),
+            edits: offenders
+                .into_iter()
+                .map(|path| Edit::SetExecutable { path })
+                .collect(),
+        })
     }
 }
 
 /// W003: Warns if scripts are missing shebang
 pub struct ScriptShebangRule;
 
+impl ScriptShebangRule {
+    /// Scripts whose first line is not a shebang.
+    fn offenders(manifest: &Manifest) -> Vec<PathBuf> {
+        scripts(manifest)
+            .into_iter()
+            .filter(|path| {
+                // Binary files are skipped; only the start of each file is read
+                read_prefix(path, 512)
+                    .is_some_and(|start| is_text(&start) && !start.starts_with(b"#!"))
+            })
+            .collect()
+    }
+}
+
 impl Rule for ScriptShebangRule {
     fn name(&self) -> &'static str {
         "script-shebang"
     }
 
     fn check(&self, manifest: &Manifest) -> Vec<Diagnostic> {
-        let Some(skill_dir) = manifest.path.parent() else {
-            return Vec::new();
-        };
+        Self::offenders(manifest)
+            .into_iter()
+            .map(|path| Diagnostic {
+                path: path.display().to_string(),
+                line: Some(1),
+                column: Some(1),
+                message: "Script missing shebang line".into(),
+                code: DiagnosticCode::W003,
+                fix_hint: Some("Add #!/usr/bin/env <interpreter> as first line".into()),
+            })
+            .collect()
+    }
 
-        let scripts_dir = skill_dir.join("scripts");
-        if !scripts_dir.exists() {
-            return Vec::new();
+    /// Add a shebang to scripts whose interpreter is known from the extension.
+    fn fix(&self, manifest: &Manifest) -> Option<Fix> {
+        let edits: Vec<Edit> = Self::offenders(manifest)
+            .into_iter()
+            .filter_map(|path| {
+                let interpreter = interpreter_for(&path)?;
+                Some(Edit::Replace {
+                    path,
+                    range: 0..0,
+                    replacement: format!("#!/usr/bin/env {}\n", interpreter),
+                })
+            })
+            .collect();
+        if edits.is_empty() {
+            return None;
         }
 
-        let Ok(entries) = std::fs::read_dir(&scripts_dir) else {
-            return Vec::new();
-        };
-
-        let mut diagnostics = Vec::new();
-
-        for entry in entries.flatten() {
-            let path = entry.path();
-            if !path.is_file() {
-                continue;
-            }
-
-            if let Ok(content) = std::fs::read_to_string(&path) {
-                if !content.starts_with("#!") {
-                    diagnostics.push(Diagnostic {
-                        path: path.display().to_string(),
-                        line: Some(1),
-                        column: Some(1),
-                        message: "Script missing shebang line".into(),
-                        code: DiagnosticCode::W003,
-                        fix_hint: Some("Add #!/usr/bin/env <interpreter> as first line".into()),
-                    });
+        Some(Fix {
+            code: DiagnosticCode::W003,
+            message: format!("added a shebang line to {} script(s)", edits.len()),
+            edits,
+        })
+    }
+}
+
+/// W014: Warns when a script's shebang interpreter is not installed.
+///
+/// Opt-in, since the result depends on the machine running the linter.
+pub struct ScriptInterpreterRule {
+    /// Agents the skill is expected to run on.
+    agents: Vec<Agent>,
+}
+
+impl ScriptInterpreterRule {
+    /// Create a new interpreter rule, naming the target `agents` that run
+    /// scripts in its warnings.
+    pub fn new(agents: Vec<Agent>) -> Self {
+        Self { agents }
+    }
+}
+
+impl Rule for ScriptInterpreterRule {
+    fn name(&self) -> &'static str {
+        "script-interpreter"
+    }
+
+    fn check(&self, manifest: &Manifest) -> Vec<Diagnostic> {
+        let runners: Vec<&str> = self
+            .agents
+            .iter()
+            .filter(|agent| agent.features().scripts)
+            .map(|agent| agent.display_name())
+            .collect();
+
+        scripts(manifest)
+            .into_iter()
+            .filter_map(|path| {
+                let start = read_prefix(&path, 512)?;
+                let interpreter = shebang_interpreter(std::str::from_utf8(&start).ok()?)?;
+                if interpreter_available(&interpreter) {
+                    return None;
                 }
-            }
-        }
 
-        diagnostics
+                let mut message = format!("Interpreter '{}' not found", interpreter);
+                if !runners.is_empty() {
+                    message.push_str(&format!(
+                        "; the script fails when run by {}",
+                        runners.join(", ")
+                    ));
+                }
+                Some(Diagnostic {
+                    path: path.display().to_string(),
+                    line: Some(1),
+                    column: Some(1),
+                    message,
+                    code: DiagnosticCode::W014,
+                    fix_hint: Some(format!(
+                        "Install {} or document it under the skill's compatibility",
+                        interpreter
+                    )),
+                })
+            })
+            .collect()
+    }
+}
+
+/// The interpreter named by a script's shebang line.
+///
+/// For `#!/usr/bin/env [-S] <program> ...` this is the program; otherwise
+/// the interpreter path itself.
+fn shebang_interpreter(content: &str) -> Option<String> {
+    let line = content.lines().next()?.strip_prefix("#!")?;
+    let mut words = line.split_whitespace();
+    let interpreter = words.next()?;
+    if Path::new(interpreter).file_name()? != "env" {
+        return Some(interpreter.to_string());
+    }
+    words
+        .find(|word| !word.starts_with('-') && !word.contains('='))
+        .map(str::to_string)
+}
+
+/// Returns true if `interpreter` is a path that exists or a program on `PATH`.
+fn interpreter_available(interpreter: &str) -> bool {
+    if interpreter.contains('/') {
+        Path::new(interpreter).is_file()
+    } else {
+        find_executable(interpreter).is_some()
+    }
+}
+
+/// Files directly in the skill's `scripts/` directory.
+pub(crate) fn scripts(manifest: &Manifest) -> Vec<PathBuf> {
+    let Some(skill_dir) = manifest.path.parent() else {
+        return Vec::new();
+    };
+    let Ok(entries) = std::fs::read_dir(skill_dir.join("scripts")) else {
+        return Vec::new();
+    };
+
+    let mut scripts: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .collect();
+    scripts.sort();
+    scripts
+}
+
+/// The first `len` bytes of a file (fewer if it is shorter).
+fn read_prefix(path: &Path, len: u64) -> Option<Vec<u8>> {
+    let mut start = Vec::new();
+    std::fs::File::open(path)
+        .ok()?
+        .take(len)
+        .read_to_end(&mut start)
+        .ok()?;
+    Some(start)
+}
+
+/// Returns true if `bytes` (possibly cut mid-character) is UTF-8 text.
+fn is_text(bytes: &[u8]) -> bool {
+    match std::str::from_utf8(bytes) {
+        Ok(_) => true,
+        // Only a character cut off at the end
+        Err(e) => e.error_len().is_none(),
+    }
+}
+
+/// The interpreter for a script, judging by its file extension.
+fn interpreter_for(path: &Path) -> Option<&'static str> {
+    match path.extension()?.to_str()? {
+        "sh" => Some("sh"),
+        "bash" => Some("bash"),
+        "py" => Some("python3"),
+        "js" | "mjs" => Some("node"),
+        "rb" => Some("ruby"),
+        "pl" => Some("perl"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_shebang_interpreter() {
+        let parse = |content: &str| shebang_interpreter(content);
+        assert_eq!(
+            parse("#!/usr/bin/env python3\n").as_deref(),
+            Some("python3")
+        );
+        assert_eq!(
+            parse("#!/usr/bin/env -S deno run --allow-read\n").as_deref(),
+            Some("deno")
+        );
+        assert_eq!(parse("#!/bin/bash -e\n").as_deref(), Some("/bin/bash"));
+        assert_eq!(parse("echo hi\n"), None);
+    }
+
+    #[test]
+    fn test_missing_interpreter() {
+        let temp = TempDir::new().unwrap();
+        let skill = temp.path().join("tool");
+        std::fs::create_dir_all(skill.join("scripts")).unwrap();
+        std::fs::write(
+            skill.join("SKILL.md"),
+            "---\nname: tool\ndescription: Test\n---\n\n# Tool\n",
+        )
+        .unwrap();
+        std::fs::write(
+            skill.join("scripts/run"),
+            "#!/usr/bin/env skilo-no-such-interpreter\n",
+        )
+        .unwrap();
+        std::fs::write(skill.join("scripts/ok"), "#!/no/such/dir/sh\n").unwrap();
+        let manifest = Manifest::parse(skill.join("SKILL.md")).unwrap();
+
+        let diags = ScriptInterpreterRule::new(vec![Agent::Claude]).check(&manifest);
+        assert_eq!(diags.len(), 2);
+        assert_eq!(diags[0].code, DiagnosticCode::W014);
+        assert!(diags[0].message.contains("'/no/such/dir/sh'"));
+        assert!(diags[1].message.contains("skilo-no-such-interpreter"));
+        assert!(diags[1].message.ends_with("run by Claude Code"));
     }
 }
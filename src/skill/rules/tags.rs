@@ -0,0 +1,133 @@
+//! Validates the optional `tags` list in frontmatter.
+
+use crate::skill::manifest::Manifest;
+use crate::skill::rules::name::NAME_REGEX;
+use crate::skill::rules::{frontmatter_key_line, Rule};
+use crate::skill::validator::{Diagnostic, DiagnosticCode};
+
+/// W006: Validates tag format (lowercase alphanumeric + single hyphens, no duplicates).
+pub struct TagFormatRule;
+
+impl Rule for TagFormatRule {
+    fn name(&self) -> &'static str {
+        "tag-format"
+    }
+
+    fn check(&self, manifest: &Manifest) -> Vec<Diagnostic> {
+        let line = tags_line(manifest);
+        let mut seen = std::collections::HashSet::new();
+        let mut diagnostics = Vec::new();
+
+        for tag in manifest.frontmatter.tags() {
+            if !NAME_REGEX.is_match(tag) {
+                diagnostics.push(Diagnostic {
+                    path: manifest.path.display().to_string(),
+                    line,
+                    column: None,
+                    message: format!(
+                        "Invalid tag '{}': must be lowercase alphanumeric with single hyphens",
+                        tag
+                    ),
+                    code: DiagnosticCode::W006,
+                    fix_hint: Some(format!("Use '{}'", normalize_tag(tag))),
+                });
+            } else if !seen.insert(tag.as_str()) {
+                diagnostics.push(Diagnostic {
+                    path: manifest.path.display().to_string(),
+                    line,
+                    column: None,
+                    message: format!("Duplicate tag '{}'", tag),
+                    code: DiagnosticCode::W006,
+                    fix_hint: None,
+                });
+            }
+        }
+
+        diagnostics
+    }
+}
+
+/// W007: Validates the number of tags.
+pub struct TagCountRule {
+    /// Maximum allowed number of tags.
+    max_count: usize,
+}
+
+impl TagCountRule {
+    /// Create a new tag count rule with the specified maximum.
+    pub fn new(max_count: usize) -> Self {
+        Self { max_count }
+    }
+}
+
+impl Rule for TagCountRule {
+    fn name(&self) -> &'static str {
+        "tag-count"
+    }
+
+    fn check(&self, manifest: &Manifest) -> Vec<Diagnostic> {
+        let count = manifest.frontmatter.tags().len();
+
+        if count <= self.max_count {
+            return Vec::new();
+        }
+
+        vec![Diagnostic {
+            path: manifest.path.display().to_string(),
+            line: tags_line(manifest),
+            column: None,
+            message: format!("Too many tags ({}, max {})", count, self.max_count),
+            code: DiagnosticCode::W007,
+            fix_hint: Some("Keep only the tags that best describe the skill".into()),
+        }]
+    }
+}
+
+/// Line of the `tags` (or `keywords`) key.
+fn tags_line(manifest: &Manifest) -> Option<usize> {
+    frontmatter_key_line(&manifest.frontmatter_raw, "tags")
+        .or_else(|| frontmatter_key_line(&manifest.frontmatter_raw, "keywords"))
+}
+
+/// Suggest a valid spelling of a tag.
+fn normalize_tag(tag: &str) -> String {
+    let lowered: String = tag
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    lowered
+        .split('-')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn manifest(frontmatter: &str) -> Manifest {
+        let content = format!("---\n{}\n---\n\n# Body\n", frontmatter);
+        Manifest::parse_content(PathBuf::from("my-skill/SKILL.md"), &content).unwrap()
+    }
+
+    #[test]
+    fn test_tag_format() {
+        let m = manifest("name: my-skill\ndescription: Test\ntags: [web, Data Science, web]");
+        let diags = TagFormatRule.check(&m);
+        assert_eq!(diags.len(), 2);
+        assert_eq!(diags[0].fix_hint.as_deref(), Some("Use 'data-science'"));
+        assert_eq!(diags[0].line, Some(4));
+        assert!(diags[1].message.contains("Duplicate"));
+    }
+
+    #[test]
+    fn test_keywords_alias_and_count() {
+        let m = manifest("name: my-skill\ndescription: Test\nkeywords:\n  - a\n  - b\n  - c");
+        assert_eq!(m.frontmatter.tags(), ["a", "b", "c"]);
+        assert!(TagCountRule::new(3).check(&m).is_empty());
+        assert_eq!(TagCountRule::new(2).check(&m)[0].code, DiagnosticCode::W007);
+    }
+}
@@ -0,0 +1,252 @@
+//! Splitting long skill bodies into `references/` files.
+//!
+//! Content is split into paragraphs at blank lines outside code fences.
+//! Paragraphs are kept in the body while they fit; the rest is grouped into
+//! reference files that start at headings, and linked from a References
+//! section at the end of the body.
+
+use crate::templates::to_kebab_case;
+
+/// Lines taken by the References heading and the blank lines around it.
+const SECTION_LINES: usize = 3;
+
+/// A file moved out of the body into `references/`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ReferenceFile {
+    /// File name within `references/`.
+    pub file_name: String,
+    /// Link text used in the body.
+    pub title: String,
+    /// File content.
+    pub content: String,
+}
+
+/// A run of lines separated from its neighbours by blank lines.
+pub(crate) struct Paragraph<'a> {
+    /// The lines of the paragraph.
+    pub(crate) lines: Vec<&'a str>,
+    /// Heading text, if the paragraph starts with a heading.
+    pub(crate) heading: Option<String>,
+}
+
+/// Split a body longer than `max_lines` lines into a shorter body that links
+/// to reference files, or `None` if it already fits.
+///
+/// `reserved` lines are left free for content the caller adds to the body.
+/// File names in `taken` are not reused.
+pub fn split_body(
+    body: &str,
+    max_lines: usize,
+    reserved: usize,
+    taken: &[String],
+) -> Option<(String, Vec<ReferenceFile>)> {
+    if body.trim().lines().count() + reserved <= max_lines {
+        return None;
+    }
+
+    // The space kept for links grows until every link fits
+    let mut links = 0;
+    loop {
+        let budget = max_lines.saturating_sub(reserved + SECTION_LINES + links);
+        let (kept, references) = split_sections(body.trim(), budget, max_lines, taken);
+        if references.len() <= links {
+            let mut body = kept;
+            if !references.is_empty() {
+                if !body.is_empty() {
+                    body.push_str("\n\n");
+                }
+                body.push_str("## References\n\n");
+                for reference in &references {
+                    body.push_str(&format!(
+                        "- [{}](references/{})\n",
+                        reference.title, reference.file_name
+                    ));
+                }
+            }
+            return Some((body, references));
+        }
+        links = references.len();
+    }
+}
+
+/// Keep paragraphs in the body while they fit in `budget` lines, moving the
+/// rest to reference files of at most `max_lines` lines each. A heading
+/// starts a new reference file. File names in `taken` are not reused.
+pub fn split_sections(
+    content: &str,
+    budget: usize,
+    max_lines: usize,
+    taken: &[String],
+) -> (String, Vec<ReferenceFile>) {
+    let paragraphs = paragraphs(content);
+
+    let mut split = 0;
+    let mut used = 0;
+    for p in &paragraphs {
+        // Paragraphs after the first are preceded by a blank line
+        let cost = p.lines.len() + usize::from(split > 0);
+        if used + cost > budget {
+            break;
+        }
+        used += cost;
+        split += 1;
+    }
+    // A heading stays with the content below it
+    while split > 0 && split < paragraphs.len() && is_bare_heading(&paragraphs[split - 1]) {
+        split -= 1;
+    }
+    let (body, rest) = paragraphs.split_at(split);
+    let body: Vec<&Paragraph> = body.iter().collect();
+
+    let mut groups: Vec<Vec<&Paragraph>> = Vec::new();
+    let mut group_lines = 0;
+    for p in rest {
+        let cost = p.lines.len() + 1;
+        let full = group_lines + cost > max_lines;
+        match groups.last_mut() {
+            Some(group) if p.heading.is_none() && !full => group.push(p),
+            _ => {
+                groups.push(vec![p]);
+                group_lines = 0;
+            }
+        }
+        group_lines += cost;
+    }
+
+    let mut references: Vec<ReferenceFile> = Vec::new();
+    let mut section = body.iter().rev().find_map(|p| p.heading.clone());
+    for (i, group) in groups.iter().enumerate() {
+        // Files continuing a long section are named after it
+        let title = match (&group[0].heading, &section) {
+            (Some(heading), _) => {
+                section = Some(heading.clone());
+                heading.clone()
+            }
+            (None, Some(heading)) => format!("{} (continued)", heading),
+            (None, None) => format!("Part {}", i + 1),
+        };
+        let stem = section
+            .as_deref()
+            .map(to_kebab_case)
+            .filter(|s| !s.is_empty())
+            .unwrap_or_else(|| format!("part-{}", i + 1));
+
+        let mut file_name = format!("{}.md", stem);
+        let mut n = 2;
+        while taken.contains(&file_name) || references.iter().any(|r| r.file_name == file_name) {
+            file_name = format!("{}-{}.md", stem, n);
+            n += 1;
+        }
+
+        references.push(ReferenceFile {
+            file_name,
+            title,
+            content: relink(&join(group)) + "\n",
+        });
+    }
+
+    (join(&body), references)
+}
+
+/// Adjust relative links in content moved from the skill root into
+/// `references/`.
+fn relink(content: &str) -> String {
+    content
+        .replace("](references/", "](")
+        .replace("](scripts/", "](../scripts/")
+        .replace("](assets/", "](../assets/")
+}
+
+/// Returns true if the paragraph is a heading with nothing below it.
+fn is_bare_heading(paragraph: &Paragraph) -> bool {
+    paragraph.heading.is_some() && paragraph.lines.len() == 1
+}
+
+/// Join paragraphs with blank lines.
+fn join(paragraphs: &[&Paragraph]) -> String {
+    paragraphs
+        .iter()
+        .map(|p| p.lines.join("\n"))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Split content into paragraphs at blank lines outside code fences.
+pub(crate) fn paragraphs(content: &str) -> Vec<Paragraph<'_>> {
+    let mut paragraphs = Vec::new();
+    let mut lines: Vec<&str> = Vec::new();
+    let mut in_fence = false;
+
+    for line in content.lines() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+        }
+        if line.trim().is_empty() && !in_fence {
+            if !lines.is_empty() {
+                paragraphs.push(paragraph(std::mem::take(&mut lines)));
+            }
+            continue;
+        }
+        lines.push(line);
+    }
+    if !lines.is_empty() {
+        paragraphs.push(paragraph(lines));
+    }
+    paragraphs
+}
+
+fn paragraph(lines: Vec<&str>) -> Paragraph<'_> {
+    let heading = lines[0]
+        .strip_prefix('#')
+        .map(|h| h.trim_start_matches('#'))
+        .filter(|h| h.starts_with(' '))
+        .map(|h| h.trim().to_string());
+    Paragraph { lines, heading }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENT: &str = "Review pull requests carefully.\n\n\
+        ## Checklist\n\n- tests\n- docs\n\n\
+        ## Style\n\nPrefer small functions.\n\n\
+        ```\nlet a = 1;\n\nlet b = 2;\n```\n";
+
+    #[test]
+    fn test_split_sections() {
+        let (body, references) = split_sections(CONTENT, 2, 100, &[]);
+        assert_eq!(body, "Review pull requests carefully.");
+        let names: Vec<&str> = references.iter().map(|r| r.file_name.as_str()).collect();
+        assert_eq!(names, vec!["checklist.md", "style.md"]);
+        assert_eq!(references[0].content, "## Checklist\n\n- tests\n- docs\n");
+        assert_eq!(references[1].title, "Style");
+        assert!(split_sections(
+            "Intro.\n\n## Run\n\nSee [run](scripts/run.sh).\n",
+            1,
+            100,
+            &[]
+        )
+        .1[0]
+            .content
+            .contains("](../scripts/run.sh)"));
+        // Code fences are never split
+        assert!(references[1].content.contains("let a = 1;\n\nlet b = 2;"));
+
+        // Long sections continue in further files
+        let (_, references) = split_sections(CONTENT, 2, 5, &["style.md".to_string()]);
+        let names: Vec<&str> = references.iter().map(|r| r.file_name.as_str()).collect();
+        assert_eq!(names, vec!["checklist.md", "style-2.md", "style-3.md"]);
+        assert_eq!(references[2].title, "Style (continued)");
+    }
+
+    #[test]
+    fn test_split_body() {
+        assert!(split_body(CONTENT, 100, 0, &[]).is_none());
+
+        let (body, references) = split_body(CONTENT, 12, 0, &[]).unwrap();
+        assert!(body.lines().count() <= 12);
+        assert!(body.ends_with("- [Style](references/style.md)\n"));
+        assert!(!references.is_empty());
+    }
+}
@@ -0,0 +1,143 @@
+//! Deterministic summaries of skill bodies for prompt embedding.
+//!
+//! Summaries are cut from the body line by line, so the same body always
+//! gives the same summary and every kept line reads as it does in SKILL.md.
+
+/// Summarize a markdown body.
+///
+/// With `lines_per_section`, every heading is kept together with the first
+/// N non-blank lines of its section. Without it, the summary is the first
+/// paragraph of the body and every heading.
+pub fn summarize(markdown: &str, lines_per_section: Option<usize>) -> String {
+    let mut blocks: Vec<Vec<&str>> = Vec::new();
+    let mut current: Vec<&str> = Vec::new();
+    let mut budget = lines_per_section;
+    let mut fence: Option<&str> = None;
+    let mut kept_fence: Option<&str> = None;
+    let mut seen_paragraph = false;
+
+    for line in markdown.lines() {
+        let trimmed = line.trim_start();
+
+        if fence.is_none() && is_heading(trimmed) {
+            seen_paragraph |= budget.is_none() && !current.is_empty();
+            close(&mut blocks, &mut current, &mut kept_fence);
+            blocks.push(vec![line]);
+            budget = lines_per_section;
+            continue;
+        }
+
+        let marker = fence_marker(trimmed);
+        let in_code = fence.is_some() || marker.is_some();
+        match (fence, marker) {
+            (None, Some(open)) => fence = Some(open),
+            (Some(open), Some(m)) if m.starts_with(open) => fence = None,
+            _ => {}
+        }
+
+        match budget {
+            Some(0) => {}
+            Some(ref mut remaining) => {
+                if line.trim().is_empty() {
+                    if !current.is_empty() {
+                        current.push(line);
+                    }
+                    continue;
+                }
+                *remaining -= 1;
+                current.push(line);
+                kept_fence = if in_code { fence } else { None };
+            }
+            None => {
+                // First paragraph mode: keep the first run of prose lines
+                if seen_paragraph {
+                    continue;
+                }
+                if line.trim().is_empty() || in_code {
+                    if !current.is_empty() {
+                        seen_paragraph = true;
+                        close(&mut blocks, &mut current, &mut kept_fence);
+                    }
+                    continue;
+                }
+                current.push(line);
+            }
+        }
+    }
+    close(&mut blocks, &mut current, &mut kept_fence);
+
+    blocks
+        .iter()
+        .map(|block| block.join("\n"))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Finish the block being collected, closing a code fence it left open.
+fn close<'a>(
+    blocks: &mut Vec<Vec<&'a str>>,
+    current: &mut Vec<&'a str>,
+    kept_fence: &mut Option<&'a str>,
+) {
+    while current.last().is_some_and(|l| l.trim().is_empty()) {
+        current.pop();
+    }
+    if let Some(marker) = kept_fence.take() {
+        current.push(marker);
+    }
+    if !current.is_empty() {
+        blocks.push(std::mem::take(current));
+    }
+}
+
+/// Returns true for an ATX heading line (`#` to `######`).
+fn is_heading(line: &str) -> bool {
+    let hashes = line.chars().take_while(|&c| c == '#').count();
+    (1..=6).contains(&hashes)
+        && line[hashes..]
+            .chars()
+            .next()
+            .is_none_or(char::is_whitespace)
+}
+
+/// The fence marker (run of backticks or tildes) opening or closing a code block.
+fn fence_marker(line: &str) -> Option<&str> {
+    let c = line.chars().next().filter(|&c| c == '`' || c == '~')?;
+    let len = line.chars().take_while(|&x| x == c).count();
+    (len >= 3).then(|| &line[..len])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BODY: &str = "# Title\n\n\
+        First paragraph\ncontinues here.\n\n\
+        Second paragraph.\n\n\
+        ## Usage\n\n\
+        ```bash\n# not a heading\nrun.sh\nmore\n```\n\n\
+        Trailing text.\n\n\
+        ## Notes\n";
+
+    #[test]
+    fn test_summarize_first_paragraph() {
+        assert_eq!(
+            summarize(BODY, None),
+            "# Title\n\nFirst paragraph\ncontinues here.\n\n## Usage\n\n## Notes"
+        );
+    }
+
+    #[test]
+    fn test_summarize_lines_per_section() {
+        assert_eq!(
+            summarize(BODY, Some(1)),
+            "# Title\n\nFirst paragraph\n\n## Usage\n\n```bash\n```\n\n## Notes"
+        );
+        assert_eq!(
+            summarize(BODY, Some(3)),
+            "# Title\n\nFirst paragraph\ncontinues here.\n\nSecond paragraph.\n\n\
+             ## Usage\n\n```bash\n# not a heading\nrun.sh\n```\n\n## Notes"
+        );
+        assert_eq!(summarize(BODY, Some(0)), "# Title\n\n## Usage\n\n## Notes");
+    }
+}
@@ -1,15 +1,27 @@
 //! Skill validation.
 
+use crate::agent::Agent;
 use crate::config::LintConfig;
-use crate::skill::manifest::Manifest;
+use crate::skill::fix::Fix;
+use crate::skill::manifest::{Manifest, ManifestError};
 use crate::skill::rules::{
-    BodyLengthRule, CompatibilityLengthRule, DescriptionLengthRule, DescriptionRequiredRule,
-    NameDirectoryRule, NameFormatRule, NameLengthRule, ReferencesExistRule, Rule,
-    ScriptExecutableRule, ScriptShebangRule,
+    not_utf8, AgentCompatibilityRule, AgentOverridesRule, BodyLengthRule, BodyTokensRule,
+    CompatibilityLengthRule, DeprecationRule, DescriptionLengthRule, DescriptionRequiredRule,
+    DescriptionTriggerRule, EncodingRule, ExampleCountRule, ExampleLengthRule, ExtendedFieldsRule,
+    NameDirectoryRule, NameFormatRule, NameLengthRule, NestedSkillRule, ReferencesExistRule,
+    RequirementsRule, ReservedNameRule, Rule, ScriptDocsRule, ScriptExecutableRule,
+    ScriptInterpreterRule, ScriptShebangRule, SecretsRule, SummaryLengthRule, TagCountRule,
+    TagFormatRule, RULE_NAMES,
 };
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::path::Path;
 
 /// Result of validating a skill.
-#[derive(Debug, Default)]
+///
+/// Serializes as `{"errors": [...], "warnings": [...]}`. The serialized form
+/// of this type, [`Diagnostic`], and [`DiagnosticCode`] is stable: fields are
+/// not renamed or removed, though new optional fields may be added.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ValidationResult {
     /// Validation errors.
     pub errors: Vec<Diagnostic>,
@@ -36,24 +48,54 @@ impl ValidationResult {
 }
 
 /// A validation diagnostic (error or warning).
-#[derive(Debug, Clone)]
+///
+/// Absent `line`, `column`, and `fix_hint` are omitted when serialized.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Diagnostic {
     /// Path to the file with the issue.
     pub path: String,
     /// Line number (if applicable).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub line: Option<usize>,
     /// Column number (if applicable).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub column: Option<usize>,
     /// Human-readable message.
     pub message: String,
     /// Diagnostic code.
     pub code: DiagnosticCode,
     /// Optional hint for fixing the issue.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub fix_hint: Option<String>,
 }
 
+impl Diagnostic {
+    /// The diagnostic for a SKILL.md that could not be parsed, if the failure
+    /// has a diagnostic code.
+    pub fn for_parse_error(path: &Path, error: &ManifestError) -> Option<Self> {
+        match error {
+            ManifestError::TooLarge { size, limit } => Some(Self {
+                path: path.display().to_string(),
+                line: None,
+                column: None,
+                message: format!("SKILL.md is too large ({} bytes, max {})", size, limit),
+                code: DiagnosticCode::E012,
+                fix_hint: Some("Move generated content to references/".into()),
+            }),
+            ManifestError::Io { source, .. }
+                if source.kind() == std::io::ErrorKind::InvalidData =>
+            {
+                not_utf8(path, &std::fs::read(path).ok()?)
+            }
+            _ => None,
+        }
+    }
+}
+
 /// Diagnostic codes for validation issues.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+///
+/// Serializes as its code string (e.g. `"W001"`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum DiagnosticCode {
     /// Invalid name format.
     E001,
@@ -73,6 +115,22 @@ pub enum DiagnosticCode {
     E008,
     /// Referenced file not found.
     E009,
+    /// Invalid deprecation metadata.
+    E010,
+    /// Invalid extended frontmatter field.
+    E011,
+    /// SKILL.md file too large to parse.
+    E012,
+    /// Invalid per-agent overrides.
+    E013,
+    /// File is not valid UTF-8.
+    E014,
+    /// Invalid runtime requirements.
+    E015,
+    /// Credential committed in a skill file.
+    E016,
+    /// Skill name used by more than one skill.
+    E017,
 
     /// Body exceeds max lines.
     W001,
@@ -82,6 +140,50 @@ pub enum DiagnosticCode {
     W003,
     /// Empty optional directory.
     W004,
+    /// `superseded-by` set on a skill that is not deprecated.
+    W005,
+    /// Invalid tag format.
+    W006,
+    /// Too many tags.
+    W007,
+    /// Body exceeds token budget.
+    W008,
+    /// Skill nested inside another skill.
+    W009,
+    /// Feature not supported by a target agent.
+    W010,
+    /// Name collides with a target agent's built-in command.
+    W011,
+    /// Summary too long.
+    W012,
+    /// Byte order mark or CRLF line endings.
+    W013,
+    /// Script interpreter not found.
+    W014,
+    /// Scripts section out of sync with `scripts/`.
+    W015,
+    /// Description does not say when to use the skill.
+    W016,
+    /// Too many examples.
+    W017,
+    /// Empty or too long example.
+    W018,
+    /// SKILL.md is not formatted.
+    W019,
+}
+
+impl Serialize for DiagnosticCode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for DiagnosticCode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let code = String::deserialize(deserializer)?;
+        Self::parse(&code)
+            .ok_or_else(|| serde::de::Error::custom(format!("unknown diagnostic code '{}'", code)))
+    }
 }
 
 impl std::fmt::Display for DiagnosticCode {
@@ -96,15 +198,159 @@ impl std::fmt::Display for DiagnosticCode {
             Self::E007 => write!(f, "E007"),
             Self::E008 => write!(f, "E008"),
             Self::E009 => write!(f, "E009"),
+            Self::E010 => write!(f, "E010"),
+            Self::E011 => write!(f, "E011"),
+            Self::E012 => write!(f, "E012"),
+            Self::E013 => write!(f, "E013"),
+            Self::E014 => write!(f, "E014"),
+            Self::E015 => write!(f, "E015"),
+            Self::E016 => write!(f, "E016"),
+            Self::E017 => write!(f, "E017"),
             Self::W001 => write!(f, "W001"),
             Self::W002 => write!(f, "W002"),
             Self::W003 => write!(f, "W003"),
             Self::W004 => write!(f, "W004"),
+            Self::W005 => write!(f, "W005"),
+            Self::W006 => write!(f, "W006"),
+            Self::W007 => write!(f, "W007"),
+            Self::W008 => write!(f, "W008"),
+            Self::W009 => write!(f, "W009"),
+            Self::W010 => write!(f, "W010"),
+            Self::W011 => write!(f, "W011"),
+            Self::W012 => write!(f, "W012"),
+            Self::W013 => write!(f, "W013"),
+            Self::W014 => write!(f, "W014"),
+            Self::W015 => write!(f, "W015"),
+            Self::W016 => write!(f, "W016"),
+            Self::W017 => write!(f, "W017"),
+            Self::W018 => write!(f, "W018"),
+            Self::W019 => write!(f, "W019"),
+        }
+    }
+}
+
+/// Lifecycle metadata for a diagnostic code.
+///
+/// Code strings are stable: a deprecated code is never reused for a
+/// different check, so external consumers can keep matching on it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RuleMetadata {
+    /// Version that introduced the code.
+    pub introduced_in: &'static str,
+    /// The code is no longer emitted.
+    pub deprecated: bool,
+    /// Code that took over the check of a deprecated code.
+    pub replaced_by: Option<DiagnosticCode>,
+}
+
+impl RuleMetadata {
+    const fn new(introduced_in: &'static str) -> Self {
+        Self {
+            introduced_in,
+            deprecated: false,
+            replaced_by: None,
+        }
+    }
+
+    const fn deprecated(self) -> Self {
+        Self {
+            deprecated: true,
+            ..self
         }
     }
 }
 
 impl DiagnosticCode {
+    /// Every diagnostic code, including deprecated ones.
+    pub const ALL: [DiagnosticCode; 36] = [
+        Self::E001,
+        Self::E002,
+        Self::E003,
+        Self::E004,
+        Self::E005,
+        Self::E006,
+        Self::E007,
+        Self::E008,
+        Self::E009,
+        Self::E010,
+        Self::E011,
+        Self::E012,
+        Self::E013,
+        Self::E014,
+        Self::E015,
+        Self::E016,
+        Self::E017,
+        Self::W001,
+        Self::W002,
+        Self::W003,
+        Self::W004,
+        Self::W005,
+        Self::W006,
+        Self::W007,
+        Self::W008,
+        Self::W009,
+        Self::W010,
+        Self::W011,
+        Self::W012,
+        Self::W013,
+        Self::W014,
+        Self::W015,
+        Self::W016,
+        Self::W017,
+        Self::W018,
+        Self::W019,
+    ];
+
+    /// Parse a code string such as `W001` (case-insensitive).
+    pub fn parse(code: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|c| c.to_string().eq_ignore_ascii_case(code.trim()))
+    }
+
+    /// Returns the code's lifecycle metadata.
+    pub fn metadata(self) -> RuleMetadata {
+        match self {
+            Self::E001
+            | Self::E002
+            | Self::E003
+            | Self::E004
+            | Self::E005
+            | Self::E006
+            | Self::E007
+            | Self::E008
+            | Self::E009
+            | Self::W001
+            | Self::W002
+            | Self::W003 => RuleMetadata::new("0.1.0"),
+            // Reserved for empty optional directories but never emitted
+            Self::W004 => RuleMetadata::new("0.1.0").deprecated(),
+            Self::E010
+            | Self::E011
+            | Self::E012
+            | Self::E013
+            | Self::W005
+            | Self::W006
+            | Self::W007
+            | Self::W008
+            | Self::W009
+            | Self::W010
+            | Self::W011
+            | Self::W012
+            | Self::E014
+            | Self::E015
+            | Self::E016
+            | Self::E017
+            | Self::W013
+            | Self::W014
+            | Self::W015
+            | Self::W016
+            | Self::W017
+            | Self::W018
+            | Self::W019 => RuleMetadata::new("0.12.0"),
+        }
+    }
+
     /// Returns true if this is an error (not a warning).
     pub fn is_error(&self) -> bool {
         matches!(
@@ -118,13 +364,55 @@ impl DiagnosticCode {
                 | Self::E007
                 | Self::E008
                 | Self::E009
+                | Self::E010
+                | Self::E011
+                | Self::E012
+                | Self::E013
+                | Self::E014
+                | Self::E015
+                | Self::E016
+                | Self::E017
         )
     }
 }
 
+/// Rules and codes picked with `lint --only`.
+#[derive(Debug, Default)]
+struct Selection {
+    names: Vec<&'static str>,
+    codes: Vec<DiagnosticCode>,
+}
+
+impl Selection {
+    /// Parse rule names and diagnostic codes.
+    fn parse(entries: &[String]) -> Result<Self, String> {
+        let mut selection = Self::default();
+        for entry in entries.iter().map(|e| e.trim()).filter(|e| !e.is_empty()) {
+            if let Some(code) = DiagnosticCode::parse(entry) {
+                selection.codes.push(code);
+            } else if let Some(name) = RULE_NAMES.iter().find(|n| n.eq_ignore_ascii_case(entry)) {
+                selection.names.push(name);
+            } else {
+                return Err(format!(
+                    "unknown rule or code '{}' (rules: {})",
+                    entry,
+                    RULE_NAMES.join(", ")
+                ));
+            }
+        }
+        Ok(selection)
+    }
+
+    fn contains(&self, rule: &str, code: DiagnosticCode) -> bool {
+        self.names.contains(&rule) || self.codes.contains(&code)
+    }
+}
+
 /// Skill validator with configurable rules.
 pub struct Validator {
     rules: Vec<Box<dyn Rule>>,
+    disabled: Vec<DiagnosticCode>,
+    only: Option<Selection>,
 }
 
 impl Default for Validator {
@@ -136,6 +424,14 @@ impl Default for Validator {
 impl Validator {
     /// Create a new validator with the given configuration.
     pub fn new(config: &LintConfig) -> Self {
+        Self::with_agents(config, &config.agents)
+    }
+
+    /// Create a validator that checks reserved names (W011) for `agents`.
+    ///
+    /// Use this when the target agents come from somewhere other than
+    /// `[lint] agents`, such as the `[add]` defaults.
+    pub fn with_agents(config: &LintConfig, agents: &[Agent]) -> Self {
         let mut rules: Vec<Box<dyn Rule>> = Vec::new();
 
         if config.rules.name_format {
@@ -145,7 +441,7 @@ impl Validator {
             rules.push(Box::new(NameLengthRule::new(max)));
         }
         if config.rules.name_directory {
-            rules.push(Box::new(NameDirectoryRule));
+            rules.push(Box::new(NameDirectoryRule::new(config.name_fix)));
         }
         if config.rules.description_required {
             rules.push(Box::new(DescriptionRequiredRule));
@@ -153,6 +449,14 @@ impl Validator {
         if let Some(max) = config.rules.description_length.resolve(1024) {
             rules.push(Box::new(DescriptionLengthRule::new(max)));
         }
+        if config.rules.description_trigger {
+            rules.push(Box::new(DescriptionTriggerRule::new(
+                &config.rules.description_triggers,
+            )));
+        }
+        if let Some(max) = config.rules.summary_length.resolve(500) {
+            rules.push(Box::new(SummaryLengthRule::new(max)));
+        }
         if let Some(max) = config.rules.compatibility_length.resolve(500) {
             rules.push(Box::new(CompatibilityLengthRule::new(max)));
         }
@@ -162,14 +466,107 @@ impl Validator {
         if let Some(max) = config.rules.body_length.resolve(500) {
             rules.push(Box::new(BodyLengthRule::new(max)));
         }
+        if let Some(max) = config.rules.body_tokens.resolve(5000) {
+            rules.push(Box::new(BodyTokensRule::new(
+                max,
+                config.rules.body_tokens_include_references,
+            )));
+        }
+        if config.rules.nested_skill {
+            rules.push(Box::new(NestedSkillRule));
+        }
         if config.rules.script_executable {
             rules.push(Box::new(ScriptExecutableRule));
         }
         if config.rules.script_shebang {
             rules.push(Box::new(ScriptShebangRule));
         }
+        if config.rules.script_interpreter {
+            rules.push(Box::new(ScriptInterpreterRule::new(agents.to_vec())));
+        }
+        if config.rules.script_docs {
+            rules.push(Box::new(ScriptDocsRule));
+        }
+        if config.rules.deprecation {
+            rules.push(Box::new(DeprecationRule));
+        }
+        if config.rules.extended_fields {
+            rules.push(Box::new(ExtendedFieldsRule));
+        }
+        if config.rules.agent_overrides {
+            rules.push(Box::new(AgentOverridesRule));
+        }
+        if config.rules.requirements {
+            rules.push(Box::new(RequirementsRule));
+        }
+        if config.rules.encoding {
+            rules.push(Box::new(EncodingRule));
+        }
+        if config.rules.secrets {
+            rules.push(Box::new(SecretsRule));
+        }
+        if config.rules.agent_compatibility && !config.agents.is_empty() {
+            rules.push(Box::new(AgentCompatibilityRule::new(config.agents.clone())));
+        }
+        if config.rules.reserved_names && !agents.is_empty() {
+            rules.push(Box::new(ReservedNameRule::new(agents.to_vec())));
+        }
+        if config.rules.tag_format {
+            rules.push(Box::new(TagFormatRule));
+        }
+        if let Some(max) = config.rules.tag_count.resolve(10) {
+            rules.push(Box::new(TagCountRule::new(max)));
+        }
+        if let Some(max) = config.rules.example_count.resolve(5) {
+            rules.push(Box::new(ExampleCountRule::new(max)));
+        }
+        if let Some(max) = config.rules.example_length.resolve(200) {
+            rules.push(Box::new(ExampleLengthRule::new(max)));
+        }
+
+        let disabled = config
+            .disable
+            .iter()
+            .filter_map(|code| DiagnosticCode::parse(code))
+            .collect();
 
-        Self { rules }
+        Self {
+            rules,
+            disabled,
+            only: None,
+        }
+    }
+
+    /// Restrict this run to some rules, without changing the config.
+    ///
+    /// Entries are rule names (`name-format`) or diagnostic codes (`W001`).
+    /// With `only`, just the listed rules and codes are reported; `skip`
+    /// leaves out the listed ones. Rules disabled in the config stay
+    /// disabled.
+    pub fn select(&mut self, only: &[String], skip: &[String]) -> Result<(), String> {
+        let skip = Selection::parse(skip)?;
+        self.rules.retain(|rule| !skip.names.contains(&rule.name()));
+        self.disabled.extend(skip.codes);
+
+        let only = Selection::parse(only)?;
+        if only.names.is_empty() && only.codes.is_empty() {
+            return Ok(());
+        }
+        // Which codes a rule reports is only known from its diagnostics
+        if only.codes.is_empty() {
+            self.rules.retain(|rule| only.names.contains(&rule.name()));
+        }
+        self.only = Some(only);
+        Ok(())
+    }
+
+    /// Returns true if `code` from `rule` is reported.
+    fn reports(&self, rule: &dyn Rule, code: DiagnosticCode) -> bool {
+        !self.disabled.contains(&code)
+            && self
+                .only
+                .as_ref()
+                .is_none_or(|only| only.contains(rule.name(), code))
     }
 
     /// Validate a skill manifest.
@@ -179,6 +576,9 @@ impl Validator {
         for rule in &self.rules {
             let diagnostics = rule.check(manifest);
             for diag in diagnostics {
+                if !self.reports(rule.as_ref(), diag.code) {
+                    continue;
+                }
                 if diag.code.is_error() {
                     result.errors.push(diag);
                 } else {
@@ -189,4 +589,102 @@ impl Validator {
 
         result
     }
+
+    /// The enabled rules, in the order they run.
+    pub fn rules(&self) -> impl Iterator<Item = &dyn Rule> {
+        self.rules.iter().map(|rule| rule.as_ref())
+    }
+
+    /// Fixes the enabled rules offer for a skill manifest.
+    pub fn fixes(&self, manifest: &Manifest) -> Vec<Fix> {
+        self.rules
+            .iter()
+            .filter_map(|rule| {
+                rule.fix(manifest)
+                    .filter(|fix| self.reports(rule.as_ref(), fix.code))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_code_strings_are_stable() {
+        for code in DiagnosticCode::ALL {
+            assert_eq!(DiagnosticCode::parse(&code.to_string()), Some(code));
+            assert_eq!(code.is_error(), code.to_string().starts_with('E'));
+            if let Some(new) = code.metadata().replaced_by {
+                assert!(code.metadata().deprecated && !new.metadata().deprecated);
+            }
+        }
+        assert_eq!(DiagnosticCode::parse("e003"), Some(DiagnosticCode::E003));
+        assert_eq!(DiagnosticCode::parse("E999"), None);
+    }
+
+    #[test]
+    fn test_select_rules() {
+        let mut config = LintConfig {
+            agents: vec![Agent::Claude],
+            ..LintConfig::default()
+        };
+        // Opt-in rules
+        config.rules.script_interpreter = true;
+        config.rules.description_trigger = true;
+        config.rules.secrets = true;
+        let all = Validator::new(&config);
+        let names: Vec<&str> = all.rules().map(|rule| rule.name()).collect();
+        assert_eq!(names.len(), RULE_NAMES.len());
+        assert!(names.iter().all(|name| RULE_NAMES.contains(name)));
+
+        let content = "---\nname: Bad_Name\ndescription: \"\"\ntags: [A]\n---\n\nBody\n";
+        let manifest = Manifest::parse_content("demo/SKILL.md".into(), content).unwrap();
+        let codes = |only: &[&str], skip: &[&str]| {
+            let mut validator = Validator::new(&LintConfig::default());
+            let owned = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+            validator.select(&owned(only), &owned(skip)).unwrap();
+            let result = validator.validate(&manifest);
+            result
+                .errors
+                .iter()
+                .chain(&result.warnings)
+                .map(|d| d.code.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(codes(&[], &[]), ["E001", "E003", "E004", "W006"]);
+        assert_eq!(codes(&["name-format", "w006"], &[]), ["E001", "W006"]);
+        assert_eq!(codes(&[], &["name-directory", "E004"]), ["E001", "W006"]);
+        assert_eq!(codes(&["E001", "E003"], &["E003"]), ["E001"]);
+
+        let mut validator = Validator::new(&LintConfig::default());
+        assert!(validator.select(&["nmae-format".into()], &[]).is_err());
+    }
+
+    #[test]
+    fn test_serialize_validation_result() {
+        let result = ValidationResult {
+            errors: vec![Diagnostic {
+                path: "a/SKILL.md".into(),
+                line: Some(2),
+                column: None,
+                message: "Invalid name".into(),
+                code: DiagnosticCode::E001,
+                fix_hint: None,
+            }],
+            warnings: Vec::new(),
+        };
+
+        let json = serde_json::to_string(&result).unwrap();
+        assert_eq!(
+            json,
+            r#"{"errors":[{"path":"a/SKILL.md","line":2,"message":"Invalid name","code":"E001"}],"warnings":[]}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<ValidationResult>(&json).unwrap(),
+            result
+        );
+    }
 }
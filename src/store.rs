@@ -0,0 +1,301 @@
+//! Content-addressed store of installed skill files.
+//!
+//! ```text
+//! ~/.skilo/store/
+//! └── <sha256>/    # A skill's files, named by their checksum
+//! ```
+//!
+//! Installs hard link the files of a store entry into each skills
+//! directory, so a skill installed for five agents is stored once. Where a
+//! hard link is not possible (another filesystem, or no support), files
+//! are copied instead; `std::fs::copy` clones them on filesystems with
+//! reflinks (Btrfs, XFS, APFS).
+//!
+//! Store files are read-only, and so are the installs linked to them, so
+//! an edit cannot silently change every install sharing the content. An
+//! entry that no longer matches its hash anyway is rebuilt on the next
+//! [`add`].
+//!
+//! Removing a store entry never affects installs, which keep their own
+//! links. [`gc`] removes the entries no install links to anymore.
+
+use crate::cache::{dir_size, skilo_home};
+use crate::lockfile::checksum_dir;
+use crate::SkiloError;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use walkdir::WalkDir;
+
+/// Prefix of entries being written, removed by [`gc`] if left behind.
+const TEMP_PREFIX: &str = ".tmp-";
+
+/// Age after which [`gc`] considers an entry being written abandoned.
+const TEMP_MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Get the store directory (`~/.skilo/store/`).
+pub fn store_dir() -> Option<PathBuf> {
+    skilo_home().map(|h| h.join("store"))
+}
+
+/// A skill's files in the store.
+#[derive(Debug)]
+pub struct StoreEntry {
+    /// Checksum of the files, naming the entry.
+    pub hash: String,
+    /// Path to the entry.
+    pub path: PathBuf,
+    /// Size in bytes.
+    pub size: u64,
+    /// Whether an install still links to the entry's files.
+    pub in_use: bool,
+}
+
+/// Add the skill directory `source` to the store, returning its entry.
+///
+/// Content already in the store is not copied again, unless the entry's
+/// files no longer match its hash, in which case it is rebuilt.
+pub fn add(source: &Path) -> Result<PathBuf, SkiloError> {
+    let store = store_dir()
+        .ok_or_else(|| SkiloError::Config("Could not determine store directory".to_string()))?;
+    add_to(&store, source)
+}
+
+/// Add the skill directory `source` to the store at `store`.
+fn add_to(store: &Path, source: &Path) -> Result<PathBuf, SkiloError> {
+    let checksum = checksum_dir(source)?;
+    let hash = checksum.trim_start_matches("sha256:");
+    let entry = store.join(hash);
+    if entry.is_dir() {
+        if checksum_dir(&entry)? == checksum {
+            return Ok(entry);
+        }
+        // Installs keep their links to the modified files
+        fs::remove_dir_all(&entry)?;
+    }
+
+    // Write to a temporary directory first so a partial entry is never used
+    fs::create_dir_all(store)?;
+    let temp = tempfile::Builder::new()
+        .prefix(TEMP_PREFIX)
+        .tempdir_in(store)?;
+    place_files(source, temp.path(), false)?;
+    set_readonly(temp.path())?;
+    if let Err(e) = fs::rename(temp.path(), &entry) {
+        // Another install may have added the same content meanwhile
+        if !entry.is_dir() {
+            return Err(e.into());
+        }
+    }
+
+    Ok(entry)
+}
+
+/// Hard link the files of the store `entry` into `dest`, copying the ones
+/// that cannot be linked.
+///
+/// Files linked this way share their content with the store and are
+/// read-only: replace them rather than writing to them in place. Copies
+/// are made writable.
+pub fn link_into(entry: &Path, dest: &Path) -> Result<(), SkiloError> {
+    place_files(entry, dest, true)
+}
+
+/// Recreate `src` at `dst`, linking files when `link` is set.
+fn place_files(src: &Path, dst: &Path, link: bool) -> Result<(), SkiloError> {
+    fs::create_dir_all(dst)?;
+
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        let src_path = entry.path();
+        let dst_path = dst.join(entry.file_name());
+
+        // Submodule checkouts hold a `.git` file pointing into the parent repo
+        if entry.file_name() == ".git" {
+            continue;
+        }
+
+        if entry.file_type()?.is_dir() {
+            place_files(&src_path, &dst_path, link)?;
+        } else if !link {
+            fs::copy(&src_path, &dst_path)?;
+        } else if fs::hard_link(&src_path, &dst_path).is_err() {
+            fs::copy(&src_path, &dst_path)?;
+            set_writable(&dst_path)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Remove write permission from the files under `dir`.
+fn set_readonly(dir: &Path) -> std::io::Result<()> {
+    for entry in WalkDir::new(dir) {
+        let entry = entry?;
+        if entry.file_type().is_file() {
+            let mut permissions = entry.metadata()?.permissions();
+            permissions.set_readonly(true);
+            fs::set_permissions(entry.path(), permissions)?;
+        }
+    }
+    Ok(())
+}
+
+/// Give the owner write permission on `path`, a copy of a store file.
+fn set_writable(path: &Path) -> std::io::Result<()> {
+    let mut permissions = fs::metadata(path)?.permissions();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        permissions.set_mode(permissions.mode() | 0o200);
+    }
+    #[cfg(not(unix))]
+    {
+        #[allow(clippy::permissions_set_readonly_false)]
+        permissions.set_readonly(false);
+    }
+    fs::set_permissions(path, permissions)
+}
+
+/// List the entries in the store, sorted by hash.
+pub fn entries() -> Vec<StoreEntry> {
+    let Some(store) = store_dir() else {
+        return Vec::new();
+    };
+    let Ok(dir) = fs::read_dir(&store) else {
+        return Vec::new();
+    };
+
+    let mut entries: Vec<StoreEntry> = dir
+        .filter_map(Result::ok)
+        .filter(|e| e.path().is_dir())
+        .filter(|e| !e.file_name().to_string_lossy().starts_with(TEMP_PREFIX))
+        .map(|e| {
+            let path = e.path();
+            StoreEntry {
+                hash: e.file_name().to_string_lossy().to_string(),
+                size: dir_size(&path),
+                in_use: is_linked(&path),
+                path,
+            }
+        })
+        .collect();
+    entries.sort_by(|a, b| a.hash.cmp(&b.hash));
+    entries
+}
+
+/// Remove the store entries no install links to, and entries left behind
+/// by interrupted installs.
+///
+/// Entries being written are only removed once older than a day, so an
+/// install running alongside is left alone.
+///
+/// Returns the entries removed (or that would be, with `dry_run`) and the
+/// bytes freed.
+pub fn gc(dry_run: bool) -> std::io::Result<(Vec<StoreEntry>, u64)> {
+    let mut removed: Vec<StoreEntry> = entries().into_iter().filter(|e| !e.in_use).collect();
+
+    if let Some(Ok(dir)) = store_dir().map(fs::read_dir) {
+        for temp in dir.filter_map(Result::ok) {
+            let name = temp.file_name().to_string_lossy().to_string();
+            let abandoned = temp
+                .metadata()
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+                .is_some_and(|age| age > TEMP_MAX_AGE);
+            if name.starts_with(TEMP_PREFIX) && abandoned {
+                let path = temp.path();
+                removed.push(StoreEntry {
+                    hash: name,
+                    size: dir_size(&path),
+                    in_use: false,
+                    path,
+                });
+            }
+        }
+    }
+
+    let freed = removed.iter().map(|e| e.size).sum();
+    if !dry_run {
+        for entry in &removed {
+            fs::remove_dir_all(&entry.path)?;
+        }
+    }
+
+    Ok((removed, freed))
+}
+
+/// Returns true if a file of the entry at `path` has another hard link.
+#[cfg(unix)]
+fn is_linked(path: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    WalkDir::new(path)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|e| e.file_type().is_file())
+        .any(|e| e.metadata().is_ok_and(|m| m.nlink() > 1))
+}
+
+/// Hard links are not detected on this platform, so entries are never
+/// considered in use; installs keep their own copy of the content.
+#[cfg(not(unix))]
+fn is_linked(_path: &Path) -> bool {
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_place_files_links() {
+        let temp = TempDir::new().unwrap();
+        let entry = temp.path().join("entry");
+        fs::create_dir_all(entry.join("scripts")).unwrap();
+        fs::write(entry.join("SKILL.md"), "---\nname: a\n---\n").unwrap();
+        fs::write(entry.join("scripts/run.sh"), "#!/bin/sh\n").unwrap();
+        fs::write(entry.join(".git"), "gitdir: ../.git/modules/a\n").unwrap();
+
+        let dest = temp.path().join("installed");
+        link_into(&entry, &dest).unwrap();
+        assert_eq!(
+            fs::read_to_string(dest.join("scripts/run.sh")).unwrap(),
+            "#!/bin/sh\n"
+        );
+        assert!(!dest.join(".git").exists());
+
+        #[cfg(unix)]
+        {
+            assert!(is_linked(&entry));
+            fs::remove_dir_all(&dest).unwrap();
+            assert!(!is_linked(&entry));
+        }
+    }
+
+    #[test]
+    fn test_add_rebuilds_modified_entry() {
+        let temp = TempDir::new().unwrap();
+        let store = temp.path().join("store");
+        let source = temp.path().join("source");
+        fs::create_dir_all(&source).unwrap();
+        fs::write(source.join("SKILL.md"), "---\nname: a\n---\n").unwrap();
+
+        let entry = add_to(&store, &source).unwrap();
+        assert_eq!(add_to(&store, &source).unwrap(), entry);
+        let file = entry.join("SKILL.md");
+        assert!(fs::metadata(&file).unwrap().permissions().readonly());
+
+        // Written through despite the permissions
+        set_writable(&file).unwrap();
+        fs::write(&file, "changed").unwrap();
+
+        let rebuilt = add_to(&store, &source).unwrap();
+        assert_eq!(rebuilt, entry);
+        assert_eq!(
+            fs::read_to_string(rebuilt.join("SKILL.md")).unwrap(),
+            "---\nname: a\n---\n"
+        );
+    }
+}
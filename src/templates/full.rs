@@ -1,7 +1,7 @@
 //! Creates a complete skill structure with scripts, references,
 //! and assets directories, suitable for feature-rich skills.
 
-use super::{to_title_case, SkillTemplate, TemplateContext};
+use super::{SkillTemplate, TemplateContext};
 use crate::cli::ScriptLang;
 use std::fs;
 use std::path::Path;
@@ -16,7 +16,7 @@ impl SkillTemplate for FullTemplate {
 
         // Write SKILL.md
         let skill_md = self.render_skill_md(ctx);
-        fs::write(skill_dir.join("SKILL.md"), skill_md)?;
+        fs::write(skill_dir.join("SKILL.md"), ctx.finish_skill_md(&skill_md))?;
 
         // Create all optional directories
         let scripts_dir = skill_dir.join("scripts");
@@ -69,7 +69,7 @@ impl FullTemplate {
 
         frontmatter.push_str("---\n\n");
 
-        let title = to_title_case(&ctx.name);
+        let title = &ctx.title;
 
         let body = format!(
             r#"# {}
@@ -203,7 +203,7 @@ console.log("Hello from {}!");
 
     /// Render the reference documentation content.
     fn render_reference(&self, ctx: &TemplateContext) -> String {
-        let title = to_title_case(&ctx.name);
+        let title = &ctx.title;
         format!(
             r#"# {} Reference
 
@@ -1,7 +1,7 @@
 //! Creates a skill with a simple greeting script, suitable for
 //! getting started with Agent Skills development.
 
-use super::{to_title_case, SkillTemplate, TemplateContext};
+use super::{SkillTemplate, TemplateContext};
 use crate::cli::ScriptLang;
 use std::fs;
 use std::path::Path;
@@ -16,7 +16,7 @@ impl SkillTemplate for HelloWorldTemplate {
 
         // Write SKILL.md
         let skill_md = self.render_skill_md(ctx);
-        fs::write(skill_dir.join("SKILL.md"), skill_md)?;
+        fs::write(skill_dir.join("SKILL.md"), ctx.finish_skill_md(&skill_md))?;
 
         // Write script
         if ctx.include_scripts {
@@ -58,7 +58,7 @@ impl HelloWorldTemplate {
 
         frontmatter.push_str("---\n\n");
 
-        let title = to_title_case(&ctx.name);
+        let title = &ctx.title;
 
         let body = format!(
             r#"# {}
@@ -1,7 +1,7 @@
 //! Creates a skill with only a SKILL.md file, suitable for simple
 //! prompt-only skills without scripts or additional resources.
 
-use super::{to_title_case, SkillTemplate, TemplateContext};
+use super::{SkillTemplate, TemplateContext};
 use std::fs;
 use std::path::Path;
 
@@ -15,7 +15,7 @@ impl SkillTemplate for MinimalTemplate {
 
         // Write SKILL.md only
         let skill_md = self.render_skill_md(ctx);
-        fs::write(skill_dir.join("SKILL.md"), skill_md)?;
+        fs::write(skill_dir.join("SKILL.md"), ctx.finish_skill_md(&skill_md))?;
 
         Ok(())
     }
@@ -36,7 +36,7 @@ impl MinimalTemplate {
 
         frontmatter.push_str("---\n\n");
 
-        let title = to_title_case(&ctx.name);
+        let title = &ctx.title;
 
         let body = format!(
             r#"# {}
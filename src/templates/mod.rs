@@ -3,26 +3,54 @@
 //! This module provides different templates for creating new Agent Skills,
 //! ranging from minimal single-file skills to full-featured skills with
 //! scripts, references, and assets.
+//!
+//! Every template passes its SKILL.md through
+//! [`TemplateContext::finish_skill_md`], which adds the `header` and
+//! `footer` variables around the body and replaces `{{key}}` placeholders
+//! with template variables.
 
 mod full;
 mod hello_world;
 mod minimal;
+mod prompt;
 mod script_based;
 
 use crate::cli::{ScriptLang, Template};
+use crate::skill::provenance::body_offset;
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use std::collections::BTreeMap;
 use std::path::Path;
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
 
 pub use full::FullTemplate;
 pub use hello_world::HelloWorldTemplate;
 pub use minimal::MinimalTemplate;
+pub use prompt::{describe, split_title, PromptTemplate};
 pub use script_based::ScriptBasedTemplate;
 
+/// Variables set from the skill itself, which cannot be redefined.
+pub const BUILTIN_VARS: &[&str] = &["name", "title", "description", "license"];
+
+/// Variable placed between the frontmatter and the body of SKILL.md.
+pub const HEADER_VAR: &str = "header";
+
+/// Variable placed at the end of SKILL.md.
+pub const FOOTER_VAR: &str = "footer";
+
+/// Pattern for `{{key}}` placeholders.
+static PLACEHOLDER_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\{\{\s*([A-Za-z0-9_-]+)\s*\}\}").unwrap());
+
 /// Context for rendering skill templates.
 ///
 /// Contains all the information needed to generate a new skill from a template.
 pub struct TemplateContext {
     /// The skill name (kebab-case identifier).
     pub name: String,
+    /// Human-readable title used for the top-level heading.
+    pub title: String,
     /// A brief description of the skill.
     pub description: String,
     /// Optional license identifier.
@@ -33,6 +61,46 @@ pub struct TemplateContext {
     pub include_optional_dirs: bool,
     /// Whether to include script files.
     pub include_scripts: bool,
+    /// Template variables from `[new.vars]` and `--var`.
+    pub vars: BTreeMap<String, String>,
+}
+
+impl TemplateContext {
+    /// The value of template variable `key`, built-in or user-defined.
+    pub fn var(&self, key: &str) -> Option<&str> {
+        match key {
+            "name" => Some(&self.name),
+            "title" => Some(&self.title),
+            "description" => Some(&self.description),
+            "license" => self.license.as_deref(),
+            _ => self.vars.get(key).map(String::as_str),
+        }
+    }
+
+    /// Replace `{{key}}` placeholders in `text`.
+    ///
+    /// Placeholders for undefined variables are left as they are.
+    pub fn expand(&self, text: &str) -> String {
+        PLACEHOLDER_REGEX
+            .replace_all(text, |caps: &Captures| {
+                self.var(&caps[1]).unwrap_or(&caps[0]).to_string()
+            })
+            .into_owned()
+    }
+
+    /// Add the header and footer variables to a rendered SKILL.md and
+    /// expand its placeholders.
+    pub fn finish_skill_md(&self, content: &str) -> String {
+        let mut content = content.to_string();
+        if let (Some(header), Some(offset)) = (self.vars.get(HEADER_VAR), body_offset(&content)) {
+            let body = content[offset..].trim_start_matches('\n');
+            content = format!("{}\n{}\n\n{}", &content[..offset], header.trim(), body);
+        }
+        if let Some(footer) = self.vars.get(FOOTER_VAR) {
+            content = format!("{}\n\n{}\n", content.trim_end(), footer.trim());
+        }
+        self.expand(&content)
+    }
 }
 
 /// Trait for skill templates that generate new skill structures.
@@ -67,3 +135,80 @@ pub fn to_title_case(name: &str) -> String {
         .collect::<Vec<_>>()
         .join(" ")
 }
+
+/// Convert a human-readable title to a kebab-case name.
+///
+/// The inverse of [`to_title_case`]: accented letters are transliterated to
+/// ASCII, apostrophes are dropped, and any other run of punctuation or
+/// whitespace becomes a single hyphen.
+pub fn to_kebab_case(title: &str) -> String {
+    let mut ascii = String::new();
+    for c in title.nfkd().filter(|c| !is_combining_mark(*c)) {
+        match c {
+            'ß' => ascii.push_str("ss"),
+            'æ' | 'Æ' => ascii.push_str("ae"),
+            'œ' | 'Œ' => ascii.push_str("oe"),
+            'þ' | 'Þ' => ascii.push_str("th"),
+            'ø' | 'Ø' => ascii.push('o'),
+            'đ' | 'Đ' | 'ð' | 'Ð' => ascii.push('d'),
+            'ł' | 'Ł' => ascii.push('l'),
+            '\'' | '\u{2019}' => {}
+            c => ascii.push(c),
+        }
+    }
+
+    ascii
+        .to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_finish_skill_md() {
+        let ctx = TemplateContext {
+            name: "demo".to_string(),
+            title: "Demo".to_string(),
+            description: "A demo skill.".to_string(),
+            license: None,
+            lang: ScriptLang::Python,
+            include_optional_dirs: false,
+            include_scripts: false,
+            vars: BTreeMap::from([
+                ("team".to_string(), "Platform".to_string()),
+                (
+                    HEADER_VAR.to_string(),
+                    "> Maintained by {{ team }}\n".to_string(),
+                ),
+                (
+                    FOOTER_VAR.to_string(),
+                    "_{{name}} ({{license}})_".to_string(),
+                ),
+            ]),
+        };
+
+        let skill_md = ctx.finish_skill_md("---\nname: demo\n---\n\n# {{title}}\n\nBody\n");
+        assert_eq!(
+            skill_md,
+            "---\nname: demo\n---\n\n> Maintained by Platform\n\n# Demo\n\nBody\n\n_demo ({{license}})_\n"
+        );
+    }
+
+    #[test]
+    fn test_to_kebab_case() {
+        assert_eq!(to_kebab_case("My Cool Skill"), "my-cool-skill");
+        assert_eq!(to_kebab_case("  Don't Panic!  "), "dont-panic");
+        assert_eq!(
+            to_kebab_case("Crème Brûlée -- Straße"),
+            "creme-brulee-strasse"
+        );
+        assert_eq!(to_kebab_case("PDF/CSV Tools v2"), "pdf-csv-tools-v2");
+        assert_eq!(to_kebab_case("日本語"), "");
+        assert_eq!(to_kebab_case(&to_title_case("code-review")), "code-review");
+    }
+}
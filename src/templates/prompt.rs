@@ -0,0 +1,119 @@
+//! Creates a skill whose body embeds an existing prompt or document.
+//!
+//! Content that would push the body past the body-length limit is moved to
+//! `references/` files with [`split_body`].
+
+use super::{SkillTemplate, TemplateContext};
+use crate::skill::split::{paragraphs, split_body, ReferenceFile};
+use std::fs;
+use std::path::Path;
+
+/// Body lines taken by the title heading and the blank line after it.
+const TITLE_LINES: usize = 2;
+
+/// Longest description generated from the prompt.
+const MAX_DESCRIPTION_LEN: usize = 1024;
+
+/// Template that embeds a prompt in the skill body.
+pub struct PromptTemplate {
+    /// The prompt, without a leading title heading.
+    content: String,
+    /// Maximum number of body lines.
+    max_lines: usize,
+}
+
+impl PromptTemplate {
+    /// Create a template for `content`, keeping the body within `max_lines`.
+    pub fn new(content: &str, max_lines: usize) -> Self {
+        Self {
+            content: content.trim().to_string(),
+            max_lines,
+        }
+    }
+
+    /// Render the SKILL.md content and reference files.
+    fn render_files(&self, ctx: &TemplateContext) -> (String, Vec<ReferenceFile>) {
+        let mut skill_md = format!(
+            "---\nname: {}\ndescription: {}\n",
+            ctx.name,
+            ctx.description.replace('\n', " ")
+        );
+        if let Some(license) = &ctx.license {
+            skill_md.push_str(&format!("license: {}\n", license));
+        }
+        skill_md.push_str(&format!("---\n\n# {}\n", ctx.title));
+
+        let (body, references) = split_body(&self.content, self.max_lines, TITLE_LINES, &[])
+            .unwrap_or_else(|| (self.content.clone(), Vec::new()));
+        if !body.is_empty() {
+            skill_md.push_str(&format!("\n{}\n", body.trim_end()));
+        }
+
+        (skill_md, references)
+    }
+}
+
+impl SkillTemplate for PromptTemplate {
+    fn render(&self, ctx: &TemplateContext, output_dir: &Path) -> std::io::Result<()> {
+        let skill_dir = output_dir.join(&ctx.name);
+        fs::create_dir_all(&skill_dir)?;
+
+        let (skill_md, references) = self.render_files(ctx);
+        fs::write(skill_dir.join("SKILL.md"), ctx.finish_skill_md(&skill_md))?;
+
+        if !references.is_empty() {
+            let references_dir = skill_dir.join("references");
+            fs::create_dir_all(&references_dir)?;
+            for reference in references {
+                fs::write(references_dir.join(reference.file_name), reference.content)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Split a leading `# Title` line off a prompt.
+pub fn split_title(content: &str) -> (Option<&str>, &str) {
+    let trimmed = content.trim_start();
+    let (first, rest) = trimmed.split_once('\n').unwrap_or((trimmed, ""));
+    match first.strip_prefix("# ") {
+        Some(title) if !title.trim().is_empty() => (Some(title.trim()), rest),
+        _ => (None, content),
+    }
+}
+
+/// A description stub from the first paragraph of prose in a prompt.
+pub fn describe(content: &str) -> Option<String> {
+    let paragraph = paragraphs(content)
+        .into_iter()
+        .find(|p| p.heading.is_none() && !p.lines[0].trim_start().starts_with("```"))?;
+    let text = paragraph.lines.join(" ");
+    let mut description = text.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    if description.len() > MAX_DESCRIPTION_LEN {
+        let mut end = MAX_DESCRIPTION_LEN;
+        while !description.is_char_boundary(end) {
+            end -= 1;
+        }
+        let cut = description[..end].rfind(' ').unwrap_or(end);
+        description.truncate(cut);
+    }
+    Some(description)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_title_and_describe() {
+        let prompt = "# Code Review\n\nYou review code\nfor bugs.\n\n## Steps\n";
+        let (title, rest) = split_title(prompt);
+        assert_eq!(title, Some("Code Review"));
+        assert_eq!(describe(rest).as_deref(), Some("You review code for bugs."));
+
+        assert_eq!(split_title("No heading\n").0, None);
+        assert_eq!(describe("## Only headings\n"), None);
+    }
+}
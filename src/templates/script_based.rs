@@ -1,7 +1,7 @@
 //! Creates a skill focused on multiple scripts with setup, run,
 //! and cleanup phases, suitable for automation tasks.
 
-use super::{to_title_case, SkillTemplate, TemplateContext};
+use super::{SkillTemplate, TemplateContext};
 use crate::cli::ScriptLang;
 use std::fs;
 use std::path::Path;
@@ -16,7 +16,7 @@ impl SkillTemplate for ScriptBasedTemplate {
 
         // Write SKILL.md
         let skill_md = self.render_skill_md(ctx);
-        fs::write(skill_dir.join("SKILL.md"), skill_md)?;
+        fs::write(skill_dir.join("SKILL.md"), ctx.finish_skill_md(&skill_md))?;
 
         // Create scripts directory with multiple example scripts
         let scripts_dir = skill_dir.join("scripts");
@@ -55,7 +55,7 @@ impl ScriptBasedTemplate {
 
         frontmatter.push_str("---\n\n");
 
-        let title = to_title_case(&ctx.name);
+        let title = &ctx.title;
         let ext = ctx.lang.extension();
 
         let body = format!(